@@ -76,7 +76,10 @@ pub fn fmt_file(contents: &str, indent: IndentOptions) -> Vec<FormattedBlock> {
             continue;
         }
 
-        let body = item.parse_body::<CallBody>().unwrap();
+        // Blocks that are still being written don't parse, leave them alone
+        let Ok(body) = item.parse_body::<CallBody>() else {
+            continue;
+        };
 
         let rsx_start = macro_path.span().start();
 
@@ -139,6 +142,15 @@ pub fn write_block_out(body: CallBody) -> Option<String> {
     buf.consume()
 }
 
+/// Write out a body that was parsed from `src`, keeping the comments in `src`.
+pub fn write_block_out_with_source(body: CallBody, src: &str) -> Option<String> {
+    let mut buf = Writer::new(src);
+
+    write_body(&mut buf, &body);
+
+    buf.consume()
+}
+
 fn write_body(buf: &mut Writer, body: &CallBody) {
     let is_short = buf.is_short_children(&body.roots).is_some();
     let is_empty = buf.is_empty_children(&body.roots);
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use syn::{spanned::Spanned, visit::Visit, Pat};
+use syn::{spanned::Spanned, Pat};
 
 use crate::{
     issues::{Issue, IssueReport},
@@ -8,15 +8,16 @@ use crate::{
         AnyLoopInfo, ClosureInfo, ComponentInfo, ConditionalInfo, FnInfo, ForInfo, HookInfo,
         IfInfo, LoopInfo, MatchInfo, Span, WhileInfo,
     },
+    rules::RuleSet,
 };
 
-struct VisitHooks {
-    issues: Vec<Issue>,
+pub(crate) struct VisitHooks {
+    pub(crate) issues: Vec<Issue>,
     context: Vec<Node>,
 }
 
 impl VisitHooks {
-    const fn new() -> Self {
+    pub(crate) const fn new() -> Self {
         Self {
             issues: vec![],
             context: vec![],
@@ -24,17 +25,23 @@ impl VisitHooks {
     }
 }
 
-/// Checks a Dioxus file for issues.
+/// Checks a Dioxus file for issues with the default rules.
 pub fn check_file(path: PathBuf, file_content: &str) -> IssueReport {
+    check_file_with_rules(path, file_content, &RuleSet::default())
+}
+
+/// Checks a Dioxus file for issues with the rules that aren't allowed in the rule set.
+pub fn check_file_with_rules(path: PathBuf, file_content: &str, rules: &RuleSet) -> IssueReport {
     let file = syn::parse_file(file_content).unwrap();
-    let mut visit_hooks = VisitHooks::new();
-    visit_hooks.visit_file(&file);
-    IssueReport::new(
+    let issues = rules.enabled().flat_map(|rule| rule.check(&file)).collect();
+    let mut report = IssueReport::new(
         path,
         std::env::current_dir().unwrap_or_default(),
         file_content.to_string(),
-        visit_hooks.issues,
-    )
+        issues,
+    );
+    report.rules = rules.clone();
+    report
 }
 
 #[derive(Debug, Clone)]
@@ -77,8 +84,8 @@ fn is_component_fn(item_fn: &syn::ItemFn) -> bool {
 fn get_closure_hook_body(local: &syn::Local) -> Option<&syn::Expr> {
     if let Pat::Ident(ident) = &local.pat {
         if is_hook_ident(&ident.ident) {
-            if let Some((_, expr)) = &local.init {
-                if let syn::Expr::Closure(closure) = &**expr {
+            if let Some(init) = &local.init {
+                if let syn::Expr::Closure(closure) = &*init.expr {
                     return Some(&closure.body);
                 }
             }
@@ -268,8 +275,8 @@ impl<'ast> syn::visit::Visit<'ast> for VisitHooks {
 #[cfg(test)]
 mod tests {
     use crate::metadata::{
-        AnyLoopInfo, ClosureInfo, ConditionalInfo, ForInfo, HookInfo, IfInfo, LineColumn, LoopInfo,
-        MatchInfo, Span, WhileInfo,
+        AnyLoopInfo, AttributeInfo, ClosureInfo, ConditionalInfo, ForInfo, GuardInfo, HookInfo,
+        IfInfo, LineColumn, LoopInfo, MatchInfo, RsxNodeInfo, Span, StateReadInfo, WhileInfo,
     };
     use indoc::indoc;
     use pretty_assertions::assert_eq;
@@ -636,4 +643,225 @@ mod tests {
 
         assert_eq!(report.issues, vec![]);
     }
+
+    #[test]
+    fn test_missing_key() {
+        let contents = indoc! {r#"
+            fn App(cx: Scope) -> Element {
+                render! {
+                    ul {
+                        for item in items {
+                            li { "{item}" }
+                        }
+                        for item in items {
+                            li { key: "{item}", "{item}" }
+                        }
+                    }
+                    {items.iter().map(|item| rsx! { Item { item: item } })}
+                }
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues,
+            vec![
+                Issue::MissingKey(RsxNodeInfo::new(
+                    "li".to_string(),
+                    Span::new_from_str(
+                        "li",
+                        LineColumn {
+                            line: 5,
+                            column: 16
+                        }
+                    )
+                )),
+                Issue::MissingKey(RsxNodeInfo::new(
+                    "Item".to_string(),
+                    Span::new_from_str(
+                        "Item",
+                        LineColumn {
+                            line: 11,
+                            column: 40
+                        }
+                    )
+                )),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stale_state_in_future() {
+        let contents = indoc! {r#"
+            fn App(cx: Scope) -> Element {
+                let count = use_state(cx, || 0);
+                let before = *count.get();
+                cx.spawn({
+                    to_owned![count];
+                    async move {
+                        sleep().await;
+                        count.set(*count + 1);
+                        count.set(*count.current() + 1);
+                    }
+                });
+                None
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues,
+            vec![Issue::StaleStateInFuture(StateReadInfo::new(
+                "count".to_string(),
+                Span::new_from_str(
+                    "*count",
+                    LineColumn {
+                        line: 8,
+                        column: 22
+                    }
+                ),
+                true
+            ))]
+        );
+        assert_eq!(
+            report.fixed_file_content().unwrap(),
+            contents.replace("set(*count + 1)", "set(*count.current() + 1)")
+        );
+    }
+
+    #[test]
+    fn test_read_guard_across_await() {
+        let contents = indoc! {r#"
+            fn App(cx: Scope) -> Element {
+                let value = use_signal(cx, || 0);
+                cx.spawn(async move {
+                    let held = value.read();
+                    let dropped = value.read();
+                    drop(dropped);
+                    sleep().await;
+                });
+                None
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues,
+            vec![Issue::ReadGuardAcrossAwait(GuardInfo::new(
+                "held".to_string(),
+                Span::new_from_str(
+                    "value.read()",
+                    LineColumn {
+                        line: 4,
+                        column: 19
+                    }
+                ),
+                Span::new_from_str("sleep().await", LineColumn { line: 7, column: 8 }),
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_unknown_attribute() {
+        let contents = indoc! {r#"
+            fn App(cx: Scope) -> Element {
+                render! {
+                    div { class_name: "row", tab_index: 0, hidden: true, "data-test": "custom" }
+                    my-element { anything: 0 }
+                }
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues,
+            vec![
+                Issue::UnknownAttribute(AttributeInfo::new(
+                    "div".to_string(),
+                    "class_name".to_string(),
+                    Span::new_from_str(
+                        "class_name",
+                        LineColumn {
+                            line: 3,
+                            column: 14
+                        }
+                    ),
+                    Some("class".to_string())
+                )),
+                Issue::UnknownAttribute(AttributeInfo::new(
+                    "div".to_string(),
+                    "tab_index".to_string(),
+                    Span::new_from_str(
+                        "tab_index",
+                        LineColumn {
+                            line: 3,
+                            column: 33
+                        }
+                    ),
+                    Some("tabindex".to_string())
+                )),
+            ]
+        );
+        assert_eq!(
+            report.fixed_file_content().unwrap(),
+            contents
+                .replace("class_name", "class")
+                .replace("tab_index", "tabindex")
+        );
+    }
+
+    #[test]
+    fn test_click_without_role() {
+        let contents = indoc! {r#"
+            fn App(cx: Scope) -> Element {
+                render! {
+                    div { onclick: move |_| {}, "Click me" }
+                    div { role: "button", onclick: move |_| {}, "Click me" }
+                    button { onclick: move |_| {}, "Click me" }
+                }
+            }
+        "#};
+
+        let report = check_file("app.rs".into(), contents);
+
+        assert_eq!(
+            report.issues,
+            vec![Issue::ClickWithoutRole(AttributeInfo::new(
+                "div".to_string(),
+                "onclick".to_string(),
+                Span::new_from_str(
+                    "onclick",
+                    LineColumn {
+                        line: 3,
+                        column: 14
+                    }
+                ),
+                None
+            ))]
+        );
+        assert_eq!(
+            report.fixed_file_content().unwrap(),
+            contents.replacen("div { onclick", "div { role: \"button\", onclick", 1)
+        );
+    }
+
+    #[test]
+    fn test_allowed_rules_are_skipped() {
+        let contents = indoc! {r#"
+            fn App(cx: Scope) -> Element {
+                render! {
+                    div { onclick: move |_| {}, "Click me" }
+                }
+            }
+        "#};
+
+        let rules = RuleSet::from_levels([("click-without-role", "allow")]).unwrap();
+        let report = check_file_with_rules("app.rs".into(), contents, &rules);
+
+        assert_eq!(report.issues, vec![]);
+    }
 }
@@ -0,0 +1,104 @@
+use crate::metadata::{LineColumn, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A machine-applicable change that fixes an issue.
+pub struct Fix {
+    /// The text to replace. Empty spans insert the replacement.
+    pub span: Span,
+    pub replacement: String,
+}
+
+impl Fix {
+    pub fn new(span: Span, replacement: impl ToString) -> Self {
+        Self {
+            span,
+            replacement: replacement.to_string(),
+        }
+    }
+}
+
+/// Apply fixes to the content of a file.
+///
+/// Fixes that overlap an earlier fix are skipped, checking the file again will report them if they are still needed.
+pub fn apply_fixes<'a>(file_content: &str, fixes: impl IntoIterator<Item = &'a Fix>) -> String {
+    let mut edits: Vec<(usize, usize, &str)> = vec![];
+    for fix in fixes {
+        let (Some(start), Some(end)) = (
+            byte_offset(file_content, &fix.span.start),
+            byte_offset(file_content, &fix.span.end),
+        ) else {
+            continue;
+        };
+        let overlaps = edits
+            .iter()
+            .any(|(other_start, other_end, _)| start < *other_end && *other_start < end);
+        if !overlaps {
+            edits.push((start, end, &fix.replacement));
+        }
+    }
+
+    // Apply the edits back to front so the offsets of the earlier ones stay valid
+    edits.sort_by_key(|(start, end, _)| (*start, *end));
+    let mut fixed = file_content.to_string();
+    for (start, end, replacement) in edits.into_iter().rev() {
+        fixed.replace_range(start..end, replacement);
+    }
+    fixed
+}
+
+/// Convert a line (starting at 1) and column (in characters) into a byte offset.
+fn byte_offset(file_content: &str, location: &LineColumn) -> Option<usize> {
+    let line_start = if location.line == 1 {
+        0
+    } else {
+        file_content
+            .match_indices('\n')
+            .nth(location.line.checked_sub(2)?)?
+            .0
+            + 1
+    };
+    let line = &file_content[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let column = line
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(line.len()))
+        .nth(location.column)?;
+    Some(line_start + column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn span(start: (usize, usize), end: (usize, usize)) -> Span {
+        Span {
+            source_text: None,
+            start: LineColumn {
+                line: start.0,
+                column: start.1,
+            },
+            end: LineColumn {
+                line: end.0,
+                column: end.1,
+            },
+        }
+    }
+
+    #[test]
+    fn test_apply_fixes() {
+        let content = "let a = 1;\nlet b = ünïcode + 2;\n";
+        let fixes = [
+            Fix::new(span((2, 8), (2, 15)), "unicode"),
+            Fix::new(span((1, 4), (1, 4)), "mut "),
+            // overlaps the first fix
+            Fix::new(span((2, 4), (2, 10)), "c"),
+        ];
+
+        assert_eq!(
+            apply_fixes(content, &fixes),
+            "let mut a = 1;\nlet b = unicode + 2;\n"
+        );
+    }
+}
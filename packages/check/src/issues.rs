@@ -1,5 +1,5 @@
 use owo_colors::{
-    colors::{css::LightBlue, BrightRed},
+    colors::{css::LightBlue, BrightRed, BrightYellow},
     OwoColorize, Stream,
 };
 use std::{
@@ -7,8 +7,13 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::metadata::{
-    AnyLoopInfo, ClosureInfo, ConditionalInfo, ForInfo, HookInfo, IfInfo, MatchInfo, WhileInfo,
+use crate::{
+    fix::{apply_fixes, Fix},
+    metadata::{
+        AnyLoopInfo, AttributeInfo, ClosureInfo, ConditionalInfo, ForInfo, GuardInfo, HookInfo,
+        IfInfo, MatchInfo, RsxNodeInfo, Span, StateReadInfo, WhileInfo,
+    },
+    rules::{Rule, RuleSet, Severity},
 };
 
 /// The result of checking a Dioxus file for issues.
@@ -17,6 +22,8 @@ pub struct IssueReport {
     pub crate_root: PathBuf,
     pub file_content: String,
     pub issues: Vec<Issue>,
+    /// The rules the file was checked with.
+    pub rules: RuleSet,
 }
 
 impl IssueReport {
@@ -31,8 +38,30 @@ impl IssueReport {
             crate_root,
             file_content: file_content.to_string(),
             issues,
+            rules: RuleSet::default(),
         }
     }
+
+    /// The severity of an issue in this report.
+    pub fn severity(&self, issue: &Issue) -> Severity {
+        self.rules.severity(issue.rule())
+    }
+
+    /// Whether any of the issues is an error.
+    pub fn has_errors(&self) -> bool {
+        self.issues
+            .iter()
+            .any(|issue| self.severity(issue) == Severity::Error)
+    }
+
+    /// The content of the file with every machine-applicable fix applied, if there are any.
+    pub fn fixed_file_content(&self) -> Option<String> {
+        let fixes: Vec<Fix> = self.issues.iter().filter_map(Issue::fix).collect();
+        if fixes.is_empty() {
+            return None;
+        }
+        Some(apply_fixes(&self.file_content, &fixes))
+    }
 }
 
 fn lightblue(text: &str) -> String {
@@ -45,6 +74,11 @@ fn brightred(text: &str) -> String {
         .to_string()
 }
 
+fn brightyellow(text: &str) -> String {
+    text.if_supports_color(Stream::Stderr, |text| text.fg::<BrightYellow>())
+        .to_string()
+}
+
 fn bold(text: &str) -> String {
     text.if_supports_color(Stream::Stderr, |text| text.bold())
         .to_string()
@@ -60,10 +94,13 @@ impl Display for IssueReport {
         let pipe_char = lightblue("|");
 
         for (i, issue) in self.issues.iter().enumerate() {
-            let hook_info = issue.hook_info();
-            let hook_span = hook_info.span;
-            let hook_name_span = hook_info.name_span;
-            let error_line = format!("{}: {}", brightred("error"), issue);
+            let hook_span = issue.span();
+            let hook_name_span = issue.highlight_span();
+            let color = match self.severity(issue) {
+                Severity::Error => brightred,
+                _ => brightyellow,
+            };
+            let error_line = format!("{}: {}", color(&self.severity(issue).to_string()), issue);
             writeln!(f, "{}", bold(&error_line))?;
             writeln!(
                 f,
@@ -98,7 +135,7 @@ impl Display for IssueReport {
                             "{:>max_line_num_len$} {} {}",
                             "",
                             pipe_char,
-                            brightred(&caret),
+                            color(&caret),
                         )?;
                     }
                 }
@@ -111,6 +148,13 @@ impl Display for IssueReport {
                 "",
                 lightblue("=")
             );
+            let help_text_prefix = format!(
+                "{:>max_line_num_len$} {}\n{:>max_line_num_len$} {} help:",
+                "",
+                pipe_char,
+                "",
+                lightblue("=")
+            );
 
             match issue {
                 Issue::HookInsideConditional(
@@ -143,6 +187,39 @@ impl Display for IssueReport {
                     writeln!(f, "{} `loop {{ … }}` is the loop", note_text_prefix,)?;
                 }
                 Issue::HookOutsideComponent(_) | Issue::HookInsideClosure(_, _) => {}
+                Issue::MissingKey(_) => {
+                    writeln!(
+                        f,
+                        "{} without a `key`, the items can't be told apart when the list changes",
+                        note_text_prefix,
+                    )?;
+                }
+                Issue::StaleStateInFuture(state_info) => {
+                    writeln!(
+                        f,
+                        "{} the future only sees the value of `{}` from when it was created, use `{}.current()` to read the latest value",
+                        note_text_prefix, state_info.name, state_info.name,
+                    )?;
+                }
+                Issue::ReadGuardAcrossAwait(guard_info) => {
+                    writeln!(
+                        f,
+                        "{} `{}` is still held at the `.await` on line {}, drop it before awaiting",
+                        note_text_prefix, guard_info.name, guard_info.await_span.start.line,
+                    )?;
+                }
+                Issue::UnknownAttribute(attribute_info) => {
+                    if let Some(suggestion) = &attribute_info.suggestion {
+                        writeln!(f, "{} did you mean `{}`?", help_text_prefix, suggestion)?;
+                    }
+                }
+                Issue::ClickWithoutRole(_) => {
+                    writeln!(
+                        f,
+                        "{} add a role like `role: \"button\"` so assistive technology knows it can be clicked",
+                        help_text_prefix,
+                    )?;
+                }
             }
 
             if i < self.issues.len() - 1 {
@@ -155,7 +232,6 @@ impl Display for IssueReport {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-#[allow(clippy::enum_variant_names)] // we'll add non-hook ones in the future
 /// Issues that might be found via static analysis of a Dioxus file.
 pub enum Issue {
     /// https://dioxuslabs.com/learn/0.4/reference/hooks#no-hooks-in-conditionals
@@ -165,15 +241,85 @@ pub enum Issue {
     /// https://dioxuslabs.com/learn/0.4/reference/hooks#no-hooks-in-closures
     HookInsideClosure(HookInfo, ClosureInfo),
     HookOutsideComponent(HookInfo),
+    /// An element or component created by an iterator in `rsx!` without a `key`.
+    MissingKey(RsxNodeInfo),
+    /// A `use_state` value read inside a future with `*state` or `state.get()`.
+    StaleStateInFuture(StateReadInfo),
+    /// A guard from `.read()` that is held across an `.await`.
+    ReadGuardAcrossAwait(GuardInfo),
+    /// An attribute the element doesn't have.
+    UnknownAttribute(AttributeInfo),
+    /// An `onclick` handler on a non-interactive element without a `role`.
+    ClickWithoutRole(AttributeInfo),
 }
 
 impl Issue {
-    pub fn hook_info(&self) -> HookInfo {
+    /// The information about the hook, if this is an issue with a hook.
+    pub fn hook_info(&self) -> Option<HookInfo> {
+        match self {
+            Issue::HookInsideConditional(hook_info, _)
+            | Issue::HookInsideLoop(hook_info, _)
+            | Issue::HookInsideClosure(hook_info, _)
+            | Issue::HookOutsideComponent(hook_info) => Some(hook_info.clone()),
+            _ => None,
+        }
+    }
+
+    /// The rule that found this issue.
+    pub fn rule(&self) -> Rule {
+        match self {
+            Issue::HookInsideConditional(..)
+            | Issue::HookInsideLoop(..)
+            | Issue::HookInsideClosure(..)
+            | Issue::HookOutsideComponent(_) => Rule::RulesOfHooks,
+            Issue::MissingKey(_) => Rule::MissingKey,
+            Issue::StaleStateInFuture(_) => Rule::StaleStateInFuture,
+            Issue::ReadGuardAcrossAwait(_) => Rule::ReadGuardAcrossAwait,
+            Issue::UnknownAttribute(_) => Rule::UnknownAttribute,
+            Issue::ClickWithoutRole(_) => Rule::ClickWithoutRole,
+        }
+    }
+
+    /// The span of the code with the issue.
+    pub fn span(&self) -> Span {
         match self {
             Issue::HookInsideConditional(hook_info, _)
             | Issue::HookInsideLoop(hook_info, _)
             | Issue::HookInsideClosure(hook_info, _)
-            | Issue::HookOutsideComponent(hook_info) => hook_info.clone(),
+            | Issue::HookOutsideComponent(hook_info) => hook_info.span.clone(),
+            Issue::MissingKey(node_info) => node_info.span.clone(),
+            Issue::StaleStateInFuture(state_info) => state_info.span.clone(),
+            Issue::ReadGuardAcrossAwait(guard_info) => guard_info.span.clone(),
+            Issue::UnknownAttribute(attribute_info) | Issue::ClickWithoutRole(attribute_info) => {
+                attribute_info.span.clone()
+            }
+        }
+    }
+
+    /// The part of the span that is highlighted, e.g. the name of the hook.
+    pub fn highlight_span(&self) -> Span {
+        match self.hook_info() {
+            Some(hook_info) => hook_info.name_span,
+            None => self.span(),
+        }
+    }
+
+    /// A machine-applicable fix for the issue, if there is one.
+    pub fn fix(&self) -> Option<Fix> {
+        match self {
+            Issue::StaleStateInFuture(state_info) if state_info.deref => Some(Fix::new(
+                state_info.span.clone(),
+                format!("*{}.current()", state_info.name),
+            )),
+            Issue::UnknownAttribute(attribute_info) => {
+                let suggestion = attribute_info.suggestion.as_ref()?;
+                Some(Fix::new(attribute_info.span.clone(), suggestion))
+            }
+            Issue::ClickWithoutRole(attribute_info) => Some(Fix::new(
+                Span::empty(attribute_info.span.start.clone()),
+                "role: \"button\", ",
+            )),
+            _ => None,
         }
     }
 }
@@ -214,6 +360,33 @@ impl std::fmt::Display for Issue {
                     hook_info.name
                 )
             }
+            Issue::MissingKey(node_info) => {
+                write!(f, "missing `key` on an iterated node: `{}`", node_info.name)
+            }
+            Issue::StaleStateInFuture(state_info) => {
+                write!(f, "stale state read in a future: `{}`", state_info.name)
+            }
+            Issue::ReadGuardAcrossAwait(guard_info) => {
+                write!(
+                    f,
+                    "read guard held across an `.await`: `{}`",
+                    guard_info.name
+                )
+            }
+            Issue::UnknownAttribute(attribute_info) => {
+                write!(
+                    f,
+                    "unknown attribute for `{}`: `{}`",
+                    attribute_info.element, attribute_info.name
+                )
+            }
+            Issue::ClickWithoutRole(attribute_info) => {
+                write!(
+                    f,
+                    "`onclick` on a non-interactive element without a role: `{}`",
+                    attribute_info.element
+                )
+            }
         }
     }
 }
@@ -424,4 +597,32 @@ mod tests {
 
         assert_eq!(expected, issue_report.to_string());
     }
+
+    #[test]
+    fn test_issue_report_display_warning_with_help() {
+        owo_colors::set_override(false);
+        let issue_report = check_file(
+            "src/main.rs".into(),
+            indoc! {r#"
+                fn App(cx: Scope) -> Element {
+                    render! {
+                        div { onclick: move |_| {}, "Click me" }
+                    }
+                }
+            "#},
+        );
+
+        let expected = indoc! {r#"
+            warning: `onclick` on a non-interactive element without a role: `div`
+              --> src/main.rs:3:15
+              |
+            3 |         div { onclick: move |_| {}, "Click me" }
+              |               ^^^^^^^
+              |
+              = help: add a role like `role: "button"` so assistive technology knows it can be clicked
+        "#};
+
+        assert_eq!(expected, issue_report.to_string());
+        assert!(!issue_report.has_errors());
+    }
 }
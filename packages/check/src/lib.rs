@@ -3,8 +3,14 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 
 mod check;
+mod fix;
 mod issues;
 mod metadata;
+mod rsx;
+mod rules;
 
-pub use check::check_file;
+pub use check::{check_file, check_file_with_rules};
+pub use fix::{apply_fixes, Fix};
 pub use issues::{Issue, IssueReport};
+pub use metadata::{LineColumn, Span};
+pub use rules::{ConfigError, Rule, RuleSet, Severity};
@@ -173,6 +173,15 @@ impl Span {
             end,
         }
     }
+
+    /// An empty span at a location, used to insert text.
+    pub fn empty(at: LineColumn) -> Self {
+        Self {
+            source_text: None,
+            start: at.clone(),
+            end: at,
+        }
+    }
 }
 
 impl From<proc_macro2::Span> for Span {
@@ -200,3 +209,85 @@ impl From<proc_macro2::LineColumn> for LineColumn {
         }
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about an element or component in `rsx!`.
+pub struct RsxNodeInfo {
+    /// The name of the element or component, e.g. `li`.
+    pub name: String,
+    /// The span of the name.
+    pub span: Span,
+}
+
+impl RsxNodeInfo {
+    pub const fn new(name: String, span: Span) -> Self {
+        Self { name, span }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about a `use_state` value that is read inside a future.
+pub struct StateReadInfo {
+    /// The name of the state, e.g. `count`.
+    pub name: String,
+    /// The span of the read, e.g. `*count` or `count.get()`.
+    pub span: Span,
+    /// Whether the state is read by dereferencing it.
+    pub deref: bool,
+}
+
+impl StateReadInfo {
+    pub const fn new(name: String, span: Span, deref: bool) -> Self {
+        Self { name, span, deref }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about a read guard that is held across an `.await`.
+pub struct GuardInfo {
+    /// The name the guard is bound to.
+    pub name: String,
+    /// The span of the read, e.g. `signal.read()`.
+    pub span: Span,
+    /// The span of the first `.await` while the guard is held.
+    pub await_span: Span,
+}
+
+impl GuardInfo {
+    pub const fn new(name: String, span: Span, await_span: Span) -> Self {
+        Self {
+            name,
+            span,
+            await_span,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about an attribute of an element in `rsx!`.
+pub struct AttributeInfo {
+    /// The name of the element, e.g. `div`.
+    pub element: String,
+    /// The name of the attribute, e.g. `onclick`.
+    pub name: String,
+    /// The span of the attribute name.
+    pub span: Span,
+    /// A known attribute the name could be replaced with.
+    pub suggestion: Option<String>,
+}
+
+impl AttributeInfo {
+    pub const fn new(
+        element: String,
+        name: String,
+        span: Span,
+        suggestion: Option<String>,
+    ) -> Self {
+        Self {
+            element,
+            name,
+            span,
+            suggestion,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Helpers for looking inside `rsx!` calls, which `syn` only sees as a stream of tokens.
+
+use dioxus_rsx::{
+    AttributeType, BodyNode, CallBody, ContentField, Element, ElementAttrValue, IfChain,
+};
+use syn::visit::Visit;
+
+/// Parse the body of an `rsx!` or `render!` call.
+pub(crate) fn parse_rsx(mac: &syn::Macro) -> Option<CallBody> {
+    let name = mac.path.segments.last()?.ident.to_string();
+    if name != "rsx" && name != "render" {
+        return None;
+    }
+    syn::parse2(mac.tokens.clone()).ok()
+}
+
+/// Get the `rsx!` call an expression evaluates to, like the body of `|item| rsx! { … }`.
+pub(crate) fn returned_macro(expr: &syn::Expr) -> Option<&syn::Macro> {
+    match expr {
+        syn::Expr::Macro(mac) => Some(&mac.mac),
+        syn::Expr::Block(block) => match block.block.stmts.last()? {
+            syn::Stmt::Expr(expr, None) => returned_macro(expr),
+            syn::Stmt::Macro(mac) if mac.semi_token.is_none() => Some(&mac.mac),
+            _ => None,
+        },
+        syn::Expr::Paren(paren) => returned_macro(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Visit the Rust expressions inside `rsx!` nodes, like attribute values and event handlers.
+pub(crate) fn visit_rsx_exprs<V: for<'a> Visit<'a>>(visitor: &mut V, nodes: &[BodyNode]) {
+    for node in nodes {
+        match node {
+            BodyNode::Element(element) => {
+                for attribute in &element.attributes {
+                    match attribute {
+                        AttributeType::Named(named) => {
+                            visit_attribute_value(visitor, &named.attr.value)
+                        }
+                        AttributeType::Spread(expr) => visitor.visit_expr(expr),
+                    }
+                }
+                visit_rsx_exprs(visitor, &element.children);
+            }
+            BodyNode::Component(component) => {
+                for field in &component.fields {
+                    match &field.content {
+                        ContentField::ManExpr(expr) | ContentField::OnHandlerRaw(expr) => {
+                            visitor.visit_expr(expr)
+                        }
+                        ContentField::Shorthand(_) | ContentField::Formatted(_) => {}
+                    }
+                }
+                if let Some(props) = &component.manual_props {
+                    visitor.visit_expr(props);
+                }
+                visit_rsx_exprs(visitor, &component.children);
+            }
+            BodyNode::ForLoop(for_loop) => {
+                visitor.visit_expr(&for_loop.expr);
+                visit_rsx_exprs(visitor, &for_loop.body);
+            }
+            BodyNode::IfChain(chain) => {
+                visitor.visit_expr(&chain.cond);
+                for branch in if_chain_branches(chain) {
+                    visit_rsx_exprs(visitor, branch);
+                }
+            }
+            BodyNode::RawExpr(expr) => visitor.visit_expr(expr),
+            BodyNode::Text(_) => {}
+        }
+    }
+}
+
+fn visit_attribute_value<V: for<'a> Visit<'a>>(visitor: &mut V, value: &ElementAttrValue) {
+    match value {
+        ElementAttrValue::AttrExpr(expr) | ElementAttrValue::EventTokens(expr) => {
+            visitor.visit_expr(expr)
+        }
+        ElementAttrValue::AttrOptionalExpr { condition, value } => {
+            visitor.visit_expr(condition);
+            visit_attribute_value(visitor, value);
+        }
+        ElementAttrValue::Shorthand(_) | ElementAttrValue::AttrLiteral(_) => {}
+    }
+}
+
+/// The bodies of every branch of an `if` chain in `rsx!`.
+pub(crate) fn if_chain_branches(chain: &IfChain) -> Vec<&[BodyNode]> {
+    let mut branches = vec![chain.then_branch.as_slice()];
+    let mut next = chain;
+    while let Some(else_if) = &next.else_if_branch {
+        branches.push(&else_if.then_branch);
+        next = else_if;
+    }
+    if let Some(else_branch) = &next.else_branch {
+        branches.push(else_branch);
+    }
+    branches
+}
+
+/// Call `f` on every element in the nodes, including nested ones.
+pub(crate) fn for_each_element(nodes: &[BodyNode], f: &mut impl FnMut(&Element)) {
+    for node in nodes {
+        match node {
+            BodyNode::Element(element) => {
+                f(element);
+                for_each_element(&element.children, f);
+            }
+            BodyNode::Component(component) => for_each_element(&component.children, f),
+            BodyNode::ForLoop(for_loop) => for_each_element(&for_loop.body, f),
+            BodyNode::IfChain(chain) => {
+                for branch in if_chain_branches(chain) {
+                    for_each_element(branch, f);
+                }
+            }
+            BodyNode::Text(_) | BodyNode::RawExpr(_) => {}
+        }
+    }
+}
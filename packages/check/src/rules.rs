@@ -0,0 +1,236 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use syn::visit::Visit;
+
+use crate::{check::VisitHooks, issues::Issue};
+
+mod attributes;
+mod missing_key;
+mod read_guard;
+mod stale_state;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// How seriously an issue found by a rule is taken.
+pub enum Severity {
+    /// The rule is disabled.
+    Allow,
+    /// Issues are reported, but don't fail the check.
+    Warning,
+    /// Issues are reported and fail the check.
+    Error,
+}
+
+impl FromStr for Severity {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" | "off" => Ok(Self::Allow),
+            "warn" | "warning" => Ok(Self::Warning),
+            "error" | "deny" => Ok(Self::Error),
+            _ => Err(ConfigError::UnknownSeverity(s.to_string())),
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Allow => "allow",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A rule that can be checked on a Dioxus file.
+pub enum Rule {
+    /// https://dioxuslabs.com/learn/0.4/reference/hooks#rules-of-hooks
+    RulesOfHooks,
+    /// Elements and components created by an iterator in `rsx!` need a `key`.
+    MissingKey,
+    /// A future reads a `use_state` value it captured, which won't see later updates.
+    StaleStateInFuture,
+    /// A `read()` guard is held across an `.await`, so writes while the future is suspended panic.
+    ReadGuardAcrossAwait,
+    /// An attribute that the element doesn't have.
+    UnknownAttribute,
+    /// An `onclick` handler on an element that isn't interactive, without a `role`.
+    ClickWithoutRole,
+}
+
+impl Rule {
+    /// Every rule, in the order they are checked.
+    pub const ALL: [Rule; 6] = [
+        Rule::RulesOfHooks,
+        Rule::MissingKey,
+        Rule::StaleStateInFuture,
+        Rule::ReadGuardAcrossAwait,
+        Rule::UnknownAttribute,
+        Rule::ClickWithoutRole,
+    ];
+
+    /// The name of the rule, used in `Dioxus.toml`.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Rule::RulesOfHooks => "rules-of-hooks",
+            Rule::MissingKey => "missing-key",
+            Rule::StaleStateInFuture => "stale-state-in-future",
+            Rule::ReadGuardAcrossAwait => "read-guard-across-await",
+            Rule::UnknownAttribute => "unknown-attribute",
+            Rule::ClickWithoutRole => "click-without-role",
+        }
+    }
+
+    /// A short description of what the rule checks.
+    pub const fn description(self) -> &'static str {
+        match self {
+            Rule::RulesOfHooks => {
+                "Hooks must be called unconditionally at the top level of a component or hook"
+            }
+            Rule::MissingKey => "Elements and components created by an iterator in rsx! need a key",
+            Rule::StaleStateInFuture => {
+                "Futures should read use_state values with current() to see updates"
+            }
+            Rule::ReadGuardAcrossAwait => "Read guards must be dropped before awaiting",
+            Rule::UnknownAttribute => "Elements can only use attributes they have",
+            Rule::ClickWithoutRole => {
+                "Non-interactive elements with an onclick handler need a role"
+            }
+        }
+    }
+
+    /// The severity of the rule if it isn't configured.
+    pub const fn default_severity(self) -> Severity {
+        match self {
+            Rule::RulesOfHooks | Rule::ReadGuardAcrossAwait | Rule::UnknownAttribute => {
+                Severity::Error
+            }
+            Rule::MissingKey | Rule::StaleStateInFuture | Rule::ClickWithoutRole => {
+                Severity::Warning
+            }
+        }
+    }
+
+    pub(crate) fn check(self, file: &syn::File) -> Vec<Issue> {
+        match self {
+            Rule::RulesOfHooks => {
+                let mut visit_hooks = VisitHooks::new();
+                visit_hooks.visit_file(file);
+                visit_hooks.issues
+            }
+            Rule::MissingKey => missing_key::check(file),
+            Rule::StaleStateInFuture => stale_state::check(file),
+            Rule::ReadGuardAcrossAwait => read_guard::check(file),
+            Rule::UnknownAttribute => attributes::check_unknown_attributes(file),
+            Rule::ClickWithoutRole => attributes::check_click_without_role(file),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Rule::ALL
+            .into_iter()
+            .find(|rule| rule.name() == s)
+            .ok_or_else(|| ConfigError::UnknownRule(s.to_string()))
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The rules to check and their severities.
+///
+/// Rules that aren't configured use their [`Rule::default_severity`].
+pub struct RuleSet {
+    severities: HashMap<Rule, Severity>,
+}
+
+impl RuleSet {
+    /// Create a rule set from `rule = "level"` pairs, like the `[check.rules]` table of `Dioxus.toml`.
+    pub fn from_levels<K: AsRef<str>, V: AsRef<str>>(
+        levels: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self, ConfigError> {
+        let mut rules = Self::default();
+        for (rule, level) in levels {
+            rules.set_severity(rule.as_ref().parse()?, level.as_ref().parse()?);
+        }
+        Ok(rules)
+    }
+
+    /// Change the severity of a rule.
+    pub fn set_severity(&mut self, rule: Rule, severity: Severity) {
+        self.severities.insert(rule, severity);
+    }
+
+    /// The severity of a rule.
+    pub fn severity(&self, rule: Rule) -> Severity {
+        self.severities
+            .get(&rule)
+            .copied()
+            .unwrap_or_else(|| rule.default_severity())
+    }
+
+    /// The rules that aren't allowed.
+    pub fn enabled(&self) -> impl Iterator<Item = Rule> + '_ {
+        Rule::ALL
+            .into_iter()
+            .filter(|rule| self.severity(*rule) != Severity::Allow)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An error in the configuration of the rules.
+pub enum ConfigError {
+    UnknownRule(String),
+    UnknownSeverity(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::UnknownRule(rule) => write!(f, "unknown rule `{rule}`"),
+            ConfigError::UnknownSeverity(severity) => write!(
+                f,
+                "unknown severity `{severity}` (expected `allow`, `warn` or `error`)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_set_from_levels() {
+        let rules =
+            RuleSet::from_levels([("missing-key", "error"), ("rules-of-hooks", "allow")]).unwrap();
+
+        assert_eq!(rules.severity(Rule::MissingKey), Severity::Error);
+        assert_eq!(rules.severity(Rule::ClickWithoutRole), Severity::Warning);
+        assert!(!rules.enabled().any(|rule| rule == Rule::RulesOfHooks));
+    }
+
+    #[test]
+    fn test_rule_set_unknown_rule() {
+        assert_eq!(
+            RuleSet::from_levels([("missing-keys", "error")]),
+            Err(ConfigError::UnknownRule("missing-keys".to_string()))
+        );
+        assert_eq!(
+            RuleSet::from_levels([("missing-key", "loud")]),
+            Err(ConfigError::UnknownSeverity("loud".to_string()))
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use dioxus_html::HtmlCtx;
+use dioxus_rsx::{AttributeType, Element, ElementAttrName, ElementName, HotReloadingContext};
+use syn::visit::Visit;
+
+use crate::{
+    issues::Issue,
+    metadata::AttributeInfo,
+    rsx::{for_each_element, parse_rsx, visit_rsx_exprs},
+};
+
+/// Elements that users can't interact with unless they are given a role.
+const NON_INTERACTIVE_ELEMENTS: &[&str] = &[
+    "div", "span", "p", "li", "ul", "ol", "img", "section", "article", "header", "footer", "main",
+    "nav", "aside", "h1", "h2", "h3", "h4", "h5", "h6", "label", "table", "tr", "td",
+];
+
+/// Finds attributes that the element doesn't have.
+pub(super) fn check_unknown_attributes(file: &syn::File) -> Vec<Issue> {
+    check_elements(file, |element, issues| {
+        let Some(element_name) = known_element_name(element) else {
+            return;
+        };
+
+        for (name, ident) in builtin_attributes(element) {
+            // Events and other special attributes are checked by the rsx! macro itself
+            if name.starts_with("on") || HtmlCtx::map_attribute(&element_name, &name).is_some() {
+                continue;
+            }
+
+            let suggestion = suggest_attribute(&element_name, &name);
+            issues.push(Issue::UnknownAttribute(AttributeInfo::new(
+                element_name.clone(),
+                name,
+                ident.span().into(),
+                suggestion,
+            )));
+        }
+    })
+}
+
+/// Finds `onclick` handlers on non-interactive elements which don't have a role.
+pub(super) fn check_click_without_role(file: &syn::File) -> Vec<Issue> {
+    check_elements(file, |element, issues| {
+        let Some(element_name) = known_element_name(element) else {
+            return;
+        };
+        if !NON_INTERACTIVE_ELEMENTS.contains(&element_name.as_str()) {
+            return;
+        }
+
+        let has_role = element.attributes.iter().any(|attribute| match attribute {
+            AttributeType::Named(named) => match &named.attr.name {
+                ElementAttrName::BuiltIn(ident) => ident == "role",
+                ElementAttrName::Custom(name) => name.value() == "role",
+            },
+            AttributeType::Spread(_) => true,
+        });
+        if has_role {
+            return;
+        }
+
+        if let Some((name, ident)) = builtin_attributes(element).find(|(name, _)| name == "onclick")
+        {
+            issues.push(Issue::ClickWithoutRole(AttributeInfo::new(
+                element_name,
+                name,
+                ident.span().into(),
+                None,
+            )));
+        }
+    })
+}
+
+fn check_elements(file: &syn::File, check: fn(&Element, &mut Vec<Issue>)) -> Vec<Issue> {
+    let mut visit = VisitElements {
+        check,
+        issues: vec![],
+    };
+    visit.visit_file(file);
+    visit.issues
+}
+
+struct VisitElements {
+    check: fn(&Element, &mut Vec<Issue>),
+    issues: Vec<Issue>,
+}
+
+impl<'ast> Visit<'ast> for VisitElements {
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        if let Some(body) = parse_rsx(i) {
+            let check = self.check;
+            for_each_element(&body.roots, &mut |element| check(element, &mut self.issues));
+            visit_rsx_exprs(self, &body.roots);
+        }
+    }
+}
+
+/// The name of an element if it is one of the elements in `dioxus_html`.
+fn known_element_name(element: &Element) -> Option<String> {
+    match &element.name {
+        ElementName::Ident(ident) => {
+            let name = ident.to_string();
+            HtmlCtx::map_element(&name).map(|_| name)
+        }
+        ElementName::Custom(_) => None,
+    }
+}
+
+fn builtin_attributes(element: &Element) -> impl Iterator<Item = (String, &syn::Ident)> {
+    element
+        .attributes
+        .iter()
+        .filter_map(|attribute| match attribute {
+            AttributeType::Named(named) => match &named.attr.name {
+                ElementAttrName::BuiltIn(ident) => Some((ident.to_string(), ident)),
+                ElementAttrName::Custom(_) => None,
+            },
+            AttributeType::Spread(_) => None,
+        })
+}
+
+/// Find a known attribute that was probably meant, like `class` for `className` or `tabindex` for `tab_index`.
+fn suggest_attribute(element: &str, attribute: &str) -> Option<String> {
+    let lowercase: String = attribute
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    let mut snake_case = String::new();
+    for c in attribute.chars() {
+        if c.is_uppercase() {
+            if !snake_case.is_empty() {
+                snake_case.push('_');
+            }
+            snake_case.extend(c.to_lowercase());
+        } else if c == '-' {
+            snake_case.push('_');
+        } else {
+            snake_case.push(c);
+        }
+    }
+
+    let alias = match lowercase.as_str() {
+        "classname" | "classes" => Some("class"),
+        "htmlfor" | "for" => Some("r#for"),
+        "type" => Some("r#type"),
+        "async" => Some("r#async"),
+        _ => None,
+    };
+
+    let suggestion = [Some(lowercase.as_str()), Some(snake_case.as_str()), alias]
+        .into_iter()
+        .flatten()
+        .find(|candidate| {
+            *candidate != attribute && HtmlCtx::map_attribute(element, candidate).is_some()
+        })
+        .map(str::to_string);
+    suggestion
+}
@@ -0,0 +1,96 @@
+use dioxus_rsx::{BodyNode, ElementName};
+use syn::{spanned::Spanned, visit::Visit};
+
+use crate::{
+    issues::Issue,
+    metadata::RsxNodeInfo,
+    rsx::{if_chain_branches, parse_rsx, returned_macro, visit_rsx_exprs},
+};
+
+/// Finds elements and components created by an iterator without a `key`.
+pub(super) fn check(file: &syn::File) -> Vec<Issue> {
+    let mut visit = VisitMissingKeys { issues: vec![] };
+    visit.visit_file(file);
+    visit.issues
+}
+
+struct VisitMissingKeys {
+    issues: Vec<Issue>,
+}
+
+impl VisitMissingKeys {
+    /// Check the children of nodes for `for` loops.
+    fn check_nodes(&mut self, nodes: &[BodyNode]) {
+        for node in nodes {
+            match node {
+                BodyNode::Element(element) => self.check_nodes(&element.children),
+                BodyNode::Component(component) => self.check_nodes(&component.children),
+                BodyNode::ForLoop(for_loop) => {
+                    self.check_iterated(&for_loop.body);
+                    self.check_nodes(&for_loop.body);
+                }
+                BodyNode::IfChain(chain) => {
+                    for branch in if_chain_branches(chain) {
+                        self.check_nodes(branch);
+                    }
+                }
+                BodyNode::Text(_) | BodyNode::RawExpr(_) => {}
+            }
+        }
+    }
+
+    /// Check the nodes that are created for every item of an iterator.
+    fn check_iterated(&mut self, nodes: &[BodyNode]) {
+        for node in nodes {
+            match node {
+                BodyNode::Element(element) if element.key.is_none() => {
+                    let (name, span) = match &element.name {
+                        ElementName::Ident(ident) => (ident.to_string(), ident.span()),
+                        ElementName::Custom(name) => (name.value(), name.span()),
+                    };
+                    self.issues
+                        .push(Issue::MissingKey(RsxNodeInfo::new(name, span.into())));
+                }
+                BodyNode::Component(component) if component.key().is_none() => {
+                    let name = component
+                        .name
+                        .segments
+                        .last()
+                        .map(|segment| segment.ident.to_string())
+                        .unwrap_or_default();
+                    self.issues.push(Issue::MissingKey(RsxNodeInfo::new(
+                        name,
+                        component.name.span().into(),
+                    )));
+                }
+                BodyNode::IfChain(chain) => {
+                    for branch in if_chain_branches(chain) {
+                        self.check_iterated(branch);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for VisitMissingKeys {
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        if let Some(body) = parse_rsx(i) {
+            self.check_nodes(&body.roots);
+            visit_rsx_exprs(self, &body.roots);
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, i: &'ast syn::ExprMethodCall) {
+        // items.iter().map(|item| rsx! { … })
+        if i.method == "map" {
+            if let Some(syn::Expr::Closure(closure)) = i.args.first() {
+                if let Some(body) = returned_macro(&closure.body).and_then(parse_rsx) {
+                    self.check_iterated(&body.roots);
+                }
+            }
+        }
+        syn::visit::visit_expr_method_call(self, i);
+    }
+}
@@ -0,0 +1,133 @@
+use syn::{spanned::Spanned, visit::Visit};
+
+use crate::{
+    issues::Issue,
+    metadata::{GuardInfo, Span},
+    rsx::{parse_rsx, visit_rsx_exprs},
+};
+
+/// Finds guards from `.read()` that are still held when a future awaits.
+///
+/// Anything that writes to the value while the future is suspended will panic because it is already borrowed.
+pub(super) fn check(file: &syn::File) -> Vec<Issue> {
+    let mut visit = VisitReadGuards::default();
+    visit.visit_file(file);
+    visit.issues
+}
+
+#[derive(Default)]
+struct VisitReadGuards {
+    issues: Vec<Issue>,
+    async_depth: usize,
+}
+
+impl VisitReadGuards {
+    fn check_block(&mut self, block: &syn::Block) {
+        let mut guards: Vec<(String, Span)> = vec![];
+
+        for stmt in &block.stmts {
+            if let Some(await_span) = find_await(stmt) {
+                for (name, span) in guards.drain(..) {
+                    self.issues.push(Issue::ReadGuardAcrossAwait(GuardInfo::new(
+                        name,
+                        span,
+                        await_span.clone(),
+                    )));
+                }
+            }
+
+            match stmt {
+                syn::Stmt::Local(local) => {
+                    if let (syn::Pat::Ident(ident), Some(init)) = (&local.pat, &local.init) {
+                        let name = ident.ident.to_string();
+                        // Shadowing the guard doesn't drop it, but we only track the latest binding
+                        guards.retain(|(guard, _)| *guard != name);
+                        if is_read_call(&init.expr) {
+                            guards.push((name, init.expr.span().into()));
+                        }
+                    }
+                }
+                syn::Stmt::Expr(syn::Expr::Call(call), _) if is_drop_call(call) => {
+                    if let Some(syn::Expr::Path(path)) = call.args.first() {
+                        if let Some(ident) = path.path.get_ident() {
+                            guards.retain(|(guard, _)| ident != guard);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn is_read_call(expr: &syn::Expr) -> bool {
+    matches!(expr, syn::Expr::MethodCall(call) if call.method == "read" && call.args.is_empty())
+}
+
+fn is_drop_call(call: &syn::ExprCall) -> bool {
+    matches!(&*call.func, syn::Expr::Path(path) if path.path.is_ident("drop"))
+}
+
+/// Find the first `.await` in a statement, ignoring nested futures and closures which don't suspend the current one.
+fn find_await(stmt: &syn::Stmt) -> Option<Span> {
+    #[derive(Default)]
+    struct FindAwait {
+        span: Option<Span>,
+    }
+
+    impl<'ast> Visit<'ast> for FindAwait {
+        fn visit_expr_await(&mut self, i: &'ast syn::ExprAwait) {
+            if self.span.is_none() {
+                self.span = Some(i.span().into());
+            }
+        }
+
+        fn visit_expr_async(&mut self, _: &'ast syn::ExprAsync) {}
+
+        fn visit_expr_closure(&mut self, _: &'ast syn::ExprClosure) {}
+
+        fn visit_item(&mut self, _: &'ast syn::Item) {}
+    }
+
+    let mut find = FindAwait::default();
+    find.visit_stmt(stmt);
+    find.span
+}
+
+impl<'ast> Visit<'ast> for VisitReadGuards {
+    fn visit_item_fn(&mut self, i: &'ast syn::ItemFn) {
+        // A sync function inside of a future can't await
+        let async_depth = std::mem::replace(
+            &mut self.async_depth,
+            usize::from(i.sig.asyncness.is_some()),
+        );
+        syn::visit::visit_item_fn(self, i);
+        self.async_depth = async_depth;
+    }
+
+    fn visit_expr_async(&mut self, i: &'ast syn::ExprAsync) {
+        self.async_depth += 1;
+        syn::visit::visit_expr_async(self, i);
+        self.async_depth -= 1;
+    }
+
+    fn visit_expr_closure(&mut self, i: &'ast syn::ExprClosure) {
+        let async_depth =
+            std::mem::replace(&mut self.async_depth, usize::from(i.asyncness.is_some()));
+        syn::visit::visit_expr_closure(self, i);
+        self.async_depth = async_depth;
+    }
+
+    fn visit_block(&mut self, i: &'ast syn::Block) {
+        if self.async_depth > 0 {
+            self.check_block(i);
+        }
+        syn::visit::visit_block(self, i);
+    }
+
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        if let Some(body) = parse_rsx(i) {
+            visit_rsx_exprs(self, &body.roots);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+
+use syn::{spanned::Spanned, visit::Visit};
+
+use crate::{
+    issues::Issue,
+    metadata::StateReadInfo,
+    rsx::{parse_rsx, visit_rsx_exprs},
+};
+
+/// Finds `use_state` values that are read inside a future.
+///
+/// A future captures the value of the state when it is created, so reading it with `*state` or `state.get()` won't
+/// see updates made while the future runs. `state.current()` always returns the latest value.
+pub(super) fn check(file: &syn::File) -> Vec<Issue> {
+    let mut visit = VisitStaleState::default();
+    visit.visit_file(file);
+    visit.issues
+}
+
+#[derive(Default)]
+struct VisitStaleState {
+    issues: Vec<Issue>,
+    /// The names of the `use_state` values in each function we are inside of.
+    states: Vec<HashSet<String>>,
+    async_depth: usize,
+}
+
+impl VisitStaleState {
+    fn state_name(&self, expr: &syn::Expr) -> Option<String> {
+        if let syn::Expr::Path(path) = expr {
+            let ident = path.path.get_ident()?.to_string();
+            if self.states.last()?.contains(&ident) {
+                return Some(ident);
+            }
+        }
+        None
+    }
+}
+
+fn is_use_state_call(expr: &syn::Expr) -> bool {
+    if let syn::Expr::Call(call) = expr {
+        if let syn::Expr::Path(path) = &*call.func {
+            let name = path.path.segments.last();
+            return matches!(name, Some(segment) if segment.ident == "use_state");
+        }
+    }
+    false
+}
+
+impl<'ast> Visit<'ast> for VisitStaleState {
+    fn visit_item_fn(&mut self, i: &'ast syn::ItemFn) {
+        self.states.push(HashSet::new());
+        syn::visit::visit_item_fn(self, i);
+        self.states.pop();
+    }
+
+    fn visit_local(&mut self, i: &'ast syn::Local) {
+        if let (syn::Pat::Ident(ident), Some(init)) = (&i.pat, &i.init) {
+            if is_use_state_call(&init.expr) {
+                if let Some(states) = self.states.last_mut() {
+                    states.insert(ident.ident.to_string());
+                }
+            }
+        }
+        syn::visit::visit_local(self, i);
+    }
+
+    fn visit_expr_async(&mut self, i: &'ast syn::ExprAsync) {
+        self.async_depth += 1;
+        syn::visit::visit_expr_async(self, i);
+        self.async_depth -= 1;
+    }
+
+    fn visit_expr_unary(&mut self, i: &'ast syn::ExprUnary) {
+        if self.async_depth > 0 && matches!(i.op, syn::UnOp::Deref(_)) {
+            if let Some(name) = self.state_name(&i.expr) {
+                self.issues
+                    .push(Issue::StaleStateInFuture(StateReadInfo::new(
+                        name,
+                        i.span().into(),
+                        true,
+                    )));
+                return;
+            }
+        }
+        syn::visit::visit_expr_unary(self, i);
+    }
+
+    fn visit_expr_method_call(&mut self, i: &'ast syn::ExprMethodCall) {
+        if self.async_depth > 0 && i.method == "get" && i.args.is_empty() {
+            if let Some(name) = self.state_name(&i.receiver) {
+                self.issues
+                    .push(Issue::StaleStateInFuture(StateReadInfo::new(
+                        name,
+                        i.span().into(),
+                        false,
+                    )));
+                return;
+            }
+        }
+        syn::visit::visit_expr_method_call(self, i);
+    }
+
+    fn visit_macro(&mut self, i: &'ast syn::Macro) {
+        if let Some(body) = parse_rsx(i) {
+            visit_rsx_exprs(self, &body.roots);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// The name of the file in the out dir that release builds write the [`StaticAssetManifest`] to.
+pub const STATIC_ASSET_MANIFEST: &str = "__assets_manifest.json";
+
+/// The fingerprinted copies of the assets of a release build.
+///
+/// Release builds copy every asset to a name that includes a hash of its contents, like `style.3f9a1c0b2e4d5f67.css`,
+/// and point the generated `index.html` at those copies. A fingerprinted file never changes, so servers can tell
+/// browsers to cache it forever. The original files are kept for code that refers to assets by name.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaticAssetManifest {
+    /// The path of each asset mapped to the path of its fingerprinted copy, both relative to the out dir and
+    /// separated by `/`.
+    pub assets: BTreeMap<String, String>,
+}
+
+impl StaticAssetManifest {
+    /// Load the manifest from an out dir. Builds without fingerprinted assets have an empty manifest.
+    pub fn load(out_dir: impl AsRef<Path>) -> Self {
+        let path = out_dir.as_ref().join(STATIC_ASSET_MANIFEST);
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|err| {
+            tracing::error!("Failed to read the asset manifest at {:?}: {}", path, err);
+            Self::default()
+        })
+    }
+
+    /// Write the manifest to an out dir.
+    pub fn save(&self, out_dir: impl AsRef<Path>) -> std::io::Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(out_dir.as_ref().join(STATIC_ASSET_MANIFEST), contents)
+    }
+
+    /// The path of the fingerprinted copy of an asset.
+    pub fn fingerprinted(&self, asset: &str) -> Option<&str> {
+        self.assets.get(asset).map(String::as_str)
+    }
+
+    /// The paths of all fingerprinted copies, which can be cached forever.
+    pub fn fingerprinted_files(&self) -> impl Iterator<Item = &str> {
+        self.assets.values().map(String::as_str)
+    }
+
+    /// Check if a path is a fingerprinted copy.
+    pub fn is_fingerprinted(&self, path: &str) -> bool {
+        self.fingerprinted_files().any(|file| file == path)
+    }
+}
@@ -27,6 +27,9 @@ pub struct DioxusConfig {
     #[serde(default)]
     pub bundle: BundleConfig,
 
+    #[serde(default)]
+    pub check: CheckConfig,
+
     #[cfg(feature = "cli")]
     #[serde(default = "default_plugin")]
     pub plugin: toml::Value,
@@ -209,6 +212,7 @@ impl Default for DioxusConfig {
                 publisher: Some(name),
                 ..Default::default()
             },
+            check: CheckConfig::default(),
             #[cfg(feature = "cli")]
             plugin: toml::Value::Table(toml::map::Map::new()),
         }
@@ -336,6 +340,14 @@ pub struct WebHttpsConfig {
     pub cert_path: Option<String>,
 }
 
+/// The configuration of `dx check`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct CheckConfig {
+    /// The severity of each rule, e.g. `missing-key = "error"`. Rules can be turned off with `"allow"`.
+    #[serde(default)]
+    pub rules: std::collections::HashMap<String, String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrateConfig {
     pub crate_dir: PathBuf,
@@ -8,6 +8,8 @@ mod bundle;
 pub use bundle::*;
 mod cargo;
 pub use cargo::*;
+mod assets;
+pub use assets::*;
 
 #[doc(hidden)]
 pub mod __private {
@@ -15,6 +17,12 @@ pub mod __private {
 
     pub const CONFIG_ENV: &str = "DIOXUS_CONFIG";
 
+    /// Set by `dx build --ssg` to the directory a fullstack server should pre-render its static routes into instead of serving
+    pub const SSG_ENV: &str = "DIOXUS_SSG";
+
+    /// Set by `dx build --ssg --base-url` to the url the static site is hosted at, which is used to generate a sitemap.xml
+    pub const SSG_BASE_URL_ENV: &str = "DIOXUS_SSG_BASE_URL";
+
     pub fn save_config(config: &CrateConfig) -> CrateConfigDropGuard {
         std::env::set_var(CONFIG_ENV, serde_json::to_string(config).unwrap());
         CrateConfigDropGuard
@@ -1,3 +1,4 @@
+use crate::report::{json_report, line_diff, sarif_report, Diagnostic, ReportFormat, FORMAT_RULE};
 use dioxus_autofmt::{IndentOptions, IndentType};
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use std::{fs, path::Path, process::exit};
@@ -26,6 +27,10 @@ pub struct Autoformat {
     /// Split attributes in lines or not
     #[clap(short, long, default_value = "false")]
     pub split_line_attributes: bool,
+
+    /// How to report unformatted blocks in check mode
+    #[clap(long, value_enum, default_value_t)]
+    pub format: ReportFormat,
 }
 
 impl Autoformat {
@@ -36,19 +41,19 @@ impl Autoformat {
             raw,
             file,
             split_line_attributes,
-            ..
+            format,
         } = self;
 
         // Default to formatting the project
         if raw.is_none() && file.is_none() {
-            if let Err(e) = autoformat_project(check, split_line_attributes).await {
+            if let Err(e) = autoformat_project(check, split_line_attributes, format).await {
                 eprintln!("error formatting project: {}", e);
                 exit(1);
             }
         }
 
         if let Some(raw) = raw {
-            let indent = indentation_for(".", self.split_line_attributes)?;
+            let indent = indentation_for(".", split_line_attributes)?;
             if let Some(inner) = dioxus_autofmt::fmt_block(&raw, 0, indent) {
                 println!("{}", inner);
             } else {
@@ -60,14 +65,19 @@ impl Autoformat {
 
         // Format single file
         if let Some(file) = file {
-            refactor_file(file, split_line_attributes)?;
+            refactor_file(file, split_line_attributes, check, format)?;
         }
 
         Ok(())
     }
 }
 
-fn refactor_file(file: String, split_line_attributes: bool) -> Result<(), Error> {
+fn refactor_file(
+    file: String,
+    split_line_attributes: bool,
+    check: bool,
+    format: ReportFormat,
+) -> Result<(), Error> {
     let indent = indentation_for(".", split_line_attributes)?;
     let file_content = if file == "-" {
         let mut contents = String::new();
@@ -81,6 +91,11 @@ fn refactor_file(file: String, split_line_attributes: bool) -> Result<(), Error>
         exit(1);
     };
     let edits = dioxus_autofmt::fmt_file(&s, indent);
+
+    if check {
+        report_unformatted(unformatted_blocks(Path::new(&file), &s, &edits), format);
+    }
+
     let out = dioxus_autofmt::apply_formats(&s, edits);
 
     if file == "-" {
@@ -143,12 +158,75 @@ async fn format_file(
     Ok(len)
 }
 
+/// Find the blocks of a file that aren't formatted, with a diff of the changes formatting would make
+async fn check_file_format(
+    path: PathBuf,
+    indent: IndentOptions,
+) -> Result<Vec<(Diagnostic, String)>, tokio::io::Error> {
+    let contents = tokio::fs::read_to_string(&path).await?;
+    let edits = dioxus_autofmt::fmt_file(&contents, indent);
+    Ok(unformatted_blocks(&path, &contents, &edits))
+}
+
+fn unformatted_blocks(
+    path: &Path,
+    contents: &str,
+    edits: &[dioxus_autofmt::FormattedBlock],
+) -> Vec<(Diagnostic, String)> {
+    edits
+        .iter()
+        .map(|block| {
+            let diff = line_diff(&contents[block.start..block.end], &block.formatted);
+            (
+                Diagnostic::from_formatted_block(path, contents, block),
+                diff,
+            )
+        })
+        .collect()
+}
+
+/// Report the blocks found in check mode, and exit with 1 if there are any
+fn report_unformatted(blocks: Vec<(Diagnostic, String)>, format: ReportFormat) -> ! {
+    let diagnostics = || blocks.iter().map(|(d, _)| d.clone()).collect::<Vec<_>>();
+
+    match format {
+        ReportFormat::Text => {
+            for (diagnostic, diff) in &blocks {
+                println!(
+                    "Diff in {} at line {}:\n{}",
+                    diagnostic.file, diagnostic.start.line, diff
+                );
+            }
+
+            let mut files = blocks.iter().map(|(d, _)| &d.file).collect::<Vec<_>>();
+            files.dedup();
+            if !files.is_empty() {
+                eprintln!("{} files needed formatting", files.len());
+            }
+        }
+        ReportFormat::Json => println!("{}", json_report(&diagnostics())),
+        ReportFormat::Sarif => {
+            let rules = [(FORMAT_RULE, "rsx! blocks should be formatted with dx fmt")];
+            println!("{}", sarif_report("dx fmt", &rules, &diagnostics()));
+        }
+    }
+
+    match blocks.is_empty() {
+        true => exit(0),
+        false => exit(1),
+    }
+}
+
 /// Read every .rs file accessible when considering the .gitignore and try to format it
 ///
 /// Runs using Tokio for multithreading, so it should be really really fast
 ///
 /// Doesn't do mod-descending, so it will still try to format unreachable files. TODO.
-async fn autoformat_project(check: bool, split_line_attributes: bool) -> Result<()> {
+async fn autoformat_project(
+    check: bool,
+    split_line_attributes: bool,
+    format: ReportFormat,
+) -> Result<()> {
     let crate_config = dioxus_cli_config::CrateConfig::new(None)?;
 
     let files_to_format = get_project_files(&crate_config);
@@ -159,7 +237,32 @@ async fn autoformat_project(check: bool, split_line_attributes: bool) -> Result<
 
     let indent = indentation_for(&files_to_format[0], split_line_attributes)?;
 
-    let counts = files_to_format
+    // Check mode only reports the unformatted blocks instead of rewriting the files
+    if check {
+        let blocks = files_to_format
+            .into_iter()
+            .map(|path| async {
+                let path_clone = path.clone();
+                match tokio::spawn(check_file_format(path, indent.clone())).await {
+                    Ok(Ok(blocks)) => blocks,
+                    Ok(Err(err)) => {
+                        eprintln!("error checking file: {}\n{err}", path_clone.display());
+                        vec![]
+                    }
+                    Err(err) => {
+                        eprintln!("error checking file: {}\n{err}", path_clone.display());
+                        vec![]
+                    }
+                }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .collect::<Vec<_>>()
+            .await;
+
+        report_unformatted(blocks.into_iter().flatten().collect(), format);
+    }
+
+    files_to_format
         .into_iter()
         .map(|path| async {
             let path_clone = path.clone();
@@ -181,13 +284,6 @@ async fn autoformat_project(check: bool, split_line_attributes: bool) -> Result<
         .collect::<Vec<_>>()
         .await;
 
-    let files_formatted: usize = counts.into_iter().flatten().sum();
-
-    if files_formatted > 0 && check {
-        eprintln!("{} files needed formatting", files_formatted);
-        exit(1);
-    }
-
     Ok(())
 }
 
@@ -284,6 +380,7 @@ async fn test_auto_fmt() {
         raw: Some(test_rsx),
         file: None,
         split_line_attributes: false,
+        format: ReportFormat::Text,
     };
 
     fmt.autoformat().await.unwrap();
@@ -49,6 +49,12 @@ impl Build {
 
         crate_config.set_cargo_args(self.build.cargo_args.clone());
 
+        if self.build.ssg && platform != Platform::Fullstack {
+            return Err(Error::BuildFailed(
+                "Static site generation is only supported for fullstack apps".to_string(),
+            ));
+        }
+
         // #[cfg(feature = "plugin")]
         // let _ = PluginManager::on_build_start(&crate_config, &platform);
 
@@ -100,9 +106,56 @@ impl Build {
         )?;
         file.write_all(temp.as_bytes())?;
 
+        // The pre-rendered pages are fingerprinted and precompressed with the rest of the out dir
+        if self.build.ssg {
+            generate_static_site(&crate_config, self.build.base_url.as_deref())?;
+        }
+
+        // Desktop apps load their assets from disk, so only served assets are fingerprinted
+        if crate_config.release && platform != Platform::Desktop {
+            crate::fingerprint::fingerprint_assets(&crate_config)?;
+        } else {
+            crate::fingerprint::remove_fingerprinted_assets(&crate_config)?;
+        }
+
         // #[cfg(feature = "plugin")]
         // let _ = PluginManager::on_build_finish(&crate_config, &platform);
 
         Ok(())
     }
 }
+
+/// Run the server binary of a fullstack app in static site generation mode, which renders every static route into the out dir and exits
+fn generate_static_site(config: &CrateConfig, base_url: Option<&str>) -> Result<()> {
+    use dioxus_cli_config::{
+        __private::{SSG_BASE_URL_ENV, SSG_ENV},
+        ExecutableType,
+    };
+
+    log::info!("📄 Generating static site...");
+
+    let (ExecutableType::Binary(name) | ExecutableType::Lib(name) | ExecutableType::Example(name)) =
+        &config.executable;
+    let mut server = config.out_dir().join(name);
+    if cfg!(windows) {
+        server.set_extension("exe");
+    }
+
+    let mut command = std::process::Command::new(&server);
+    command
+        .current_dir(&config.crate_dir)
+        .env(SSG_ENV, config.out_dir());
+    if let Some(base_url) = base_url {
+        command.env(SSG_BASE_URL_ENV, base_url);
+    }
+
+    let status = command.status()?;
+    if !status.success() {
+        return Err(Error::BuildFailed(format!(
+            "Static site generation failed: {} exited with {status}",
+            server.display()
+        )));
+    }
+
+    Ok(())
+}
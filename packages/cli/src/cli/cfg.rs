@@ -49,6 +49,15 @@ pub struct ConfigOptsBuild {
     #[clap(long, default_value_t = { "ssr".to_string() })]
     pub server_feature: String,
 
+    /// Pre-render every static route of a fullstack app into the out dir after building [default: false]
+    #[clap(long)]
+    #[serde(default)]
+    pub ssg: bool,
+
+    /// The url the static site is hosted at. When set, `--ssg` also writes a sitemap.xml
+    #[clap(long, requires = "ssg")]
+    pub base_url: Option<String>,
+
     /// Rustc platform triple
     #[clap(long)]
     pub target: Option<String>,
@@ -72,6 +81,8 @@ impl From<ConfigOptsServe> for ConfigOptsBuild {
             server_feature: serve.server_feature,
             skip_assets: serve.skip_assets,
             force_debug: serve.force_debug,
+            ssg: false,
+            base_url: None,
             cargo_args: serve.cargo_args,
         }
     }
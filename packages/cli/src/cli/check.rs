@@ -1,3 +1,5 @@
+use crate::report::{json_report, sarif_report, Diagnostic, ReportFormat};
+use dioxus_check::{IssueReport, Rule, RuleSet};
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use std::{path::Path, process::exit};
 
@@ -12,21 +14,37 @@ pub struct Check {
     /// Input file
     #[clap(short, long)]
     pub file: Option<PathBuf>,
+
+    /// Apply the fixes for issues that can be fixed automatically
+    #[clap(long)]
+    pub fix: bool,
+
+    /// How to report the issues
+    #[clap(long, value_enum, default_value_t)]
+    pub format: ReportFormat,
 }
 
 impl Check {
     // Todo: check the entire crate
     pub async fn check(self) -> Result<()> {
+        let rules = match load_rules() {
+            Ok(rules) => rules,
+            Err(e) => {
+                eprintln!("invalid check config: {}", e);
+                exit(1);
+            }
+        };
+
         match self.file {
             // Default to checking the project
             None => {
-                if let Err(e) = check_project_and_report().await {
+                if let Err(e) = check_project_and_report(rules, self.fix, self.format).await {
                     eprintln!("error checking project: {}", e);
                     exit(1);
                 }
             }
             Some(file) => {
-                if let Err(e) = check_file_and_report(file).await {
+                if let Err(e) = check_file_and_report(file, rules, self.fix, self.format).await {
                     eprintln!("failed to check file: {}", e);
                     exit(1);
                 }
@@ -37,8 +55,19 @@ impl Check {
     }
 }
 
-async fn check_file_and_report(path: PathBuf) -> Result<()> {
-    check_files_and_report(vec![path]).await
+/// Load the rules from the `[check.rules]` table in Dioxus.toml
+pub(crate) fn load_rules() -> Result<RuleSet> {
+    let config = dioxus_cli_config::DioxusConfig::load(None)?.unwrap_or_default();
+    RuleSet::from_levels(&config.check.rules).map_err(|e| Error::Unique(e.to_string()))
+}
+
+async fn check_file_and_report(
+    path: PathBuf,
+    rules: RuleSet,
+    fix: bool,
+    format: ReportFormat,
+) -> Result<()> {
+    check_files_and_report(vec![path], rules, fix, format).await
 }
 
 /// Read every .rs file accessible when considering the .gitignore and check it
@@ -46,33 +75,46 @@ async fn check_file_and_report(path: PathBuf) -> Result<()> {
 /// Runs using Tokio for multithreading, so it should be really really fast
 ///
 /// Doesn't do mod-descending, so it will still try to check unreachable files. TODO.
-async fn check_project_and_report() -> Result<()> {
+async fn check_project_and_report(rules: RuleSet, fix: bool, format: ReportFormat) -> Result<()> {
     let crate_config = dioxus_cli_config::CrateConfig::new(None)?;
 
     let mut files_to_check = vec![];
     collect_rs_files(&crate_config.crate_dir, &mut files_to_check);
-    check_files_and_report(files_to_check).await
+    check_files_and_report(files_to_check, rules, fix, format).await
 }
 
 /// Check a list of files and report the issues.
-async fn check_files_and_report(files_to_check: Vec<PathBuf>) -> Result<()> {
+///
+/// If `fix` is set, the fixes for the issues are written back to the files and only the remaining issues are reported.
+async fn check_files_and_report(
+    files_to_check: Vec<PathBuf>,
+    rules: RuleSet,
+    fix: bool,
+    format: ReportFormat,
+) -> Result<()> {
     let issue_reports = files_to_check
         .into_iter()
         .filter(|file| file.components().all(|f| f.as_os_str() != "target"))
-        .map(|path| async move {
-            let _path = path.clone();
-            let res = tokio::spawn(async move {
-                tokio::fs::read_to_string(&_path)
-                    .await
-                    .map(|contents| dioxus_check::check_file(_path, &contents))
-            })
-            .await;
-
-            if res.is_err() {
-                eprintln!("error checking file: {}", path.display());
-            }
+        .map(|path| {
+            let rules = rules.clone();
+            async move {
+                let _path = path.clone();
+                let res = tokio::spawn(async move {
+                    let contents = tokio::fs::read_to_string(&_path).await?;
+                    let report = dioxus_check::check_file_with_rules(_path, &contents, &rules);
+                    if fix {
+                        return fix_file(report, &rules).await;
+                    }
+                    Ok((report, 0))
+                })
+                .await;
+
+                if res.is_err() {
+                    eprintln!("error checking file: {}", path.display());
+                }
 
-            res
+                res
+            }
         })
         .collect::<FuturesUnordered<_>>()
         .collect::<Vec<_>>()
@@ -85,24 +127,69 @@ async fn check_files_and_report(files_to_check: Vec<PathBuf>) -> Result<()> {
         .flatten()
         .collect::<Vec<_>>();
 
-    let total_issues = issue_reports.iter().map(|r| r.issues.len()).sum::<usize>();
+    let total_issues = issue_reports
+        .iter()
+        .map(|(r, _)| r.issues.len())
+        .sum::<usize>();
+    let total_fixed = issue_reports.iter().map(|(_, fixed)| fixed).sum::<usize>();
+    let has_errors = issue_reports.iter().any(|(r, _)| r.has_errors());
+
+    let diagnostics = || {
+        issue_reports
+            .iter()
+            .flat_map(|(report, _)| Diagnostic::from_issue_report(report))
+            .collect::<Vec<_>>()
+    };
+
+    match format {
+        ReportFormat::Text => {
+            for (report, _) in issue_reports.iter() {
+                if !report.issues.is_empty() {
+                    println!("{}", report);
+                }
+            }
+
+            match total_fixed {
+                0 => {}
+                1 => println!("1 issue fixed."),
+                _ => println!("{} issues fixed.", total_fixed),
+            }
 
-    for report in issue_reports.into_iter() {
-        if !report.issues.is_empty() {
-            println!("{}", report);
+            match total_issues {
+                0 => println!("No issues found."),
+                1 => println!("1 issue found."),
+                _ => println!("{} issues found.", total_issues),
+            }
+        }
+        ReportFormat::Json => println!("{}", json_report(&diagnostics())),
+        ReportFormat::Sarif => {
+            let rules = Rule::ALL.map(|rule| (rule.name(), rule.description()));
+            println!("{}", sarif_report("dx check", &rules, &diagnostics()));
         }
     }
 
-    match total_issues {
-        0 => println!("No issues found."),
-        1 => println!("1 issue found."),
-        _ => println!("{} issues found.", total_issues),
+    // Warnings are reported, but only errors fail the check
+    match has_errors {
+        false => exit(0),
+        true => exit(1),
     }
+}
 
-    match total_issues {
-        0 => exit(0),
-        _ => exit(1),
-    }
+/// Write the fixes in the report to its file, and check the fixed file again.
+///
+/// Returns the new report and the number of issues that were fixed.
+async fn fix_file(report: IssueReport, rules: &RuleSet) -> std::io::Result<(IssueReport, usize)> {
+    let Some(fixed) = report.fixed_file_content() else {
+        return Ok((report, 0));
+    };
+
+    tokio::fs::write(&report.path, &fixed).await?;
+    let fixed_report = dioxus_check::check_file_with_rules(report.path.clone(), &fixed, rules);
+    let fixed_issues = report
+        .issues
+        .len()
+        .saturating_sub(fixed_report.issues.len());
+    Ok((fixed_report, fixed_issues))
 }
 
 fn collect_rs_files(folder: &Path, files: &mut Vec<PathBuf>) {
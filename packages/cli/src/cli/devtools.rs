@@ -0,0 +1,21 @@
+use super::*;
+
+/// Inspect the component tree of a running app in the terminal.
+#[derive(Clone, Debug, Parser)]
+#[clap(name = "devtools")]
+pub struct Devtools {
+    /// The address the app serves devtools on. Defaults to `DIOXUS_DEVTOOLS_ADDR` or 127.0.0.1:7373
+    #[clap(long)]
+    pub addr: Option<String>,
+}
+
+impl Devtools {
+    pub async fn devtools(self) -> Result<()> {
+        let addr = self.addr.unwrap_or_else(dioxus_devtools::devtools_addr);
+
+        // The terminal renderer runs its own runtime, so it can't run on the runtime of the cli
+        tokio::task::spawn_blocking(move || dioxus_devtools::client::launch(addr))
+            .await
+            .map_err(|err| Error::Other(err.into()))
+    }
+}
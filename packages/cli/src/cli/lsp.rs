@@ -0,0 +1,13 @@
+use super::*;
+
+/// Run a language server for rsx! over stdin and stdout, for editors.
+#[derive(Clone, Debug, Parser)]
+#[clap(name = "lsp")]
+pub struct Lsp {}
+
+impl Lsp {
+    pub async fn lsp(self) -> Result<()> {
+        crate::lsp::run().await;
+        Ok(())
+    }
+}
@@ -6,7 +6,9 @@ pub mod check;
 pub mod clean;
 pub mod config;
 pub mod create;
+pub mod devtools;
 pub mod init;
+pub mod lsp;
 pub mod plugin;
 pub mod serve;
 pub mod translate;
@@ -81,6 +83,14 @@ pub enum Commands {
     #[clap(name = "check")]
     Check(check::Check),
 
+    /// Inspect the component tree of a running app.
+    #[clap(name = "devtools")]
+    Devtools(devtools::Devtools),
+
+    /// Run a language server for rsx!.
+    #[clap(name = "lsp")]
+    Lsp(lsp::Lsp),
+
     /// Dioxus config file controls.
     #[clap(subcommand)]
     Config(config::Config),
@@ -104,6 +114,8 @@ impl Display for Commands {
             Commands::Version(_) => write!(f, "version"),
             Commands::Autoformat(_) => write!(f, "fmt"),
             Commands::Check(_) => write!(f, "check"),
+            Commands::Devtools(_) => write!(f, "devtools"),
+            Commands::Lsp(_) => write!(f, "lsp"),
             Commands::Bundle(_) => write!(f, "bundle"),
 
             #[cfg(feature = "plugin")]
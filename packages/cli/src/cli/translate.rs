@@ -1,6 +1,7 @@
-use std::process::exit;
+use std::{path::Path, process::exit};
 
 use dioxus_rsx::{BodyNode, CallBody};
+use syn::visit::Visit;
 
 use super::*;
 
@@ -24,18 +25,54 @@ pub struct Translate {
     /// Output file, stdout if not present
     #[arg(short, long)]
     pub output: Option<PathBuf>,
+
+    /// The language of the input, guessed from the extension of the input file if not present
+    #[clap(long, value_enum)]
+    pub from: Option<Language>,
+}
+
+/// The languages `dx translate` reads
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Language {
+    /// HTML, translated to rsx
+    Html,
+    /// JSX or TSX as React components are written, translated to rsx
+    Jsx,
+    /// Rsx, rendered back to HTML
+    Rsx,
+}
+
+impl Language {
+    fn of_file(file: Option<&str>) -> Self {
+        let extension = file.and_then(|file| Path::new(file).extension()?.to_str());
+        match extension {
+            Some("jsx" | "tsx") => Language::Jsx,
+            Some("rs") => Language::Rsx,
+            _ => Language::Html,
+        }
+    }
 }
 
 impl Translate {
     pub fn translate(self) -> Result<()> {
+        let from = self
+            .from
+            .unwrap_or_else(|| Language::of_file(self.file.as_deref()));
+
         // Get the right input for the translation
         let contents = determine_input(self.file, self.raw)?;
 
-        // Ensure we're loading valid HTML
-        let dom = html_parser::Dom::parse(&contents)?;
+        let out = match from {
+            Language::Html => {
+                // Ensure we're loading valid HTML
+                let dom = html_parser::Dom::parse(&contents)?;
 
-        // Convert the HTML to RSX
-        let out = convert_html_to_formatted_rsx(&dom, self.component);
+                // Convert the HTML to RSX
+                convert_html_to_formatted_rsx(&dom, self.component)?
+            }
+            Language::Jsx => convert_jsx_to_formatted_rsx(&contents, self.component)?,
+            Language::Rsx => convert_rsx_to_html(&contents)?,
+        };
 
         // Write the output
         match self.output {
@@ -47,24 +84,75 @@ impl Translate {
     }
 }
 
-pub fn convert_html_to_formatted_rsx(dom: &Dom, component: bool) -> String {
-    let callbody = rsx_rosetta::rsx_from_html(dom);
+pub fn convert_html_to_formatted_rsx(dom: &Dom, component: bool) -> Result<String> {
+    format_rsx_source(&rsx_rosetta::rsx_source_from_html(dom), component)
+}
+
+pub fn convert_jsx_to_formatted_rsx(jsx: &str, component: bool) -> Result<String> {
+    let source = rsx_rosetta::rsx_source_from_jsx(jsx)
+        .map_err(|e| Error::ParseError(format!("invalid JSX: {}", e)))?;
+    format_rsx_source(&source, component)
+}
+
+/// Format the rsx source rosetta writes, which keeps the comments of the input
+fn format_rsx_source(source: &str, component: bool) -> Result<String> {
+    let callbody = syn::parse_str::<CallBody>(source)
+        .map_err(|e| Error::ParseError(format!("translated rsx doesn't parse: {}", e)))?;
+
+    Ok(match component {
+        true => write_callbody_with_icon_section(callbody, source),
+        false => dioxus_autofmt::write_block_out_with_source(callbody, source).unwrap(),
+    })
+}
+
+/// Render the static parts of rsx as HTML. The input can be the body of an rsx! block, or rust code with rsx! blocks.
+pub fn convert_rsx_to_html(contents: &str) -> Result<String> {
+    if let Ok(body) = syn::parse_str::<CallBody>(contents) {
+        return Ok(rsx_rosetta::html_from_rsx(&body));
+    }
+
+    let file = syn::parse_file(contents)
+        .map_err(|e| Error::ParseError(format!("input isn't rsx or rust: {}", e)))?;
+    let mut macros = RsxMacros(Vec::new());
+    macros.visit_file(&file);
+
+    if macros.0.is_empty() {
+        return custom_error!("No rsx! blocks to translate.");
+    }
+
+    let mut out = Vec::new();
+    for mac in macros.0 {
+        let body = mac
+            .parse_body::<CallBody>()
+            .map_err(|e| Error::ParseError(format!("invalid rsx: {}", e)))?;
+        out.push(rsx_rosetta::html_from_rsx(&body));
+    }
+
+    Ok(out.join("\n"))
+}
+
+/// The `rsx!` and `render!` macros in a file
+struct RsxMacros(Vec<syn::Macro>);
 
-    match component {
-        true => write_callbody_with_icon_section(callbody),
-        false => dioxus_autofmt::write_block_out(callbody).unwrap(),
+impl<'ast> Visit<'ast> for RsxMacros {
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let name = mac.path.segments.last().map(|segment| &segment.ident);
+        if name.is_some_and(|name| name == "rsx" || name == "render") {
+            self.0.push(mac.clone());
+        }
     }
 }
 
-fn write_callbody_with_icon_section(mut callbody: CallBody) -> String {
+fn write_callbody_with_icon_section(mut callbody: CallBody, source: &str) -> String {
     let mut svgs = vec![];
 
     rsx_rosetta::collect_svgs(&mut callbody.roots, &mut svgs);
 
-    let mut out = write_component_body(dioxus_autofmt::write_block_out(callbody).unwrap());
+    let body = dioxus_autofmt::write_block_out_with_source(callbody, source).unwrap();
+    let mut out = write_component_body(body);
 
     if !svgs.is_empty() {
-        write_svg_section(&mut out, svgs);
+        write_svg_section(&mut out, svgs, source);
     }
 
     out
@@ -77,11 +165,13 @@ fn write_component_body(raw: String) -> String {
     out
 }
 
-fn write_svg_section(out: &mut String, svgs: Vec<BodyNode>) {
+fn write_svg_section(out: &mut String, svgs: Vec<BodyNode>, source: &str) {
     out.push_str("\n\nmod icons {");
     out.push_str("\n    use super::*;");
     for (idx, icon) in svgs.into_iter().enumerate() {
-        let raw = dioxus_autofmt::write_block_out(CallBody { roots: vec![icon] }).unwrap();
+        let raw =
+            dioxus_autofmt::write_block_out_with_source(CallBody { roots: vec![icon] }, source)
+                .unwrap();
         out.push_str("\n\n    pub fn icon_");
         out.push_str(&idx.to_string());
         out.push_str("(cx: Scope) -> Element {\n        cx.render(rsx! {");
@@ -132,7 +222,24 @@ fn determine_input(file: Option<String>, raw: Option<String>) -> Result<String>
 fn generates_svgs() {
     let st = include_str!("../../tests/svg.html");
 
-    let out = convert_html_to_formatted_rsx(&html_parser::Dom::parse(st).unwrap(), true);
+    let out = convert_html_to_formatted_rsx(&html_parser::Dom::parse(st).unwrap(), true).unwrap();
 
     println!("{}", out);
 }
+
+#[test]
+fn renders_rsx_blocks_in_files() {
+    let file = r#"
+        fn app(cx: Scope) -> Element {
+            cx.render(rsx! { p { "one" } })
+        }
+
+        fn other(cx: Scope) -> Element {
+            render! { p { "two" } }
+        }
+    "#;
+
+    let out = convert_rsx_to_html(file).unwrap();
+    assert_eq!(out, "<p>one</p>\n\n<p>two</p>\n");
+    assert_eq!(Language::of_file(Some("App.tsx")), Language::Jsx);
+}
@@ -0,0 +1,505 @@
+//! The asset pipeline of release builds.
+//!
+//! - CSS and JS files are minified
+//! - Every asset is copied to a name that includes a hash of its contents, like `style.3f9a1c0b2e4d5f67.css`
+//! - References to assets in HTML, CSS and JS files are pointed at the fingerprinted copies
+//! - Text files are precompressed to `.br` and `.gz` files next to them
+//! - The fingerprinted copies are listed in a [`StaticAssetManifest`] that servers use to cache them forever
+//!
+//! Paths that are compiled into the app, like the paths manganis assets resolve to, can't be rewritten. They keep
+//! pointing at the original files, which are not cached forever. The `<head>` tags of manganis assets are part of the
+//! generated `index.html`, so they are pointed at the fingerprinted copies.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use dioxus_cli_config::{CrateConfig, StaticAssetManifest, STATIC_ASSET_MANIFEST};
+use sha2::{Digest, Sha256};
+
+use crate::Result;
+
+/// Files that are only loaded by the browser through a page, which are never fingerprinted
+const PAGE_EXTENSIONS: &[&str] = &["html", "htm"];
+
+/// Files that can refer to other assets
+const TEXT_EXTENSIONS: &[&str] = &["html", "htm", "css", "js", "mjs"];
+
+/// Files that are worth compressing. Images, fonts like woff2 and media are already compressed.
+const COMPRESSIBLE_EXTENSIONS: &[&str] = &[
+    "html", "htm", "css", "js", "mjs", "wasm", "json", "map", "svg", "txt", "xml", "ico", "ttf",
+    "otf", "eot",
+];
+
+/// Minify, fingerprint and precompress the assets in the out dir, and write the [`StaticAssetManifest`].
+pub(crate) fn fingerprint_assets(config: &CrateConfig) -> Result<StaticAssetManifest> {
+    log::info!("🔖 Fingerprinting assets...");
+
+    let out_dir = config.out_dir();
+    clean_out_dir(&out_dir)?;
+
+    let files = collect_files(&out_dir);
+    for file in &files {
+        minify(&out_dir, file)?;
+    }
+
+    let mut fingerprinter = Fingerprinter {
+        out_dir: &out_dir,
+        base_path: config.dioxus_config.web.app.base_path.as_deref(),
+        files: &files,
+        fingerprinted: BTreeMap::new(),
+        visiting: HashSet::new(),
+    };
+    for file in &files {
+        if !is_page(file) {
+            fingerprinter.fingerprint(file)?;
+        }
+    }
+
+    // Pages aren't fingerprinted, they are the entry points that refer to the fingerprinted copies
+    for file in files.iter().filter(|file| is_page(file)) {
+        let path = out_dir.join(file);
+        let contents = std::fs::read_to_string(&path)?;
+        let rewritten = fingerprinter.rewrite_references(file, &contents)?;
+        if rewritten != contents {
+            std::fs::write(&path, rewritten)?;
+        }
+    }
+
+    let manifest = StaticAssetManifest {
+        assets: fingerprinter.fingerprinted,
+    };
+    for file in files
+        .iter()
+        .map(String::as_str)
+        .chain(manifest.fingerprinted_files())
+    {
+        precompress(&out_dir.join(file))?;
+    }
+    manifest.save(&out_dir)?;
+
+    log::info!("🔖 Fingerprinted {} assets", manifest.assets.len());
+
+    Ok(manifest)
+}
+
+/// Remove the fingerprinted assets of the last release build.
+///
+/// Builds that aren't fingerprinted call this as well, because servers would keep serving the precompressed copies
+/// of files that changed since then.
+pub(crate) fn remove_fingerprinted_assets(config: &CrateConfig) -> Result<()> {
+    clean_out_dir(&config.out_dir())
+}
+
+/// Remove the fingerprinted copies and the manifest of the last release build, and the precompressed copies of
+/// every asset. The assets themselves are overwritten by the build.
+fn clean_out_dir(out_dir: &Path) -> Result<()> {
+    let manifest = StaticAssetManifest::load(out_dir);
+    for file in manifest.fingerprinted_files() {
+        let path = out_dir.join(file);
+        if path.is_file() {
+            std::fs::remove_file(&path)?;
+        }
+        remove_precompressed(&path)?;
+    }
+
+    let manifest_path = out_dir.join(STATIC_ASSET_MANIFEST);
+    if manifest_path.is_file() {
+        std::fs::remove_file(manifest_path)?;
+    }
+
+    for file in collect_files(out_dir) {
+        remove_precompressed(&out_dir.join(file))?;
+    }
+
+    Ok(())
+}
+
+fn remove_precompressed(path: &Path) -> Result<()> {
+    for suffix in [".br", ".gz"] {
+        let path = with_suffix(path, suffix);
+        if path.is_file() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    Ok(())
+}
+
+/// The assets in the out dir, relative to it and separated by `/`.
+///
+/// The out dir also has the server binary of fullstack apps, so files without an extension are left out.
+fn collect_files(out_dir: &Path) -> BTreeSet<String> {
+    walkdir::WalkDir::new(out_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let extension = path.extension()?.to_str()?;
+            let name = path.file_name()?.to_str()?;
+            if extension == "exe" || name == STATIC_ASSET_MANIFEST {
+                return None;
+            }
+            // Precompressed copies of other files
+            if matches!(extension, "br" | "gz") && path.with_extension("").is_file() {
+                return None;
+            }
+
+            let relative = path.strip_prefix(out_dir).ok()?;
+            let segments: Option<Vec<_>> = relative.iter().map(|s| s.to_str()).collect();
+            Some(segments?.join("/"))
+        })
+        .collect()
+}
+
+fn minify(out_dir: &Path, file: &str) -> Result<()> {
+    if file.ends_with(".min.css") || file.ends_with(".min.js") {
+        return Ok(());
+    }
+
+    let path = out_dir.join(file);
+    let minified = match extension(file) {
+        "css" => {
+            let contents = std::fs::read_to_string(&path)?;
+            match minifier::css::minify(&contents) {
+                Ok(minified) => minified.to_string(),
+                Err(err) => {
+                    log::warn!("Failed to minify {}: {}", file, err);
+                    return Ok(());
+                }
+            }
+        }
+        "js" | "mjs" => {
+            let contents = std::fs::read_to_string(&path)?;
+            minifier::js::minify(&contents).to_string()
+        }
+        _ => return Ok(()),
+    };
+    std::fs::write(path, minified)?;
+
+    Ok(())
+}
+
+struct Fingerprinter<'a> {
+    out_dir: &'a Path,
+    base_path: Option<&'a str>,
+    files: &'a BTreeSet<String>,
+    fingerprinted: BTreeMap<String, String>,
+    /// The files that are being fingerprinted, to break cycles of files that refer to each other
+    visiting: HashSet<String>,
+}
+
+impl Fingerprinter<'_> {
+    /// Copy a file to its fingerprinted name, after fingerprinting the files it refers to. Returns the fingerprinted
+    /// path, or `None` if the file is part of a cycle that is still being fingerprinted.
+    fn fingerprint(&mut self, file: &str) -> Result<Option<String>> {
+        if let Some(fingerprinted) = self.fingerprinted.get(file) {
+            return Ok(Some(fingerprinted.clone()));
+        }
+        if !self.visiting.insert(file.to_string()) {
+            return Ok(None);
+        }
+
+        let path = self.out_dir.join(file);
+        let mut contents = std::fs::read(&path)?;
+        if TEXT_EXTENSIONS.contains(&extension(file)) {
+            if let Ok(text) = String::from_utf8(contents.clone()) {
+                contents = self.rewrite_references(file, &text)?.into_bytes();
+            }
+        }
+
+        let fingerprinted = fingerprinted_name(file, &contents);
+        std::fs::write(self.out_dir.join(&fingerprinted), contents)?;
+
+        self.visiting.remove(file);
+        self.fingerprinted
+            .insert(file.to_string(), fingerprinted.clone());
+        Ok(Some(fingerprinted))
+    }
+
+    /// Point the references to assets in a file at their fingerprinted copies.
+    fn rewrite_references(&mut self, file: &str, contents: &str) -> Result<String> {
+        let dir = file
+            .rsplit_once('/')
+            .map(|(dir, _)| dir)
+            .unwrap_or_default();
+
+        let mut rewritten = String::with_capacity(contents.len());
+        let mut last = 0;
+        for (start, reference) in references(contents) {
+            let Some(asset) = self.resolve(dir, reference) else {
+                continue;
+            };
+            let Some(fingerprinted) = self.fingerprint(&asset)? else {
+                continue;
+            };
+
+            // The fingerprinted copy is next to the asset, so only the file name changes
+            let path = reference.split(['?', '#']).next().unwrap_or_default();
+            let name_start = start + path.rfind('/').map(|i| i + 1).unwrap_or(0);
+            let name_end = start + path.len();
+            let fingerprinted_name = fingerprinted.rsplit('/').next().unwrap_or_default();
+            rewritten.push_str(&contents[last..name_start]);
+            rewritten.push_str(fingerprinted_name);
+            last = name_end;
+        }
+        rewritten.push_str(&contents[last..]);
+
+        Ok(rewritten)
+    }
+
+    /// The asset a reference in a file in `dir` points to.
+    fn resolve(&self, dir: &str, reference: &str) -> Option<String> {
+        let path = reference.split(['?', '#']).next()?;
+        if path.is_empty() || path.starts_with("//") || path.contains(':') {
+            return None;
+        }
+
+        let mut candidates = Vec::new();
+        match path.strip_prefix('/') {
+            // The out dir is served from the base path, but manganis assets are copied into a folder named after it
+            Some(path) => {
+                let base_path = self.base_path.unwrap_or(".").trim_matches('/');
+                if let Some(path) = path
+                    .strip_prefix(base_path)
+                    .and_then(|p| p.strip_prefix('/'))
+                {
+                    candidates.push(normalize("", path)?);
+                }
+                candidates.push(normalize("", path)?);
+            }
+            None => candidates.push(normalize(dir, path)?),
+        }
+
+        candidates
+            .into_iter()
+            .find(|candidate| self.files.contains(candidate) && !is_page(candidate))
+    }
+}
+
+/// The strings in quotes and the values of CSS `url()`s in a file, with the offsets they start at.
+fn references(contents: &str) -> Vec<(usize, &str)> {
+    let mut references = Vec::new();
+    let bytes = contents.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        let start = match bytes[index] {
+            b'"' | b'\'' | b'`' => index + 1,
+            b'(' if contents[..index].ends_with("url") => {
+                // Quoted urls are found as strings
+                let start = index + 1;
+                let value = contents[start..].trim_start();
+                if value.starts_with(['"', '\'']) {
+                    index += 1;
+                    continue;
+                }
+                start + (contents.len() - start - value.len())
+            }
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+
+        let end_char = match bytes[index] {
+            b'(' => ')',
+            quote => quote as char,
+        };
+        let Some(length) = contents[start..].find([end_char, '\n']) else {
+            break;
+        };
+        let reference = contents[start..start + length].trim_end();
+        if contents[start + length..].starts_with(end_char) && !reference.contains('\\') {
+            references.push((start, reference));
+        }
+        index = start + length + 1;
+    }
+    references
+}
+
+/// Join a relative path to a dir, resolving `.` and `..`. Returns `None` for paths outside the out dir.
+fn normalize(dir: &str, path: &str) -> Option<String> {
+    let mut segments: Vec<&str> = dir.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+/// The path of a file with a hash of its contents before the extension, like `assets/style.3f9a1c0b2e4d5f67.css`.
+fn fingerprinted_name(file: &str, contents: &[u8]) -> String {
+    let hash: String = Sha256::digest(contents)
+        .iter()
+        .take(8)
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    match file.rsplit_once('.') {
+        Some((stem, extension)) if !extension.contains('/') => {
+            format!("{}.{}.{}", stem, hash, extension)
+        }
+        _ => format!("{}.{}", file, hash),
+    }
+}
+
+/// Write brotli and gzip copies of a file next to it, if they are smaller. Old copies are removed by [`clean_out_dir`].
+fn precompress(path: &Path) -> Result<()> {
+    let is_compressible = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| COMPRESSIBLE_EXTENSIONS.contains(&extension));
+    if !is_compressible {
+        return Ok(());
+    }
+
+    let contents = std::fs::read(path)?;
+
+    let mut brotli = brotli::CompressorWriter::new(Vec::new(), 4096, 11, 22);
+    brotli.write_all(&contents)?;
+    let brotli = brotli.into_inner();
+
+    let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    gzip.write_all(&contents)?;
+    let gzip = gzip.finish()?;
+
+    for (compressed, suffix) in [(brotli, ".br"), (gzip, ".gz")] {
+        if compressed.len() < contents.len() {
+            std::fs::write(with_suffix(path, suffix), compressed)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    path.into()
+}
+
+fn extension(file: &str) -> &str {
+    file.rsplit_once('.')
+        .map(|(_, extension)| extension)
+        .unwrap_or_default()
+}
+
+fn is_page(file: &str) -> bool {
+    PAGE_EXTENSIONS.contains(&extension(file))
+}
+
+#[test]
+fn finds_references() {
+    let css =
+        r#"@import "fonts.css"; body { background: url( img/bg.png ); } a { b: url('x.svg') }"#;
+    let found = references(css);
+    let values: Vec<_> = found.iter().map(|(_, reference)| *reference).collect();
+    assert_eq!(values, ["fonts.css", "img/bg.png", "x.svg"]);
+    assert_eq!(found[1].0, css.find("img/").unwrap());
+}
+
+#[test]
+fn normalizes_paths() {
+    assert_eq!(
+        normalize("assets/css", "../img/a.png").as_deref(),
+        Some("assets/img/a.png")
+    );
+    assert_eq!(normalize("", "./style.css").as_deref(), Some("style.css"));
+    assert_eq!(normalize("", "../style.css"), None);
+}
+
+#[test]
+fn fingerprints_assets() {
+    let dir = tempfile::tempdir().unwrap();
+    let out_dir = dir.path();
+    std::fs::create_dir_all(out_dir.join("assets/dioxus")).unwrap();
+    std::fs::write(
+        out_dir.join("style.css"),
+        "body { background: url(bg.png); }",
+    )
+    .unwrap();
+    std::fs::write(out_dir.join("bg.png"), [0u8; 16]).unwrap();
+    std::fs::write(out_dir.join("assets/dioxus/app.js"), "init('app_bg.wasm');").unwrap();
+    std::fs::write(out_dir.join("assets/dioxus/app_bg.wasm"), [0u8; 16]).unwrap();
+    std::fs::write(
+        out_dir.join("index.html"),
+        r#"<link href="/./style.css?v=1"><script src="/./assets/dioxus/app.js"></script>"#,
+    )
+    .unwrap();
+
+    let files = collect_files(out_dir);
+    let mut fingerprinter = Fingerprinter {
+        out_dir,
+        base_path: None,
+        files: &files,
+        fingerprinted: BTreeMap::new(),
+        visiting: HashSet::new(),
+    };
+    let html = std::fs::read_to_string(out_dir.join("index.html")).unwrap();
+    let html = fingerprinter
+        .rewrite_references("index.html", &html)
+        .unwrap();
+
+    let fingerprinted = &fingerprinter.fingerprinted;
+    let style = &fingerprinted["style.css"];
+    let script = &fingerprinted["assets/dioxus/app.js"];
+    assert!(style.starts_with("style.") && style.ends_with(".css"));
+    assert_eq!(
+        html,
+        format!(
+            r#"<link href="/./{}?v=1"><script src="/./{}"></script>"#,
+            style, script
+        )
+    );
+
+    // References in fingerprinted copies point at other fingerprinted copies
+    let css = std::fs::read_to_string(out_dir.join(style)).unwrap();
+    assert_eq!(
+        css,
+        format!("body {{ background: url({}); }}", fingerprinted["bg.png"])
+    );
+    let js = std::fs::read_to_string(out_dir.join(script)).unwrap();
+    let wasm = fingerprinted["assets/dioxus/app_bg.wasm"]
+        .rsplit('/')
+        .next()
+        .unwrap();
+    assert_eq!(js, format!("init('{}');", wasm));
+
+    // The originals are kept
+    assert!(out_dir.join("style.css").is_file());
+}
+
+#[test]
+fn cleans_precompressed_copies() {
+    let dir = tempfile::tempdir().unwrap();
+    let out_dir = dir.path();
+    std::fs::write(out_dir.join("style.css"), "body {}").unwrap();
+    std::fs::write(out_dir.join("style.css.br"), "stale").unwrap();
+    std::fs::write(out_dir.join("style.css.gz"), "stale").unwrap();
+    std::fs::write(out_dir.join("style.0123456789abcdef.css"), "body {}").unwrap();
+    std::fs::write(out_dir.join("style.0123456789abcdef.css.br"), "stale").unwrap();
+    StaticAssetManifest {
+        assets: [(
+            "style.css".to_string(),
+            "style.0123456789abcdef.css".to_string(),
+        )]
+        .into(),
+    }
+    .save(out_dir)
+    .unwrap();
+
+    clean_out_dir(out_dir).unwrap();
+
+    let mut remaining: Vec<_> = std::fs::read_dir(out_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    remaining.sort();
+    assert_eq!(remaining, ["style.css"]);
+}
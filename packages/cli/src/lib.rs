@@ -6,6 +6,7 @@ pub const DIOXUS_CLI_VERSION: &str = "0.4.1";
 
 mod assets;
 pub mod builder;
+mod fingerprint;
 pub mod server;
 pub mod tools;
 
@@ -20,5 +21,9 @@ pub use error::*;
 pub mod logging;
 pub use logging::*;
 
+pub mod lsp;
+
+pub mod report;
+
 #[cfg(feature = "plugin")]
 pub mod plugin;
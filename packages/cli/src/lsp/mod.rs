@@ -0,0 +1,373 @@
+//! A language server for rsx!, which editors run with `dx lsp`.
+//!
+//! - Diagnostics for the issues `dioxus-check` finds, with quick fixes for the issues that can be fixed automatically
+//! - Formatting of the rsx! blocks in a document with `dioxus-autofmt`
+//! - Completions and hover docs for the elements and attributes in `dioxus-html`
+//! - A code action that converts selected HTML, like HTML that was just pasted, to rsx with `rsx-rosetta`
+
+use std::{collections::HashMap, sync::Mutex};
+
+use dioxus_autofmt::{IndentOptions, IndentType};
+use dioxus_check::{Issue, IssueReport, RuleSet, Severity};
+use dioxus_html::definitions::{self, Definition};
+use tower_lsp::jsonrpc::Result;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+use crate::cli::check::load_rules;
+use crate::cli::translate::convert_html_to_formatted_rsx;
+use position::{offset_of, position_of, range_of_span};
+use rsx_context::{rsx_context, word_at, RsxContext};
+
+mod position;
+mod rsx_context;
+
+/// Run the language server over stdin and stdout until the client exits.
+pub async fn run() {
+    let (service, socket) = LspService::new(Backend::new);
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+        .serve(service)
+        .await;
+}
+
+struct Backend {
+    client: Client,
+    documents: Mutex<HashMap<Url, Document>>,
+    rules: Mutex<RuleSet>,
+}
+
+/// An open document.
+struct Document {
+    text: String,
+    /// The issues in the current text, or `None` if the text doesn't parse.
+    report: Option<IssueReport>,
+}
+
+impl Backend {
+    fn new(client: Client) -> Self {
+        Self {
+            client,
+            documents: Default::default(),
+            rules: Default::default(),
+        }
+    }
+
+    /// Store the new text of a document and publish its issues.
+    ///
+    /// Code that is being written often doesn't parse, so the last diagnostics stay until the document parses again.
+    async fn update(&self, uri: Url, text: String, version: i32) {
+        let report = match syn::parse_file(&text).is_ok() {
+            true => {
+                let path = uri.to_file_path().unwrap_or_default();
+                let rules = self.rules.lock().unwrap().clone();
+                Some(dioxus_check::check_file_with_rules(path, &text, &rules))
+            }
+            false => None,
+        };
+
+        let diagnostics = report.as_ref().map(|report| {
+            report
+                .issues
+                .iter()
+                .map(|issue| diagnostic(report, issue))
+                .collect()
+        });
+
+        self.documents
+            .lock()
+            .unwrap()
+            .insert(uri.clone(), Document { text, report });
+
+        if let Some(diagnostics) = diagnostics {
+            self.client
+                .publish_diagnostics(uri, diagnostics, Some(version))
+                .await;
+        }
+    }
+
+    fn text(&self, uri: &Url) -> Option<String> {
+        let documents = self.documents.lock().unwrap();
+        documents.get(uri).map(|document| document.text.clone())
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        // Dioxus.toml is found from the current directory, which editors don't always start the server in
+        if let Some(root) = params.root_uri.and_then(|uri| uri.to_file_path().ok()) {
+            let _ = std::env::set_current_dir(root);
+        }
+
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::FULL,
+                )),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                completion_provider: Some(CompletionOptions::default()),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "dx lsp".to_string(),
+                version: Some(crate::DIOXUS_CLI_VERSION.to_string()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        match load_rules() {
+            Ok(rules) => {
+                *self.rules.lock().unwrap() = rules;
+            }
+            Err(e) => {
+                let message = format!("invalid check config: {}", e);
+                self.client
+                    .show_message(MessageType::WARNING, message)
+                    .await;
+            }
+        }
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let document = params.text_document;
+        self.update(document.uri, document.text, document.version)
+            .await;
+    }
+
+    async fn did_change(&self, mut params: DidChangeTextDocumentParams) {
+        // With full sync, the last change has the whole text
+        if let Some(change) = params.content_changes.pop() {
+            let document = params.text_document;
+            self.update(document.uri, change.text, document.version)
+                .await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.lock().unwrap().remove(&uri);
+        self.client.publish_diagnostics(uri, Vec::new(), None).await;
+    }
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let Some(text) = self.text(&params.text_document.uri) else {
+            return Ok(None);
+        };
+
+        // dioxus-autofmt can only format files that parse
+        if syn::parse_file(&text).is_err() {
+            return Ok(None);
+        }
+
+        let indent = IndentOptions::new(
+            match params.options.insert_spaces {
+                true => IndentType::Spaces,
+                false => IndentType::Tabs,
+            },
+            params.options.tab_size.max(1) as usize,
+            false,
+        );
+
+        let edits = dioxus_autofmt::fmt_file(&text, indent)
+            .into_iter()
+            .map(|block| {
+                let start = position_of(&text, block.start);
+                let end = position_of(&text, block.end);
+                TextEdit::new(Range::new(start, end), block.formatted)
+            })
+            .collect();
+
+        Ok(Some(edits))
+    }
+
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let position = params.text_document_position;
+        let items = self.text(&position.text_document.uri).and_then(|text| {
+            let offset = offset_of(&text, position.position)?;
+            completions(&text, offset)
+        });
+        Ok(items.map(CompletionResponse::Array))
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let position = params.text_document_position_params;
+        let hover = self.text(&position.text_document.uri).and_then(|text| {
+            let offset = offset_of(&text, position.position)?;
+            hover(&text, offset)
+        });
+        Ok(hover)
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let documents = self.documents.lock().unwrap();
+        let Some(document) = documents.get(&uri) else {
+            return Ok(None);
+        };
+
+        let mut actions = Vec::new();
+
+        // Fixes are only offered while the report matches the text
+        if let Some(report) = &document.report {
+            for issue in &report.issues {
+                let Some(fix) = issue.fix() else {
+                    continue;
+                };
+                let diagnostic = diagnostic(report, issue);
+                if !overlaps(diagnostic.range, params.range) {
+                    continue;
+                }
+
+                let range = range_of_span(&document.text, &fix.span);
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Fix: {}", diagnostic.message),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic]),
+                    edit: Some(workspace_edit(&uri, TextEdit::new(range, fix.replacement))),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let Some(edit) = html_to_rsx(&document.text, params.range) {
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Convert HTML to rsx".to_string(),
+                kind: Some(CodeActionKind::REFACTOR_REWRITE),
+                edit: Some(workspace_edit(&uri, edit)),
+                ..Default::default()
+            }));
+        }
+
+        Ok(Some(actions))
+    }
+}
+
+fn diagnostic(report: &IssueReport, issue: &Issue) -> Diagnostic {
+    let severity = match report.severity(issue) {
+        Severity::Error => DiagnosticSeverity::ERROR,
+        _ => DiagnosticSeverity::WARNING,
+    };
+
+    Diagnostic {
+        range: range_of_span(&report.file_content, &issue.span()),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(issue.rule().name().to_string())),
+        source: Some("dioxus".to_string()),
+        message: issue.to_string(),
+        ..Default::default()
+    }
+}
+
+fn completions(text: &str, offset: usize) -> Option<Vec<CompletionItem>> {
+    let elements = definitions::ELEMENTS
+        .iter()
+        .map(|element| completion(&element.element, CompletionItemKind::STRUCT));
+
+    let items = match rsx_context(text, offset)? {
+        RsxContext::Children => elements.collect(),
+        RsxContext::Element(element) => element
+            .all_attributes()
+            .map(|attribute| completion(attribute, CompletionItemKind::FIELD))
+            .chain(elements)
+            .collect(),
+    };
+
+    Some(items)
+}
+
+fn completion(definition: &Definition, kind: CompletionItemKind) -> CompletionItem {
+    let docs = definition.docs();
+    CompletionItem {
+        label: definition.name.to_string(),
+        kind: Some(kind),
+        documentation: (!docs.is_empty()).then(|| Documentation::MarkupContent(markdown(docs))),
+        ..Default::default()
+    }
+}
+
+/// The docs of the element or attribute at an offset.
+fn hover(text: &str, offset: usize) -> Option<Hover> {
+    let (word, start) = word_at(text, offset)?;
+    let after = text[start + word.len()..].trim_start();
+
+    let (title, definition) = if after.starts_with(':') && !after.starts_with("::") {
+        let RsxContext::Element(element) = rsx_context(text, start)? else {
+            return None;
+        };
+        let title = format!("`{}` attribute of `{}`", word, element.element.name);
+        (title, element.attribute(word)?)
+    } else if after.starts_with('{') {
+        rsx_context(text, start)?;
+        let element = definitions::element(word)?;
+        (format!("`{}` element", word), &element.element)
+    } else {
+        return None;
+    };
+
+    let docs = definition.docs();
+    let value = match docs.is_empty() {
+        true => title,
+        false => format!("{}\n\n{}", title, docs),
+    };
+
+    Some(Hover {
+        contents: HoverContents::Markup(markdown(value)),
+        range: Some(Range::new(
+            position_of(text, start),
+            position_of(text, start + word.len()),
+        )),
+    })
+}
+
+/// Replace a selection of HTML with rsx.
+fn html_to_rsx(text: &str, range: Range) -> Option<TextEdit> {
+    let start = offset_of(text, range.start)?;
+    let end = offset_of(text, range.end)?;
+    let dom = html_parser::Dom::parse(text.get(start..end)?).ok()?;
+
+    let has_elements = dom
+        .children
+        .iter()
+        .any(|node| matches!(node, html_parser::Node::Element(_)));
+    if !has_elements {
+        return None;
+    }
+
+    // The first line replaces the selection, the rest are indented like the line the selection starts on
+    let line_start = text[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let indent: String = text[line_start..start]
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .collect();
+    let rsx = convert_html_to_formatted_rsx(&dom, false).ok()?;
+    let rsx = rsx
+        .trim_end()
+        .lines()
+        .collect::<Vec<_>>()
+        .join(&format!("\n{}", indent));
+
+    Some(TextEdit::new(range, rsx))
+}
+
+fn markdown(value: String) -> MarkupContent {
+    MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    }
+}
+
+fn workspace_edit(uri: &Url, edit: TextEdit) -> WorkspaceEdit {
+    WorkspaceEdit::new(HashMap::from([(uri.clone(), vec![edit])]))
+}
+
+fn overlaps(a: Range, b: Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
@@ -0,0 +1,77 @@
+//! Conversions between the byte offsets and line/columns used by dioxus and the positions used by LSP, which count
+//! UTF-16 code units.
+
+use dioxus_check::Span;
+use tower_lsp::lsp_types::{Position, Range};
+
+/// The position of a byte offset in a document.
+pub fn position_of(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: utf16_len(&before[line_start..]),
+    }
+}
+
+/// The position of a line and column from `proc_macro2`, where lines start at 1 and columns count characters.
+pub fn position_of_line_column(text: &str, line: usize, column: usize) -> Position {
+    let line = line.saturating_sub(1);
+    let line_text = text.split('\n').nth(line).unwrap_or_default();
+    let before: String = line_text.chars().take(column).collect();
+    Position {
+        line: line as u32,
+        character: utf16_len(&before),
+    }
+}
+
+/// The range of a span found by `dioxus-check`.
+pub fn range_of_span(text: &str, span: &Span) -> Range {
+    Range::new(
+        position_of_line_column(text, span.start.line, span.start.column),
+        position_of_line_column(text, span.end.line, span.end.column),
+    )
+}
+
+/// The byte offset of a position in a document. Positions past the end of a line are moved to the end of the line.
+pub fn offset_of(text: &str, position: Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+
+    let line = &text[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+
+    let mut character = 0;
+    for (index, c) in line.char_indices() {
+        if character >= position.character as usize {
+            return Some(line_start + index);
+        }
+        character += c.len_utf16();
+    }
+    Some(line_start + line.len())
+}
+
+fn utf16_len(text: &str) -> u32 {
+    text.chars().map(char::len_utf16).sum::<usize>() as u32
+}
+
+#[test]
+fn positions_count_utf16() {
+    let text = "fn app() {\n    rsx! { \"🦀\" dïv {} }\n}";
+    let offset = text.find("dïv").unwrap();
+
+    let position = position_of(text, offset);
+    assert_eq!(position, Position::new(1, 16));
+    assert_eq!(offset_of(text, position), Some(offset));
+    assert_eq!(position_of_line_column(text, 2, 15), position);
+}
+
+#[test]
+fn offsets_past_the_line_end() {
+    let text = "one\ntwo";
+    assert_eq!(offset_of(text, Position::new(0, 10)), Some(3));
+    assert_eq!(offset_of(text, Position::new(1, 3)), Some(7));
+    assert_eq!(offset_of(text, Position::new(2, 0)), None);
+}
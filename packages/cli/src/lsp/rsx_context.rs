@@ -0,0 +1,133 @@
+//! Find out where in an `rsx!` call a position is, for completions and hover.
+//!
+//! This only looks at the text around the position, so it keeps working while the code is being written and doesn't
+//! parse.
+
+use dioxus_html::definitions::{self, ElementDefinition};
+
+/// What can be written at a position inside an `rsx!` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsxContext {
+    /// Elements, like directly inside the `rsx!` call or in the body of a `for` loop.
+    Children,
+    /// The attributes and children of an element.
+    Element(&'static ElementDefinition),
+}
+
+/// Find the context of a byte offset by walking back through the braces that are still open.
+///
+/// Returns `None` if the offset isn't inside an `rsx!` call, or it is inside a string, an attribute value, a component
+/// or a closure.
+pub fn rsx_context(text: &str, offset: usize) -> Option<RsxContext> {
+    let before = &text[..offset];
+    let line = &before[before.rfind('\n').map(|i| i + 1).unwrap_or(0)..];
+    let quotes = line.matches('"').count() - line.matches("\\\"").count();
+    if quotes % 2 == 1 {
+        return None;
+    }
+
+    let mut context = None;
+    let mut depth = 0usize;
+    for (index, c) in before.char_indices().rev() {
+        match c {
+            '}' => depth += 1,
+            '{' if depth > 0 => depth -= 1,
+            '{' => {
+                let word = word_before(before, index);
+                if word == "rsx!" || word == "render!" {
+                    return Some(context.unwrap_or(RsxContext::Children));
+                }
+
+                // Closures and async blocks contain rust code, not rsx
+                let prefix = before[..index].trim_end();
+                if prefix.ends_with('|') || word == "move" || word == "async" {
+                    return None;
+                }
+
+                if context.is_none() {
+                    let element = definitions::element(word);
+                    if element.is_none() && word.starts_with(char::is_uppercase) {
+                        // Components take props, which aren't known without type information
+                        return None;
+                    }
+                    context = Some(match element {
+                        Some(element) => {
+                            // An attribute value, unless a comma or a newline came after the attribute name
+                            let current = before[index + 1..]
+                                .rsplit([',', '\n'])
+                                .next()
+                                .unwrap_or_default();
+                            if current.contains(':') {
+                                return None;
+                            }
+                            RsxContext::Element(element)
+                        }
+                        None => RsxContext::Children,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// The word that ends right before a byte offset, skipping whitespace. Macro names include their `!`.
+fn word_before(text: &str, offset: usize) -> &str {
+    let text = text[..offset].trim_end();
+    let start = text
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '!'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    &text[start..]
+}
+
+/// The identifier around a byte offset, and the offset it starts at.
+pub fn word_at(text: &str, offset: usize) -> Option<(&str, usize)> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '#';
+    let start = text[..offset]
+        .rfind(|c: char| !is_ident(c))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let end = text[offset..]
+        .find(|c: char| !is_ident(c))
+        .map(|i| offset + i)
+        .unwrap_or(text.len());
+    let word = &text[start..end];
+    (!word.is_empty()).then_some((word, start))
+}
+
+#[cfg(test)]
+fn context_at_cursor(text: &str) -> Option<RsxContext> {
+    rsx_context(&text.replace('$', ""), text.find('$').unwrap())
+}
+
+#[test]
+fn finds_element_context() {
+    let a = definitions::element("a").unwrap();
+    assert_eq!(
+        context_at_cursor("rsx! { div { a { class: \"x\",\n $ } } }"),
+        Some(RsxContext::Element(a))
+    );
+    assert_eq!(
+        context_at_cursor("rsx! { div { for i in 0..3 { $ } } }"),
+        Some(RsxContext::Children)
+    );
+    assert_eq!(
+        context_at_cursor("rsx! { \"{name}\" $ }"),
+        Some(RsxContext::Children)
+    );
+}
+
+#[test]
+fn skips_code_and_values() {
+    assert_eq!(context_at_cursor("fn app() { $ }"), None);
+    assert_eq!(context_at_cursor("rsx! { a { href: $ } }"), None);
+    assert_eq!(context_at_cursor("rsx! { a { \"hello $ }"), None);
+    assert_eq!(context_at_cursor("rsx! { Link { $ } }"), None);
+    assert_eq!(
+        context_at_cursor("rsx! { button { onclick: move |_| { $ } } }"),
+        None
+    );
+}
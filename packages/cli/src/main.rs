@@ -43,7 +43,10 @@ fn error_wrapper(message: &str) -> String {
 async fn main() -> anyhow::Result<()> {
     let args = Cli::parse();
 
-    set_up_logging();
+    // The language server talks to the editor over stdout, so logs can't go there
+    if !matches!(args.action, Lsp(_)) {
+        set_up_logging();
+    }
 
     match args.action {
         Translate(opts) => opts
@@ -78,6 +81,16 @@ async fn main() -> anyhow::Result<()> {
             .await
             .context(error_wrapper("Error checking RSX")),
 
+        Devtools(opts) => opts
+            .devtools()
+            .await
+            .context(error_wrapper("Error running devtools")),
+
+        Lsp(opts) => opts
+            .lsp()
+            .await
+            .context(error_wrapper("Error running the language server")),
+
         Version(opt) => {
             let version = opt.version();
             println!("{}", version);
@@ -0,0 +1,252 @@
+//! Machine readable reports for `dx check` and `dx fmt --check`, for CI and editors.
+
+use dioxus_autofmt::FormattedBlock;
+use dioxus_check::{IssueReport, Severity};
+use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
+
+/// How issues are reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Human readable output
+    #[default]
+    Text,
+    /// A JSON array of diagnostics
+    Json,
+    /// A SARIF 2.1.0 log, which code scanning tools can read
+    Sarif,
+}
+
+/// The rule id of unformatted rsx! blocks found by `dx fmt --check`.
+pub const FORMAT_RULE: &str = "rsx-format";
+
+/// An issue in a file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// The path of the file, relative to the current directory if possible.
+    pub file: String,
+    pub rule: String,
+    /// Either `error` or `warning`.
+    pub severity: String,
+    pub message: String,
+    pub start: Location,
+    pub end: Location,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Replacement>,
+}
+
+/// A location in a file. Lines and columns start at 1, and columns count characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Text that replaces the text between two locations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Replacement {
+    pub start: Location,
+    pub end: Location,
+    pub text: String,
+}
+
+impl Diagnostic {
+    /// Create diagnostics for the issues in a report.
+    pub fn from_issue_report(report: &IssueReport) -> Vec<Diagnostic> {
+        let file = display_path(&report.path);
+        report
+            .issues
+            .iter()
+            .map(|issue| {
+                let span = issue.span();
+                let location = |line, column| Location {
+                    line,
+                    column: column + 1,
+                };
+                Diagnostic {
+                    file: file.clone(),
+                    rule: issue.rule().name().to_string(),
+                    severity: report.severity(issue).to_string(),
+                    message: issue.to_string(),
+                    start: location(span.start.line, span.start.column),
+                    end: location(span.end.line, span.end.column),
+                    fix: issue.fix().map(|fix| Replacement {
+                        start: location(fix.span.start.line, fix.span.start.column),
+                        end: location(fix.span.end.line, fix.span.end.column),
+                        text: fix.replacement,
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Create a diagnostic for an rsx! block that isn't formatted.
+    pub fn from_formatted_block(path: &Path, contents: &str, block: &FormattedBlock) -> Diagnostic {
+        let start = location_of(contents, block.start);
+        let end = location_of(contents, block.end);
+        Diagnostic {
+            file: display_path(path),
+            rule: FORMAT_RULE.to_string(),
+            severity: Severity::Error.to_string(),
+            message: "rsx! block is not formatted".to_string(),
+            start,
+            end,
+            fix: Some(Replacement {
+                start,
+                end,
+                text: block.formatted.clone(),
+            }),
+        }
+    }
+}
+
+fn display_path(path: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok());
+    relative.unwrap_or(path).display().to_string()
+}
+
+/// Find the location of a byte offset in a file.
+pub fn location_of(contents: &str, offset: usize) -> Location {
+    let before = &contents[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Location {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+    }
+}
+
+/// Serialize diagnostics as a JSON array.
+pub fn json_report(diagnostics: &[Diagnostic]) -> String {
+    serde_json::to_string_pretty(diagnostics).expect("diagnostics are always serializable")
+}
+
+/// Serialize diagnostics as a SARIF log.
+///
+/// `rules` are the ids and descriptions of every rule the tool checks.
+pub fn sarif_report(tool: &str, rules: &[(&str, &str)], diagnostics: &[Diagnostic]) -> String {
+    let rules = rules
+        .iter()
+        .map(|(id, description)| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": description },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let region = |start: &Location, end: &Location| {
+        json!({
+            "startLine": start.line,
+            "startColumn": start.column,
+            "endLine": end.line,
+            "endColumn": end.column,
+        })
+    };
+
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let artifact = json!({ "uri": diagnostic.file.replace('\\', "/") });
+            let mut result = json!({
+                "ruleId": diagnostic.rule,
+                "level": diagnostic.severity,
+                "message": { "text": diagnostic.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": artifact,
+                        "region": region(&diagnostic.start, &diagnostic.end),
+                    }
+                }],
+            });
+            if let Some(fix) = &diagnostic.fix {
+                result["fixes"] = json!([{
+                    "artifactChanges": [{
+                        "artifactLocation": artifact,
+                        "replacements": [{
+                            "deletedRegion": region(&fix.start, &fix.end),
+                            "insertedContent": { "text": fix.text },
+                        }],
+                    }],
+                }]);
+            }
+            result
+        })
+        .collect::<Vec<_>>();
+
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": tool,
+                    "informationUri": "https://dioxuslabs.com",
+                    "version": crate::DIOXUS_CLI_VERSION,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+    serde_json::to_string_pretty(&log).expect("SARIF logs are always serializable")
+}
+
+/// A line diff between the old and new text of a block, with unchanged lines prefixed by a space, removed lines by
+/// `-` and added lines by `+`.
+pub fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // The length of the longest common subsequence of the lines after i and j
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff.push_str(&format!(" {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            diff.push_str(&format!("-{}\n", old[i]));
+            i += 1;
+        } else {
+            diff.push_str(&format!("+{}\n", new[j]));
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[test]
+fn test_line_diff() {
+    assert_eq!(
+        line_diff("div {\n  a,\n}", "div {\n    a,\n}"),
+        " div {\n-  a,\n+    a,\n }\n"
+    );
+}
+
+#[test]
+fn test_location_of() {
+    let contents = "fn app() {\n    rsx! { dïv {} }\n}";
+    let offset = contents.find("{}").unwrap();
+    assert_eq!(
+        location_of(contents, offset),
+        Location {
+            line: 2,
+            column: 16
+        }
+    );
+}
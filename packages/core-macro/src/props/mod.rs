@@ -764,6 +764,12 @@ Finally, call `.build()` to create the instance of `{name}`.
                     unsafe fn memoize(&self, other: &Self) -> bool {
                         #can_memoize
                     }
+                    fn inspect_value(&self) -> Option<String> {
+                        // Props that don't implement Debug fall back to `FormatNoDebug`
+                        #[allow(unused_imports)]
+                        use ::dioxus::core::{FormatDebug, FormatNoDebug};
+                        (&&::dioxus::core::DebugIfPossible(self)).debug_if_possible()
+                    }
                 }
 
             })
@@ -0,0 +1,80 @@
+use crate::innerlude::*;
+
+/// Animate the keyed lists in the children of this component.
+///
+/// ## Details
+///
+/// AnimatedList renders its children without extra nodes, just like a [`Fragment`]. When a keyed list in its children
+/// changes, the VirtualDom tells the renderer which children entered, moved, or left the list with
+/// [`Mutation::Transition`]. Renderers that support transitions use them to play enter and leave animations and to
+/// animate moved children from their old position to their new one (FLIP). The removal of a child is deferred until
+/// its leave animation ends.
+///
+/// Lists are only animated when they change. The children that are rendered the first time the list is mounted don't
+/// play an enter animation. Keyed lists rendered inside of other components are not animated.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// rsx! {
+///     ul {
+///         AnimatedList {
+///             for todo in todos {
+///                 li { key: "{todo.id}", "{todo.contents}" }
+///             }
+///         }
+///     }
+/// }
+/// ```
+///
+/// ## Styling
+///
+/// The web, desktop and liveview renderers follow the class names of CSS transitions:
+///
+/// - Entering elements get `dx-enter-from` and `dx-enter-active` before the first frame, and `dx-enter-to` instead of
+///   `dx-enter-from` after it.
+/// - Leaving elements get `dx-leave-from` and `dx-leave-active`, and `dx-leave-to` instead of `dx-leave-from` after
+///   the first frame. They are removed when their transition or animation ends.
+/// - Moving elements get `dx-move` while they are animated to their new position.
+///
+/// ```css
+/// .dx-enter-active, .dx-leave-active, .dx-move { transition: all 0.3s ease; }
+/// .dx-enter-from, .dx-leave-to { opacity: 0; transform: translateX(30px); }
+/// ```
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn AnimatedList<'a>(cx: Scope<'a, AnimatedListProps<'a>>) -> Element {
+    cx.animate_keyed_lists();
+    let children = cx.props.0.as_ref()?;
+    Some(cx.vnode(
+        children.parent.clone(),
+        children.key,
+        children.template.clone(),
+        children.root_ids.clone(),
+        children.dynamic_nodes,
+        children.dynamic_attrs,
+    ))
+}
+
+pub struct AnimatedListProps<'a>(Element<'a>);
+pub struct AnimatedListBuilder<'a, const BUILT: bool>(Element<'a>);
+impl<'a> AnimatedListBuilder<'a, false> {
+    pub fn children(self, children: Element<'a>) -> AnimatedListBuilder<'a, true> {
+        AnimatedListBuilder(children)
+    }
+}
+impl<'a, const A: bool> AnimatedListBuilder<'a, A> {
+    pub fn build(self) -> AnimatedListProps<'a> {
+        AnimatedListProps(self.0)
+    }
+}
+
+impl<'a> Properties<'_> for AnimatedListProps<'a> {
+    type Builder = AnimatedListBuilder<'a, false>;
+    const IS_STATIC: bool = false;
+    fn builder(_cx: &ScopeState) -> Self::Builder {
+        AnimatedListBuilder(None)
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    innerlude::Scoped,
+    innerlude::{throw_error, RenderPanic, Scoped},
     nodes::RenderReturn,
     scopes::{Scope, ScopeState},
     Element,
@@ -15,11 +15,13 @@ pub(crate) unsafe trait AnyProps<'a> {
     fn props_ptr(&self) -> *const ();
     fn render(&'a self, bump: &'a ScopeState) -> RenderReturn<'a>;
     unsafe fn memoize(&self, other: &dyn AnyProps) -> bool;
+    fn inspect(&self) -> Option<String>;
 }
 
 pub(crate) struct VProps<'a, P> {
     pub render_fn: fn(Scope<'a, P>) -> Element<'a>,
     pub memo: unsafe fn(&P, &P) -> bool,
+    pub inspect: fn(&P) -> Option<String>,
     pub props: P,
 }
 
@@ -27,11 +29,13 @@ impl<'a, P> VProps<'a, P> {
     pub(crate) fn new(
         render_fn: fn(Scope<'a, P>) -> Element<'a>,
         memo: unsafe fn(&P, &P) -> bool,
+        inspect: fn(&P) -> Option<String>,
         props: P,
     ) -> Self {
         Self {
             render_fn,
             memo,
+            inspect,
             props,
         }
     }
@@ -52,8 +56,12 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
         (self.memo)(real_us, real_other)
     }
 
+    fn inspect(&self) -> Option<String> {
+        (self.inspect)(&self.props)
+    }
+
     fn render(&'a self, cx: &'a ScopeState) -> RenderReturn<'a> {
-        let res = std::panic::catch_unwind(AssertUnwindSafe(move || {
+        let render = move || {
             // Call the render function directly
             let scope: &mut Scoped<P> = cx.bump().alloc(Scoped {
                 props: &self.props,
@@ -61,14 +69,20 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
             });
 
             (self.render_fn)(scope)
-        }));
+        };
+
+        let res = if cx.runtime.catch_panics.get() {
+            std::panic::catch_unwind(AssertUnwindSafe(render))
+        } else {
+            Ok(render())
+        };
 
         match res {
             Ok(Some(e)) => RenderReturn::Ready(e),
             Ok(None) => RenderReturn::default(),
             Err(err) => {
-                let component_name = cx.name();
-                tracing::error!("Error while rendering component `{component_name}`: {err:?}");
+                // Let the nearest error boundary render the panic instead
+                throw_error::<()>(RenderPanic::new(cx.context().name, err));
                 RenderReturn::default()
             }
         }
@@ -2,7 +2,7 @@ use crate::ScopeState;
 use std::ptr::NonNull;
 
 use crate::{
-    innerlude::DirtyScope, nodes::RenderReturn, nodes::VNode, virtual_dom::VirtualDom,
+    innerlude::remove_dirty_scope, nodes::RenderReturn, nodes::VNode, virtual_dom::VirtualDom,
     AttributeValue, DynamicNode, ScopeId,
 };
 
@@ -85,10 +85,8 @@ impl VirtualDom {
     //
     // Note: This will not remove any ids from the arena
     pub(crate) fn drop_scope(&mut self, id: ScopeId, recursive: bool) {
-        self.dirty_scopes.remove(&DirtyScope {
-            height: self.scopes[id.0].height(),
-            id,
-        });
+        remove_dirty_scope(&mut self.dirty_scopes, id, self.scopes[id.0].height());
+        self.render_reasons.remove(&id);
 
         // Remove all VNode ids from the scope
         for id in self.scopes[id.0]
@@ -69,9 +69,18 @@ impl<'b> VirtualDom {
     ///
     /// This method pushes the ScopeID to the internal scopestack and returns the number of nodes created.
     pub(crate) fn create_scope(&mut self, scope: ScopeId, template: &'b VNode<'b>) -> usize {
+        let _span = tracing::trace_span!("create", scope = %self.scopes[scope.0].name()).entered();
+        let started = self.profile_start();
+        let edits = self.mutations.edits.len();
+
         self.runtime.scope_stack.borrow_mut().push(scope);
         let nodes = self.create(template);
         self.runtime.scope_stack.borrow_mut().pop();
+
+        if let Some(started) = started {
+            let mutations = self.mutations.edits.len().saturating_sub(edits);
+            self.profile_diff(scope, started, mutations);
+        }
         nodes
     }
 
@@ -364,9 +373,11 @@ impl<'b> VirtualDom {
                     scope: self.runtime.current_scope_id().unwrap_or(ScopeId(0)),
                 };
                 self.elements[id.0] = Some(element_ref);
+                // all listeners start with "on"
+                let name = &unbounded_name[2..];
                 self.mutations.push(NewEventListener {
-                    // all listeners start with "on"
-                    name: &unbounded_name[2..],
+                    // capture listeners listen to the same event as the bubbling listeners
+                    name: name.strip_prefix("capture").unwrap_or(name),
                     id,
                 })
             }
@@ -451,7 +462,7 @@ impl<'b> VirtualDom {
 
         // If it's all dynamic nodes, then we don't need to register it
         if !template.is_completely_dynamic() {
-            self.mutations.templates.push(template);
+            self.push_template(template);
         }
     }
 
@@ -488,7 +499,7 @@ impl<'b> VirtualDom {
 
             // If it's all dynamic nodes, then we don't need to register it
             if !template.is_completely_dynamic() {
-                self.mutations.templates.push(template);
+                self.push_template(template);
             }
         }
     }
@@ -564,14 +575,17 @@ impl<'b> VirtualDom {
 
         component.scope.set(Some(scope));
 
-        match unsafe { self.run_scope(scope).extend_lifetime_ref() } {
+        let m = match unsafe { self.run_scope(scope).extend_lifetime_ref() } {
             // Create the component's root element
             Ready(t) => {
                 self.assign_boundary_ref(parent, t);
                 self.create_scope(scope, t)
             }
             Aborted(t) => self.mount_aborted(t, parent),
-        }
+        };
+
+        // Portals move their nodes into their target
+        self.mount_portal(scope, m)
     }
 
     /// Load a scope from a vcomponent. If the props don't exist, that means the component is currently "live"
@@ -2,10 +2,10 @@ use crate::{
     any_props::AnyProps,
     arena::ElementId,
     innerlude::{
-        AttributeType, BorrowedAttributeValue, DirtyScope, ElementPath, ElementRef, VComponent,
-        VPlaceholder, VText,
+        remove_dirty_scope, AttributeType, BorrowedAttributeValue, ElementPath, ElementRef,
+        RenderReason, VComponent, VPlaceholder, VText,
     },
-    mutations::Mutation,
+    mutations::{Mutation, TransitionKind},
     nodes::RenderReturn,
     nodes::{DynamicNode, VNode},
     scopes::ScopeId,
@@ -18,6 +18,10 @@ use DynamicNode::*;
 
 impl<'b> VirtualDom {
     pub(super) fn diff_scope(&mut self, scope: ScopeId) {
+        let _span = tracing::trace_span!("diff", scope = %self.scopes[scope.0].name()).entered();
+        let started = self.profile_start();
+        let edits = self.mutations.edits.len();
+
         self.runtime.scope_stack.borrow_mut().push(scope);
         let scope_state = &mut self.get_scope(scope).unwrap();
         unsafe {
@@ -56,7 +60,13 @@ impl<'b> VirtualDom {
                 ),
             };
         }
+        self.diff_portal_target(scope);
         self.runtime.scope_stack.borrow_mut().pop();
+
+        if let Some(started) = started {
+            let mutations = self.mutations.edits.len().saturating_sub(edits);
+            self.profile_diff(scope, started, mutations);
+        }
     }
 
     fn diff_ok_to_err(&mut self, l: &'b VNode<'b>, p: &'b VPlaceholder) {
@@ -238,8 +248,18 @@ impl<'b> VirtualDom {
                 right.parent.set(left.parent.get());
             },
             (Component(left), Component(right)) => self.diff_vcomponent(left, right, Some(parent)),
-            (Placeholder(left), Fragment(right)) => self.replace_placeholder(left, *right, parent),
-            (Fragment(left), Placeholder(right)) => self.node_to_placeholder(left, right, parent),
+            (Placeholder(left), Fragment(right)) => {
+                self.replace_placeholder(left, *right, parent);
+                if right[0].key.is_some() && self.animates_keyed_lists() {
+                    right.iter().for_each(|node| self.transition_roots(node, TransitionKind::Enter));
+                }
+            }
+            (Fragment(left), Placeholder(right)) => {
+                if left[0].key.is_some() && self.animates_keyed_lists() {
+                    left.iter().for_each(|node| self.transition_roots(node, TransitionKind::Leave));
+                }
+                self.node_to_placeholder(left, right, parent)
+            }
             _ => todo!("This is an usual custom case for dynamic nodes. We don't know how to handle it yet."),
         };
     }
@@ -315,13 +335,15 @@ impl<'b> VirtualDom {
         self.scopes[scope_id.0].props = Some(new);
 
         // Now run the component and diff it
+        if let Some(parent) = self.runtime.current_scope_id() {
+            self.render_reasons
+                .insert(scope_id, RenderReason::ParentProps(parent));
+        }
         self.run_scope(scope_id);
         self.diff_scope(scope_id);
 
-        self.dirty_scopes.remove(&DirtyScope {
-            height: self.runtime.get_context(scope_id).unwrap().height,
-            id: scope_id,
-        });
+        let height = self.runtime.get_context(scope_id).unwrap().height;
+        remove_dirty_scope(&mut self.dirty_scopes, scope_id, height);
     }
 
     fn replace_vcomponent(
@@ -426,7 +448,11 @@ impl<'b> VirtualDom {
         );
 
         if new_is_keyed && old_is_keyed {
-            self.diff_keyed_children(old, new, parent);
+            if self.animates_keyed_lists() {
+                self.diff_animated_keyed_children(old, new, parent);
+            } else {
+                self.diff_keyed_children(old, new, parent);
+            }
         } else {
             self.diff_non_keyed_children(old, new, parent);
         }
@@ -555,6 +581,81 @@ impl<'b> VirtualDom {
         }
     }
 
+    /// Diff keyed children and describe how each child changed with [`Mutation::Transition`]s.
+    ///
+    /// The children that stay or leave are described before the list is diffed, while their elements are still in
+    /// place. The children that enter are described after the list is diffed, once their elements are created.
+    fn diff_animated_keyed_children(
+        &mut self,
+        old: &'b [VNode<'b>],
+        new: &'b [VNode<'b>],
+        parent: ElementRef,
+    ) {
+        let new_keys = new.iter().map(|node| node.key).collect::<FxHashSet<_>>();
+        for node in old {
+            let kind = if new_keys.contains(&node.key) {
+                TransitionKind::Move
+            } else {
+                TransitionKind::Leave
+            };
+            self.transition_roots(node, kind);
+        }
+
+        self.diff_keyed_children(old, new, parent);
+
+        let old_keys = old.iter().map(|node| node.key).collect::<FxHashSet<_>>();
+        for node in new {
+            if !old_keys.contains(&node.key) {
+                self.transition_roots(node, TransitionKind::Enter);
+            }
+        }
+    }
+
+    /// Check if the component that is being diffed animates its keyed lists
+    fn animates_keyed_lists(&self) -> bool {
+        let scope = self.runtime.scope_stack.borrow().last().copied();
+        match scope.and_then(|scope| self.runtime.get_context(scope)) {
+            Some(cx) => cx.animate_keyed_lists.get(),
+            None => false,
+        }
+    }
+
+    /// Emit a transition for every root element of the node
+    ///
+    /// Text nodes and placeholders can't be animated, so they are skipped
+    fn transition_roots(&mut self, node: &'b VNode<'b>, kind: TransitionKind) {
+        for (idx, root) in node.template.get().roots.iter().enumerate() {
+            match node.dynamic_root(idx) {
+                None => {
+                    if let TemplateNode::Element { .. } = root {
+                        if let Some(&id) = node.root_ids.borrow().get(idx) {
+                            self.mutations.push(Mutation::Transition { id, kind });
+                        }
+                    }
+                }
+                Some(Fragment(nodes)) => nodes
+                    .iter()
+                    .for_each(|node| self.transition_roots(node, kind)),
+                Some(Component(comp)) => {
+                    let scope = comp.scope.get().unwrap();
+                    // The children of a portal are not part of the list
+                    if self.portal_anchor(scope).is_some() {
+                        continue;
+                    }
+                    if let RenderReturn::Ready(node) = unsafe {
+                        self.get_scope(scope)
+                            .unwrap()
+                            .root_node()
+                            .extend_lifetime_ref()
+                    } {
+                        self.transition_roots(node, kind);
+                    }
+                }
+                Some(Text(_)) | Some(Placeholder(_)) => {}
+            }
+        }
+    }
+
     /// Diff both ends of the children that share keys.
     ///
     /// Returns a left offset and right offset of that indicates a smaller section to pass onto the middle diffing.
@@ -806,7 +907,7 @@ impl<'b> VirtualDom {
     }
 
     /// Push all the real nodes on the stack
-    fn push_all_real_nodes(&mut self, node: &'b VNode<'b>) -> usize {
+    pub(crate) fn push_all_real_nodes(&mut self, node: &'b VNode<'b>) -> usize {
         node.template
             .get()
             .roots
@@ -843,6 +944,11 @@ impl<'b> VirtualDom {
 
                     Component(comp) => {
                         let scope = comp.scope.get().unwrap();
+                        // The nodes of a portal stay in its target
+                        if let Some(anchor) = self.portal_anchor(scope) {
+                            self.mutations.push(Mutation::PushRoot { id: anchor });
+                            return 1;
+                        }
                         match unsafe {
                             self.get_scope(scope)
                                 .unwrap()
@@ -962,7 +1068,7 @@ impl<'b> VirtualDom {
             .for_each(|node| self.remove_node(node, true));
     }
 
-    fn remove_node(&mut self, node: &'b VNode<'b>, gen_muts: bool) {
+    pub(crate) fn remove_node(&mut self, node: &'b VNode<'b>, gen_muts: bool) {
         // Clean up any attributes that have claimed a static node as dynamic for mount/unmounta
         // Will not generate mutations!
         self.reclaim_attributes(node);
@@ -1058,7 +1164,7 @@ impl<'b> VirtualDom {
         };
     }
 
-    fn remove_placeholder(&mut self, t: &VPlaceholder, gen_muts: bool) {
+    pub(crate) fn remove_placeholder(&mut self, t: &VPlaceholder, gen_muts: bool) {
         if let Some(id) = t.id.take() {
             if gen_muts {
                 self.mutations.push(Mutation::Remove { id });
@@ -1083,6 +1189,10 @@ impl<'b> VirtualDom {
             .take()
             .expect("VComponents to always have a scope");
 
+        // The nodes of a portal are not inside of the parent, so they are always removed
+        let anchor = self.portal_anchor(scope);
+        let remove_roots = gen_muts || anchor.is_some();
+
         // Remove the component from the dom
         match unsafe {
             self.get_scope(scope)
@@ -1090,10 +1200,19 @@ impl<'b> VirtualDom {
                 .root_node()
                 .extend_lifetime_ref()
         } {
-            RenderReturn::Ready(t) => self.remove_node(t, gen_muts),
-            RenderReturn::Aborted(placeholder) => self.remove_placeholder(placeholder, gen_muts),
+            RenderReturn::Ready(t) => self.remove_node(t, remove_roots),
+            RenderReturn::Aborted(placeholder) => {
+                self.remove_placeholder(placeholder, remove_roots)
+            }
         };
 
+        if let Some(anchor) = anchor {
+            if gen_muts {
+                self.mutations.push(Mutation::Remove { id: anchor });
+            }
+        }
+        self.reclaim_portal(scope);
+
         // Restore the props back to the vcomponent in case it gets rendered again
         let props = self.scopes[scope.0].props.take();
         *comp.props.borrow_mut() = unsafe { std::mem::transmute(props) };
@@ -1110,6 +1229,9 @@ impl<'b> VirtualDom {
             Some(Placeholder(t)) => t.id.get().unwrap(),
             Some(Component(comp)) => {
                 let scope = comp.scope.get().unwrap();
+                if let Some(anchor) = self.portal_anchor(scope) {
+                    return anchor;
+                }
                 match unsafe {
                     self.get_scope(scope)
                         .unwrap()
@@ -1131,6 +1253,9 @@ impl<'b> VirtualDom {
             Some(Placeholder(t)) => t.id.get().unwrap(),
             Some(Component(comp)) => {
                 let scope = comp.scope.get().unwrap();
+                if let Some(anchor) = self.portal_anchor(scope) {
+                    return anchor;
+                }
                 match unsafe {
                     self.get_scope(scope)
                         .unwrap()
@@ -1,9 +1,11 @@
-use std::hash::Hash;
+use std::{collections::BTreeSet, hash::Hash};
 
-use crate::ScopeId;
+use crate::{Priority, ScopeId};
 
+/// A scope waiting to be rendered. Dirty scopes are rendered by priority first, and then from the top of the tree down.
 #[derive(Debug, Clone, Eq, PartialOrd, Ord)]
 pub struct DirtyScope {
+    pub priority: Priority,
     pub height: u32,
     pub id: ScopeId,
 }
@@ -19,3 +21,22 @@ impl Hash for DirtyScope {
         self.id.hash(state);
     }
 }
+
+/// Stop a scope from being rendered, returning the most urgent priority it was marked dirty with
+pub(crate) fn remove_dirty_scope(
+    dirty_scopes: &mut BTreeSet<DirtyScope>,
+    id: ScopeId,
+    height: u32,
+) -> Option<Priority> {
+    let mut removed = None;
+    for priority in [Priority::Background, Priority::Transition, Priority::Input] {
+        if dirty_scopes.remove(&DirtyScope {
+            priority,
+            height,
+            id,
+        }) {
+            removed = Some(priority);
+        }
+    }
+    removed
+}
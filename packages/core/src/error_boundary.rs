@@ -1,7 +1,8 @@
 use crate::{
+    runtime::with_runtime,
     scope_context::{consume_context, current_scope_id, schedule_update_any},
     Element, IntoDynNode, LazyNodes, Properties, Scope, ScopeId, ScopeState, Template,
-    TemplateAttribute, TemplateNode, VNode,
+    TemplateAttribute, TemplateNode, VNode, VirtualDom,
 };
 use std::{
     any::{Any, TypeId},
@@ -9,7 +10,7 @@ use std::{
     cell::{Cell, RefCell},
     error::Error,
     fmt::{Debug, Display},
-    rc::Rc,
+    rc::{Rc, Weak},
     sync::Arc,
 };
 
@@ -39,7 +40,6 @@ impl Debug for ErrorBoundaryInner {
     }
 }
 
-#[derive(Debug)]
 /// An instance of an error captured by a descendant component.
 pub struct CapturedError {
     /// The error captured by the error boundary
@@ -50,13 +50,33 @@ pub struct CapturedError {
 
     /// The scope that threw the error
     pub scope: ScopeId,
+
+    /// The names of the components from the root of the tree down to the scope that threw the error
+    pub scope_path: Vec<&'static str>,
+
+    /// The boundary that caught the error, if any
+    boundary: Option<Weak<ErrorBoundaryInner>>,
+}
+
+impl Debug for CapturedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CapturedError")
+            .field("error", &self.error)
+            .field("backtrace", &self.backtrace)
+            .field("scope", &self.scope)
+            .field("scope_path", &self.scope_path)
+            .finish()
+    }
 }
 
 impl Display for CapturedError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!(
-            "Encountered error: {:?}\nIn scope: {:?}\nBacktrace: {}",
-            self.error, self.scope, self.backtrace
+            "Encountered error: {:?}\nIn scope: {:?} ({})\nBacktrace: {}",
+            self.error,
+            self.scope,
+            self.scope_path.join(" > "),
+            self.backtrace
         ))
     }
 }
@@ -64,6 +84,38 @@ impl Display for CapturedError {
 impl Error for CapturedError {}
 
 impl CapturedError {
+    fn new(scope: ScopeId, error: Box<dyn Debug + 'static>, backtrace: Backtrace) -> Self {
+        Self {
+            error,
+            backtrace,
+            scope,
+            scope_path: scope_path(scope),
+            boundary: None,
+        }
+    }
+
+    /// Clear the error and render the children of the error boundary that caught it again.
+    ///
+    /// This is useful to let users retry after an error:
+    ///
+    /// ```rust, ignore
+    /// ErrorBoundary {
+    ///     handle_error: |error| {
+    ///         let message = error.to_string();
+    ///         rsx! {
+    ///             pre { "{message}" }
+    ///             button { onclick: move |_| error.reset(), "Try again" }
+    ///         }
+    ///     },
+    ///     FlakyComponent {}
+    /// }
+    /// ```
+    pub fn reset(&self) {
+        if let Some(inner) = self.boundary.as_ref().and_then(Weak::upgrade) {
+            ErrorBoundary { inner }.reset();
+        }
+    }
+
     /// Downcast the error type into a concrete error type
     pub fn downcast<T: 'static>(&self) -> Option<&T> {
         if TypeId::of::<T>() == self.error.type_id() {
@@ -113,12 +165,10 @@ impl ErrorBoundary {
         error: Box<dyn Debug + 'static>,
         backtrace: Backtrace,
     ) {
-        println!("{:?} {:?}", error, self.inner._id);
-        self.inner.error.replace(Some(CapturedError {
-            error,
-            scope,
-            backtrace,
-        }));
+        let mut error = CapturedError::new(scope, error, backtrace);
+        error.boundary = Some(Rc::downgrade(&self.inner));
+        report_error(&error);
+        self.inner.error.replace(Some(error));
         (self.inner.rerun_boundary)(self.inner._id);
     }
 
@@ -126,6 +176,101 @@ impl ErrorBoundary {
     pub fn take_error(&self) -> Option<CapturedError> {
         self.inner.error.take()
     }
+
+    /// Clear any error captured by this error boundary and render its children again
+    pub fn reset(&self) {
+        self.inner.error.take();
+        (self.inner.rerun_boundary)(self.inner._id);
+    }
+}
+
+/// A callback that every error thrown in a component is forwarded to
+pub(crate) type ErrorReporter = Rc<dyn Fn(&CapturedError)>;
+
+/// Get the names of the components from the root of the tree down to the scope
+fn scope_path(scope: ScopeId) -> Vec<&'static str> {
+    with_runtime(|rt| {
+        let mut path = Vec::new();
+        let mut next = Some(scope);
+        while let Some(context) = next.and_then(|id| rt.get_context(id)) {
+            path.push(context.name);
+            next = context.parent_id;
+        }
+        path.reverse();
+        path
+    })
+    .unwrap_or_default()
+}
+
+/// Forward an error to the reporter of the VirtualDom, or log it if there is no reporter
+fn report_error(error: &CapturedError) {
+    let reporter = with_runtime(|rt| rt.error_reporter.borrow().clone()).flatten();
+    match reporter {
+        Some(reporter) => reporter(error),
+        None => tracing::error!(
+            "Error in {}: {:?}",
+            error.scope_path.join(" > "),
+            error.error
+        ),
+    }
+}
+
+impl VirtualDom {
+    /// Forward every error thrown by a component to a reporter, like a logging or telemetry service.
+    ///
+    /// The reporter is called for errors caught by an error boundary and for errors without a boundary to catch them.
+    ///
+    /// ```rust, ignore
+    /// let dom = VirtualDom::new(app).with_error_reporter(|error| {
+    ///     telemetry::report(error.scope_path.join(" > "), format!("{:?}", error.error));
+    /// });
+    /// ```
+    pub fn with_error_reporter(self, reporter: impl Fn(&CapturedError) + 'static) -> Self {
+        self.runtime.error_reporter.replace(Some(Rc::new(reporter)));
+        self
+    }
+
+    /// Set if panics in components should be caught. This is enabled by default.
+    ///
+    /// A caught panic is thrown to the nearest error boundary as a [`RenderPanic`] and the component renders nothing.
+    /// Catching panics requires unwinding, so panics still abort on platforms built with `panic = "abort"`, like most
+    /// WASM targets.
+    pub fn with_catch_panics(self, catch_panics: bool) -> Self {
+        self.runtime.catch_panics.set(catch_panics);
+        self
+    }
+}
+
+/// A panic caught while rendering a component
+pub struct RenderPanic {
+    /// The name of the component that panicked
+    pub component: &'static str,
+
+    /// The message of the panic
+    pub message: String,
+}
+
+impl RenderPanic {
+    pub(crate) fn new(component: &'static str, payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "Box<dyn Any>".to_string(),
+            },
+        };
+        Self { component, message }
+    }
+}
+
+impl Debug for RenderPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "component `{}` panicked: {}",
+            self.component, self.message
+        )
+    }
 }
 
 /// A trait to allow results to be thrown upwards to the nearest Error Boundary
@@ -201,13 +346,15 @@ pub trait Throw<S = ()>: Sized {
     }
 }
 
-fn throw_error<T>(e: impl Debug + 'static) -> Option<T> {
-    if let Some(cx) = consume_context::<ErrorBoundary>() {
-        match current_scope_id() {
-            Some(id) => cx.insert_error(id, Box::new(e), Backtrace::capture()),
-            None => {
-                tracing::error!("Cannot throw error outside of a component's scope.")
-            }
+pub(crate) fn throw_error<T>(e: impl Debug + 'static) -> Option<T> {
+    match current_scope_id() {
+        Some(id) => match consume_context::<ErrorBoundary>() {
+            Some(cx) => cx.insert_error(id, Box::new(e), Backtrace::capture()),
+            // Errors without a boundary are still reported
+            None => report_error(&CapturedError::new(id, Box::new(e), Backtrace::capture())),
+        },
+        None => {
+            tracing::error!("Cannot throw error outside of a component's scope.")
         }
     }
 
@@ -458,6 +605,9 @@ impl<
 /// Error boundaries are an easy way to handle errors in your application.
 /// They are similar to `try/catch` in JavaScript, but they only catch errors in the tree below them.
 /// Error boundaries are quick to implement, but it can be useful to individually handle errors in your components to provide a better user experience when you know that an error is likely to occur.
+///
+/// Panics in child components are caught and rendered by the boundary as well, and [`CapturedError::reset`] renders
+/// the children again so users can retry.
 #[allow(non_upper_case_globals, non_snake_case)]
 pub fn ErrorBoundary<'a>(cx: Scope<'a, ErrorBoundaryProps<'a>>) -> Element {
     let error_boundary = use_error_boundary(cx);
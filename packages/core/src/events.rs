@@ -25,6 +25,7 @@ pub struct Event<T: 'static + ?Sized> {
     /// The data associated with this event
     pub data: Rc<T>,
     pub(crate) propagates: Rc<Cell<bool>>,
+    pub(crate) default_prevented: Rc<Cell<bool>>,
 }
 
 impl<T> Event<T> {
@@ -46,6 +47,7 @@ impl<T> Event<T> {
         Event {
             data: Rc::new(f(&self.data)),
             propagates: self.propagates.clone(),
+            default_prevented: self.default_prevented.clone(),
         }
     }
 
@@ -84,6 +86,43 @@ impl<T> Event<T> {
         self.propagates.set(false);
     }
 
+    /// Prevent the default action of the platform event, like following a link or submitting a form.
+    ///
+    /// The web renderer handles events while the browser dispatches them, and prevents the default action after all
+    /// listeners of the event have run.
+    ///
+    /// Desktop and liveview handle events after the webview dispatched them, so this has no effect on the platform
+    /// event there. Register the events an element prevents with the `prevent_default` attribute next to its listeners
+    /// instead. The interpreter reads the attribute before it dispatches the event, and the attribute can change
+    /// between renders like any other attribute.
+    ///
+    /// # Example
+    ///
+    /// ```rust, ignore
+    /// rsx! {
+    ///     a {
+    ///         href: "https://dioxuslabs.com",
+    ///         // Prevents the default action in every renderer
+    ///         prevent_default: if *allow_navigation.get() { "" } else { "onclick" },
+    ///         onclick: move |evt: Event<MouseData>| {
+    ///             // Prevents the default action in the web renderer
+    ///             if !*allow_navigation.get() {
+    ///                 evt.prevent_default();
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn prevent_default(&self) {
+        self.default_prevented.set(true);
+    }
+
+    /// Check if the default action of the platform event will run. Returns `false` if a listener called
+    /// [`Event::prevent_default`].
+    pub fn default_action_enabled(&self) -> bool {
+        !self.default_prevented.get()
+    }
+
     /// Get a reference to the inner data from this event
     ///
     /// ```rust, ignore
@@ -107,6 +146,7 @@ impl<T: ?Sized> Clone for Event<T> {
     fn clone(&self) -> Self {
         Self {
             propagates: self.propagates.clone(),
+            default_prevented: self.default_prevented.clone(),
             data: self.data.clone(),
         }
     }
@@ -0,0 +1,242 @@
+use crate::{innerlude::*, runtime::with_runtime, scopes::ScopeId};
+use std::{any::Any, fmt::Debug};
+
+/// A snapshot of a scope for devtools, created with [`VirtualDom::inspect`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeInspection {
+    /// The id of the scope
+    pub id: ScopeId,
+
+    /// The name of the component of the scope
+    pub name: String,
+
+    /// The scope the context of this scope is inherited from
+    pub parent: Option<ScopeId>,
+
+    /// The components the scope renders, in the order they are rendered
+    pub children: Vec<ScopeId>,
+
+    /// The number of scopes above this scope
+    pub height: u32,
+
+    /// The number of times the scope rendered
+    pub generation: usize,
+
+    /// If the scope is waiting for suspended work
+    pub suspended: bool,
+
+    /// The props of the component formatted with [`Debug`], if they can be formatted
+    pub props: Option<String>,
+
+    /// The hooks of the scope in the order they are called, labeled with their type. Only hooks created with
+    /// [`ScopeState::use_inspected_hook`] have a value.
+    pub hooks: Vec<InspectedValue>,
+
+    /// The type names of the contexts the scope provides
+    pub contexts: Vec<String>,
+
+    /// The values of the scope registered with [`ScopeState::inspect`] and [`add_inspector`]
+    pub values: Vec<InspectedValue>,
+}
+
+/// A value of a scope that is shown in devtools
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct InspectedValue {
+    /// What the value is, like `props` or the type of a signal
+    pub label: String,
+
+    /// The value formatted with [`Debug`], if the value can be formatted
+    pub value: Option<String>,
+
+    /// The scopes that are rendered when the value changes
+    pub subscribers: Vec<ScopeId>,
+}
+
+impl InspectedValue {
+    /// Create a value that is formatted with [`Debug`]
+    pub fn new(label: impl Into<String>, value: &(impl Debug + ?Sized)) -> Self {
+        Self {
+            label: label.into(),
+            value: Some(format!("{:?}", value)),
+            subscribers: Vec::new(),
+        }
+    }
+}
+
+/// The type of a hook, and how to format it if it was created with [`ScopeState::use_inspected_hook`]
+pub(crate) struct HookType {
+    pub name: &'static str,
+    pub format: Option<fn(&dyn Any) -> String>,
+}
+
+/// Formats props that implement [`Debug`] in the Props derive with autoref specialization:
+/// `(&&DebugIfPossible(props)).debug_if_possible()`
+#[doc(hidden)]
+pub struct DebugIfPossible<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait FormatDebug {
+    fn debug_if_possible(&self) -> Option<String>;
+}
+
+impl<T: Debug> FormatDebug for &DebugIfPossible<'_, T> {
+    fn debug_if_possible(&self) -> Option<String> {
+        Some(format!("{:?}", self.0))
+    }
+}
+
+#[doc(hidden)]
+pub trait FormatNoDebug {
+    fn debug_if_possible(&self) -> Option<String>;
+}
+
+impl<T> FormatNoDebug for DebugIfPossible<'_, T> {
+    fn debug_if_possible(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Register a function that describes a value owned by a scope every time the scope is inspected.
+///
+/// This is used by state management libraries to show their state in devtools. The inspector is removed once it
+/// returns `None`, so it should return `None` after the value it describes is dropped.
+pub fn add_inspector(scope: ScopeId, inspector: impl Fn() -> Option<InspectedValue> + 'static) {
+    with_runtime(|rt| {
+        if let Some(cx) = rt.get_context(scope) {
+            cx.inspectors.borrow_mut().push(Box::new(inspector));
+        }
+    });
+}
+
+impl ScopeContext {
+    pub(crate) fn inspect(&self, label: &'static str, value: &(impl Debug + ?Sized)) {
+        let mut inspected = self.inspected.borrow_mut();
+        let value = InspectedValue::new(label, value);
+        match inspected.iter_mut().find(|old| old.label == label) {
+            Some(old) => *old = value,
+            None => inspected.push(value),
+        }
+    }
+}
+
+impl ScopeState {
+    /// Show a value of this scope in devtools.
+    ///
+    /// The value is formatted with [`Debug`] every time the scope renders, and the latest value with the same label is
+    /// shown. This should only be used in debug builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust, ignore
+    /// fn Counter(cx: Scope) -> Element {
+    ///     let count = use_state(cx, || 0);
+    ///     cx.inspect("count", count.get());
+    ///     render! { "{count}" }
+    /// }
+    /// ```
+    pub fn inspect(&self, label: &'static str, value: &(impl Debug + ?Sized)) {
+        self.context().inspect(label, value)
+    }
+}
+
+impl VirtualDom {
+    /// Take a snapshot of every scope in the VirtualDom for devtools.
+    ///
+    /// The scopes are listed depth first, starting from the main root. Additional roots are children of the main root.
+    pub fn inspect(&self) -> Vec<ScopeInspection> {
+        let mut scopes = Vec::new();
+        self.inspect_scope(ScopeId::ROOT, &mut scopes);
+        scopes
+    }
+
+    fn inspect_scope(&self, id: ScopeId, scopes: &mut Vec<ScopeInspection>) {
+        let scope = match self.get_scope(id) {
+            Some(scope) => scope,
+            None => return,
+        };
+
+        let mut children = Vec::new();
+        if let Some(RenderReturn::Ready(node)) = scope.try_root_node() {
+            collect_components(node, &mut children);
+        }
+        // Other roots are not rendered by the main root, but they inherit its context
+        if id == ScopeId::ROOT {
+            children.extend(
+                self.subtrees
+                    .iter()
+                    .filter(|(subtree, _)| *subtree != 0)
+                    .map(|(_, subtree)| subtree.root),
+            );
+        }
+
+        let context = scope.context();
+        let mut values = context.inspected.borrow().clone();
+        // Inspectors of values that were dropped are removed
+        context
+            .inspectors
+            .borrow_mut()
+            .retain(|inspector| match inspector() {
+                Some(value) => {
+                    values.push(value);
+                    true
+                }
+                None => false,
+            });
+
+        scopes.push(ScopeInspection {
+            id,
+            name: context.name.to_string(),
+            parent: context.parent_id,
+            children: children.clone(),
+            height: context.height,
+            generation: scope.generation(),
+            suspended: self.is_suspended(id),
+            props: scope.props.as_ref().and_then(|props| props.inspect()),
+            hooks: scope
+                .hook_types
+                .borrow()
+                .iter()
+                .zip(scope.hooks.borrow().iter())
+                .map(|(ty, hook)| InspectedValue {
+                    label: ty.name.to_string(),
+                    // Safety: hooks are only borrowed mutably while the scope renders, and the VirtualDom is not
+                    // rendering while it is borrowed
+                    value: ty.format.map(|format| format(unsafe { &*hook.get() })),
+                    subscribers: Vec::new(),
+                })
+                .collect(),
+            contexts: context
+                .context_types
+                .borrow()
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            values,
+        });
+        drop(context);
+
+        for child in children {
+            self.inspect_scope(child, scopes);
+        }
+    }
+}
+
+fn collect_components(node: &VNode, children: &mut Vec<ScopeId>) {
+    for node in node.dynamic_nodes {
+        match node {
+            DynamicNode::Component(component) => {
+                if let Some(scope) = component.scope.get() {
+                    children.push(scope);
+                }
+            }
+            DynamicNode::Fragment(nodes) => {
+                for node in nodes.iter() {
+                    collect_components(node, children);
+                }
+            }
+            DynamicNode::Text(_) | DynamicNode::Placeholder(_) => {}
+        }
+    }
+}
@@ -3,6 +3,7 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 #![warn(missing_docs)]
 
+mod animated_list;
 mod any_props;
 mod arena;
 mod bump_frame;
@@ -12,32 +13,44 @@ mod dirty_scope;
 mod error_boundary;
 mod events;
 mod fragment;
+mod inspect;
 mod lazynodes;
 mod mutations;
 mod nodes;
+mod portal;
+mod priority;
+mod profiler;
 mod properties;
 mod runtime;
 mod scheduler;
 mod scope_arena;
 mod scope_context;
 mod scopes;
+mod snapshot;
+mod subtree;
 mod virtual_dom;
 
 pub(crate) mod innerlude {
+    pub use crate::animated_list::*;
     pub use crate::arena::*;
     pub use crate::dirty_scope::*;
     pub use crate::error_boundary::*;
     pub use crate::events::*;
     pub use crate::fragment::*;
+    pub use crate::inspect::*;
     pub use crate::lazynodes::*;
     pub use crate::mutations::*;
     pub use crate::nodes::RenderReturn;
     pub use crate::nodes::*;
+    pub use crate::portal::*;
+    pub use crate::priority::*;
+    pub use crate::profiler::*;
     pub use crate::properties::*;
     pub use crate::runtime::{Runtime, RuntimeGuard};
     pub use crate::scheduler::*;
     pub use crate::scope_context::*;
     pub use crate::scopes::*;
+    pub use crate::snapshot::*;
     pub use crate::virtual_dom::*;
 
     /// An [`Element`] is a possibly-none [`VNode`] created by calling `render` on [`Scope`] or [`ScopeState`].
@@ -76,11 +89,24 @@ pub(crate) mod innerlude {
 }
 
 pub use crate::innerlude::{
-    fc_to_builder, vdom_is_rendering, AnyValue, Attribute, AttributeType, AttributeValue,
-    BorrowedAttributeValue, CapturedError, Component, DynamicNode, Element, ElementId, Event,
-    Fragment, HasAttributes, IntoDynNode, LazyNodes, MountedAttribute, Mutation, Mutations,
-    Properties, RenderReturn, Scope, ScopeId, ScopeState, Scoped, TaskId, Template,
-    TemplateAttribute, TemplateNode, VComponent, VNode, VPlaceholder, VText, VirtualDom,
+    fc_to_builder, vdom_is_rendering, AnimatedList, AnyValue, Attribute, AttributeType,
+    AttributeValue, BorrowedAttributeValue, CapturedError, Component, DynamicNode, Element,
+    ElementId, Event, Fragment, HasAttributes, IntoDynNode, LazyNodes, MountedAttribute, Mutation,
+    Mutations, Portal, Priority, Properties, RenderPanic, RenderReturn, Scope, ScopeId, ScopeState,
+    Scoped, TaskId, Template, TemplateAttribute, TemplateNode, TransitionKind, VComponent, VNode,
+    VPlaceholder, VText, VirtualDom,
+};
+
+pub use crate::innerlude::{DirtyRecord, Profile, RenderPass, RenderReason, ScopeRender};
+
+pub use crate::innerlude::{InspectedValue, ScopeInspection};
+
+#[doc(hidden)]
+pub use crate::innerlude::{DebugIfPossible, FormatDebug, FormatNoDebug};
+
+pub use crate::innerlude::{
+    AttributeSnapshot, AttributeValueSnapshot, DynamicNodeSnapshot, MountedAttributeSnapshot,
+    NodeSnapshot, RenderSnapshot, ScopeSnapshot, VirtualDomSnapshot,
 };
 
 /// The purpose of this module is to alleviate imports of many common types
@@ -88,13 +114,15 @@ pub use crate::innerlude::{
 /// This includes types like [`Scope`], [`Element`], and [`Component`].
 pub mod prelude {
     pub use crate::innerlude::{
-        consume_context, consume_context_from_scope, current_scope_id, fc_to_builder, has_context,
-        provide_context, provide_context_to_scope, provide_root_context, push_future,
-        remove_future, schedule_update_any, spawn, spawn_forever, suspend, use_error_boundary,
-        AnyValue, Attribute, AttributeType, Component, Element, ErrorBoundary, Event, EventHandler,
-        Fragment, HasAttributes, IntoAttributeValue, IntoDynNode, LazyNodes, MountedAttribute,
-        Properties, Runtime, RuntimeGuard, Scope, ScopeId, ScopeState, Scoped, TaskId, Template,
-        TemplateAttribute, TemplateNode, Throw, VNode, VirtualDom,
+        add_inspector, consume_context, consume_context_from_scope, current_priority,
+        current_scope_id, fc_to_builder, has_context, provide_context, provide_context_to_scope,
+        provide_root_context, push_future, remove_future, schedule_update_any,
+        schedule_update_any_with_reason, spawn, spawn_at, spawn_forever, start_transition,
+        start_transition_in_scope, suspend, use_error_boundary, AnimatedList, AnyValue, Attribute,
+        AttributeType, Component, Element, ErrorBoundary, Event, EventHandler, Fragment,
+        HasAttributes, IntoAttributeValue, IntoDynNode, LazyNodes, MountedAttribute, Portal,
+        Priority, Properties, Runtime, RuntimeGuard, Scope, ScopeId, ScopeState, Scoped, TaskId,
+        Template, TemplateAttribute, TemplateNode, Throw, VNode, VirtualDom,
     };
 }
 
@@ -243,4 +243,52 @@ pub enum Mutation<'a> {
         /// The ID of the root node to push.
         id: ElementId,
     },
+
+    /// Find the element with the given id attribute and assign it the target ElementId.
+    ///
+    /// The element is the target of a [`crate::Portal`]. The children of the portal are appended to it with
+    /// [`Mutation::AppendChildren`].
+    AssignPortalTarget {
+        /// The id attribute of the element the portal renders into.
+        target: String,
+
+        /// The ID we're assigning to the element.
+        id: ElementId,
+    },
+
+    /// Animate a root element of a child of a keyed list.
+    ///
+    /// Transitions are only emitted for the keyed lists of components that opted into animations with
+    /// [`crate::AnimatedList`]. Renderers that don't animate lists can ignore this mutation.
+    Transition {
+        /// The ID of the root element of the child.
+        id: ElementId,
+
+        /// How the child changed in the list.
+        kind: TransitionKind,
+    },
+}
+
+/// How a child of an animated keyed list changed. See [`Mutation::Transition`].
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransitionKind {
+    /// The child was added to the list.
+    ///
+    /// This is emitted after the element was created and inserted, so the renderer can start the enter animation once
+    /// the mutations are applied.
+    Enter,
+
+    /// The child stays in the list, but it might move.
+    ///
+    /// This is emitted before any other mutation of the list, so the renderer can record the position of the element
+    /// before the mutations are applied and animate it from there to its new position.
+    Move,
+
+    /// The child was removed from the list.
+    ///
+    /// This is emitted before the element is removed with [`Mutation::Remove`] or replaced with
+    /// [`Mutation::ReplaceWith`]. The renderer can keep the element in the tree until the leave animation ends.
+    /// The [`ElementId`] of the element is reused by the VirtualDom as soon as it is removed.
+    Leave,
 }
@@ -0,0 +1,223 @@
+use crate::innerlude::*;
+
+/// Render the children of this component into another element.
+///
+/// ## Details
+///
+/// Portals are useful for modals, tooltips, and other overlays that need to escape the layout of their parent. The
+/// children are rendered into the element with the id `target` instead of the position of the portal in the tree.
+///
+/// The portal is still a child of the component that renders it. The children can consume the context of the parents
+/// of the portal, and events bubble from the children to the parents of the portal, not to the parents of the target.
+///
+/// Renderers find the target by its id attribute. The target must exist when the portal is mounted and it should not be
+/// rendered by a component that is removed before the portal.
+///
+/// ## Example
+///
+/// ```rust, ignore
+/// rsx! {
+///     div {
+///         onclick: move |_| println!("clicks in the modal bubble up to here"),
+///         Portal {
+///             target: "modal-root",
+///             div { class: "modal", "Hello from the modal!" }
+///         }
+///     }
+/// }
+/// ```
+#[allow(non_upper_case_globals, non_snake_case)]
+pub fn Portal<'a>(cx: Scope<'a, PortalProps<'a>>) -> Element {
+    {
+        let context = cx.context();
+        let mut portal = context.portal.borrow_mut();
+        match &mut *portal {
+            Some(portal) => {
+                if portal.target != cx.props.target {
+                    portal.target = cx.props.target.to_string();
+                }
+            }
+            None => {
+                *portal = Some(PortalState {
+                    target: cx.props.target.to_string(),
+                    mounted: None,
+                    anchor: None,
+                })
+            }
+        }
+    }
+
+    let children = cx.props.children.as_ref()?;
+    Some(cx.vnode(
+        children.parent.clone(),
+        children.key,
+        children.template.clone(),
+        children.root_ids.clone(),
+        children.dynamic_nodes,
+        children.dynamic_attrs,
+    ))
+}
+
+pub struct PortalProps<'a> {
+    target: &'a str,
+    children: Element<'a>,
+}
+pub struct PortalBuilder<'a, const TARGET: bool> {
+    target: &'a str,
+    children: Element<'a>,
+}
+impl<'a, const TARGET: bool> PortalBuilder<'a, TARGET> {
+    pub fn target(self, target: &'a str) -> PortalBuilder<'a, true> {
+        PortalBuilder {
+            target,
+            children: self.children,
+        }
+    }
+    pub fn children(self, children: Element<'a>) -> Self {
+        PortalBuilder {
+            target: self.target,
+            children,
+        }
+    }
+}
+impl<'a> PortalBuilder<'a, true> {
+    pub fn build(self) -> PortalProps<'a> {
+        PortalProps {
+            target: self.target,
+            children: self.children,
+        }
+    }
+}
+
+impl<'a> Properties<'_> for PortalProps<'a> {
+    type Builder = PortalBuilder<'a, false>;
+    const IS_STATIC: bool = false;
+    fn builder(_cx: &ScopeState) -> Self::Builder {
+        PortalBuilder {
+            target: "",
+            children: None,
+        }
+    }
+    unsafe fn memoize(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+/// The state of a scope that renders a [`Portal`]
+pub(crate) struct PortalState {
+    /// The id of the element the portal should render into
+    pub(crate) target: String,
+    /// The id of the element the children are mounted in, and the ElementId the renderer assigned to that element
+    pub(crate) mounted: Option<(String, ElementId)>,
+    /// The placeholder that marks the position of the portal in its parent
+    pub(crate) anchor: Option<ElementId>,
+}
+
+impl VirtualDom {
+    /// Get the placeholder that marks the position of a portal in its parent, if the scope is a mounted portal.
+    pub(crate) fn portal_anchor(&self, scope: ScopeId) -> Option<ElementId> {
+        let context = self.runtime.get_context(scope)?;
+        let portal = context.portal.borrow();
+        portal.as_ref().and_then(|portal| portal.anchor)
+    }
+
+    /// Move the `m` nodes on the stack into the target of the portal and put a placeholder in their place.
+    ///
+    /// Returns the number of nodes left on the stack, or `m` if the scope is not a portal.
+    pub(crate) fn mount_portal(&mut self, scope: ScopeId, m: usize) -> usize {
+        let is_portal = match self.runtime.get_context(scope) {
+            Some(context) => context.portal.borrow().is_some(),
+            None => false,
+        };
+        if !is_portal {
+            return m;
+        }
+
+        let target = self.assign_portal_target(scope);
+        self.mutations
+            .push(Mutation::AppendChildren { id: target, m });
+
+        let anchor = self.next_element();
+        self.mutations
+            .push(Mutation::CreatePlaceholder { id: anchor });
+        let context = self.runtime.get_context(scope).unwrap();
+        context.portal.borrow_mut().as_mut().unwrap().anchor = Some(anchor);
+
+        1
+    }
+
+    /// Move the children of the portal if it was rendered with a new target.
+    pub(crate) fn diff_portal_target(&mut self, scope: ScopeId) {
+        let moved = match self.runtime.get_context(scope) {
+            Some(context) => match &*context.portal.borrow() {
+                Some(PortalState {
+                    target,
+                    mounted: Some((mounted, _)),
+                    ..
+                }) => target != mounted,
+                _ => false,
+            },
+            None => false,
+        };
+        if !moved {
+            return;
+        }
+
+        let m = match unsafe {
+            self.get_scope(scope)
+                .unwrap()
+                .root_node()
+                .extend_lifetime_ref()
+        } {
+            RenderReturn::Ready(node) => self.push_all_real_nodes(node),
+            RenderReturn::Aborted(placeholder) => {
+                self.mutations.push(Mutation::PushRoot {
+                    id: placeholder.id.get().unwrap(),
+                });
+                1
+            }
+        };
+        let target = self.assign_portal_target(scope);
+        self.mutations
+            .push(Mutation::AppendChildren { id: target, m });
+    }
+
+    /// Ask the renderer to assign an ElementId to the target of the portal. The id of the previous target is reclaimed.
+    fn assign_portal_target(&mut self, scope: ScopeId) -> ElementId {
+        let id = self.next_element();
+        let (target, previous) = {
+            let context = self.runtime.get_context(scope).unwrap();
+            let mut portal = context.portal.borrow_mut();
+            let portal = portal.as_mut().unwrap();
+            let previous = portal.mounted.take().map(|(_, id)| id);
+            portal.mounted = Some((portal.target.clone(), id));
+            (portal.target.clone(), previous)
+        };
+        if let Some(previous) = previous {
+            self.reclaim(previous);
+        }
+        self.mutations
+            .push(Mutation::AssignPortalTarget { target, id });
+        id
+    }
+
+    /// Reclaim the ids of a portal that is being removed. The children of the portal are not inside of the parent, so
+    /// they need to be removed separately.
+    pub(crate) fn reclaim_portal(&mut self, scope: ScopeId) {
+        let portal = match self.runtime.get_context(scope) {
+            Some(context) => context.portal.borrow_mut().take(),
+            None => None,
+        };
+        if let Some(PortalState {
+            mounted, anchor, ..
+        }) = portal
+        {
+            if let Some((_, target)) = mounted {
+                self.reclaim(target);
+            }
+            if let Some(anchor) = anchor {
+                self.reclaim(anchor);
+            }
+        }
+    }
+}
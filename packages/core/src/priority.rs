@@ -0,0 +1,138 @@
+use crate::{
+    innerlude::{RenderReason, Runtime, SchedulerMsg, ScopeId, ScopeState, VirtualDom},
+    runtime::with_runtime,
+};
+
+/// How urgent an update is.
+///
+/// Dirty scopes are rendered in order of priority, and then from the top of the tree down. Updates with
+/// [`Priority::Input`] are always rendered before the VirtualDom yields, while other updates may be split over multiple
+/// calls to [`VirtualDom::render_with_deadline`] and [`VirtualDom::render_until`].
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Priority {
+    /// Updates caused by user input, like typing into a text field. These are scheduled by event handlers.
+    Input,
+
+    /// Updates that can be interrupted by user input, started with [`start_transition`].
+    Transition,
+
+    /// Every other update, like updates from futures or timers.
+    #[default]
+    Background,
+}
+
+impl Runtime {
+    /// Run a function and schedule every update it causes with the given priority
+    pub(crate) fn with_priority<O>(&self, priority: Priority, f: impl FnOnce() -> O) -> O {
+        let previous = self.priority.replace(priority);
+        let out = f();
+        self.priority.set(previous);
+        out
+    }
+}
+
+/// Get the priority updates scheduled right now are rendered with
+pub fn current_priority() -> Priority {
+    with_runtime(|rt| rt.priority.get()).unwrap_or_default()
+}
+
+/// Run a function and schedule every update it causes as a transition.
+///
+/// Transitions are rendered after user input. Large updates, like filtering a long list, can be started as a
+/// transition to keep the app responsive while they render.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// input {
+///     oninput: move |evt| {
+///         // The input updates right away...
+///         text.set(evt.value());
+///         // ...while the list is rendered when the VirtualDom has time
+///         start_transition(|| filter.set(evt.value()));
+///     }
+/// }
+/// ```
+pub fn start_transition<O>(f: impl FnOnce() -> O) -> O {
+    match Runtime::current() {
+        Some(rt) => rt.with_priority(Priority::Transition, f),
+        None => f(),
+    }
+}
+
+/// Start a transition (see [`start_transition`]) and render the scope again once it has been rendered.
+///
+/// Until then, [`ScopeState::is_transition_pending`] returns `true` for the scope, which can be used to show a loading
+/// state.
+pub fn start_transition_in_scope<O>(scope: ScopeId, f: impl FnOnce() -> O) -> O {
+    match Runtime::current() {
+        Some(rt) => {
+            rt.pending_transitions.borrow_mut().insert(scope);
+            // Show the pending state with the priority of the update that started the transition
+            _ = rt.scheduler.sender.unbounded_send(SchedulerMsg::Immediate(
+                scope,
+                RenderReason::Update,
+                rt.priority.get(),
+            ));
+            rt.with_priority(Priority::Transition, f)
+        }
+        None => f(),
+    }
+}
+
+impl ScopeState {
+    /// Start a transition (see [`start_transition`]) and render this component again once it has been rendered.
+    ///
+    /// Until then, [`ScopeState::is_transition_pending`] returns `true`, which can be used to show a loading state.
+    pub fn start_transition<O>(&self, f: impl FnOnce() -> O) -> O {
+        start_transition_in_scope(self.scope_id(), f)
+    }
+
+    /// Check if a transition started with [`ScopeState::start_transition`] from this component is still rendering
+    pub fn is_transition_pending(&self) -> bool {
+        self.runtime
+            .pending_transitions
+            .borrow()
+            .contains(&self.scope_id())
+    }
+}
+
+impl VirtualDom {
+    /// Check if there are scopes of the main root waiting to be rendered.
+    ///
+    /// Renderers that render with [`VirtualDom::render_until`] should keep rendering until this returns `false`. The
+    /// dirty scopes of roots added with [`VirtualDom::add_root`] are not included because they are only rendered by
+    /// [`VirtualDom::root_mutations`].
+    pub fn has_dirty_scopes(&self) -> bool {
+        self.next_dirty_scope(0).is_some()
+    }
+
+    /// Render the components waiting for a transition again once every transition has been rendered
+    pub(crate) fn finish_transitions(&mut self) {
+        if self.runtime.pending_transitions.borrow().is_empty() {
+            return;
+        }
+
+        // Dirty scopes are sorted by priority, so every transition is after the scopes with input priority
+        let transitions_left = self
+            .dirty_scopes
+            .iter()
+            .find(|dirty| dirty.priority != Priority::Input)
+            .map(|dirty| dirty.priority == Priority::Transition)
+            .unwrap_or(false);
+        if transitions_left {
+            return;
+        }
+
+        let waiting: Vec<_> = self
+            .runtime
+            .pending_transitions
+            .borrow_mut()
+            .drain()
+            .collect();
+        for id in waiting {
+            self.mark_dirty_with_reason(id, RenderReason::Update, Priority::Input);
+        }
+    }
+}
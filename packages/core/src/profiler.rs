@@ -0,0 +1,336 @@
+use crate::{innerlude::*, scopes::ScopeId};
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+/// Why a scope was rendered
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderReason {
+    /// The scope was rendered for the first time
+    Mount,
+
+    /// The scope called `needs_update` or an update callback created with `schedule_update` or `schedule_update_any`
+    Update,
+
+    /// A signal the scope subscribed to was written to
+    Signal,
+
+    /// The parent of the scope rendered it with new props
+    ParentProps(ScopeId),
+
+    /// The scope was marked dirty with [`VirtualDom::mark_dirty`]
+    MarkDirty,
+
+    /// A template the scope renders was replaced with [`VirtualDom::replace_template`]
+    HotReload,
+}
+
+/// A scope that was marked dirty while the VirtualDom was profiled
+#[derive(Debug, Clone)]
+pub struct DirtyRecord {
+    /// The scope that was marked dirty
+    pub scope: ScopeId,
+
+    /// The name of the component of the scope
+    pub name: String,
+
+    /// The reason the scope was marked dirty
+    pub reason: RenderReason,
+
+    /// When the scope was marked dirty, relative to the start of the profile
+    pub time: Duration,
+}
+
+/// A render of a scope recorded by the profiler
+#[derive(Debug, Clone)]
+pub struct ScopeRender {
+    /// The scope that was rendered
+    pub scope: ScopeId,
+
+    /// The name of the component of the scope
+    pub name: String,
+
+    /// The reason the scope was rendered
+    pub reason: RenderReason,
+
+    /// When the render started, relative to the start of the profile
+    pub start: Duration,
+
+    /// How long the component took to render
+    pub render: Duration,
+
+    /// How long it took to diff or create the nodes of the component, including the components it rendered
+    pub diff: Duration,
+
+    /// The number of mutations the diff generated, including the mutations of the components it rendered
+    pub mutations: usize,
+}
+
+/// The work the VirtualDom did between two batches of mutations
+#[derive(Debug, Clone, Default)]
+pub struct RenderPass {
+    /// When the first scope of the pass was rendered, relative to the start of the profile
+    pub start: Duration,
+
+    /// How long the pass took
+    pub duration: Duration,
+
+    /// The scopes that were marked dirty before or during the pass
+    pub dirty: Vec<DirtyRecord>,
+
+    /// The scopes rendered during the pass in the order they were rendered
+    pub renders: Vec<ScopeRender>,
+
+    /// The number of mutations sent to the renderer
+    pub mutations: usize,
+
+    /// The number of new templates sent to the renderer
+    pub templates: usize,
+}
+
+/// A recording of the renders of a VirtualDom started with [`VirtualDom::start_profiling`]
+#[derive(Debug, Clone, Default)]
+pub struct Profile {
+    /// The render passes in the order they happened
+    pub passes: Vec<RenderPass>,
+}
+
+impl Profile {
+    /// Convert the profile into the Chrome trace event format.
+    ///
+    /// The trace can be opened in `chrome://tracing`, [Perfetto](https://ui.perfetto.dev), or any other profiler that
+    /// supports the format.
+    pub fn to_chrome_trace(&self) -> String {
+        let mut events = Vec::new();
+
+        for pass in &self.passes {
+            events.push(format!(
+                r#"{{"name":"render pass","cat":"pass","ph":"X","ts":{},"dur":{},"pid":1,"tid":1,"args":{{"mutations":{},"templates":{},"renders":{}}}}}"#,
+                micros(pass.start),
+                micros(pass.duration),
+                pass.mutations,
+                pass.templates,
+                pass.renders.len()
+            ));
+
+            for dirty in &pass.dirty {
+                events.push(format!(
+                    r#"{{"name":"dirty {}","cat":"dirty","ph":"i","s":"t","ts":{},"pid":1,"tid":1,"args":{{"scope":{},"reason":"{}"}}}}"#,
+                    escape(&dirty.name),
+                    micros(dirty.time),
+                    dirty.scope.0,
+                    escape(&format!("{:?}", dirty.reason))
+                ));
+            }
+
+            for render in &pass.renders {
+                events.push(format!(
+                    r#"{{"name":"{}","cat":"render","ph":"X","ts":{},"dur":{},"pid":1,"tid":1,"args":{{"scope":{},"reason":"{}"}}}}"#,
+                    escape(&render.name),
+                    micros(render.start),
+                    micros(render.render),
+                    render.scope.0,
+                    escape(&format!("{:?}", render.reason))
+                ));
+                events.push(format!(
+                    r#"{{"name":"diff {}","cat":"diff","ph":"X","ts":{},"dur":{},"pid":1,"tid":1,"args":{{"scope":{},"mutations":{}}}}}"#,
+                    escape(&render.name),
+                    micros(render.start + render.render),
+                    micros(render.diff),
+                    render.scope.0,
+                    render.mutations
+                ));
+            }
+        }
+
+        format!(r#"{{"traceEvents":[{}]}}"#, events.join(","))
+    }
+
+    /// Write the profile to a file in the Chrome trace event format. See [`Profile::to_chrome_trace`].
+    pub fn write_chrome_trace(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_chrome_trace())
+    }
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Records the render passes of a VirtualDom while profiling is enabled
+pub(crate) struct Profiler {
+    start: Instant,
+    dirty: Vec<DirtyRecord>,
+    current: Option<(Instant, RenderPass)>,
+    profile: Profile,
+}
+
+impl Profiler {
+    fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            dirty: Vec::new(),
+            current: None,
+            profile: Profile::default(),
+        }
+    }
+
+    fn pass(&mut self) -> &mut RenderPass {
+        let start = self.start;
+        let dirty = &mut self.dirty;
+        let (_, pass) = self.current.get_or_insert_with(|| {
+            let now = Instant::now();
+            let pass = RenderPass {
+                start: now - start,
+                dirty: std::mem::take(dirty),
+                ..Default::default()
+            };
+            (now, pass)
+        });
+        pass
+    }
+
+    fn end_pass(&mut self, mutations: usize, templates: usize) {
+        if let Some((started, mut pass)) = self.current.take() {
+            pass.duration = started.elapsed();
+            pass.mutations = mutations;
+            pass.templates = templates;
+            self.profile.passes.push(pass);
+        }
+    }
+}
+
+impl VirtualDom {
+    /// Start recording the renders of the VirtualDom.
+    ///
+    /// While profiling, the VirtualDom records which scopes were marked dirty and why, how long each component took to
+    /// render and diff, and how many mutations each render pass generated. Call [`VirtualDom::stop_profiling`] to get
+    /// the [`Profile`].
+    ///
+    /// Renders are always traced with `tracing` spans, even if profiling is disabled.
+    ///
+    /// Profiling uses [`std::time::Instant`] which is not supported on `wasm32-unknown-unknown`.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(App);
+    /// dom.start_profiling();
+    ///
+    /// apply(dom.rebuild());
+    /// loop {
+    ///     dom.wait_for_work().await;
+    ///     apply(dom.render_immediate());
+    /// }
+    ///
+    /// dom.stop_profiling().write_chrome_trace("trace.json").unwrap();
+    /// ```
+    pub fn start_profiling(&mut self) {
+        if self.profiler.is_none() {
+            self.profiler = Some(Profiler::new());
+        }
+    }
+
+    /// Stop recording the renders of the VirtualDom and return the profile recorded since profiling started.
+    ///
+    /// Returns an empty profile if profiling was not started.
+    pub fn stop_profiling(&mut self) -> Profile {
+        match self.profiler.take() {
+            Some(mut profiler) => {
+                profiler.end_pass(0, 0);
+                profiler.profile
+            }
+            None => Profile::default(),
+        }
+    }
+
+    /// Check if the VirtualDom is recording a profile
+    pub fn is_profiling(&self) -> bool {
+        self.profiler.is_some()
+    }
+
+    /// Record why a scope was marked dirty
+    pub(crate) fn profile_dirty(&mut self, scope: ScopeId, reason: RenderReason) {
+        if let Some(profiler) = &mut self.profiler {
+            let name = match self.scopes.get(scope.0) {
+                Some(scope) => scope.name().to_string(),
+                None => return,
+            };
+            let time = profiler.start.elapsed();
+            let record = DirtyRecord {
+                scope,
+                name,
+                reason,
+                time,
+            };
+            match &mut profiler.current {
+                Some((_, pass)) => pass.dirty.push(record),
+                None => profiler.dirty.push(record),
+            }
+        }
+    }
+
+    /// Record a render of a scope that started at the given time
+    pub(crate) fn profile_render(
+        &mut self,
+        scope: ScopeId,
+        reason: RenderReason,
+        started: Instant,
+    ) {
+        if let Some(profiler) = &mut self.profiler {
+            let render = ScopeRender {
+                scope,
+                name: self.scopes[scope.0].name().to_string(),
+                reason,
+                start: started - profiler.start,
+                render: started.elapsed(),
+                diff: Duration::ZERO,
+                mutations: 0,
+            };
+            profiler.pass().renders.push(render);
+        }
+    }
+
+    /// Record how long the nodes of the last render of a scope took to diff or create
+    pub(crate) fn profile_diff(&mut self, scope: ScopeId, started: Instant, mutations: usize) {
+        if let Some(profiler) = &mut self.profiler {
+            let render = profiler
+                .pass()
+                .renders
+                .iter_mut()
+                .rev()
+                .find(|render| render.scope == scope);
+            if let Some(render) = render {
+                render.diff = started.elapsed();
+                render.mutations = mutations;
+            }
+        }
+    }
+
+    /// Finish the current render pass
+    pub(crate) fn profile_finalize(&mut self, mutations: &Mutations) {
+        if let Some(profiler) = &mut self.profiler {
+            profiler.end_pass(mutations.edits.len(), mutations.templates.len());
+        }
+    }
+
+    /// Get the time a piece of work started at if the VirtualDom is profiled
+    pub(crate) fn profile_start(&self) -> Option<Instant> {
+        self.profiler.as_ref().map(|_| Instant::now())
+    }
+}
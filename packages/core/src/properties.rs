@@ -49,6 +49,13 @@ pub trait Properties<'a>: Sized {
     /// The user must know if their props are static, but if they make a mistake, UB happens
     /// Therefore it's unsafe to memoize.
     unsafe fn memoize(&self, other: &Self) -> bool;
+
+    /// Format the props to show them in devtools.
+    ///
+    /// The derive macro formats props that implement [`Debug`](std::fmt::Debug) and returns `None` for other props.
+    fn inspect_value(&self) -> Option<String> {
+        None
+    }
 }
 
 impl Properties<'_> for () {
@@ -1,6 +1,11 @@
 use std::cell::{Cell, Ref, RefCell};
 
-use crate::{innerlude::Scheduler, scope_context::ScopeContext, scopes::ScopeId};
+use crate::{
+    innerlude::{ErrorReporter, Priority, Scheduler},
+    scope_context::ScopeContext,
+    scopes::ScopeId,
+};
+use rustc_hash::FxHashSet;
 use std::rc::Rc;
 
 thread_local! {
@@ -49,6 +54,18 @@ pub struct Runtime {
     // We use this to track the current scope
     pub(crate) scope_stack: RefCell<Vec<ScopeId>>,
     pub(crate) rendering: Cell<bool>,
+
+    // The priority updates are scheduled with right now
+    pub(crate) priority: Cell<Priority>,
+
+    // Scopes that started a transition which is still rendering
+    pub(crate) pending_transitions: RefCell<FxHashSet<ScopeId>>,
+
+    // Panics in components are caught and thrown to the nearest error boundary if this is set
+    pub(crate) catch_panics: Cell<bool>,
+
+    // Every error thrown in a component is forwarded to this reporter
+    pub(crate) error_reporter: RefCell<Option<ErrorReporter>>,
 }
 
 impl Runtime {
@@ -61,6 +78,14 @@ impl Runtime {
             scope_stack: Default::default(),
 
             rendering: Cell::new(true),
+
+            priority: Default::default(),
+
+            pending_transitions: Default::default(),
+
+            catch_panics: Cell::new(true),
+
+            error_reporter: Default::default(),
         })
     }
 
@@ -1,4 +1,4 @@
-use crate::ScopeId;
+use crate::{Priority, RenderReason, ScopeId};
 use slab::Slab;
 
 mod task;
@@ -12,7 +12,7 @@ pub use task::*;
 #[derive(Debug)]
 pub(crate) enum SchedulerMsg {
     /// Immediate updates from Components that mark them as dirty
-    Immediate(ScopeId),
+    Immediate(ScopeId, RenderReason, Priority),
 
     /// A task has woken and needs to be progressed
     TaskNotified(TaskId),
@@ -24,7 +24,7 @@ pub(crate) struct Scheduler {
     pub sender: futures_channel::mpsc::UnboundedSender<SchedulerMsg>,
 
     /// Tasks created with cx.spawn
-    pub tasks: RefCell<Slab<LocalTask>>,
+    pub tasks: RefCell<Slab<Rc<LocalTask>>>,
 }
 
 impl Scheduler {
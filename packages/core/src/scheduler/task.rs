@@ -5,6 +5,7 @@ use crate::ScopeId;
 use std::cell::RefCell;
 use std::future::Future;
 use std::pin::Pin;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::task::Waker;
 
@@ -34,20 +35,27 @@ impl Scheduler {
     /// Spawning a future onto the root scope will cause it to be dropped when the root component is dropped - which
     /// will only occur when the VirtuaalDom itself has been dropped.
     pub fn spawn(&self, scope: ScopeId, task: impl Future<Output = ()> + 'static) -> TaskId {
-        let mut tasks = self.tasks.borrow_mut();
+        let (task_id, task) = {
+            let mut tasks = self.tasks.borrow_mut();
 
-        let entry = tasks.vacant_entry();
-        let task_id = TaskId(entry.key());
+            let entry = tasks.vacant_entry();
+            let task_id = TaskId(entry.key());
 
-        let task = LocalTask {
-            task: RefCell::new(Box::pin(task)),
-            scope,
-            waker: futures_util::task::waker(Arc::new(LocalTaskHandle {
-                id: task_id,
-                tx: self.sender.clone(),
-            })),
+            let task = Rc::new(LocalTask {
+                task: RefCell::new(Box::pin(task)),
+                scope,
+                waker: futures_util::task::waker(Arc::new(LocalTaskHandle {
+                    id: task_id,
+                    tx: self.sender.clone(),
+                })),
+            });
+
+            entry.insert(task.clone());
+
+            (task_id, task)
         };
 
+        // Poll the task without holding a borrow of the tasks, so the task can spawn or remove other tasks
         let mut cx = std::task::Context::from_waker(&task.waker);
 
         if !task.task.borrow_mut().as_mut().poll(&mut cx).is_ready() {
@@ -56,15 +64,13 @@ impl Scheduler {
                 .expect("Scheduler should exist");
         }
 
-        entry.insert(task);
-
         task_id
     }
 
     /// Drop the future with the given TaskId
     ///
     /// This does not abort the task, so you'll want to wrap it in an aborthandle if that's important to you
-    pub fn remove(&self, id: TaskId) -> Option<LocalTask> {
+    pub fn remove(&self, id: TaskId) -> Option<Rc<LocalTask>> {
         self.tasks.borrow_mut().try_remove(id.0)
     }
 }
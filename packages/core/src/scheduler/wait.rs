@@ -1,4 +1,5 @@
 use crate::{runtime::RuntimeGuard, TaskId, VirtualDom};
+use std::rc::Rc;
 use std::task::Context;
 
 impl VirtualDom {
@@ -8,10 +9,9 @@ impl VirtualDom {
     /// queue
     pub(crate) fn handle_task_wakeup(&mut self, id: TaskId) {
         let _runtime = RuntimeGuard::new(self.runtime.clone());
-        let mut tasks = self.runtime.scheduler.tasks.borrow_mut();
-
-        let task = match tasks.get(id.0) {
-            Some(task) => task,
+        // Don't hold a borrow of the tasks while polling, so the task can spawn or remove other tasks
+        let task = match self.runtime.scheduler.tasks.borrow().get(id.0) {
+            Some(task) => task.clone(),
             // The task was removed from the scheduler, so we can just ignore it
             None => return,
         };
@@ -25,11 +25,15 @@ impl VirtualDom {
         // If the task completes...
         if task.task.borrow_mut().as_mut().poll(&mut cx).is_ready() {
             // Remove it from the scope so we dont try to double drop it when the scope dropes
-            let scope = &self.get_scope(task.scope).unwrap();
-            scope.context().spawned_tasks.borrow_mut().remove(&id);
+            if let Some(scope) = self.get_scope(task.scope) {
+                scope.context().spawned_tasks.borrow_mut().remove(&id);
+            }
 
-            // Remove it from the scheduler
-            tasks.try_remove(id.0);
+            // Remove it from the scheduler, unless the task already removed itself and the id was reused
+            let mut tasks = self.runtime.scheduler.tasks.borrow_mut();
+            if matches!(tasks.get(id.0), Some(current) if Rc::ptr_eq(current, &task)) {
+                tasks.remove(id.0);
+            }
         }
 
         // Remove the scope from the stack
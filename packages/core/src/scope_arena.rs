@@ -1,7 +1,7 @@
 use crate::{
     any_props::AnyProps,
     bump_frame::BumpFrame,
-    innerlude::DirtyScope,
+    innerlude::{remove_dirty_scope, RenderReason},
     nodes::RenderReturn,
     scope_context::ScopeContext,
     scopes::{ScopeId, ScopeState},
@@ -18,6 +18,9 @@ impl VirtualDom {
         let height = parent_id
             .and_then(|parent_id| self.get_scope(parent_id).map(|f| f.context().height + 1))
             .unwrap_or(0);
+        let subtree = parent_id
+            .and_then(|parent_id| self.get_scope(parent_id).map(|f| f.subtree))
+            .unwrap_or(0);
         let entry = self.scopes.vacant_entry();
         let id = ScopeId(entry.key());
 
@@ -33,10 +36,13 @@ impl VirtualDom {
             render_cnt: Default::default(),
             hooks: Default::default(),
             hook_idx: Default::default(),
+            hook_types: Default::default(),
 
             borrowed_props: Default::default(),
             attributes_to_drop_before_render: Default::default(),
             element_refs_to_drop: Default::default(),
+
+            subtree,
         }));
 
         let context =
@@ -53,11 +59,19 @@ impl VirtualDom {
         // Remove all the outdated listeners
         self.ensure_drop_safety(scope_id);
 
+        let reason = self
+            .render_reasons
+            .remove(&scope_id)
+            .unwrap_or(RenderReason::Mount);
+        let started = self.profile_start();
+
         let new_nodes = unsafe {
             let scope = &self.scopes[scope_id.0];
             scope.previous_frame().reset();
 
             scope.context().suspended.set(false);
+            // Values shown in devtools are registered again every render
+            scope.context().inspected.borrow_mut().clear();
 
             scope.hook_idx.set(0);
 
@@ -65,10 +79,20 @@ impl VirtualDom {
             let props: &dyn AnyProps = scope.props.as_ref().unwrap().as_ref();
             let props: &dyn AnyProps = std::mem::transmute(props);
 
-            let _span = tracing::trace_span!("render", scope = %scope.context().name);
+            let _span = tracing::trace_span!(
+                "render",
+                scope = %scope.context().name,
+                id = scope_id.0,
+                reason = ?reason
+            )
+            .entered();
             props.render(scope).extend_lifetime()
         };
 
+        if let Some(started) = started {
+            self.profile_render(scope_id, reason, started);
+        }
+
         let scope = &self.scopes[scope_id.0];
 
         // We write on top of the previous frame and then make it the current by pushing the generation forward
@@ -83,10 +107,7 @@ impl VirtualDom {
 
         let context = scope.context();
         // remove this scope from dirty scopes
-        self.dirty_scopes.remove(&DirtyScope {
-            height: context.height,
-            id: context.id,
-        });
+        remove_dirty_scope(&mut self.dirty_scopes, context.id, context.height);
 
         if context.suspended.get() {
             if matches!(allocated, RenderReturn::Aborted(_)) {
@@ -1,5 +1,7 @@
 use crate::{
-    innerlude::{Scheduler, SchedulerMsg},
+    innerlude::{
+        current_priority, InspectedValue, PortalState, RenderReason, Scheduler, SchedulerMsg,
+    },
     runtime::{with_current_scope, with_runtime},
     Element, ScopeId, TaskId,
 };
@@ -23,8 +25,14 @@ pub(crate) struct ScopeContext {
 
     pub(crate) height: u32,
     pub(crate) suspended: Cell<bool>,
+    pub(crate) animate_keyed_lists: Cell<bool>,
+    pub(crate) portal: RefCell<Option<PortalState>>,
 
     pub(crate) shared_contexts: RefCell<Vec<Box<dyn Any>>>,
+    pub(crate) context_types: RefCell<Vec<&'static str>>,
+
+    pub(crate) inspected: RefCell<Vec<InspectedValue>>,
+    pub(crate) inspectors: RefCell<Vec<Box<dyn Fn() -> Option<InspectedValue>>>>,
 
     pub(crate) tasks: Rc<Scheduler>,
     pub(crate) spawned_tasks: RefCell<FxHashSet<TaskId>>,
@@ -44,7 +52,12 @@ impl ScopeContext {
             parent_id,
             height,
             suspended: Cell::new(false),
+            animate_keyed_lists: Cell::new(false),
+            portal: RefCell::new(None),
             shared_contexts: RefCell::new(vec![]),
+            context_types: RefCell::new(vec![]),
+            inspected: RefCell::new(vec![]),
+            inspectors: RefCell::new(vec![]),
             tasks,
             spawned_tasks: RefCell::new(FxHashSet::default()),
         }
@@ -63,7 +76,13 @@ impl ScopeContext {
     /// ## Notice: you should prefer using [`Self::schedule_update_any`] and [`Self::scope_id`]
     pub fn schedule_update(&self) -> Arc<dyn Fn() + Send + Sync + 'static> {
         let (chan, id) = (self.tasks.sender.clone(), self.scope_id());
-        Arc::new(move || drop(chan.unbounded_send(SchedulerMsg::Immediate(id))))
+        Arc::new(move || {
+            drop(chan.unbounded_send(SchedulerMsg::Immediate(
+                id,
+                RenderReason::Update,
+                current_priority(),
+            )))
+        })
     }
 
     /// Schedule an update for any component given its [`ScopeId`].
@@ -72,9 +91,20 @@ impl ScopeContext {
     ///
     /// This method should be used when you want to schedule an update for a component
     pub fn schedule_update_any(&self) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
+        self.schedule_update_any_with_reason(RenderReason::Update)
+    }
+
+    /// Schedule an update for any component given its [`ScopeId`] and tell the profiler why the component was updated.
+    ///
+    /// This is useful for state management libraries that want their updates to show up in profiles.
+    pub fn schedule_update_any_with_reason(
+        &self,
+        reason: RenderReason,
+    ) -> Arc<dyn Fn(ScopeId) + Send + Sync> {
         let chan = self.tasks.sender.clone();
         Arc::new(move |id| {
-            chan.unbounded_send(SchedulerMsg::Immediate(id)).unwrap();
+            chan.unbounded_send(SchedulerMsg::Immediate(id, reason, current_priority()))
+                .unwrap();
         })
     }
 
@@ -89,7 +119,11 @@ impl ScopeContext {
     pub fn needs_update_any(&self, id: ScopeId) {
         self.tasks
             .sender
-            .unbounded_send(SchedulerMsg::Immediate(id))
+            .unbounded_send(SchedulerMsg::Immediate(
+                id,
+                RenderReason::Update,
+                current_priority(),
+            ))
             .expect("Scheduler to exist if scope exists");
     }
 
@@ -191,6 +225,9 @@ impl ScopeContext {
 
         // Else, just push it
         contexts.push(Box::new(value.clone()));
+        self.context_types
+            .borrow_mut()
+            .push(std::any::type_name::<T>());
 
         value
     }
@@ -236,7 +273,14 @@ impl ScopeContext {
     ///
     /// This drops the task immediately.
     pub fn remove_future(&self, id: TaskId) {
-        self.tasks.remove(id);
+        if let Some(task) = self.tasks.remove(id) {
+            // Forget the task in the scope that spawned it so the scope doesn't drop a new task that reuses the id
+            with_runtime(|runtime| {
+                if let Some(scope) = runtime.get_context(task.scope) {
+                    scope.spawned_tasks.borrow_mut().remove(&id);
+                }
+            });
+        }
     }
 
     /// Mark this component as suspended and then return None
@@ -255,6 +299,15 @@ pub fn schedule_update_any() -> Option<Arc<dyn Fn(ScopeId) + Send + Sync>> {
     with_current_scope(|cx| cx.schedule_update_any())
 }
 
+/// Schedule an update for any component given its [`ScopeId`] and tell the profiler why the component was updated.
+///
+/// This is useful for state management libraries that want their updates to show up in profiles.
+pub fn schedule_update_any_with_reason(
+    reason: RenderReason,
+) -> Option<Arc<dyn Fn(ScopeId) + Send + Sync>> {
+    with_current_scope(|cx| cx.schedule_update_any_with_reason(reason))
+}
+
 /// Get the current scope id
 pub fn current_scope_id() -> Option<ScopeId> {
     with_runtime(|rt| rt.current_scope_id()).flatten()
@@ -2,7 +2,7 @@ use crate::{
     any_props::AnyProps,
     any_props::VProps,
     bump_frame::BumpFrame,
-    innerlude::{DynamicNode, ElementRef, EventHandler, VComponent, VNodeId, VText},
+    innerlude::{DynamicNode, ElementRef, EventHandler, HookType, VComponent, VNodeId, VText},
     lazynodes::LazyNodes,
     nodes::{IntoAttributeValue, IntoDynNode, RenderReturn},
     runtime::Runtime,
@@ -92,12 +92,16 @@ pub struct ScopeState {
 
     pub(crate) hooks: RefCell<Vec<Box<UnsafeCell<dyn Any>>>>,
     pub(crate) hook_idx: Cell<usize>,
+    pub(crate) hook_types: RefCell<Vec<HookType>>,
 
     pub(crate) borrowed_props: RefCell<Vec<*const VComponent<'static>>>,
     pub(crate) element_refs_to_drop: RefCell<Vec<VNodeId>>,
     pub(crate) attributes_to_drop_before_render: RefCell<Vec<*const Attribute<'static>>>,
 
     pub(crate) props: Option<Box<dyn AnyProps<'static>>>,
+
+    /// The subtree this scope is rendered into
+    pub(crate) subtree: usize,
 }
 
 impl Drop for ScopeState {
@@ -487,7 +491,7 @@ impl<'src> ScopeState {
         // The current bump allocator frame must outlive the child's borrowed props
         'src: 'child,
     {
-        let vcomp = VProps::new(component, P::memoize, props);
+        let vcomp = VProps::new(component, P::memoize, P::inspect_value, props);
 
         // cast off the lifetime of the render return
         let as_dyn: Box<dyn AnyProps<'child> + '_> = Box::new(vcomp);
@@ -529,6 +533,7 @@ impl<'src> ScopeState {
                 if let Ok(data) = event.data.downcast::<T>() {
                     callback(Event {
                         propagates: event.propagates,
+                        default_prevented: event.default_prevented,
                         data,
                     });
                 }
@@ -556,6 +561,17 @@ impl<'src> ScopeState {
         None
     }
 
+    /// Animate the keyed lists this component renders.
+    ///
+    /// When a keyed list is diffed, the VirtualDom emits [`crate::Mutation::Transition`] mutations for the children
+    /// that enter, move, or leave the list. Keyed lists rendered by child components are not animated unless the
+    /// child components opt in as well.
+    ///
+    /// Prefer the [`crate::AnimatedList`] component if you are animating the children of a component.
+    pub fn animate_keyed_lists(&self) {
+        self.context().animate_keyed_lists.set(true);
+    }
+
     /// Store a value between renders. The foundational hook for all other hooks.
     ///
     /// Accepts an `initializer` closure, which is run on the first use of the hook (typically the initial render). The return value of this closure is stored for the lifetime of the component, and a mutable reference to it is provided on every render as the return value of `use_hook`.
@@ -574,11 +590,48 @@ impl<'src> ScopeState {
     /// ```
     #[allow(clippy::mut_from_ref)]
     pub fn use_hook<State: 'static>(&self, initializer: impl FnOnce() -> State) -> &mut State {
+        self.use_hook_with_format(initializer, None)
+    }
+
+    /// Store a value between renders like [`ScopeState::use_hook`], and show its value in devtools.
+    ///
+    /// Values stored with [`ScopeState::use_hook`] are only shown by their type in devtools because they may not implement [`Debug`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dioxus_core::ScopeState;
+    ///
+    /// pub fn use_counter(cx: &ScopeState) -> &mut i32 {
+    ///     cx.use_inspected_hook(|| 0)
+    /// }
+    /// ```
+    #[allow(clippy::mut_from_ref)]
+    pub fn use_inspected_hook<State: Debug + 'static>(
+        &self,
+        initializer: impl FnOnce() -> State,
+    ) -> &mut State {
+        self.use_hook_with_format(
+            initializer,
+            Some(|value| format!("{:?}", value.downcast_ref::<State>().unwrap())),
+        )
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    fn use_hook_with_format<State: 'static>(
+        &self,
+        initializer: impl FnOnce() -> State,
+        format: Option<fn(&dyn Any) -> String>,
+    ) -> &mut State {
         let cur_hook = self.hook_idx.get();
         let mut hooks = self.hooks.try_borrow_mut().expect("The hook list is already borrowed: This error is likely caused by trying to use a hook inside a hook which violates the rules of hooks.");
 
         if cur_hook >= hooks.len() {
             hooks.push(Box::new(UnsafeCell::new(initializer())));
+            self.hook_types.borrow_mut().push(HookType {
+                name: std::any::type_name::<State>(),
+                format,
+            });
         }
 
         hooks
@@ -0,0 +1,607 @@
+//! Snapshots of the tree mounted by a [`VirtualDom`].
+//!
+//! A snapshot records the templates, dynamic values and [`ElementId`]s of every node a renderer knows about. It can be
+//! used to assert on the structure of the tree in tests, or to pick up a renderer that is still showing an old tree
+//! with a brand new VirtualDom (for example after a liveview server restarts).
+//!
+//! Snapshots don't contain component state. Restoring one runs every component again, and only the [`ElementId`]s of
+//! the nodes the renderer is showing carry over to the new VirtualDom.
+
+use crate::{
+    arena::ElementId, innerlude::BorrowedAttributeValue, nodes::RenderReturn, Attribute,
+    AttributeType, AttributeValue, DynamicNode, MountedAttribute, Mutation, Mutations, ScopeId,
+    TemplateNode, VNode, VirtualDom,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use slab::Slab;
+
+/// A snapshot of the tree mounted by a [`VirtualDom`]
+///
+/// Create one with [`VirtualDom::snapshot`] and restore it with [`VirtualDom::rebuild_from_snapshot`].
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualDomSnapshot {
+    /// The root scope of the VirtualDom
+    pub root: ScopeSnapshot,
+}
+
+/// A snapshot of a mounted scope
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeSnapshot {
+    /// The id of the scope
+    pub id: ScopeId,
+
+    /// The name of the component the scope was created for
+    pub name: String,
+
+    /// The number of scopes above this scope
+    pub height: u32,
+
+    /// The last thing the scope rendered
+    pub node: RenderSnapshot,
+}
+
+/// A snapshot of what a scope rendered
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderSnapshot {
+    /// The scope rendered a template
+    Ready(NodeSnapshot),
+
+    /// The scope aborted rendering and left a placeholder in the dom
+    Aborted {
+        /// The id of the placeholder
+        id: Option<ElementId>,
+    },
+}
+
+/// A snapshot of a mounted [`VNode`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeSnapshot {
+    /// The name of the template this node was created from
+    pub template: String,
+
+    /// The key of the node
+    pub key: Option<String>,
+
+    /// The ids of the roots of the template. Dynamic roots have an id of 0
+    pub root_ids: Vec<ElementId>,
+
+    /// The index of the dynamic node at each root of the template, or `None` if the root is static
+    pub dynamic_roots: Vec<Option<usize>>,
+
+    /// The dynamic nodes of the template
+    pub dynamic_nodes: Vec<DynamicNodeSnapshot>,
+
+    /// The dynamic attributes of the template
+    pub dynamic_attrs: Vec<MountedAttributeSnapshot>,
+}
+
+/// A snapshot of a mounted [`DynamicNode`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynamicNodeSnapshot {
+    /// A component and the scope it is mounted to
+    Component(ScopeSnapshot),
+
+    /// A text node
+    Text {
+        /// The id of the text node
+        id: Option<ElementId>,
+
+        /// The text
+        value: String,
+    },
+
+    /// A placeholder
+    Placeholder {
+        /// The id of the placeholder
+        id: Option<ElementId>,
+    },
+
+    /// A list of nodes
+    Fragment(Vec<NodeSnapshot>),
+}
+
+/// A snapshot of a dynamic attribute and the element it is mounted to
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MountedAttributeSnapshot {
+    /// The element the attributes are set on
+    pub element: ElementId,
+
+    /// The attributes. Spread attributes can contain more than one attribute
+    pub attributes: Vec<AttributeSnapshot>,
+}
+
+/// A snapshot of an [`Attribute`]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeSnapshot {
+    /// The name of the attribute
+    pub name: String,
+
+    /// The namespace of the attribute
+    pub namespace: Option<String>,
+
+    /// The value of the attribute
+    pub value: AttributeValueSnapshot,
+}
+
+/// A snapshot of an [`AttributeValue`]
+///
+/// Listeners and [`AttributeValue::Any`] values can't be serialized, so only their presence is recorded.
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValueSnapshot {
+    /// Text attribute
+    Text(String),
+
+    /// A float
+    Float(f64),
+
+    /// Signed integer
+    Int(i64),
+
+    /// Boolean
+    Bool(bool),
+
+    /// A listener
+    Listener,
+
+    /// An arbitrary value
+    Any,
+
+    /// A "none" value
+    None,
+}
+
+impl From<&AttributeValue<'_>> for AttributeValueSnapshot {
+    fn from(value: &AttributeValue<'_>) -> Self {
+        match value {
+            AttributeValue::Text(value) => Self::Text(value.to_string()),
+            AttributeValue::Float(value) => Self::Float(*value),
+            AttributeValue::Int(value) => Self::Int(*value),
+            AttributeValue::Bool(value) => Self::Bool(*value),
+            AttributeValue::Listener(_) => Self::Listener,
+            AttributeValue::Any(_) => Self::Any,
+            AttributeValue::None => Self::None,
+        }
+    }
+}
+
+impl AttributeValueSnapshot {
+    /// Check if the renderer already shows this value. Arbitrary values can't be compared, so they never match
+    fn matches(&self, value: &AttributeValue<'_>) -> bool {
+        match (self, value) {
+            (Self::Text(l), AttributeValue::Text(r)) => l == r,
+            (Self::Float(l), AttributeValue::Float(r)) => l == r,
+            (Self::Int(l), AttributeValue::Int(r)) => l == r,
+            (Self::Bool(l), AttributeValue::Bool(r)) => l == r,
+            (Self::None, AttributeValue::None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl VirtualDomSnapshot {
+    /// Collect the ids of every node at the top of the tree. Removing them removes the whole tree from the renderer
+    fn top_level_ids(&self, ids: &mut Vec<ElementId>) {
+        self.root.node.top_level_ids(ids)
+    }
+
+    /// The largest id in the snapshot
+    fn max_id(&self) -> usize {
+        self.root.node.max_id()
+    }
+}
+
+impl RenderSnapshot {
+    fn top_level_ids(&self, ids: &mut Vec<ElementId>) {
+        match self {
+            RenderSnapshot::Ready(node) => node.top_level_ids(ids),
+            RenderSnapshot::Aborted { id } => ids.extend(id),
+        }
+    }
+
+    fn max_id(&self) -> usize {
+        match self {
+            RenderSnapshot::Ready(node) => node.max_id(),
+            RenderSnapshot::Aborted { id } => id.map_or(0, |id| id.0),
+        }
+    }
+}
+
+impl NodeSnapshot {
+    fn top_level_ids(&self, ids: &mut Vec<ElementId>) {
+        for (id, dynamic) in self.root_ids.iter().zip(&self.dynamic_roots) {
+            match dynamic.and_then(|idx| self.dynamic_nodes.get(idx)) {
+                Some(DynamicNodeSnapshot::Component(scope)) => scope.node.top_level_ids(ids),
+                Some(
+                    DynamicNodeSnapshot::Text { id, .. } | DynamicNodeSnapshot::Placeholder { id },
+                ) => ids.extend(id),
+                Some(DynamicNodeSnapshot::Fragment(nodes)) => {
+                    nodes.iter().for_each(|node| node.top_level_ids(ids))
+                }
+                None => ids.push(*id),
+            }
+        }
+    }
+
+    fn max_id(&self) -> usize {
+        let roots = self.root_ids.iter().map(|id| id.0);
+        let attrs = self.dynamic_attrs.iter().map(|attr| attr.element.0);
+        let nodes = self.dynamic_nodes.iter().map(|node| match node {
+            DynamicNodeSnapshot::Component(scope) => scope.node.max_id(),
+            DynamicNodeSnapshot::Text { id, .. } | DynamicNodeSnapshot::Placeholder { id } => {
+                id.map_or(0, |id| id.0)
+            }
+            DynamicNodeSnapshot::Fragment(nodes) => nodes
+                .iter()
+                .map(NodeSnapshot::max_id)
+                .max()
+                .unwrap_or_default(),
+        });
+        roots.chain(attrs).chain(nodes).max().unwrap_or_default()
+    }
+}
+
+impl VirtualDom {
+    /// Take a snapshot of the tree that is currently mounted
+    ///
+    /// Panics if the tree has not been built yet.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(app);
+    /// let _ = dom.rebuild();
+    ///
+    /// let snapshot = dom.snapshot();
+    /// assert_eq!(snapshot.root.name, "app");
+    /// ```
+    pub fn snapshot(&self) -> VirtualDomSnapshot {
+        VirtualDomSnapshot {
+            root: self.snapshot_scope(ScopeId::ROOT),
+        }
+    }
+
+    fn snapshot_scope(&self, id: ScopeId) -> ScopeSnapshot {
+        let scope = self.get_scope(id).unwrap();
+        let node = match scope.root_node() {
+            RenderReturn::Ready(node) => RenderSnapshot::Ready(self.snapshot_node(node)),
+            RenderReturn::Aborted(placeholder) => RenderSnapshot::Aborted {
+                id: placeholder.id.get(),
+            },
+        };
+
+        ScopeSnapshot {
+            id,
+            name: scope.name().to_string(),
+            height: scope.height(),
+            node,
+        }
+    }
+
+    fn snapshot_node(&self, node: &VNode) -> NodeSnapshot {
+        NodeSnapshot {
+            template: node.template.get().name.to_string(),
+            key: node.key.map(ToString::to_string),
+            root_ids: node.root_ids.borrow().to_vec(),
+            dynamic_roots: node
+                .template
+                .get()
+                .roots
+                .iter()
+                .map(|root| match root {
+                    TemplateNode::Dynamic { id } | TemplateNode::DynamicText { id } => Some(*id),
+                    _ => None,
+                })
+                .collect(),
+            dynamic_nodes: node
+                .dynamic_nodes
+                .iter()
+                .map(|node| self.snapshot_dynamic_node(node))
+                .collect(),
+            dynamic_attrs: node
+                .dynamic_attrs
+                .iter()
+                .map(|attr| MountedAttributeSnapshot {
+                    element: attr.mounted_element.get(),
+                    attributes: attributes(attr)
+                        .iter()
+                        .map(|attr| AttributeSnapshot {
+                            name: attr.name.to_string(),
+                            namespace: attr.namespace.map(ToString::to_string),
+                            value: (&attr.value).into(),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    fn snapshot_dynamic_node(&self, node: &DynamicNode) -> DynamicNodeSnapshot {
+        match node {
+            DynamicNode::Component(component) => match component.mounted_scope() {
+                Some(scope) => DynamicNodeSnapshot::Component(self.snapshot_scope(scope)),
+                // Components are always mounted once the tree is built
+                None => DynamicNodeSnapshot::Placeholder { id: None },
+            },
+            DynamicNode::Text(text) => DynamicNodeSnapshot::Text {
+                id: text.id.get(),
+                value: text.value.to_string(),
+            },
+            DynamicNode::Placeholder(placeholder) => DynamicNodeSnapshot::Placeholder {
+                id: placeholder.id.get(),
+            },
+            DynamicNode::Fragment(nodes) => DynamicNodeSnapshot::Fragment(
+                nodes.iter().map(|node| self.snapshot_node(node)).collect(),
+            ),
+        }
+    }
+
+    /// Build the VirtualDom for a renderer that is still showing the tree from a snapshot. Call this instead of
+    /// [`VirtualDom::rebuild`].
+    ///
+    /// Component state can't be stored in a snapshot, so every component is run again. If the new tree has the same
+    /// structure as the snapshot, it takes over the [`ElementId`]s of the snapshot and the mutations only update the text
+    /// and attributes that changed. Otherwise, the mutations remove the old tree and create the new one from scratch.
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let snapshot = old_dom.snapshot();
+    ///
+    /// let mut dom = VirtualDom::new(app);
+    /// let edits = dom.rebuild_from_snapshot(&snapshot);
+    ///
+    /// apply_edits(edits);
+    /// ```
+    pub fn rebuild_from_snapshot(&mut self, snapshot: &VirtualDomSnapshot) -> Mutations {
+        // The renderer is still using every id in the snapshot, so the new tree can't be created on top of them
+        let max_id = snapshot.max_id();
+        let mut reserved = Vec::new();
+        while self.elements.vacant_key() <= max_id {
+            reserved.push(self.elements.insert(None));
+        }
+
+        self.create_root();
+
+        let root = unsafe { self.base_scope().root_node().extend_lifetime_ref() };
+        let mut diff = SnapshotDiff::default();
+
+        if diff.render(self, root, &snapshot.root.node) {
+            diff.remap_render(self, root);
+
+            let mut elements = Slab::with_capacity(max_id + 1);
+            for _ in 0..=max_id {
+                elements.insert(None);
+            }
+            let old_elements = std::mem::replace(&mut self.elements, elements);
+            self.elements[0] = old_elements[0];
+            for (&new, &old) in &diff.ids {
+                self.elements[old] = old_elements[new];
+            }
+            let used: FxHashSet<usize> = diff.ids.values().copied().collect();
+            for id in 1..=max_id {
+                if !used.contains(&id) {
+                    self.elements.remove(id);
+                }
+            }
+
+            self.mutations.edits = diff.edits;
+        } else {
+            tracing::debug!(
+                "The rebuilt tree does not match the snapshot. Replacing the whole tree"
+            );
+
+            // Removing the top level nodes of the old tree removes everything below them
+            let mut old_roots = Vec::new();
+            snapshot.top_level_ids(&mut old_roots);
+            let mut removed = FxHashSet::default();
+            old_roots.retain(|id| removed.insert(*id));
+            let removed = old_roots.into_iter().map(|id| Mutation::Remove { id });
+            self.mutations.edits.splice(0..0, removed);
+
+            for id in reserved {
+                self.elements.remove(id);
+            }
+        }
+
+        self.finalize()
+    }
+}
+
+fn attributes<'a, 'b>(attr: &'b MountedAttribute<'a>) -> &'b [Attribute<'a>] {
+    match &attr.ty {
+        AttributeType::Single(attr) => std::slice::from_ref(attr),
+        AttributeType::Many(attrs) => attrs,
+    }
+}
+
+/// Compares a freshly built tree to a snapshot
+#[derive(Default)]
+struct SnapshotDiff {
+    /// Maps the ids of the new tree to the ids in the snapshot
+    ids: FxHashMap<usize, usize>,
+
+    /// The edits that bring the renderer up to date with the new tree
+    edits: Vec<Mutation<'static>>,
+}
+
+impl SnapshotDiff {
+    fn link(&mut self, new: Option<ElementId>, old: Option<ElementId>) -> bool {
+        match (new, old) {
+            (None, None) => true,
+            (Some(new), Some(old)) => *self.ids.entry(new.0).or_insert(old.0) == old.0,
+            _ => false,
+        }
+    }
+
+    fn render<'b>(
+        &mut self,
+        dom: &VirtualDom,
+        new: &'b RenderReturn<'b>,
+        old: &RenderSnapshot,
+    ) -> bool {
+        match (new, old) {
+            (RenderReturn::Ready(new), RenderSnapshot::Ready(old)) => self.node(dom, new, old),
+            (RenderReturn::Aborted(new), RenderSnapshot::Aborted { id }) => {
+                self.link(new.id.get(), *id)
+            }
+            _ => false,
+        }
+    }
+
+    fn node<'b>(&mut self, dom: &VirtualDom, new: &'b VNode<'b>, old: &NodeSnapshot) -> bool {
+        if new.template.get().name != old.template
+            || new.dynamic_nodes.len() != old.dynamic_nodes.len()
+            || new.dynamic_attrs.len() != old.dynamic_attrs.len()
+            || new.root_ids.borrow().len() != old.root_ids.len()
+        {
+            return false;
+        }
+
+        let root_ids = new.root_ids.borrow().clone();
+        root_ids
+            .iter()
+            .zip(&old.root_ids)
+            .all(|(new, old)| self.link(Some(*new), Some(*old)))
+            && new
+                .dynamic_attrs
+                .iter()
+                .zip(&old.dynamic_attrs)
+                .all(|(new, old)| self.mounted_attribute(new, old))
+            && new
+                .dynamic_nodes
+                .iter()
+                .zip(&old.dynamic_nodes)
+                .all(|(new, old)| self.dynamic_node(dom, new, old))
+    }
+
+    fn mounted_attribute<'b>(
+        &mut self,
+        new: &'b MountedAttribute<'b>,
+        old: &MountedAttributeSnapshot,
+    ) -> bool {
+        let new_attributes = attributes(new);
+        self.link(Some(new.mounted_element.get()), Some(old.element))
+            && new_attributes.len() == old.attributes.len()
+            && new_attributes
+                .iter()
+                .zip(&old.attributes)
+                .all(|(new, old_attr)| self.attribute(new, old_attr, old.element))
+    }
+
+    fn attribute<'b>(
+        &mut self,
+        new: &'b Attribute<'b>,
+        old: &AttributeSnapshot,
+        id: ElementId,
+    ) -> bool {
+        if new.name != old.name || new.namespace != old.namespace.as_deref() {
+            return false;
+        }
+
+        match (&new.value, &old.value) {
+            (AttributeValue::Listener(_), AttributeValueSnapshot::Listener) => true,
+            (AttributeValue::Listener(_), _) | (_, AttributeValueSnapshot::Listener) => false,
+            (value, old) => {
+                if !old.matches(value) {
+                    let name = unsafe { std::mem::transmute(new.name) };
+                    let value: BorrowedAttributeValue<'b> = value.into();
+                    let value = unsafe { std::mem::transmute(value) };
+                    self.edits.push(Mutation::SetAttribute {
+                        name,
+                        value,
+                        ns: new.namespace,
+                        id,
+                    });
+                }
+                true
+            }
+        }
+    }
+
+    fn dynamic_node<'b>(
+        &mut self,
+        dom: &VirtualDom,
+        new: &'b DynamicNode<'b>,
+        old: &DynamicNodeSnapshot,
+    ) -> bool {
+        match (new, old) {
+            (DynamicNode::Text(new), DynamicNodeSnapshot::Text { id, value }) => {
+                if !self.link(new.id.get(), *id) {
+                    return false;
+                }
+                if let Some(id) = id {
+                    if new.value != value {
+                        let value = unsafe { std::mem::transmute(new.value) };
+                        self.edits.push(Mutation::SetText { value, id: *id });
+                    }
+                }
+                true
+            }
+            (DynamicNode::Placeholder(new), DynamicNodeSnapshot::Placeholder { id }) => {
+                self.link(new.id.get(), *id)
+            }
+            (DynamicNode::Fragment(new), DynamicNodeSnapshot::Fragment(old)) => {
+                new.len() == old.len()
+                    && new
+                        .iter()
+                        .zip(old)
+                        .all(|(new, old)| self.node(dom, new, old))
+            }
+            (DynamicNode::Component(new), DynamicNodeSnapshot::Component(old)) => {
+                let Some(scope) = new.mounted_scope().and_then(|id| dom.get_scope(id)) else {
+                    return false;
+                };
+                let node = unsafe { scope.root_node().extend_lifetime_ref() };
+                new.name == old.name && self.render(dom, node, &old.node)
+            }
+            _ => false,
+        }
+    }
+
+    fn remap(&self, id: ElementId) -> ElementId {
+        ElementId(self.ids.get(&id.0).copied().unwrap_or(id.0))
+    }
+
+    /// Move the new tree over to the ids of the snapshot
+    fn remap_render(&self, dom: &VirtualDom, node: &RenderReturn) {
+        match node {
+            RenderReturn::Ready(node) => self.remap_node(dom, node),
+            RenderReturn::Aborted(placeholder) => placeholder
+                .id
+                .set(placeholder.id.get().map(|id| self.remap(id))),
+        }
+    }
+
+    fn remap_node(&self, dom: &VirtualDom, node: &VNode) {
+        for id in node.root_ids.borrow_mut().iter_mut() {
+            *id = self.remap(*id);
+        }
+        for attr in node.dynamic_attrs {
+            attr.mounted_element
+                .set(self.remap(attr.mounted_element.get()));
+        }
+        for node in node.dynamic_nodes {
+            match node {
+                DynamicNode::Component(component) => {
+                    if let Some(scope) = component.mounted_scope().and_then(|id| dom.get_scope(id))
+                    {
+                        self.remap_render(dom, scope.root_node());
+                    }
+                }
+                DynamicNode::Text(text) => text.id.set(text.id.get().map(|id| self.remap(id))),
+                DynamicNode::Placeholder(placeholder) => placeholder
+                    .id
+                    .set(placeholder.id.get().map(|id| self.remap(id))),
+                DynamicNode::Fragment(nodes) => {
+                    nodes.iter().for_each(|node| self.remap_node(dom, node))
+                }
+            }
+        }
+    }
+}
@@ -1,30 +1,243 @@
 /*
-This is a WIP module
+Subtrees split up the mutation stream into smaller chunks which can be directed to different parts of the dom.
 
-Subtrees allow the virtualdom to split up the mutation stream into smaller chunks which can be directed to different parts of the dom.
-It's core to implementing multiwindow desktop support, portals, and alternative inline renderers like react-three-fiber.
+Every subtree is rendered by a root component. The main root (subtree 0) is the component the VirtualDom was created
+with, other roots are added with `VirtualDom::add_root`. Additional roots are children of the main root, so they share
+its context and signals, but each of them is mounted into its own container by the renderer.
 
-The primary idea is to give each renderer a linear element tree managed by Dioxus to maximize performance and minimize memory usage.
-This can't be done if two renderers need to share the same native tree.
-With subtrees, we have an entirely different slab of elements
+Elements ids are shared between all subtrees, which means events can be handled without knowing the subtree they come
+from. Each renderer uses ElementId(0) as the container of the subtree it renders.
 
+Edits borrow the arenas of the scopes that produced them, so they are never buffered between calls into the VirtualDom.
+The dirty scopes of an additional root are only rendered when the edits of that root are taken, and the returned edits
+borrow the VirtualDom like the edits of the main root. Only templates, which are 'static, are buffered for subtrees.
+
+None of the first party renderers create additional roots yet. They only apply the edits of the main root.
 */
 
-use std::borrow::Cow;
+use crate::{
+    any_props::VProps,
+    innerlude::{ElementId, Mutation, Mutations, RenderReturn, RuntimeGuard},
+    nodes::Template,
+    scopes::ScopeId,
+    virtual_dom::VirtualDom,
+    Element, Scope,
+};
+
+/// A root component and the templates its renderer hasn't received yet
+pub(crate) struct Subtree {
+    /// The scope of the root component
+    pub(crate) root: ScopeId,
+
+    /// If the root has been rendered
+    pub(crate) mounted: bool,
+
+    /// The templates this subtree hasn't taken yet
+    ///
+    /// The templates of the active subtree are kept in [`VirtualDom::mutations`] instead
+    pub(crate) templates: Vec<Template<'static>>,
+}
+
+impl VirtualDom {
+    /// Add another root component to the VirtualDom and return the id of its subtree.
+    ///
+    /// # Description
+    ///
+    /// The root is rendered into its own mutation stream, so a single VirtualDom can drive multiple mount points like
+    /// multiple windows or multiple widgets on the same page. The root is a child of the main root component, so it can
+    /// consume the context provided there and it shares signals with the rest of the app.
+    ///
+    /// The root is not rendered until [`VirtualDom::rebuild_root`] is called. After that, the dirty scopes of the root are
+    /// rendered when its edits are taken with [`VirtualDom::root_mutations`], so call it for every root after
+    /// [`VirtualDom::wait_for_work`].
+    ///
+    /// # Example
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(App);
+    /// let panel = dom.add_root(Panel, ());
+    ///
+    /// main_window.apply(dom.rebuild());
+    /// panel_window.apply(dom.rebuild_root(panel));
+    ///
+    /// loop {
+    ///     dom.wait_for_work().await;
+    ///     main_window.apply(dom.render_immediate());
+    ///     panel_window.apply(dom.root_mutations(panel));
+    /// }
+    /// ```
+    pub fn add_root<P: 'static>(&mut self, root: fn(Scope<P>) -> Element, root_props: P) -> usize {
+        // Run the new scope as a child of the main root
+        self.runtime.scope_stack.borrow_mut().push(ScopeId::ROOT);
+        let scope = self
+            .new_scope(
+                Box::new(VProps::new(
+                    root,
+                    |_, _| unreachable!(),
+                    |_| None,
+                    root_props,
+                )),
+                "root",
+            )
+            .scope_id();
+        self.runtime.scope_stack.borrow_mut().pop();
+
+        // Templates are shared between all subtrees, so the renderer of the new root needs every template we've seen
+        let templates = self
+            .templates
+            .values()
+            .flat_map(|templates| templates.values())
+            .filter(|template| !template.is_completely_dynamic())
+            .copied()
+            .collect();
+
+        let subtree = self.subtrees.insert(Subtree {
+            root: scope,
+            mounted: false,
+            templates,
+        });
+        self.scopes[scope.0].subtree = subtree;
+        subtree
+    }
+
+    /// Render a root added with [`VirtualDom::add_root`] and return the edits that create it.
+    ///
+    /// The edits append the root to ElementId(0), the container of the subtree in its renderer. If the root was already
+    /// rendered, this renders its dirty scopes like [`VirtualDom::root_mutations`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subtree doesn't exist.
+    pub fn rebuild_root(&mut self, subtree: usize) -> Mutations {
+        let root = self.subtrees[subtree].root;
+        self.enter_subtree(subtree);
+        match std::mem::replace(&mut self.subtrees[subtree].mounted, true) {
+            true => self.render_subtree(subtree),
+            false => self.create_scope_root(root),
+        }
+        self.finalize_subtree(subtree)
+    }
+
+    /// Render the dirty scopes of a root added with [`VirtualDom::add_root`] and return the edits to update it.
+    ///
+    /// [`VirtualDom::render_immediate`] and [`VirtualDom::render_with_deadline`] only render the main root. The dirty
+    /// scopes of other roots stay dirty until the edits of their root are taken with this method. Roots that were not
+    /// rebuilt yet don't render anything.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subtree doesn't exist.
+    pub fn root_mutations(&mut self, subtree: usize) -> Mutations {
+        assert!(
+            self.subtrees.contains(subtree),
+            "subtree {} doesn't exist",
+            subtree
+        );
+        self.process_events();
+        if self.subtrees[subtree].mounted {
+            self.render_subtree(subtree);
+        }
+        self.finalize_subtree(subtree)
+    }
+
+    /// Run and diff the dirty scopes of a subtree
+    fn render_subtree(&mut self, subtree: usize) {
+        self.render_dirty(subtree, || false);
+    }
+
+    /// Remove a root added with [`VirtualDom::add_root`] and return the edits that remove its nodes.
+    ///
+    /// The hooks of the root and all of its children are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subtree doesn't exist or if it is the main root.
+    pub fn remove_root(&mut self, subtree: usize) -> Mutations {
+        assert_ne!(subtree, 0, "the main root can't be removed");
+        let root = self.subtrees[subtree].root;
+
+        self.enter_subtree(subtree);
+        if self.subtrees[subtree].mounted {
+            let _runtime = RuntimeGuard::new(self.runtime.clone());
+            match unsafe {
+                self.get_scope(root)
+                    .unwrap()
+                    .root_node()
+                    .extend_lifetime_ref()
+            } {
+                RenderReturn::Ready(node) => self.remove_node(node, true),
+                RenderReturn::Aborted(placeholder) => self.remove_placeholder(placeholder, true),
+            }
+        }
+        self.drop_scope(root, false);
+
+        // Removing nodes only writes their ids, so the edits don't borrow the arenas of the dropped scopes and can be
+        // returned after the subtree is removed
+        let mut mutations = std::mem::take(&mut self.mutations);
+        mutations.subtree = subtree;
+        self.profile_finalize(&mutations);
+        self.enter_subtree(0);
+        self.subtrees.remove(subtree);
+
+        mutations
+    }
+
+    /// Run a root scope and write the edits to append it to its container into the mutations buffer
+    pub(crate) fn create_scope_root(&mut self, root: ScopeId) {
+        let _runtime = RuntimeGuard::new(self.runtime.clone());
+        match unsafe { self.run_scope(root).extend_lifetime_ref() } {
+            // Rebuilding implies we append the created elements to the root
+            RenderReturn::Ready(node) => {
+                let m = self.create_scope(root, node);
+                self.mutations.edits.push(Mutation::AppendChildren {
+                    id: ElementId(0),
+                    m,
+                });
+            }
+            // If an error occurs, we should try to render the default error component and context where the error occured
+            RenderReturn::Aborted(placeholder) => {
+                tracing::debug!("Ran into suspended or aborted scope during rebuild");
+                let id = self.next_element();
+                placeholder.id.set(Some(id));
+                self.mutations.push(Mutation::CreatePlaceholder { id });
+            }
+        }
+    }
 
-use slab::Slab;
+    /// Write the following edits into the buffer of the given subtree
+    pub(crate) fn enter_subtree(&mut self, subtree: usize) {
+        if self.active_subtree == subtree {
+            return;
+        }
+        // Edits borrow the arenas of the scopes, which are reset when they render again, so they are taken before
+        // another subtree is rendered
+        debug_assert!(
+            self.mutations.edits.is_empty(),
+            "the edits of subtree {} were not taken",
+            self.active_subtree
+        );
+        self.subtrees[self.active_subtree].templates =
+            std::mem::take(&mut self.mutations.templates);
+        self.mutations.templates = std::mem::take(&mut self.subtrees[subtree].templates);
+        self.active_subtree = subtree;
+    }
 
-use crate::{ElementPath, ScopeId};
+    /// Take the edits of the given subtree
+    pub(crate) fn finalize_subtree(&mut self, subtree: usize) -> Mutations {
+        self.enter_subtree(subtree);
+        let mut mutations = std::mem::take(&mut self.mutations);
+        mutations.subtree = subtree;
+        self.profile_finalize(&mutations);
+        mutations
+    }
 
-/// A collection of elements confined to a single scope under a chunk of the tree
-///
-/// All elements in this collection are guaranteed to be in the same scope and share the same numbering
-///
-/// This unit can be multithreaded
-/// Whenever multiple subtrees are present, we can perform **parallel diffing**
-pub struct Subtree {
-    id: usize,
-    namespace: Cow<'static, str>,
-    root: ScopeId,
-    elements: Slab<ElementPath>,
+    /// Register a template with the renderers of every subtree
+    pub(crate) fn push_template(&mut self, template: Template<'static>) {
+        self.mutations.templates.push(template);
+        let active = self.active_subtree;
+        for (id, subtree) in self.subtrees.iter_mut() {
+            if id != active {
+                subtree.templates.push(template);
+            }
+        }
+    }
 }
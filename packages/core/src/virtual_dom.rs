@@ -5,19 +5,29 @@
 use crate::{
     any_props::VProps,
     arena::{ElementId, ElementRef},
-    innerlude::{DirtyScope, ErrorBoundary, Mutations, Scheduler, SchedulerMsg},
-    mutations::Mutation,
+    innerlude::{
+        remove_dirty_scope, DirtyScope, ErrorBoundary, Mutations, Priority, Profiler, RenderReason,
+        Scheduler, SchedulerMsg,
+    },
+    nodes::ListenerCb,
     nodes::RenderReturn,
     nodes::{Template, TemplateId},
     runtime::{Runtime, RuntimeGuard},
     scopes::{ScopeId, ScopeState},
+    subtree::Subtree,
     AttributeValue, Element, Event, Scope, VNode,
 };
 use futures_util::{pin_mut, StreamExt};
 use rustc_hash::{FxHashMap, FxHashSet};
 use slab::Slab;
 use std::{
-    any::Any, cell::Cell, collections::BTreeSet, future::Future, ptr::NonNull, rc::Rc, sync::Arc,
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::BTreeSet,
+    future::Future,
+    ptr::NonNull,
+    rc::Rc,
+    sync::Arc,
 };
 
 /// A virtual node system that progresses user events and diffs UI trees.
@@ -184,6 +194,9 @@ pub struct VirtualDom {
 
     pub(crate) dirty_scopes: BTreeSet<DirtyScope>,
 
+    // If the dirty scopes were left by a render that yielded, instead of being marked dirty since the last render
+    pub(crate) deferred_work: bool,
+
     // Maps a template path to a map of byteindexes to templates
     pub(crate) templates: FxHashMap<TemplateId, FxHashMap<usize, Template<'static>>>,
 
@@ -195,6 +208,18 @@ pub struct VirtualDom {
 
     pub(crate) mutations: Mutations<'static>,
 
+    // The roots of the VirtualDom. The main root is always subtree 0
+    pub(crate) subtrees: Slab<Subtree>,
+
+    // The subtree the edits in `mutations` belong to
+    pub(crate) active_subtree: usize,
+
+    // Why the dirty scopes need to be rendered
+    pub(crate) render_reasons: FxHashMap<ScopeId, RenderReason>,
+
+    // Records renders while profiling is enabled
+    pub(crate) profiler: Option<Profiler>,
+
     pub(crate) runtime: Rc<Runtime>,
 
     // Currently suspended scopes
@@ -266,15 +291,31 @@ impl VirtualDom {
             runtime: Runtime::new(scheduler),
             scopes: Default::default(),
             dirty_scopes: Default::default(),
+            deferred_work: false,
             templates: Default::default(),
             elements: Default::default(),
             element_refs: Default::default(),
             mutations: Mutations::default(),
+            subtrees: Default::default(),
+            active_subtree: 0,
+            render_reasons: Default::default(),
+            profiler: None,
             suspended_scopes: Default::default(),
         };
 
+        dom.subtrees.insert(Subtree {
+            root: ScopeId::ROOT,
+            mounted: true,
+            templates: Vec::new(),
+        });
+
         let root = dom.new_scope(
-            Box::new(VProps::new(root, |_, _| unreachable!(), root_props)),
+            Box::new(VProps::new(
+                root,
+                |_, _| unreachable!(),
+                |_| None,
+                root_props,
+            )),
             "app",
         );
 
@@ -304,6 +345,18 @@ impl VirtualDom {
         self.get_scope(ScopeId::ROOT).unwrap()
     }
 
+    /// Check if a scope is currently suspended
+    ///
+    /// A suspended scope has rendered a placeholder and will be re-rendered once the work it is waiting on resolves.
+    pub fn is_suspended(&self, id: ScopeId) -> bool {
+        self.suspended_scopes.contains(&id)
+    }
+
+    /// Check if any scope in the VirtualDom is currently suspended
+    pub fn has_suspended_work(&self) -> bool {
+        !self.suspended_scopes.is_empty()
+    }
+
     /// Build the virtualdom with a global context inserted into the base scope
     ///
     /// This is useful for what is essentially dependency injection when building the app
@@ -316,10 +369,40 @@ impl VirtualDom {
     ///
     /// Whenever the Runtime "works", it will re-render this scope
     pub fn mark_dirty(&mut self, id: ScopeId) {
+        self.mark_dirty_with_reason(id, RenderReason::MarkDirty, Priority::Background)
+    }
+
+    /// Mark a scope as requiring a re-render and remember why
+    ///
+    /// A scope that is already dirty is rendered once, with the most urgent priority it was marked with.
+    pub(crate) fn mark_dirty_with_reason(
+        &mut self,
+        id: ScopeId,
+        reason: RenderReason,
+        priority: Priority,
+    ) {
         if let Some(scope) = self.get_scope(id) {
             let height = scope.height();
-            tracing::trace!("Marking scope {:?} ({}) as dirty", id, scope.context().name);
-            self.dirty_scopes.insert(DirtyScope { height, id });
+            tracing::trace!(
+                "Marking scope {:?} ({}) as dirty because of {:?} with {:?} priority",
+                id,
+                scope.context().name,
+                reason,
+                priority
+            );
+            // A new update means the dirty scopes are no longer just left over work
+            self.deferred_work = false;
+            let priority = match remove_dirty_scope(&mut self.dirty_scopes, id, height) {
+                Some(dirty) => dirty.min(priority),
+                None => priority,
+            };
+            self.dirty_scopes.insert(DirtyScope {
+                priority,
+                height,
+                id,
+            });
+            self.render_reasons.entry(id).or_insert(reason);
+            self.profile_dirty(id, reason);
         }
     }
 
@@ -329,16 +412,23 @@ impl VirtualDom {
     /// this method does not give any indication as to the success of the listener call. If the listener is not found,
     /// nothing will happen.
     ///
+    /// Listeners declared with the `oncapture` prefix (like `oncaptureclick`) are called first, from the root of the
+    /// tree down to the target element. Then the listeners of the target element are called, followed by the listeners
+    /// of its parents if the event bubbles.
+    ///
     /// It is up to the listeners themselves to mark nodes as dirty.
     ///
     /// If you have multiple events, you can call this method multiple times before calling "render_with_deadline"
+    ///
+    /// Returns `false` if any listener called [`Event::prevent_default`]. Renderers should prevent the default action
+    /// of the platform event in that case.
     pub fn handle_event(
         &mut self,
         name: &str,
         data: Rc<dyn Any>,
         element: ElementId,
         bubbles: bool,
-    ) {
+    ) -> bool {
         let _runtime = RuntimeGuard::new(self.runtime.clone());
 
         /*
@@ -349,7 +439,7 @@ impl VirtualDom {
         With the target path, we try and move up to the parent until there is no parent.
         Due to how bubbling works, we call the listeners before walking to the parent.
 
-        If we wanted to do capturing, then we would accumulate all the listeners and call them in reverse order.
+        For capturing, we accumulate all the capture listeners first and call them in reverse order.
         ----------------------
 
         For a visual demonstration, here we present a tree on the left and whether or not a listener is collected on the
@@ -364,9 +454,9 @@ impl VirtualDom {
         */
         let parent_path = match self.elements.get(element.0) {
             Some(Some(el)) => el,
-            _ => return,
+            _ => return true,
         };
-        let mut parent_node = self
+        let target_node = self
             .element_refs
             .get(parent_path.template.0)
             .cloned()
@@ -374,11 +464,61 @@ impl VirtualDom {
 
         // We will clone this later. The data itself is wrapped in RC to be used in callbacks if required
         let uievent = Event {
-            propagates: Rc::new(Cell::new(bubbles)),
+            propagates: Rc::new(Cell::new(true)),
+            default_prevented: Rc::new(Cell::new(false)),
             data,
         };
 
+        // First call the capture listeners of the target element and all of its parents, starting at the root
+        let mut capture_listeners = vec![];
+        let mut parent_node = target_node;
+        while let Some((path, el_ref)) = parent_node {
+            let mut listeners = vec![];
+
+            // safety: we maintain references of all vnodes in the element slab
+            let template = unsafe { el_ref.unwrap().as_ref() };
+            let node_template = template.template.get();
+            let target_path = path.path;
+
+            for (idx, attr) in template.dynamic_attrs.iter().enumerate() {
+                let this_path = node_template.attr_paths[idx];
+
+                if target_path.is_decendant(&this_path) {
+                    attr.ty.for_each(|attribute| {
+                        let capture_name = attribute
+                            .name
+                            .trim_start_matches("on")
+                            .strip_prefix("capture");
+                        if capture_name == Some(name) {
+                            if let AttributeValue::Listener(listener) = &attribute.value {
+                                listeners.push((path.scope, listener));
+                            }
+                        }
+                    });
+                }
+            }
+
+            // The listeners of this template are in depth first order, so the listeners of the parents come first
+            capture_listeners.extend(listeners.into_iter().rev());
+
+            parent_node = template.parent.get().and_then(|element_ref| {
+                self.element_refs
+                    .get(element_ref.template.0)
+                    .cloned()
+                    .map(|el| (element_ref, el))
+            });
+        }
+
+        for (origin, listener) in capture_listeners.into_iter().rev() {
+            self.call_listener(origin, listener, &uievent);
+
+            if !uievent.propagates.get() {
+                return !uievent.default_prevented.get();
+            }
+        }
+
         // If the event bubbles, we traverse through the tree until we find the target element.
+        let mut parent_node = target_node;
         if bubbles {
             // Loop through each dynamic attribute (in a depth first order) in this template before moving up to the template's parent.
             while let Some((path, el_ref)) = parent_node {
@@ -407,17 +547,10 @@ impl VirtualDom {
                 // Now that we've accumulated all the parent attributes for the target element, call them in reverse order
                 // We check the bubble state between each call to see if the event has been stopped from bubbling
                 for listener in listeners.into_iter().rev() {
-                    let origin = path.scope;
-                    self.runtime.scope_stack.borrow_mut().push(origin);
-                    self.runtime.rendering.set(false);
-                    if let Some(cb) = listener.borrow_mut().as_deref_mut() {
-                        cb(uievent.clone());
-                    }
-                    self.runtime.scope_stack.borrow_mut().pop();
-                    self.runtime.rendering.set(true);
+                    self.call_listener(path.scope, listener, &uievent);
 
                     if !uievent.propagates.get() {
-                        return;
+                        return !uievent.default_prevented.get();
                     }
                 }
 
@@ -430,7 +563,7 @@ impl VirtualDom {
             }
         } else {
             // Otherwise, we just call the listener on the target element
-            if let Some((path, el_ref)) = parent_node {
+            if let Some((path, el_ref)) = parent_node.take() {
                 // safety: we maintain references of all vnodes in the element slab
                 let template = unsafe { el_ref.unwrap().as_ref() };
                 let node_template = template.template.get();
@@ -446,26 +579,39 @@ impl VirtualDom {
                         attr.ty.for_each(|attribute| {
                             if attribute.name.trim_start_matches("on") == name {
                                 if let AttributeValue::Listener(listener) = &attribute.value {
-                                    let origin = path.scope;
-                                    self.runtime.scope_stack.borrow_mut().push(origin);
-                                    self.runtime.rendering.set(false);
-                                    if let Some(cb) = listener.borrow_mut().as_deref_mut() {
-                                        cb(uievent.clone());
-                                    }
-                                    self.runtime.scope_stack.borrow_mut().pop();
-                                    self.runtime.rendering.set(true);
+                                    self.call_listener(path.scope, listener, &uievent);
 
                                     should_stop = true;
                                 }
                             }
                         });
                         if should_stop {
-                            return;
+                            break;
                         }
                     }
                 }
             }
         }
+
+        !uievent.default_prevented.get()
+    }
+
+    /// Call a listener inside of the scope that declared it
+    fn call_listener(
+        &self,
+        origin: ScopeId,
+        listener: &RefCell<Option<ListenerCb>>,
+        event: &Event<dyn Any>,
+    ) {
+        self.runtime.scope_stack.borrow_mut().push(origin);
+        self.runtime.rendering.set(false);
+        if let Some(cb) = listener.borrow_mut().as_deref_mut() {
+            // Updates from event handlers are rendered before anything else
+            self.runtime
+                .with_priority(Priority::Input, || cb(event.clone()));
+        }
+        self.runtime.scope_stack.borrow_mut().pop();
+        self.runtime.rendering.set(true);
     }
 
     /// Wait for the scheduler to have any work.
@@ -478,6 +624,13 @@ impl VirtualDom {
     ///
     /// This lets us poll async tasks and suspended trees during idle periods without blocking the main thread.
     ///
+    /// Scopes that were left dirty by a render that yielded are not new work. If they are the only work, this method
+    /// lets the executor run other tasks once before it completes, so input is handled before they are rendered.
+    ///
+    /// The dirty scopes of roots added with [`VirtualDom::add_root`] are only new work when they are marked dirty. Once
+    /// this method returned for them, it waits for the next update until their edits are taken with
+    /// [`VirtualDom::root_mutations`].
+    ///
     /// # Example
     ///
     /// ```rust, ignore
@@ -486,12 +639,16 @@ impl VirtualDom {
     /// ```
     pub async fn wait_for_work(&mut self) {
         let mut some_msg = None;
+        let mut marked_dirty = false;
 
         loop {
             match some_msg.take() {
                 // If a bunch of messages are ready in a sequence, try to pop them off synchronously
                 Some(msg) => match msg {
-                    SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                    SchedulerMsg::Immediate(id, reason, priority) => {
+                        marked_dirty = true;
+                        self.mark_dirty_with_reason(id, reason, priority)
+                    }
                     SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
                 },
 
@@ -502,10 +659,20 @@ impl VirtualDom {
                         Ok(None) => return,
                         Err(_) => {
                             // If we have any dirty scopes, or finished fiber trees then we should exit
-                            if !self.dirty_scopes.is_empty() || !self.suspended_scopes.is_empty() {
+                            if marked_dirty
+                                || (!self.deferred_work && self.has_dirty_scopes())
+                                || !self.suspended_scopes.is_empty()
+                            {
                                 return;
                             }
 
+                            if self.deferred_work && self.has_dirty_scopes() {
+                                // Let the executor handle input before the deferred scopes are rendered
+                                self.deferred_work = false;
+                                yield_now().await;
+                                continue;
+                            }
+
                             some_msg = self.rx.next().await
                         }
                     }
@@ -518,7 +685,9 @@ impl VirtualDom {
     pub fn process_events(&mut self) {
         while let Ok(Some(msg)) = self.rx.try_next() {
             match msg {
-                SchedulerMsg::Immediate(id) => self.mark_dirty(id),
+                SchedulerMsg::Immediate(id, reason, priority) => {
+                    self.mark_dirty_with_reason(id, reason, priority)
+                }
                 SchedulerMsg::TaskNotified(task) => self.handle_task_wakeup(task),
             }
         }
@@ -533,20 +702,19 @@ impl VirtualDom {
     pub fn replace_template(&mut self, template: Template<'static>) {
         self.register_template_first_byte_index(template);
         // iterating a slab is very inefficient, but this is a rare operation that will only happen during development so it's fine
+        let mut dirty = Vec::new();
         for (_, scope) in self.scopes.iter() {
             if let Some(RenderReturn::Ready(sync)) = scope.try_root_node() {
                 if sync.template.get().name.rsplit_once(':').unwrap().0
                     == template.name.rsplit_once(':').unwrap().0
                 {
-                    let context = scope.context();
-                    let height = context.height;
-                    self.dirty_scopes.insert(DirtyScope {
-                        height,
-                        id: context.id,
-                    });
+                    dirty.push(scope.scope_id());
                 }
             }
         }
+        for id in dirty {
+            self.mark_dirty_with_reason(id, RenderReason::HotReload, Priority::Input);
+        }
     }
 
     /// Performs a *full* rebuild of the virtual dom, returning every edit required to generate the actual dom from scratch.
@@ -570,46 +738,108 @@ impl VirtualDom {
     /// apply_edits(edits);
     /// ```
     pub fn rebuild(&mut self) -> Mutations {
-        let _runtime = RuntimeGuard::new(self.runtime.clone());
-        match unsafe { self.run_scope(ScopeId::ROOT).extend_lifetime_ref() } {
-            // Rebuilding implies we append the created elements to the root
-            RenderReturn::Ready(node) => {
-                let m = self.create_scope(ScopeId::ROOT, node);
-                self.mutations.edits.push(Mutation::AppendChildren {
-                    id: ElementId(0),
-                    m,
-                });
-            }
-            // If an error occurs, we should try to render the default error component and context where the error occured
-            RenderReturn::Aborted(placeholder) => {
-                tracing::debug!("Ran into suspended or aborted scope during rebuild");
-                let id = self.next_element();
-                placeholder.id.set(Some(id));
-                self.mutations.push(Mutation::CreatePlaceholder { id });
-            }
-        }
-
+        self.create_root();
         self.finalize()
     }
 
+    /// Run the root scope and write the edits to create it into the mutations buffer
+    pub(crate) fn create_root(&mut self) {
+        self.enter_subtree(0);
+        self.create_scope_root(ScopeId::ROOT);
+    }
+
     /// Render whatever the VirtualDom has ready as fast as possible without requiring an executor to progress
     /// suspended subtrees.
     pub fn render_immediate(&mut self) -> Mutations {
-        // Build a waker that won't wake up since our deadline is already expired when it's polled
-        let waker = futures_util::task::noop_waker();
-        let mut cx = std::task::Context::from_waker(&waker);
-
-        // Now run render with deadline but dont even try to poll any async tasks
-        let fut = self.render_with_deadline(std::future::ready(()));
-        pin_mut!(fut);
-
-        // The root component is not allowed to be async
-        match fut.poll(&mut cx) {
-            std::task::Poll::Ready(mutations) => mutations,
-            std::task::Poll::Pending => panic!("render_immediate should never return pending"),
+        self.render_until(|| false)
+    }
+
+    /// Render dirty scopes until `should_yield` returns `true`, without waiting for any async work.
+    ///
+    /// `should_yield` is checked before every scope that was not updated by user input, so updates with
+    /// [`Priority::Input`] are always rendered while transitions and background updates can be split over multiple
+    /// calls. At least one scope is rendered in every call. Use [`VirtualDom::has_dirty_scopes`] to check if there is
+    /// work left.
+    ///
+    /// # Example
+    ///
+    /// ```rust, ignore
+    /// let start = Instant::now();
+    /// let edits = dom.render_until(|| start.elapsed() > Duration::from_millis(16));
+    /// real_dom.apply(edits);
+    ///
+    /// if dom.has_dirty_scopes() {
+    ///     // Let the renderer handle input before rendering the rest
+    /// }
+    /// ```
+    pub fn render_until(&mut self, should_yield: impl FnMut() -> bool) -> Mutations {
+        self.process_events();
+        self.render_dirty(0, should_yield);
+        self.finalize()
+    }
+
+    /// Run and diff the dirty scopes of a subtree until there are none left or `should_yield` returns `true`. Returns
+    /// `true` if the VirtualDom yielded with dirty scopes left.
+    ///
+    /// At least one scope is rendered before the VirtualDom yields, so every call makes progress.
+    pub(crate) fn render_dirty(
+        &mut self,
+        subtree: usize,
+        mut should_yield: impl FnMut() -> bool,
+    ) -> bool {
+        let mut rendered = false;
+        loop {
+            self.finish_transitions();
+
+            let dirty = match self.next_dirty_scope(subtree) {
+                Some(dirty) => dirty,
+                None => {
+                    self.deferred_work = false;
+                    return false;
+                }
+            };
+
+            if dirty.priority != Priority::Input && rendered && should_yield() {
+                self.deferred_work = true;
+                return true;
+            }
+
+            self.dirty_scopes.remove(&dirty);
+
+            // If the scope doesn't exist for whatever reason, then we should skip it
+            if !self.scopes.contains(dirty.id.0) {
+                continue;
+            }
+
+            // Roots that were not rebuilt yet are created from scratch when they are rebuilt
+            if !self.subtrees[self.scopes[dirty.id.0].subtree].mounted {
+                continue;
+            }
+            self.enter_subtree(subtree);
+
+            {
+                let _runtime = RuntimeGuard::new(self.runtime.clone());
+                // Run the scope and get the mutations
+                self.run_scope(dirty.id);
+                self.diff_scope(dirty.id);
+            }
+            rendered = true;
         }
     }
 
+    /// Find the most urgent dirty scope that should be rendered with the given subtree
+    pub(crate) fn next_dirty_scope(&self, subtree: usize) -> Option<DirtyScope> {
+        // The scopes of other subtrees are rendered when the edits of their subtree are taken. Scopes that were removed
+        // or belong to a root that was not rebuilt yet are only cleaned up
+        self.dirty_scopes
+            .iter()
+            .find(|dirty| match self.scopes.get(dirty.id.0) {
+                Some(scope) => scope.subtree == subtree || !self.subtrees[scope.subtree].mounted,
+                None => true,
+            })
+            .cloned()
+    }
+
     /// Render the virtual dom, waiting for all suspense to be finished
     ///
     /// The mutations will be thrown out, so it's best to use this method for things like SSR that have async content
@@ -629,34 +859,21 @@ impl VirtualDom {
     ///
     /// It's generally a good idea to put some sort of limit on the suspense process in case a future is having issues.
     ///
-    /// If no suspense trees are present
+    /// The deadline is checked between scopes, so large updates are split over multiple calls. Updates with
+    /// [`Priority::Input`] are always rendered before the VirtualDom yields, while transitions and background updates are
+    /// left for the next call. At least one scope is rendered in every call, even if the deadline already passed.
     pub async fn render_with_deadline(&mut self, deadline: impl Future<Output = ()>) -> Mutations {
         pin_mut!(deadline);
 
         self.process_events();
 
         loop {
-            // Next, diff any dirty scopes
-            // We choose not to poll the deadline since we complete pretty quickly anyways
-            if let Some(dirty) = self.dirty_scopes.iter().next().cloned() {
-                self.dirty_scopes.remove(&dirty);
-
-                // If the scope doesn't exist for whatever reason, then we should skip it
-                if !self.scopes.contains(dirty.id.0) {
-                    continue;
-                }
-
-                {
-                    let _runtime = RuntimeGuard::new(self.runtime.clone());
-                    // Run the scope and get the mutations
-                    self.run_scope(dirty.id);
-                    self.diff_scope(dirty.id);
-                }
-            }
-
-            // If there's more work, then just continue, plenty of work to do
-            if !self.dirty_scopes.is_empty() {
-                continue;
+            // Diff dirty scopes until the deadline passes
+            let waker = futures_util::task::noop_waker();
+            let mut cx = std::task::Context::from_waker(&waker);
+            let yielded = self.render_dirty(0, || deadline.as_mut().poll(&mut cx).is_ready());
+            if yielded {
+                return self.finalize();
             }
 
             // Poll the suspense leaves in the meantime
@@ -675,9 +892,9 @@ impl VirtualDom {
         }
     }
 
-    /// Swap the current mutations with a new
-    fn finalize(&mut self) -> Mutations {
-        std::mem::take(&mut self.mutations)
+    /// Swap the current mutations of the main root with a new
+    pub(crate) fn finalize(&mut self) -> Mutations {
+        self.finalize_subtree(0)
     }
 
     /// Get the current runtime
@@ -688,7 +905,32 @@ impl VirtualDom {
 
 impl Drop for VirtualDom {
     fn drop(&mut self) {
+        // The other roots are children of the main root, so they are dropped first
+        let roots: Vec<_> = self
+            .subtrees
+            .iter()
+            .filter(|(id, _)| *id != 0)
+            .map(|(_, subtree)| subtree.root)
+            .collect();
+        for root in roots {
+            self.drop_scope(root, true);
+        }
+
         // Simply drop this scope which drops all of its children
         self.drop_scope(ScopeId::ROOT, true);
     }
 }
+
+/// Let the executor run other tasks once
+async fn yield_now() {
+    let mut yielded = false;
+    futures_util::future::poll_fn(|cx| {
+        if yielded {
+            return std::task::Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        std::task::Poll::Pending
+    })
+    .await
+}
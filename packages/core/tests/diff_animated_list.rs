@@ -0,0 +1,148 @@
+//! Animated List Tests
+//!
+//! These tests verify that keyed lists in an AnimatedList describe how their children changed.
+
+use dioxus::core::{ElementId, Mutation::*, TransitionKind};
+use dioxus::prelude::*;
+
+#[test]
+fn moved_children_are_measured_before_they_move() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order = match cx.generation() % 2 {
+            0 => &[0, 1, 2],
+            1 => &[2, 0, 1],
+            _ => unreachable!(),
+        };
+
+        render! {
+            AnimatedList {
+                { order.iter().map(|i| rsx!(div { key: "{i}" })) }
+            }
+        }
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().edits,
+        [
+            Transition { id: ElementId(1), kind: TransitionKind::Move },
+            Transition { id: ElementId(2), kind: TransitionKind::Move },
+            Transition { id: ElementId(3), kind: TransitionKind::Move },
+            PushRoot { id: ElementId(3) },
+            InsertBefore { id: ElementId(1), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn entering_and_leaving_children() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[0, 1, 2],
+            1 => &[1, 2, 3],
+            _ => unreachable!(),
+        };
+
+        render! {
+            AnimatedList {
+                { order.iter().map(|i| rsx!(div { key: "{i}" })) }
+            }
+        }
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            Transition { id: ElementId(1), kind: TransitionKind::Leave },
+            Transition { id: ElementId(2), kind: TransitionKind::Move },
+            Transition { id: ElementId(3), kind: TransitionKind::Move },
+            Remove { id: ElementId(1) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            InsertAfter { id: ElementId(3), m: 1 },
+            Transition { id: ElementId(1), kind: TransitionKind::Enter },
+        ]
+    );
+}
+
+#[test]
+fn emptying_and_filling_the_list() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order: &[_] = match cx.generation() % 2 {
+            0 => &[0, 1],
+            1 => &[],
+            _ => unreachable!(),
+        };
+
+        render! {
+            AnimatedList {
+                { order.iter().map(|i| rsx!(div { key: "{i}" })) }
+            }
+        }
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().edits,
+        [
+            Transition { id: ElementId(1), kind: TransitionKind::Leave },
+            Transition { id: ElementId(2), kind: TransitionKind::Leave },
+            CreatePlaceholder { id: ElementId(3) },
+            Remove { id: ElementId(2) },
+            ReplaceWith { id: ElementId(1), m: 1 },
+        ]
+    );
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            ReplaceWith { id: ElementId(3), m: 2 },
+            Transition { id: ElementId(1), kind: TransitionKind::Enter },
+            Transition { id: ElementId(2), kind: TransitionKind::Enter },
+        ]
+    );
+}
+
+#[test]
+fn lists_outside_of_animated_lists_are_not_animated() {
+    let mut dom = VirtualDom::new(|cx| {
+        let order = match cx.generation() % 2 {
+            0 => &[0, 1, 2],
+            1 => &[2, 0, 1],
+            _ => unreachable!(),
+        };
+
+        render! {
+            AnimatedList {
+                Item { order: order }
+            }
+        }
+    });
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().edits,
+        [
+            PushRoot { id: ElementId(3) },
+            InsertBefore { id: ElementId(1), m: 1 },
+        ]
+    );
+}
+
+#[component]
+fn Item(cx: Scope, order: &'static [usize; 3]) -> Element {
+    render! {
+        { order.iter().map(|i| rsx!(div { key: "{i}" })) }
+    }
+}
@@ -1,6 +1,11 @@
 #![allow(non_snake_case)]
 
 use dioxus::prelude::*;
+use dioxus_core::CapturedError;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 #[test]
 fn catches_panic() {
@@ -32,3 +37,61 @@ fn ThrowChild(cx: Scope) -> Element {
         div {}
     })
 }
+
+thread_local! {
+    static FAIL: Cell<bool> = Cell::new(true);
+    static CAUGHT: RefCell<Option<CapturedError>> = RefCell::new(None);
+}
+
+fn boundary_app(cx: Scope) -> Element {
+    render! {
+        ErrorBoundary {
+            handle_error: |error| {
+                CAUGHT.with(|caught| *caught.borrow_mut() = Some(error));
+                rsx! { "fallback" }
+            },
+            Flaky {}
+        }
+    }
+}
+
+fn Flaky(cx: Scope) -> Element {
+    if FAIL.with(Cell::get) {
+        panic!("flaky");
+    }
+    render! { "recovered" }
+}
+
+#[test]
+fn panics_are_caught_and_reset() {
+    let reported = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new(boundary_app).with_error_reporter({
+        let reported = reported.clone();
+        move |error| reported.borrow_mut().push(error.scope_path.clone())
+    });
+    _ = dom.rebuild();
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "fallback");
+
+    // The reporter sees where the error was thrown
+    assert_eq!(*reported.borrow(), [vec!["app", "ErrorBoundary", "Flaky"]]);
+
+    let error = CAUGHT.with(|caught| caught.borrow_mut().take()).unwrap();
+    assert_eq!(
+        format!("{:?}", error.error),
+        "component `Flaky` panicked: flaky"
+    );
+
+    // Resetting the error renders the children again
+    FAIL.with(|fail| fail.set(false));
+    error.reset();
+    _ = dom.render_immediate();
+    assert_eq!(dioxus_ssr::render(&dom), "recovered");
+}
+
+#[test]
+#[should_panic(expected = "uncaught")]
+fn panics_can_propagate() {
+    let mut dom = VirtualDom::new(|_| panic!("uncaught")).with_catch_panics(false);
+    _ = dom.rebuild();
+}
@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use dioxus_core::ElementId;
+use dioxus_core::{ElementId, Mutation};
 use std::{rc::Rc, sync::Mutex};
 
 static CLICKS: Mutex<usize> = Mutex::new(0);
@@ -80,3 +80,116 @@ fn problematic_child(cx: Scope) -> Element {
             } }
     }
 }
+
+thread_local! {
+    static CALLS: std::cell::RefCell<Vec<&'static str>> = Default::default();
+}
+
+fn record(call: &'static str) {
+    CALLS.with(|calls| calls.borrow_mut().push(call));
+}
+
+fn take_calls() -> Vec<&'static str> {
+    CALLS.with(|calls| std::mem::take(&mut *calls.borrow_mut()))
+}
+
+#[test]
+fn capture_listeners_run_before_bubbling() {
+    set_event_converter(Box::new(dioxus_html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(|cx| {
+        render! {
+            div {
+                oncaptureclick: move |_| record("outer capture"),
+                onclick: move |_| record("outer bubble"),
+                div {
+                    oncaptureclick: move |_| record("inner capture"),
+                    onclick: move |_| record("inner bubble"),
+                    button {
+                        oncaptureclick: move |_| record("target capture"),
+                        onclick: move |_| record("target bubble"),
+                    }
+                }
+            }
+        }
+    });
+    // Capture listeners listen to the same platform event as bubbling listeners
+    assert!(dom.rebuild().santize().edits.iter().all(|edit| match edit {
+        Mutation::NewEventListener { name, .. } => *name == "click",
+        _ => true,
+    }));
+
+    let default_action_enabled = dom.handle_event(
+        "click",
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())),
+        ElementId(3),
+        true,
+    );
+    assert!(default_action_enabled);
+    assert_eq!(
+        take_calls(),
+        [
+            "outer capture",
+            "inner capture",
+            "target capture",
+            "target bubble",
+            "inner bubble",
+            "outer bubble"
+        ]
+    );
+}
+
+#[test]
+fn stopping_propagation_during_capture_skips_the_target() {
+    set_event_converter(Box::new(dioxus_html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(|cx| {
+        render! {
+            div {
+                oncaptureclick: move |evt| {
+                    record("outer capture");
+                    evt.stop_propagation();
+                },
+                button {
+                    onclick: move |_| record("target bubble"),
+                }
+            }
+        }
+    });
+    _ = dom.rebuild();
+
+    dom.handle_event(
+        "click",
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())),
+        ElementId(2),
+        true,
+    );
+    assert_eq!(take_calls(), ["outer capture"]);
+}
+
+#[test]
+fn prevent_default_is_reported() {
+    set_event_converter(Box::new(dioxus_html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(|cx| {
+        render! {
+            div {
+                onclick: move |evt| {
+                    assert!(!evt.default_action_enabled());
+                },
+                a {
+                    onclick: move |evt| evt.prevent_default(),
+                }
+            }
+        }
+    });
+    _ = dom.rebuild();
+
+    let default_action_enabled = dom.handle_event(
+        "click",
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())),
+        ElementId(2),
+        true,
+    );
+    assert!(!default_action_enabled);
+}
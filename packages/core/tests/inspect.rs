@@ -0,0 +1,133 @@
+//! Inspection Tests
+//!
+//! These tests verify that devtools can see the scopes of a VirtualDom and the values they expose.
+
+use dioxus::core::InspectedValue;
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn scopes_are_listed_depth_first() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.provide_context(1usize);
+        render! {
+            Child { count: 1 }
+            div { Child { count: 2 } }
+        }
+    });
+    _ = dom.rebuild();
+
+    let scopes = dom.inspect();
+    let names: Vec<_> = scopes.iter().map(|scope| scope.name.as_str()).collect();
+    assert_eq!(names, ["app", "Child", "Child"]);
+
+    let root = &scopes[0];
+    assert_eq!(root.id, ScopeId::ROOT);
+    assert_eq!(root.parent, None);
+    assert_eq!(root.children, [scopes[1].id, scopes[2].id]);
+    // The root always provides the default error boundary
+    assert_eq!(root.contexts.last().unwrap(), "usize");
+
+    let child = &scopes[1];
+    assert_eq!(child.parent, Some(ScopeId::ROOT));
+    assert_eq!(child.height, 1);
+    assert_eq!(child.props.as_deref(), Some("ChildProps { count: 1 }"));
+    assert_eq!(
+        child.hooks,
+        [
+            InspectedValue::new("usize", &10usize),
+            InspectedValue { label: "()".to_string(), value: None, subscribers: Vec::new() },
+        ]
+    );
+}
+
+#[test]
+fn props_without_debug_are_not_formatted() {
+    #[derive(Props, PartialEq)]
+    struct OpaqueProps {
+        count: usize,
+    }
+
+    fn Opaque(cx: Scope<OpaqueProps>) -> Element {
+        render! { "{cx.props.count}" }
+    }
+
+    #[derive(Props, PartialEq, Debug)]
+    struct GenericProps<T: 'static + PartialEq> {
+        value: T,
+    }
+
+    fn Generic<T: 'static + PartialEq>(cx: Scope<GenericProps<T>>) -> Element {
+        render! { div {} }
+    }
+
+    let mut dom = VirtualDom::new(|cx| {
+        render! {
+            Opaque { count: 1 }
+            Generic::<i32> { value: 1 }
+        }
+    });
+    _ = dom.rebuild();
+
+    let scopes = dom.inspect();
+    // The root props have no Debug bound
+    assert_eq!(scopes[0].props, None);
+    assert_eq!(scopes[1].props, None);
+    // Generic props can't be formatted because the derive can't know if the generics implement Debug
+    assert_eq!(scopes[2].props, None);
+}
+
+#[test]
+fn values_are_refreshed_every_render() {
+    let mut dom = VirtualDom::new(|cx| {
+        let count = cx.generation();
+        cx.inspect("count", &count);
+        render! { "{count}" }
+    });
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+
+    let scopes = dom.inspect();
+    assert_eq!(scopes[0].generation, 2);
+    assert_eq!(scopes[0].values, [InspectedValue::new("count", &1)]);
+}
+
+#[test]
+fn dropped_inspectors_are_removed() {
+    let alive = Rc::new(Cell::new(true));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            cx.use_hook(|| {
+                let alive = cx.props.clone();
+                add_inspector(cx.scope_id(), move || {
+                    alive.get().then(|| InspectedValue {
+                        label: "store".to_string(),
+                        value: None,
+                        subscribers: vec![ScopeId::ROOT],
+                    })
+                });
+            });
+            render! { div {} }
+        },
+        alive.clone(),
+    );
+    _ = dom.rebuild();
+
+    assert_eq!(dom.inspect()[0].values[0].subscribers, [ScopeId::ROOT]);
+
+    alive.set(false);
+    assert!(dom.inspect()[0].values.is_empty());
+}
+
+#[derive(Props, PartialEq, Debug)]
+struct ChildProps {
+    count: usize,
+}
+
+fn Child(cx: Scope<ChildProps>) -> Element {
+    cx.use_inspected_hook(|| cx.props.count * 10);
+    cx.use_hook(|| ());
+    render! { "{cx.props.count}" }
+}
@@ -0,0 +1,178 @@
+//! Multiple Root Tests
+//!
+//! These tests verify that additional roots share the state of the VirtualDom while getting their own edits.
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+#[test]
+fn roots_consume_the_context_of_the_main_root() {
+    let mut dom = VirtualDom::new(|cx| {
+        cx.provide_context("shared");
+        render! { div {} }
+    });
+    let panel = dom.add_root(
+        |cx| {
+            let text = cx.consume_context::<&'static str>().unwrap();
+            render! { "{text}" }
+        },
+        (),
+    );
+
+    _ = dom.rebuild();
+
+    let mutations = dom.rebuild_root(panel);
+    assert_eq!(mutations.subtree, panel);
+    assert_eq!(
+        mutations.edits,
+        [
+            CreateTextNode { value: "shared", id: ElementId(2) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn roots_get_their_own_edits() {
+    let scope = Rc::new(Cell::new(None));
+    let mut dom = VirtualDom::new(|cx| render! { div {} });
+    let panel = dom.add_root(
+        |cx| {
+            cx.props.set(Some(cx.scope_id()));
+            let count = cx.generation();
+            render! { "{count}" }
+        },
+        scope.clone(),
+    );
+
+    _ = dom.rebuild();
+    _ = dom.rebuild_root(panel);
+
+    dom.mark_dirty(scope.get().unwrap());
+    assert!(dom.render_immediate().edits.is_empty());
+    assert_eq!(
+        dom.root_mutations(panel).edits,
+        [SetText { value: "1", id: ElementId(2) }]
+    );
+}
+
+#[test]
+fn roots_render_when_their_edits_are_taken() {
+    let scope = Rc::new(Cell::new(None));
+    let mut dom = VirtualDom::new(|cx| render! { div {} });
+    let panel = dom.add_root(
+        |cx| {
+            cx.props.set(Some(cx.scope_id()));
+            let count = cx.generation();
+            render! { "{count}" }
+        },
+        scope.clone(),
+    );
+
+    _ = dom.rebuild();
+    _ = dom.rebuild_root(panel);
+
+    // The main root renders twice before the edits of the panel are taken
+    dom.mark_dirty(scope.get().unwrap());
+    assert!(dom.render_immediate().edits.is_empty());
+    dom.mark_dirty(ScopeId::ROOT);
+    assert!(dom.render_immediate().edits.is_empty());
+    dom.mark_dirty(scope.get().unwrap());
+    assert!(dom.render_immediate().edits.is_empty());
+    // The main root has nothing left to render
+    assert!(!dom.has_dirty_scopes());
+
+    assert_eq!(
+        dom.root_mutations(panel).edits,
+        [SetText { value: "1", id: ElementId(2) }]
+    );
+    assert!(!dom.has_dirty_scopes());
+    assert!(dom.root_mutations(panel).edits.is_empty());
+}
+
+#[tokio::test]
+async fn dirty_roots_are_only_new_work_once() {
+    let update = Rc::new(RefCell::new(None));
+    let mut dom = VirtualDom::new(|cx| render! { div {} });
+    let panel = dom.add_root(
+        |cx| {
+            cx.props
+                .borrow_mut()
+                .get_or_insert_with(|| cx.schedule_update());
+            render! { div {} }
+        },
+        update.clone(),
+    );
+
+    _ = dom.rebuild();
+    _ = dom.rebuild_root(panel);
+
+    (update.borrow().as_ref().unwrap())();
+    dom.wait_for_work().await;
+
+    // The panel stays dirty until its edits are taken, but the main loop doesn't wake up for it again
+    let timeout = std::time::Duration::from_millis(10);
+    assert!(tokio::time::timeout(timeout, dom.wait_for_work())
+        .await
+        .is_err());
+
+    _ = dom.root_mutations(panel);
+    (update.borrow().as_ref().unwrap())();
+    assert!(tokio::time::timeout(timeout, dom.wait_for_work())
+        .await
+        .is_ok());
+}
+
+#[test]
+fn roots_receive_templates_seen_by_other_roots() {
+    let mut dom = VirtualDom::new(|cx| render! { Shared {} });
+    let panel = dom.add_root(|cx| render! { Shared {} }, ());
+
+    let main_templates = dom.rebuild().templates;
+    assert_eq!(main_templates.len(), 1);
+
+    let mutations = dom.rebuild_root(panel);
+    assert_eq!(mutations.templates, main_templates);
+    assert_eq!(
+        mutations.santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn removing_a_root_drops_its_hooks() {
+    struct DropGuard(Rc<Cell<bool>>);
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let dropped = Rc::new(Cell::new(false));
+    let mut dom = VirtualDom::new(|cx| render! { div {} });
+    let panel = dom.add_root(
+        |cx| {
+            cx.use_hook(|| DropGuard(cx.props.clone()));
+            render! { div {} }
+        },
+        dropped.clone(),
+    );
+
+    _ = dom.rebuild();
+    _ = dom.rebuild_root(panel);
+
+    assert_eq!(dom.remove_root(panel).edits, [Remove { id: ElementId(2) }]);
+    assert!(dropped.get());
+}
+
+#[component]
+fn Shared(cx: Scope) -> Element {
+    render! { div { "shared" } }
+}
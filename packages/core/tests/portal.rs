@@ -0,0 +1,122 @@
+//! Portal Tests
+//!
+//! These tests verify that portals render their children into their target while staying in their logical parent.
+
+use dioxus::core::{ElementId, Mutation::*};
+use dioxus::prelude::*;
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn children_are_mounted_in_the_target() {
+    let mut dom = VirtualDom::new(|cx| {
+        render! {
+            div {
+                Portal {
+                    target: "modal",
+                    p {}
+                }
+            }
+        }
+    });
+
+    assert_eq!(
+        dom.rebuild().santize().edits,
+        [
+            LoadTemplate { name: "template", index: 0, id: ElementId(1) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            AssignPortalTarget { target: "modal".to_string(), id: ElementId(3) },
+            AppendChildren { id: ElementId(3), m: 1 },
+            CreatePlaceholder { id: ElementId(4) },
+            ReplacePlaceholder { path: &[0], m: 1 },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn events_bubble_to_the_logical_parent() {
+    set_event_converter(Box::new(dioxus_html::SerializedHtmlEventConverter));
+
+    let clicks = Rc::new(Cell::new(0));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let outer = cx.props.clone();
+            let inner = cx.props.clone();
+            render! {
+                div {
+                    onclick: move |_| outer.set(outer.get() + 1),
+                    Portal {
+                        target: "modal",
+                        button { onclick: move |_| inner.set(inner.get() + 1) }
+                    }
+                }
+            }
+        },
+        clicks.clone(),
+    );
+    _ = dom.rebuild();
+
+    dom.handle_event(
+        "click",
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())),
+        ElementId(2),
+        true,
+    );
+    assert_eq!(clicks.get(), 2);
+}
+
+#[test]
+fn removing_a_portal_removes_its_children() {
+    let mut dom = VirtualDom::new(|cx| {
+        let show = cx.generation() % 2 == 0;
+        render! {
+            if show {
+                Portal {
+                    target: "modal",
+                    p {}
+                }
+            }
+        }
+    });
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            CreatePlaceholder { id: ElementId(4) },
+            Remove { id: ElementId(1) },
+            ReplaceWith { id: ElementId(3), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn changing_the_target_moves_the_children() {
+    let mut dom = VirtualDom::new(|cx| {
+        let target = match cx.generation() % 2 {
+            0 => "first",
+            1 => "second",
+            _ => unreachable!(),
+        };
+        render! {
+            Portal {
+                target: target,
+                p {}
+                span {}
+            }
+        }
+    });
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(
+        dom.render_immediate().santize().edits,
+        [
+            PushRoot { id: ElementId(1) },
+            PushRoot { id: ElementId(2) },
+            AssignPortalTarget { target: "second".to_string(), id: ElementId(5) },
+            AppendChildren { id: ElementId(5), m: 2 },
+        ]
+    );
+}
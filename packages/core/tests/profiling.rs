@@ -0,0 +1,99 @@
+//! Profiling Tests
+//!
+//! These tests verify that the profiler records why and how scopes were rendered.
+
+use dioxus::core::{RenderReason, ScopeRender};
+use dioxus::prelude::*;
+
+fn renders(renders: &[ScopeRender]) -> Vec<(&str, RenderReason)> {
+    renders
+        .iter()
+        .map(|render| (render.name.as_str(), render.reason))
+        .collect()
+}
+
+#[test]
+fn renders_are_recorded_with_their_reason() {
+    let mut dom = VirtualDom::new(|cx| {
+        if cx.generation() == 1 {
+            cx.needs_update();
+        }
+        let count = cx.generation();
+        render! { Child { count: count } }
+    });
+    dom.start_profiling();
+
+    _ = dom.rebuild();
+
+    dom.mark_dirty(ScopeId::ROOT);
+    _ = dom.render_immediate();
+
+    // The root asked for another render while it was rendering
+    _ = dom.render_immediate();
+
+    let profile = dom.stop_profiling();
+    assert_eq!(profile.passes.len(), 3);
+
+    let rebuild = &profile.passes[0];
+    assert!(rebuild.dirty.is_empty());
+    assert_eq!(
+        renders(&rebuild.renders),
+        [("app", RenderReason::Mount), ("Child", RenderReason::Mount)]
+    );
+    assert_eq!(rebuild.mutations, 3);
+    assert_eq!(rebuild.templates, 1);
+
+    let marked = &profile.passes[1];
+    assert_eq!(marked.dirty.len(), 1);
+    assert_eq!(marked.dirty[0].scope, ScopeId::ROOT);
+    assert_eq!(marked.dirty[0].reason, RenderReason::MarkDirty);
+    assert_eq!(
+        renders(&marked.renders),
+        [
+            ("app", RenderReason::MarkDirty),
+            ("Child", RenderReason::ParentProps(ScopeId::ROOT))
+        ]
+    );
+    assert_eq!(marked.mutations, 1);
+    assert_eq!(marked.renders[1].mutations, 1);
+
+    let updated = &profile.passes[2];
+    assert_eq!(updated.dirty[0].reason, RenderReason::Update);
+    assert_eq!(updated.renders[0].reason, RenderReason::Update);
+}
+
+#[test]
+fn nothing_is_recorded_without_profiling() {
+    let mut dom = VirtualDom::new(|cx| render! { div {} });
+    _ = dom.rebuild();
+
+    assert!(!dom.is_profiling());
+    assert!(dom.stop_profiling().passes.is_empty());
+}
+
+#[test]
+fn profiles_export_chrome_traces() {
+    let mut dom = VirtualDom::new(|cx| render! { Child { count: 0 } });
+    dom.start_profiling();
+    _ = dom.rebuild();
+
+    let trace = dom.stop_profiling().to_chrome_trace();
+    let trace: serde_json::Value = serde_json::from_str(&trace).unwrap();
+    let events = trace["traceEvents"].as_array().unwrap();
+
+    let names: Vec<_> = events
+        .iter()
+        .map(|event| event["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        ["render pass", "app", "diff app", "Child", "diff Child"]
+    );
+    assert_eq!(events[3]["args"]["reason"], "Mount");
+    assert_eq!(events[4]["args"]["mutations"], 2);
+}
+
+#[component]
+fn Child(cx: Scope, count: usize) -> Element {
+    render! { p { "{count}" } }
+}
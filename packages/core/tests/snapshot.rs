@@ -0,0 +1,151 @@
+#![allow(non_snake_case)]
+
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::{
+    AttributeValueSnapshot, BorrowedAttributeValue, DynamicNodeSnapshot, ElementId, RenderSnapshot,
+};
+
+#[test]
+fn snapshot_structure() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div { "hello" }
+            Child { name: "world" }
+        }
+    }
+
+    #[component]
+    fn Child<'a>(cx: Scope, name: &'a str) -> Element<'a> {
+        render! { p { class: "{name}", "{name}" } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let snapshot = dom.snapshot();
+    assert_eq!(snapshot.root.name, "app");
+    assert_eq!(snapshot.root.height, 0);
+
+    let RenderSnapshot::Ready(node) = &snapshot.root.node else {
+        panic!("the root should be rendered");
+    };
+    let DynamicNodeSnapshot::Component(child) = &node.dynamic_nodes[0] else {
+        panic!("the first dynamic node should be the child component");
+    };
+    assert_eq!(child.name, "Child");
+    assert_eq!(child.height, 1);
+
+    let RenderSnapshot::Ready(node) = &child.node else {
+        panic!("the child should be rendered");
+    };
+    assert_eq!(
+        node.dynamic_nodes,
+        [DynamicNodeSnapshot::Text { id: Some(ElementId(3)), value: "world".to_string() }]
+    );
+    assert_eq!(node.dynamic_attrs[0].element, ElementId(2));
+    assert_eq!(
+        node.dynamic_attrs[0].attributes[0].value,
+        AttributeValueSnapshot::Text("world".to_string())
+    );
+}
+
+#[test]
+fn restore_matching_tree() {
+    fn app(cx: Scope<String>) -> Element {
+        // Render something else first so the old dom assigns different ids than a fresh one would
+        if cx.generation() == 0 && cx.props == "old" {
+            return render! { span {} };
+        }
+
+        render! { div { class: "{cx.props}", "hello {cx.props}" } }
+    }
+
+    let mut old = VirtualDom::new_with_props(app, "old".to_string());
+    _ = old.rebuild();
+    old.mark_dirty(ScopeId::ROOT);
+    _ = old.render_immediate();
+    let snapshot = old.snapshot();
+
+    let mut dom = VirtualDom::new_with_props(app, "new".to_string());
+    assert_eq!(
+        dom.rebuild_from_snapshot(&snapshot).edits,
+        [
+            SetAttribute {
+                name: "class",
+                value: BorrowedAttributeValue::Text("new"),
+                id: ElementId(2),
+                ns: None
+            },
+            SetText { value: "hello new", id: ElementId(3) },
+        ]
+    );
+
+    let RenderSnapshot::Ready(old_node) = &snapshot.root.node else {
+        panic!("the old root should be rendered");
+    };
+    let RenderSnapshot::Ready(new_node) = &dom.snapshot().root.node else {
+        panic!("the new root should be rendered");
+    };
+    assert_eq!(old_node.root_ids, new_node.root_ids);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    assert_eq!(dom.render_immediate().edits, []);
+}
+
+#[test]
+fn restore_mismatched_tree() {
+    fn app(cx: Scope<bool>) -> Element {
+        match cx.props {
+            true => render! { div {} },
+            false => render! { p {} },
+        }
+    }
+
+    let mut old = VirtualDom::new_with_props(app, true);
+    _ = old.rebuild();
+    let snapshot = old.snapshot();
+
+    let mut dom = VirtualDom::new_with_props(app, false);
+    assert_eq!(
+        dom.rebuild_from_snapshot(&snapshot).santize().edits,
+        [
+            Remove { id: ElementId(1) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(2) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
+
+#[test]
+fn restore_mismatched_tree_removes_top_level_nodes() {
+    fn app(cx: Scope<bool>) -> Element {
+        match cx.props {
+            true => render! {
+                div { class: "{cx.props}", p { "{cx.props}" } }
+                Child {}
+            },
+            false => render! { h1 {} },
+        }
+    }
+
+    fn Child(cx: Scope) -> Element {
+        render! { span { "child" } }
+    }
+
+    let mut old = VirtualDom::new_with_props(app, true);
+    _ = old.rebuild();
+    let snapshot = old.snapshot();
+
+    // The nested text node, the element with a dynamic attribute and the component are removed with their roots
+    let mut dom = VirtualDom::new_with_props(app, false);
+    assert_eq!(
+        dom.rebuild_from_snapshot(&snapshot).santize().edits,
+        [
+            Remove { id: ElementId(1) },
+            Remove { id: ElementId(3) },
+            LoadTemplate { name: "template", index: 0, id: ElementId(4) },
+            AppendChildren { id: ElementId(0), m: 1 },
+        ]
+    );
+}
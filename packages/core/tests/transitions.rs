@@ -0,0 +1,172 @@
+//! Transition Tests
+//!
+//! These tests verify that updates from user input are rendered before transitions, and that transitions can be split
+//! over multiple renders.
+
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+
+#[derive(Default)]
+struct Updates {
+    log: RefCell<Vec<&'static str>>,
+    schedulers: RefCell<HashMap<&'static str, Arc<dyn Fn() + Send + Sync>>>,
+}
+
+impl Updates {
+    fn update(&self, name: &str) {
+        (self.schedulers.borrow()[name])()
+    }
+
+    fn take_log(&self) -> Vec<&'static str> {
+        std::mem::take(&mut *self.log.borrow_mut())
+    }
+}
+
+#[component]
+fn Item(cx: Scope, name: &'static str) -> Element {
+    let updates = cx.consume_context::<Rc<Updates>>().unwrap();
+    updates.log.borrow_mut().push(name);
+    updates
+        .schedulers
+        .borrow_mut()
+        .entry(name)
+        .or_insert_with(|| cx.schedule_update());
+    render! { "{name}" }
+}
+
+fn click(dom: &mut VirtualDom) {
+    dom.handle_event(
+        "click",
+        Rc::new(PlatformEventData::new(Box::<SerializedMouseData>::default())),
+        ElementId(1),
+        true,
+    );
+}
+
+fn app(cx: Scope) -> Element {
+    let updates = cx.use_hook(|| cx.provide_context(Rc::new(Updates::default())));
+    render! {
+        button {
+            onclick: move |_| {
+                updates.update("input");
+                start_transition(|| {
+                    updates.update("row 1");
+                    updates.update("row 2");
+                });
+            }
+        }
+        // The rows are above the input, so they would be rendered first without priorities
+        Item { name: "row 1" }
+        Item { name: "row 2" }
+        Item { name: "input" }
+    }
+}
+
+fn rebuilt_app() -> (VirtualDom, Rc<Updates>) {
+    set_event_converter(Box::new(dioxus_html::SerializedHtmlEventConverter));
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    let updates = dom
+        .base_scope()
+        .consume_context::<Rc<Updates>>()
+        .unwrap();
+    updates.take_log();
+    (dom, updates)
+}
+
+#[test]
+fn input_is_rendered_before_transitions() {
+    let (mut dom, updates) = rebuilt_app();
+
+    click(&mut dom);
+
+    // Input is rendered even if the VirtualDom should yield right away
+    _ = dom.render_until(|| true);
+    assert_eq!(updates.take_log(), ["input"]);
+    assert!(dom.has_dirty_scopes());
+
+    // The transition is rendered later, one scope at a time
+    _ = dom.render_until(|| true);
+    assert_eq!(updates.take_log(), ["row 1"]);
+
+    _ = dom.render_immediate();
+    assert_eq!(updates.take_log(), ["row 2"]);
+    assert!(!dom.has_dirty_scopes());
+}
+
+#[tokio::test]
+async fn transitions_yield_to_the_deadline() {
+    let (mut dom, updates) = rebuilt_app();
+
+    click(&mut dom);
+
+    _ = dom.render_with_deadline(std::future::ready(())).await;
+    assert_eq!(updates.take_log(), ["input"]);
+
+    let deadline = tokio::time::sleep(std::time::Duration::from_millis(10));
+    _ = dom.render_with_deadline(deadline).await;
+    assert_eq!(updates.take_log(), ["row 1", "row 2"]);
+}
+
+#[tokio::test]
+async fn transitions_make_progress_after_the_deadline() {
+    let (mut dom, updates) = rebuilt_app();
+
+    click(&mut dom);
+    dom.process_events();
+
+    // Even if the deadline always passed, every render makes progress on the transition
+    let mut renders = 0;
+    while dom.has_dirty_scopes() {
+        tokio::time::timeout(std::time::Duration::from_secs(1), dom.wait_for_work())
+            .await
+            .unwrap();
+        _ = dom.render_with_deadline(std::future::ready(())).await;
+        renders += 1;
+        assert!(renders <= 3);
+    }
+    assert_eq!(updates.take_log(), ["input", "row 1", "row 2"]);
+}
+
+#[test]
+fn transitions_are_pending_until_rendered() {
+    fn app(cx: Scope) -> Element {
+        let updates = cx.use_hook(|| cx.provide_context(Rc::new(Updates::default())));
+        let transition = use_transition(cx);
+        updates
+            .log
+            .borrow_mut()
+            .push(if transition.is_pending() {
+                "pending"
+            } else {
+                "idle"
+            });
+
+        render! {
+            button { onclick: move |_| transition.start(|| updates.update("row 1")) }
+            Item { name: "row 1" }
+        }
+    }
+
+    set_event_converter(Box::new(dioxus_html::SerializedHtmlEventConverter));
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+    let updates = dom
+        .base_scope()
+        .consume_context::<Rc<Updates>>()
+        .unwrap();
+    assert_eq!(updates.take_log(), ["idle", "row 1"]);
+
+    click(&mut dom);
+
+    // The component that started the transition shows the pending state first
+    _ = dom.render_until(|| true);
+    assert_eq!(updates.take_log(), ["pending"]);
+
+    // And is rendered again once the transition is done
+    _ = dom.render_immediate();
+    assert_eq!(updates.take_log(), ["row 1", "idle"]);
+    assert!(!dom.has_dirty_scopes());
+}
@@ -197,7 +197,7 @@ impl<P: 'static> App<P> {
 
     pub fn handle_initialize_msg(&mut self, id: WindowId) {
         let view = self.webviews.get_mut(&id).unwrap();
-        view.desktop_context.send_edits(view.dom.rebuild());
+        view.send_edits(VirtualDom::rebuild);
         view.desktop_context
             .window
             .set_visible(self.is_visible_before_start);
@@ -248,8 +248,9 @@ impl<P: 'static> App<P> {
             _ => data.into_any(),
         };
 
+        // The webview already dispatched this event, so it was prevented by the `prevent_default` attribute if at all
         view.dom.handle_event(&name, as_any, element, bubbles);
-        view.desktop_context.send_edits(view.dom.render_immediate());
+        view.send_edits(VirtualDom::render_immediate);
     }
 
     #[cfg(all(feature = "hot-reload", debug_assertions))]
@@ -306,7 +307,7 @@ impl<P: 'static> App<P> {
             view.dom.handle_event(event_name, data, id, event_bubbles);
         }
 
-        view.desktop_context.send_edits(view.dom.render_immediate());
+        view.send_edits(VirtualDom::render_immediate);
     }
 
     /// Poll the virtualdom until it's pending
@@ -108,6 +108,8 @@ pub(crate) fn apply_edits(
             }
             Remove { id } => channel.remove(id.0 as u32),
             PushRoot { id } => channel.push_root(id.0 as u32),
+            Transition { id, kind } => channel.transition(id.0 as u32, kind as u8),
+            AssignPortalTarget { target, id } => channel.assign_portal_target(&target, id.0 as u32),
         }
     }
 
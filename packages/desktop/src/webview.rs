@@ -8,7 +8,7 @@ use crate::{
     waker::tao_waker,
     Config, DesktopContext, DesktopService,
 };
-use dioxus_core::VirtualDom;
+use dioxus_core::{Mutations, VirtualDom};
 use dioxus_html::prelude::EvalProvider;
 use futures_util::{pin_mut, FutureExt};
 use std::{any::Any, rc::Rc, task::Waker};
@@ -29,6 +29,10 @@ pub struct WebviewInstance {
     //
     // This would be a good thing for someone looking to contribute to fix.
     _menu: Option<Box<dyn Any>>,
+
+    // Serves the component tree of the window to `dx devtools`
+    #[cfg(all(feature = "inspector", debug_assertions))]
+    devtools: dioxus_devtools::Devtools,
 }
 
 impl WebviewInstance {
@@ -182,6 +186,8 @@ impl WebviewInstance {
 
         WebviewInstance {
             waker: tao_waker(shared.proxy.clone(), desktop_context.window.id()),
+            #[cfg(all(feature = "inspector", debug_assertions))]
+            devtools: dioxus_devtools::Devtools::new(desktop_context.window.title()),
             desktop_context,
             dom,
             _menu: menu,
@@ -189,6 +195,14 @@ impl WebviewInstance {
         }
     }
 
+    /// Send the edits of the last render to the webview and the new component tree to devtools
+    pub fn send_edits(&mut self, render: impl FnOnce(&mut VirtualDom) -> Mutations) {
+        self.desktop_context.send_edits(render(&mut self.dom));
+
+        #[cfg(all(feature = "inspector", debug_assertions))]
+        self.devtools.update(&self.dom);
+    }
+
     pub fn poll_vdom(&mut self) {
         let mut cx = std::task::Context::from_waker(&self.waker);
 
@@ -206,7 +220,7 @@ impl WebviewInstance {
                 }
             }
 
-            self.desktop_context.send_edits(self.dom.render_immediate());
+            self.send_edits(VirtualDom::render_immediate);
         }
     }
 }
@@ -0,0 +1,252 @@
+//! A terminal client that shows the component trees served by the devtools server
+
+use std::{cell::RefCell, collections::BTreeMap, time::Duration};
+
+use dioxus::prelude::*;
+use dioxus_core::ScopeInspection;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tungstenite::Message;
+
+use crate::DevtoolsMsg;
+
+/// Open the devtools client in the terminal and connect to the server at the address.
+///
+/// The client reconnects when the app restarts, so it can stay open while the app is rebuilt.
+pub fn launch(addr: impl Into<String>) {
+    let addr = addr.into();
+    let (tx, rx) = unbounded_channel();
+    let url = format!("ws://{}", addr);
+    std::thread::spawn(move || connect(url, tx));
+
+    dioxus_tui::launch_cfg_with_props(
+        app,
+        ClientProps {
+            addr,
+            events: RefCell::new(Some(rx)),
+        },
+        dioxus_tui::Config::default(),
+    );
+}
+
+enum ClientEvent {
+    Connected,
+    Disconnected,
+    Msg(DevtoolsMsg),
+}
+
+/// Read messages from the server until the client is closed, reconnecting whenever the connection is lost
+fn connect(url: String, tx: UnboundedSender<ClientEvent>) {
+    loop {
+        if let Ok((mut socket, _)) = tungstenite::connect(&url) {
+            if tx.send(ClientEvent::Connected).is_err() {
+                return;
+            }
+            while let Ok(msg) = socket.read() {
+                if let Message::Text(text) = msg {
+                    match serde_json::from_str(&text) {
+                        Ok(msg) => {
+                            if tx.send(ClientEvent::Msg(msg)).is_err() {
+                                return;
+                            }
+                        }
+                        Err(err) => tracing::error!("invalid devtools message: {}", err),
+                    }
+                }
+            }
+            if tx.send(ClientEvent::Disconnected).is_err() {
+                return;
+            }
+        }
+        std::thread::sleep(Duration::from_secs(1));
+    }
+}
+
+struct ClientProps {
+    addr: String,
+    events: RefCell<Option<UnboundedReceiver<ClientEvent>>>,
+}
+
+/// The name and scopes of every app connected to the server
+type Apps = BTreeMap<usize, (String, Vec<ScopeInspection>)>;
+
+fn app(cx: Scope<ClientProps>) -> Element {
+    let apps = use_state(cx, Apps::new);
+    let connected = use_state(cx, || false);
+    let selected = use_state(cx, || 0usize);
+
+    use_future(cx, (), |_| {
+        let events = cx.props.events.borrow_mut().take();
+        let apps = apps.clone();
+        let connected = connected.clone();
+        async move {
+            let mut events = match events {
+                Some(events) => events,
+                None => return,
+            };
+            while let Some(event) = events.recv().await {
+                match event {
+                    ClientEvent::Connected => connected.set(true),
+                    ClientEvent::Disconnected => {
+                        connected.set(false);
+                        apps.with_mut(|apps| apps.clear());
+                    }
+                    ClientEvent::Msg(DevtoolsMsg::Tree { app, name, scopes }) => {
+                        apps.with_mut(|apps| {
+                            apps.insert(app, (name, scopes));
+                        });
+                    }
+                    ClientEvent::Msg(DevtoolsMsg::Closed { app }) => {
+                        apps.with_mut(|apps| {
+                            apps.remove(&app);
+                        });
+                    }
+                }
+            }
+        }
+    });
+
+    let apps = apps.get();
+    let rows: Vec<_> = apps
+        .values()
+        .flat_map(|(_, scopes)| scopes.iter().map(move |scope| (scope, scopes)))
+        .collect();
+    // The index of the first row of every app
+    let offsets: Vec<_> = apps
+        .values()
+        .scan(0, |offset, (_, scopes)| {
+            let start = *offset;
+            *offset += scopes.len();
+            Some(start)
+        })
+        .collect();
+    let selected_index = (*selected.get()).min(rows.len().saturating_sub(1));
+    let count = rows.len();
+
+    let status = if **connected {
+        format!("connected to {}", cx.props.addr)
+    } else {
+        format!("waiting for an app on {}", cx.props.addr)
+    };
+
+    render! {
+        div {
+            width: "100%",
+            height: "100%",
+            flex_direction: "column",
+            tabindex: "0",
+            onkeydown: move |event| match event.key() {
+                Key::ArrowUp => selected.set(selected_index.saturating_sub(1)),
+                Key::ArrowDown => selected.set((selected_index + 1).min(count.saturating_sub(1))),
+                _ => {}
+            },
+
+            div { height: "1px", color: "green", "dioxus devtools - {status}" }
+            div { height: "1px", color: "darkgray", "click a scope or press tab, then use the arrow keys" }
+
+            div {
+                width: "100%",
+                flex_grow: "1",
+                flex_direction: "row",
+                border_width: "1px",
+
+                div {
+                    width: "50%",
+                    flex_direction: "column",
+                    for ((name, scopes), offset) in apps.values().zip(offsets) {
+                        div { height: "1px", color: "yellow", "{name}" }
+                        for (i, scope) in scopes.iter().enumerate() {
+                            ScopeRow {
+                                scope: scope,
+                                index: offset + i,
+                                selected: selected_index,
+                                onselect: move |index| selected.set(index),
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    width: "50%",
+                    flex_direction: "column",
+                    padding_left: "1px",
+                    if let Some((scope, scopes)) = rows.get(selected_index) {
+                        ScopeDetails { scope: scope, scopes: scopes }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ScopeRow<'a>(
+    cx: Scope<'a>,
+    scope: &'a ScopeInspection,
+    index: usize,
+    selected: usize,
+    onselect: EventHandler<'a, usize>,
+) -> Element {
+    let indent = "  ".repeat(scope.height as usize);
+    let background = if index == selected { "blue" } else { "black" };
+
+    render! {
+        div {
+            height: "1px",
+            background_color: "{background}",
+            onclick: move |_| onselect.call(*index),
+            "{indent}{scope.name} #{scope.id.0}"
+        }
+    }
+}
+
+#[component]
+fn ScopeDetails<'a>(
+    cx: Scope<'a>,
+    scope: &'a ScopeInspection,
+    scopes: &'a [ScopeInspection],
+) -> Element {
+    let name_of = |id: ScopeId| {
+        scopes
+            .iter()
+            .find(|scope| scope.id == id)
+            .map(|scope| format!("{} #{}", scope.name, id.0))
+            .unwrap_or_else(|| format!("#{}", id.0))
+    };
+    let parent = scope.parent.map(name_of).unwrap_or_default();
+    let suspended = if scope.suspended { " (suspended)" } else { "" };
+
+    render! {
+        div { height: "1px", color: "green", "{scope.name} #{scope.id.0}{suspended}" }
+        div { height: "1px", "parent: {parent}" }
+        div { height: "1px", "renders: {scope.generation}" }
+        div { height: "1px", "props: {scope.props.as_deref().unwrap_or(\"-\")}" }
+
+        div { height: "1px", color: "yellow", "values" }
+        for value in scope.values.iter() {
+            div {
+                height: "1px",
+                "  {value.label}: {value.value.as_deref().unwrap_or(\"-\")}"
+            }
+            if !value.subscribers.is_empty() {
+                div {
+                    height: "1px",
+                    color: "darkgray",
+                    "    subscribers: {value.subscribers.iter().map(|id| name_of(*id)).collect::<Vec<_>>().join(\", \")}"
+                }
+            }
+        }
+
+        div { height: "1px", color: "yellow", "hooks" }
+        for hook in scope.hooks.iter() {
+            div {
+                height: "1px",
+                "  {hook.label}: {hook.value.as_deref().unwrap_or(\"-\")}"
+            }
+        }
+
+        div { height: "1px", color: "yellow", "context" }
+        for context in scope.contexts.iter() {
+            div { height: "1px", "  {context}" }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+
+use dioxus_core::ScopeInspection;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+mod server;
+#[cfg(feature = "server")]
+pub use server::*;
+
+#[cfg(feature = "client")]
+pub mod client;
+
+/// The address devtools use if `DIOXUS_DEVTOOLS_ADDR` is not set
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7373";
+
+/// Get the address of the devtools websocket from the `DIOXUS_DEVTOOLS_ADDR` environment variable
+pub fn devtools_addr() -> String {
+    std::env::var("DIOXUS_DEVTOOLS_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string())
+}
+
+/// A message the devtools server sends to the client
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DevtoolsMsg {
+    /// The scopes of an app changed
+    Tree {
+        /// The id of the app, unique within the process
+        app: usize,
+        /// The name of the app, like the title of a window
+        name: String,
+        /// The scopes of the app, depth first from the root
+        scopes: Vec<ScopeInspection>,
+    },
+    /// An app was closed and its tree should be removed
+    Closed {
+        /// The id of the app
+        app: usize,
+    },
+}
@@ -0,0 +1,190 @@
+use std::{
+    collections::BTreeMap,
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, Sender},
+        Mutex,
+    },
+};
+
+use dioxus_core::VirtualDom;
+use once_cell::sync::Lazy;
+use tungstenite::{Message, WebSocket};
+
+use crate::{devtools_addr, DevtoolsMsg};
+
+/// The server every app of the process shares. `None` if the address is in use.
+static SERVER: Lazy<Option<&'static Server>> = Lazy::new(Server::start);
+
+static NEXT_APP: AtomicUsize = AtomicUsize::new(0);
+
+/// An event handled by the thread that talks to the clients
+enum Event {
+    Connected(WebSocket<TcpStream>),
+    Update(usize, DevtoolsMsg),
+    Closed(usize),
+}
+
+struct Server {
+    /// The number of connected clients. Apps skip inspecting their VirtualDom when it is zero.
+    clients: AtomicUsize,
+    events: Mutex<Sender<Event>>,
+}
+
+impl Server {
+    fn start() -> Option<&'static Self> {
+        let addr = devtools_addr();
+        let listener = match TcpListener::bind(&addr) {
+            Ok(listener) => listener,
+            Err(err) => {
+                tracing::warn!(
+                    "devtools are disabled, failed to listen on {}: {}",
+                    addr,
+                    err
+                );
+                return None;
+            }
+        };
+        tracing::info!("devtools are listening on ws://{}", addr);
+
+        let (tx, rx) = channel();
+        let server: &'static Self = Box::leak(Box::new(Self {
+            clients: AtomicUsize::new(0),
+            events: Mutex::new(tx.clone()),
+        }));
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                match tungstenite::accept(stream) {
+                    Ok(socket) => {
+                        if tx.send(Event::Connected(socket)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(err) => tracing::debug!("devtools client failed to connect: {}", err),
+                }
+            }
+        });
+
+        // Serializing and writing to the sockets blocks, so it happens on its own thread instead of the render loop
+        std::thread::spawn(move || {
+            // The latest tree of every open app, sent to clients when they connect
+            let mut trees = BTreeMap::<usize, String>::new();
+            let mut clients = Vec::<WebSocket<TcpStream>>::new();
+
+            for event in rx {
+                let text = match event {
+                    Event::Connected(mut socket) => {
+                        if trees
+                            .values()
+                            .all(|tree| socket.send(Message::Text(tree.clone())).is_ok())
+                        {
+                            clients.push(socket);
+                            server.clients.store(clients.len(), Ordering::Relaxed);
+                        }
+                        continue;
+                    }
+                    Event::Update(app, msg) => {
+                        let tree = match serde_json::to_string(&msg) {
+                            Ok(tree) => tree,
+                            Err(err) => {
+                                tracing::error!("failed to serialize the devtools tree: {}", err);
+                                continue;
+                            }
+                        };
+                        if trees.get(&app) == Some(&tree) {
+                            continue;
+                        }
+                        trees.insert(app, tree.clone());
+                        tree
+                    }
+                    Event::Closed(app) => {
+                        if trees.remove(&app).is_none() {
+                            continue;
+                        }
+                        serde_json::to_string(&DevtoolsMsg::Closed { app }).unwrap()
+                    }
+                };
+
+                // Clients that disconnected are dropped
+                clients.retain_mut(|socket| socket.send(Message::Text(text.clone())).is_ok());
+                server.clients.store(clients.len(), Ordering::Relaxed);
+
+                // Apps aren't inspected without clients, so their trees would go stale
+                if clients.is_empty() {
+                    trees.clear();
+                }
+            }
+        });
+
+        Some(server)
+    }
+
+    fn send(&self, event: Event) {
+        _ = self.events.lock().unwrap().send(event);
+    }
+}
+
+/// The connection of a VirtualDom to devtools.
+///
+/// The first connection of the process starts the devtools server on [`devtools_addr`]. If the server can't be started,
+/// the connection does nothing.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// let mut dom = VirtualDom::new(app);
+/// let devtools = Devtools::new("my app");
+///
+/// apply(dom.rebuild());
+/// devtools.update(&dom);
+///
+/// loop {
+///     dom.wait_for_work().await;
+///     apply(dom.render_immediate());
+///     devtools.update(&dom);
+/// }
+/// ```
+pub struct Devtools {
+    app: usize,
+    name: String,
+}
+
+impl Devtools {
+    /// Connect a new app to devtools. The name is shown to identify the app in the client.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            app: NEXT_APP.fetch_add(1, Ordering::Relaxed),
+            name: name.into(),
+        }
+    }
+
+    /// Send the scopes of the VirtualDom to the connected clients if they changed
+    ///
+    /// The VirtualDom is only inspected while a client is connected. A client that connects later sees the app after
+    /// its next update.
+    pub fn update(&self, dom: &VirtualDom) {
+        if let Some(server) = *SERVER {
+            if server.clients.load(Ordering::Relaxed) == 0 {
+                return;
+            }
+
+            let msg = DevtoolsMsg::Tree {
+                app: self.app,
+                name: self.name.clone(),
+                scopes: dom.inspect(),
+            };
+            server.send(Event::Update(self.app, msg));
+        }
+    }
+}
+
+impl Drop for Devtools {
+    fn drop(&mut self) {
+        // Don't start the server just to tell clients the app closed
+        if let Some(Some(server)) = Lazy::get(&SERVER) {
+            server.send(Event::Closed(self.app));
+        }
+    }
+}
@@ -1,5 +1,8 @@
 import * as vscode from 'vscode';
 import init, * as dioxus from 'dioxus-ext';
+import { LanguageClient, LanguageClientOptions, ServerOptions } from 'vscode-languageclient/node';
+
+let client: LanguageClient | undefined;
 
 export async function activate(context: vscode.ExtensionContext) {
 	// Load the wasm from the file system
@@ -22,6 +25,27 @@ export async function activate(context: vscode.ExtensionContext) {
 		vscode.commands.registerCommand('extension.formatRsxDocument', formatRsxDocument),
 		vscode.workspace.onWillSaveTextDocument(fmtDocumentOnSave)
 	);
+
+	if (vscode.workspace.getConfiguration('dioxus').get('languageServer.enabled')) {
+		startLanguageServer(context);
+	}
+}
+
+export function deactivate() {
+	return client?.stop();
+}
+
+function startLanguageServer(context: vscode.ExtensionContext) {
+	const command = vscode.workspace.getConfiguration('dioxus').get<string>('languageServer.path') || 'dx';
+
+	const serverOptions: ServerOptions = { command, args: ['lsp'] };
+	const clientOptions: LanguageClientOptions = {
+		documentSelector: [{ scheme: 'file', language: 'rust' }],
+	};
+
+	client = new LanguageClient('dioxus', 'Dioxus', serverOptions, clientOptions);
+	client.start();
+	context.subscriptions.push(client);
 }
 
 function translate(component: boolean) {
@@ -28,13 +28,22 @@ enum Route {
     #[route("/")]
     Home {},
     #[route("/blog/:id")]
+    // The title is loaded while the blog renders, and sent to the client with the page
+    #[loader(get_blog_title(id))]
     Blog { id: i32 },
 }
 
 #[component]
 fn Blog(cx: Scope, id: i32) -> Element {
+    let title = use_route_loader::<Result<String, ServerFnError>>(cx)?;
+    let title = match &*title {
+        Ok(title) => title.clone(),
+        Err(err) => format!("Failed to load the title: {err}"),
+    };
+
     render! {
         Link { to: Route::Home {}, "Go to counter" }
+        h1 { "{title}" }
         table {
             tbody {
                 for _ in 0..*id {
@@ -94,3 +103,8 @@ async fn post_server_data(data: String) -> Result<(), ServerFnError> {
 async fn get_server_data() -> Result<String, ServerFnError> {
     Ok("Hello from the server!".to_string())
 }
+
+#[server(GetBlogTitle)]
+async fn get_blog_title(id: i32) -> Result<String, ServerFnError> {
+    Ok(format!("Blog {id}"))
+}
@@ -68,9 +68,10 @@ use std::sync::Arc;
 use std::sync::RwLock;
 
 use crate::{
-    prelude::*, render::SSRState, serve_config::ServeConfig, server_context::DioxusServerContext,
-    server_fn::DioxusServerFnRegistry,
+    adapters::IMMUTABLE_CACHE_CONTROL, prelude::*, render::SSRState, serve_config::ServeConfig,
+    server_context::DioxusServerContext, server_fn::DioxusServerFnRegistry,
 };
+use dioxus_cli_config::StaticAssetManifest;
 
 /// A extension trait with utilities for integrating Dioxus with your Axum router.
 pub trait DioxusRouterExt<S> {
@@ -273,44 +274,10 @@ where
         })
     }
 
-    fn serve_static_assets(mut self, assets_path: impl Into<std::path::PathBuf>) -> Self {
-        use tower_http::services::{ServeDir, ServeFile};
-
+    fn serve_static_assets(self, assets_path: impl Into<std::path::PathBuf>) -> Self {
         let assets_path = assets_path.into();
-
-        // Serve all files in dist folder except index.html
-        let dir = std::fs::read_dir(&assets_path).unwrap_or_else(|e| {
-            panic!(
-                "Couldn't read assets directory at {:?}: {}",
-                &assets_path, e
-            )
-        });
-
-        for entry in dir.flatten() {
-            let path = entry.path();
-            if path.ends_with("index.html") {
-                continue;
-            }
-            let route = path
-                .strip_prefix(&assets_path)
-                .unwrap()
-                .iter()
-                .map(|segment| {
-                    segment.to_str().unwrap_or_else(|| {
-                        panic!("Failed to convert path segment {:?} to string", segment)
-                    })
-                })
-                .collect::<Vec<_>>()
-                .join("/");
-            let route = format!("/{}", route);
-            if path.is_dir() {
-                self = self.nest_service(&route, ServeDir::new(path));
-            } else {
-                self = self.nest_service(&route, ServeFile::new(path));
-            }
-        }
-
-        self
+        let assets = StaticAssetManifest::load(&assets_path);
+        serve_assets(self, assets_path, &assets)
     }
 
     fn serve_dioxus_application<P: Clone + serde::Serialize + Send + Sync + 'static>(
@@ -322,7 +289,7 @@ where
         let ssr_state = SSRState::new(&cfg);
 
         // Add server functions and render index.html
-        self.serve_static_assets(cfg.assets_path)
+        serve_assets(self, cfg.assets_path.into(), &cfg.assets)
             .connect_hot_reload()
             .register_server_fns(server_fn_route)
             .fallback(get(render_handler).with_state((cfg, ssr_state)))
@@ -358,6 +325,74 @@ where
     }
 }
 
+/// Serve the files in the assets folder except index.html.
+///
+/// Fingerprinted assets are cached forever, and every asset is served from its `.br` or `.gz` copy when the build
+/// precompressed it and the client accepts the encoding.
+fn serve_assets<S>(
+    mut router: Router<S>,
+    assets_path: std::path::PathBuf,
+    assets: &StaticAssetManifest,
+) -> Router<S>
+where
+    S: Send + Sync + Clone + 'static,
+{
+    use tower::Layer;
+    use tower_http::services::{ServeDir, ServeFile};
+    use tower_http::set_header::SetResponseHeaderLayer;
+
+    // Fingerprinted assets get their own routes, so only they are cached forever
+    let cache_control = SetResponseHeaderLayer::overriding(
+        http::header::CACHE_CONTROL,
+        http::HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+    );
+    for file in assets.fingerprinted_files() {
+        let service = ServeFile::new(assets_path.join(file))
+            .precompressed_br()
+            .precompressed_gzip();
+        router = router.route_service(&format!("/{}", file), cache_control.layer(service));
+    }
+
+    // Serve all files in dist folder except index.html
+    let dir = std::fs::read_dir(&assets_path).unwrap_or_else(|e| {
+        panic!(
+            "Couldn't read assets directory at {:?}: {}",
+            &assets_path, e
+        )
+    });
+
+    for entry in dir.flatten() {
+        let path = entry.path();
+        if path.ends_with("index.html") {
+            continue;
+        }
+        let route = path
+            .strip_prefix(&assets_path)
+            .unwrap()
+            .iter()
+            .map(|segment| {
+                segment.to_str().unwrap_or_else(|| {
+                    panic!("Failed to convert path segment {:?} to string", segment)
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        if assets.is_fingerprinted(&route) {
+            continue;
+        }
+        let route = format!("/{}", route);
+        if path.is_dir() {
+            let service = ServeDir::new(path).precompressed_br().precompressed_gzip();
+            router = router.nest_service(&route, service);
+        } else {
+            let service = ServeFile::new(path).precompressed_br().precompressed_gzip();
+            router = router.nest_service(&route, service);
+        }
+    }
+
+    router
+}
+
 fn apply_request_parts_to_response<B>(
     headers: hyper::header::HeaderMap,
     response: &mut axum::response::Response<B>,
@@ -428,6 +463,18 @@ pub async fn render_handler_with_context<
     let mut server_context = DioxusServerContext::new(parts.clone());
     inject_context(&mut server_context);
 
+    if cfg.streaming {
+        let stream = ssr_state.render_stream(&cfg, &server_context);
+        let mut response = Response::builder()
+            .header(http::header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Body::from_stream(stream))
+            .unwrap()
+            .into_response();
+        let headers = server_context.response_parts().unwrap().headers.clone();
+        apply_request_parts_to_response(headers, &mut response);
+        return response;
+    }
+
     match ssr_state.render(url, &cfg, &server_context).await {
         Ok(rendered) => {
             let crate::render::RenderResponse { html, freshness } = rendered;
@@ -26,6 +26,9 @@ use crate::{
     prelude::{DioxusServerContext, ProvideServerContext},
 };
 
+/// The `Cache-Control` header of fingerprinted assets. Their contents never change, so browsers can cache them forever.
+pub(crate) const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
 /// Create a server function handler with the given server context and server function.
 pub fn server_fn_service(
     context: DioxusServerContext,
@@ -67,9 +67,10 @@ use std::sync::Arc;
 use std::sync::RwLock;
 
 use crate::{
-    layer::Service, prelude::*, render::SSRState, serve_config::ServeConfig,
-    server_fn::DioxusServerFnRegistry, server_fn_service,
+    adapters::IMMUTABLE_CACHE_CONTROL, layer::Service, prelude::*, render::SSRState,
+    serve_config::ServeConfig, server_fn::DioxusServerFnRegistry, server_fn_service,
 };
+use dioxus_cli_config::StaticAssetManifest;
 
 type HyperRequest = hyper::Request<hyper::Body>;
 type HyperResponse = hyper::Response<HyperBody>;
@@ -240,45 +241,10 @@ impl DioxusRouterExt for Router {
         })
     }
 
-    fn serve_static_assets(mut self, assets_path: impl Into<std::path::PathBuf>) -> Self {
+    fn serve_static_assets(self, assets_path: impl Into<std::path::PathBuf>) -> Self {
         let assets_path = assets_path.into();
-
-        // Serve all files in dist folder except index.html
-        let dir = std::fs::read_dir(&assets_path).unwrap_or_else(|e| {
-            panic!(
-                "Couldn't read assets directory at {:?}: {}",
-                &assets_path, e
-            )
-        });
-
-        for entry in dir.flatten() {
-            let path = entry.path();
-            if path.ends_with("index.html") {
-                continue;
-            }
-            let route = path
-                .strip_prefix(&assets_path)
-                .unwrap()
-                .iter()
-                .map(|segment| {
-                    segment.to_str().unwrap_or_else(|| {
-                        panic!("Failed to convert path segment {:?} to string", segment)
-                    })
-                })
-                .collect::<Vec<_>>()
-                .join("/");
-            if path.is_file() {
-                let route = format!("/{}", route);
-                let serve_dir = StaticFile::new(path.clone());
-                self = self.push(Router::with_path(route).get(serve_dir))
-            } else {
-                let route = format!("/{}/<**path>", route);
-                let serve_dir = StaticDir::new([path.clone()]);
-                self = self.push(Router::with_path(route).get(serve_dir))
-            }
-        }
-
-        self
+        let assets = StaticAssetManifest::load(&assets_path);
+        serve_assets(self, assets_path, &assets)
     }
 
     fn serve_dioxus_application<P: Clone + serde::Serialize + Send + Sync + 'static>(
@@ -288,7 +254,7 @@ impl DioxusRouterExt for Router {
     ) -> Self {
         let cfg = cfg.into();
 
-        self.serve_static_assets(cfg.assets_path)
+        serve_assets(self, cfg.assets_path.into(), &cfg.assets)
             .connect_hot_reload()
             .register_server_fns(server_fn_path)
             .push(Router::with_path("/<**any_path>").get(SSRHandler { cfg }))
@@ -306,6 +272,80 @@ impl DioxusRouterExt for Router {
     }
 }
 
+/// Serve the files in the assets folder except index.html. Fingerprinted assets are cached forever.
+fn serve_assets(
+    mut router: Router,
+    assets_path: std::path::PathBuf,
+    assets: &StaticAssetManifest,
+) -> Router {
+    // Fingerprinted assets get their own routes before the folders they are in, so only they are cached forever
+    for file in assets.fingerprinted_files() {
+        let serve_file = StaticFile::new(assets_path.join(file));
+        router = router.push(
+            Router::with_path(format!("/{}", file))
+                .hoop(immutable_cache)
+                .get(serve_file),
+        );
+    }
+
+    // Serve all files in dist folder except index.html
+    let dir = std::fs::read_dir(&assets_path).unwrap_or_else(|e| {
+        panic!(
+            "Couldn't read assets directory at {:?}: {}",
+            &assets_path, e
+        )
+    });
+
+    for entry in dir.flatten() {
+        let path = entry.path();
+        if path.ends_with("index.html") {
+            continue;
+        }
+        let route = path
+            .strip_prefix(&assets_path)
+            .unwrap()
+            .iter()
+            .map(|segment| {
+                segment.to_str().unwrap_or_else(|| {
+                    panic!("Failed to convert path segment {:?} to string", segment)
+                })
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        if assets.is_fingerprinted(&route) {
+            continue;
+        }
+        if path.is_file() {
+            let route = format!("/{}", route);
+            let serve_dir = StaticFile::new(path.clone());
+            router = router.push(Router::with_path(route).get(serve_dir))
+        } else {
+            let route = format!("/{}/<**path>", route);
+            let serve_dir = StaticDir::new([path.clone()]);
+            router = router.push(Router::with_path(route).get(serve_dir))
+        }
+    }
+
+    router
+}
+
+/// Mark the response for a fingerprinted asset as cacheable forever.
+#[handler]
+async fn immutable_cache(
+    req: &mut Request,
+    depot: &mut Depot,
+    res: &mut Response,
+    ctrl: &mut FlowCtrl,
+) {
+    use salvo::http::header::{HeaderValue, CACHE_CONTROL};
+
+    ctrl.call_next(req, depot, res).await;
+    res.headers_mut().insert(
+        CACHE_CONTROL,
+        HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+    );
+}
+
 /// Extracts the parts of a request that are needed for server functions. This will take parts of the request and replace them with empty values.
 pub fn extract_parts(req: &mut Request) -> http::request::Parts {
     let mut parts = http::request::Request::new(()).into_parts().0;
@@ -382,7 +422,12 @@ pub struct SSRHandler<P: Clone> {
 
 impl<P: Clone> SSRHandler<P> {
     /// Creates a new SSR handler with the given configuration.
+    ///
+    /// Salvo does not support streaming, so pages are always rendered completely before they are sent.
     pub fn new(cfg: ServeConfig<P>) -> Self {
+        if cfg.streaming {
+            tracing::warn!("Streaming is not supported by the salvo adapter, pages will be rendered completely before they are sent");
+        }
         Self { cfg }
     }
 }
@@ -46,13 +46,16 @@
 //!
 //! ```
 
+use crate::adapters::IMMUTABLE_CACHE_CONTROL;
 use crate::layer::Service;
 use crate::{
     prelude::*, render::SSRState, serve_config::ServeConfig, server_fn::DioxusServerFnRegistry,
 };
 
 use crate::server_fn_service;
+use dioxus_cli_config::StaticAssetManifest;
 use server_fn::{Encoding, Payload, ServerFunctionRegistry};
+use std::collections::HashSet;
 use std::error::Error;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -185,7 +188,7 @@ pub fn serve_dioxus_application<P: Clone + serde::Serialize + Send + Sync + 'sta
 ) -> BoxedFilter<(impl Reply,)> {
     let cfg = cfg.into();
     // Serve the dist folder and the index.html file
-    let serve_dir = warp::fs::dir(cfg.assets_path);
+    let serve_dir = serve_static_assets(cfg.assets_path, &cfg.assets);
 
     connect_hot_reload()
         // First register the server functions
@@ -199,6 +202,28 @@ pub fn serve_dioxus_application<P: Clone + serde::Serialize + Send + Sync + 'sta
         .boxed()
 }
 
+/// Serve the files in the assets folder. Fingerprinted assets are cached forever.
+fn serve_static_assets(
+    assets_path: &'static str,
+    assets: &StaticAssetManifest,
+) -> impl Filter<Extract = (warp::reply::Response,), Error = Rejection> + Clone {
+    let fingerprinted: Arc<HashSet<String>> =
+        Arc::new(assets.fingerprinted_files().map(String::from).collect());
+
+    path::full().and(warp::fs::dir(assets_path)).map(
+        move |path: FullPath, file: warp::filters::fs::File| {
+            let mut response = file.into_response();
+            if fingerprinted.contains(path.as_str().trim_start_matches('/')) {
+                response.headers_mut().insert(
+                    warp::http::header::CACHE_CONTROL,
+                    warp::http::HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+                );
+            }
+            response
+        },
+    )
+}
+
 /// Server render the application.
 pub fn render_ssr<P: Clone + serde::Serialize + Send + Sync + 'static>(
     cfg: ServeConfig<P>,
@@ -213,13 +238,29 @@ pub fn render_ssr<P: Clone + serde::Serialize + Send + Sync + 'static>(
             async move {
                 let server_context = DioxusServerContext::new(parts);
 
+                if cfg.streaming {
+                    let stream = renderer.render_stream(&cfg, &server_context);
+                    let mut res = Response::builder()
+                        .header("Content-Type", "text/html; charset=utf-8")
+                        .body(warp::hyper::Body::wrap_stream(stream))
+                        .unwrap();
+
+                    let headers_mut = res.headers_mut();
+                    let headers = server_context.response_parts().unwrap().headers.clone();
+                    for (key, value) in headers.iter() {
+                        headers_mut.insert(key, value.clone());
+                    }
+
+                    return res;
+                }
+
                 match renderer.render(route, &cfg, &server_context).await {
                     Ok(rendered) => {
                         let crate::render::RenderResponse { html, freshness } = rendered;
 
                         let mut res = Response::builder()
                             .header("Content-Type", "text/html")
-                            .body(html)
+                            .body(html.into())
                             .unwrap();
 
                         let headers_mut = res.headers_mut();
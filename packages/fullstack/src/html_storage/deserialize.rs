@@ -24,6 +24,10 @@ pub(crate) fn serde_from_bytes<T: DeserializeOwned>(string: &[u8]) -> Option<T>
     }
 }
 
+/// The number of streamed data chunks that have already been added to the cursor
+#[cfg(target_arch = "wasm32")]
+static STREAMED_DATA_READ: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
 static SERVER_DATA: once_cell::sync::Lazy<Option<HTMLDataCursor>> =
     once_cell::sync::Lazy::new(|| {
         #[cfg(target_arch = "wasm32")]
@@ -31,10 +35,8 @@ static SERVER_DATA: once_cell::sync::Lazy<Option<HTMLDataCursor>> =
             let window = web_sys::window()?.document()?;
             let element = match window.get_element_by_id("dioxus-storage-data") {
                 Some(element) => element,
-                None => {
-                    tracing::error!("Failed to get element by id: dioxus-storage-data");
-                    return None;
-                }
+                // If the page is still streaming in, the data is read from the streamed chunks as they arrive
+                None => return Some(super::HTMLData::default().cursor()),
             };
             let attribute = match element.get_attribute("data-serialized") {
                 Some(attribute) => attribute,
@@ -46,6 +48,12 @@ static SERVER_DATA: once_cell::sync::Lazy<Option<HTMLDataCursor>> =
 
             let data: super::HTMLData = serde_from_bytes(attribute.as_bytes())?;
 
+            // The data element is written after the last streamed chunk and contains all of the streamed data
+            STREAMED_DATA_READ.store(
+                streamed_data().map(|chunks| chunks.length()).unwrap_or(0) as usize,
+                std::sync::atomic::Ordering::SeqCst,
+            );
+
             Some(data.cursor())
         }
         #[cfg(not(target_arch = "wasm32"))]
@@ -54,8 +62,52 @@ static SERVER_DATA: once_cell::sync::Lazy<Option<HTMLDataCursor>> =
         }
     });
 
+/// Get the chunks of data the server has streamed in so far
+#[cfg(target_arch = "wasm32")]
+fn streamed_data() -> Option<js_sys::Array> {
+    let window = web_sys::window()?;
+    let chunks = js_sys::Reflect::get(&window, &"__dioxus_streamed_data".into()).ok()?;
+    if chunks.is_undefined() {
+        return None;
+    }
+    Some(chunks.into())
+}
+
+/// Add any data chunks that were streamed in since the last time the cursor was read
+#[cfg(target_arch = "wasm32")]
+fn read_streamed_data(cursor: &HTMLDataCursor) {
+    let Some(chunks) = streamed_data() else {
+        return;
+    };
+    let read = STREAMED_DATA_READ.swap(
+        chunks.length() as usize,
+        std::sync::atomic::Ordering::SeqCst,
+    );
+    for chunk in chunks.iter().skip(read) {
+        if let Some(data) = chunk
+            .as_string()
+            .and_then(|chunk| serde_from_bytes::<super::HTMLData>(chunk.as_bytes()))
+        {
+            cursor.extend(data);
+        }
+    }
+}
+
 pub(crate) fn take_server_data<T: DeserializeOwned>() -> Option<T> {
-    SERVER_DATA.as_ref()?.take()
+    let cursor = SERVER_DATA.as_ref()?;
+    #[cfg(target_arch = "wasm32")]
+    if cursor.is_exhausted() {
+        read_streamed_data(cursor);
+    }
+    cursor.take()
+}
+
+pub(crate) fn take_route_loader_data(route: &str) -> Option<String> {
+    let cursor = SERVER_DATA.as_ref()?;
+    // The loader may have been streamed in with a suspended component
+    #[cfg(target_arch = "wasm32")]
+    read_streamed_data(cursor);
+    cursor.take_route_loader(route)
 }
 
 #[cfg(not(feature = "ssr"))]
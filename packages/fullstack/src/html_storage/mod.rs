@@ -1,6 +1,10 @@
 #![allow(unused)]
 
-use std::{io::Cursor, sync::atomic::AtomicUsize};
+use std::{
+    collections::HashMap,
+    io::Cursor,
+    sync::{atomic::AtomicUsize, Mutex},
+};
 
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -11,6 +15,8 @@ pub(crate) mod serialize;
 #[derive(serde::Serialize, serde::Deserialize, Default)]
 pub(crate) struct HTMLData {
     pub data: Vec<Vec<u8>>,
+    /// The data of route loaders, keyed by the route they were loaded for
+    pub route_loaders: HashMap<String, String>,
 }
 
 impl HTMLData {
@@ -20,31 +26,62 @@ impl HTMLData {
         self.data.push(serialized);
     }
 
+    pub(crate) fn push_route_loader(&mut self, route: &str, data: String) {
+        self.route_loaders.insert(route.to_string(), data);
+    }
+
     pub(crate) fn cursor(self) -> HTMLDataCursor {
         HTMLDataCursor {
-            data: self.data,
+            data: Mutex::new(self.data),
             index: AtomicUsize::new(0),
+            route_loaders: Mutex::new(self.route_loaders),
         }
     }
 }
 
 pub(crate) struct HTMLDataCursor {
-    data: Vec<Vec<u8>>,
+    data: Mutex<Vec<Vec<u8>>>,
     index: AtomicUsize,
+    route_loaders: Mutex<HashMap<String, String>>,
 }
 
 impl HTMLDataCursor {
+    /// Take the data of the loader of a route. Loader data is only used once, when the client hydrates the initial route
+    pub fn take_route_loader(&self, route: &str) -> Option<String> {
+        self.route_loaders.lock().ok()?.remove(route)
+    }
+
+    /// Add data that was streamed in after the cursor was created
+    pub fn extend(&self, streamed: HTMLData) {
+        if let Ok(mut current) = self.data.lock() {
+            current.extend(streamed.data);
+        }
+        if let Ok(mut route_loaders) = self.route_loaders.lock() {
+            route_loaders.extend(streamed.route_loaders);
+        }
+    }
+
+    /// Check if all of the data in the cursor has been taken
+    pub fn is_exhausted(&self) -> bool {
+        let current = self.index.load(std::sync::atomic::Ordering::SeqCst);
+        self.data
+            .lock()
+            .map(|data| current >= data.len())
+            .unwrap_or(true)
+    }
+
     pub fn take<T: DeserializeOwned>(&self) -> Option<T> {
+        let data = self.data.lock().ok()?;
         let current = self.index.load(std::sync::atomic::Ordering::SeqCst);
-        if current >= self.data.len() {
+        if current >= data.len() {
             tracing::error!(
                 "Tried to take more data than was available, len: {}, index: {}",
-                self.data.len(),
+                data.len(),
                 current
             );
             return None;
         }
-        let mut cursor = &self.data[current];
+        let mut cursor = &data[current];
         self.index.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
         match ciborium::from_reader(Cursor::new(cursor)) {
             Ok(x) => Some(x),
@@ -108,3 +145,21 @@ fn serialized_and_deserializes() {
         }
     }
 }
+
+#[test]
+fn streamed_data_adds_route_loaders() {
+    let cursor = HTMLData::default().cursor();
+    assert!(cursor.is_exhausted());
+
+    let mut streamed = HTMLData::default();
+    streamed.push(&1u32);
+    streamed.push_route_loader("/blog", "post".to_string());
+    let mut serialized = Vec::new();
+    serialize::serde_to_writable(&streamed, &mut serialized).unwrap();
+    cursor.extend(deserialize::serde_from_bytes(&serialized).unwrap());
+
+    assert!(!cursor.is_exhausted());
+    assert_eq!(cursor.take_route_loader("/blog"), Some("post".to_string()));
+    // Loader data is only used once
+    assert_eq!(cursor.take_route_loader("/blog"), None);
+}
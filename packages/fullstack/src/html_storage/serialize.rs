@@ -39,3 +39,17 @@ pub(crate) fn encode_in_element(
     serde_to_writable(&data, write_to)?;
     Ok(write_to.write_all(r#"" />"#.as_bytes())?)
 }
+
+#[cfg(feature = "ssr")]
+/// Encode data and route loaders that were pushed while streaming a page into a script. The client adds them to the data it read from the page when it runs out.
+pub(crate) fn encode_streamed_data(
+    data: &super::HTMLData,
+    write_to: &mut impl std::io::Write,
+) -> Result<(), ciborium::ser::Error<std::io::Error>> {
+    write_to.write_all(
+        r#"<script>(window.__dioxus_streamed_data=window.__dioxus_streamed_data||[]).push(""#
+            .as_bytes(),
+    )?;
+    serde_to_writable(&data, write_to)?;
+    Ok(write_to.write_all(r#"")</script>"#.as_bytes())?)
+}
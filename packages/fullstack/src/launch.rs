@@ -5,6 +5,19 @@ use dioxus::prelude::*;
 #[cfg(feature = "router")]
 use dioxus_router::prelude::*;
 
+/// Renders the static routes of an app into a directory for `dx build --ssg`
+#[cfg(feature = "ssr")]
+type GenerateStaticSite<Props> = fn(
+    ServeConfig<Props>,
+    std::path::PathBuf,
+) -> std::pin::Pin<
+    Box<
+        dyn std::future::Future<
+            Output = Result<(), dioxus_ssr::incremental::IncrementalRendererError>,
+        >,
+    >,
+>;
+
 /// A builder for a fullstack app.
 pub struct LaunchBuilder<Props: Clone> {
     component: Component<Props>,
@@ -16,6 +29,8 @@ pub struct LaunchBuilder<Props: Clone> {
     server_cfg: ServeConfigBuilder<Props>,
     #[cfg(feature = "ssr")]
     addr: std::net::SocketAddr,
+    #[cfg(feature = "ssr")]
+    generate_static_site: Option<GenerateStaticSite<Props>>,
     #[cfg(feature = "web")]
     web_cfg: dioxus_web::Config,
     #[cfg(feature = "desktop")]
@@ -48,6 +63,8 @@ impl<Props: Clone + serde::Serialize + serde::de::DeserializeOwned + Send + Sync
             addr: std::net::SocketAddr::from(([127, 0, 0, 1], 8080)),
             #[cfg(feature = "ssr")]
             server_cfg: ServeConfigBuilder::new(component, props),
+            #[cfg(feature = "ssr")]
+            generate_static_site: None,
             #[cfg(feature = "web")]
             web_cfg: dioxus_web::Config::default(),
             #[cfg(feature = "desktop")]
@@ -107,7 +124,11 @@ impl<Props: Clone + serde::Serialize + serde::de::DeserializeOwned + Send + Sync
         tokio::runtime::Runtime::new()
             .unwrap()
             .block_on(async move {
-                self.launch_server().await;
+                // `dx build --ssg` runs the server to render the static routes instead of serving them
+                match std::env::var_os(dioxus_cli_config::__private::SSG_ENV) {
+                    Some(static_dir) => self.launch_static_site(static_dir.into()).await,
+                    None => self.launch_server().await,
+                }
             });
         #[cfg(not(feature = "ssr"))]
         {
@@ -139,6 +160,18 @@ impl<Props: Clone + serde::Serialize + serde::de::DeserializeOwned + Send + Sync
         dioxus_desktop::launch_with_props(self.component, self.props, cfg);
     }
 
+    #[cfg(feature = "ssr")]
+    /// Render the static routes of the app into a directory and exit
+    async fn launch_static_site(self, static_dir: std::path::PathBuf) {
+        let generate_static_site = self
+            .generate_static_site
+            .expect("static site generation requires an app launched with LaunchBuilder::router");
+        if let Err(err) = generate_static_site(self.server_cfg.build(), static_dir).await {
+            tracing::error!("Failed to generate static site: {}", err);
+            std::process::exit(1);
+        }
+    }
+
     #[cfg(feature = "ssr")]
     /// Launch a server application
     pub async fn launch_server(self) {
@@ -218,9 +251,45 @@ where
     R: Clone + serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
 {
     /// Create a new launch builder for the given router.
+    ///
+    /// Apps launched with a router can be pre-rendered into a static site with `dx build --ssg`.
     pub fn router() -> Self {
         let component = crate::router::RouteWithCfg::<R>;
         let props = crate::router::FullstackRouterConfig::default();
-        Self::new_with_props(component, props)
+        Self {
+            #[cfg(feature = "ssr")]
+            generate_static_site: Some(|cfg, static_dir| {
+                Box::pin(generate_static_site::<R>(cfg, static_dir))
+            }),
+            ..Self::new_with_props(component, props)
+        }
     }
 }
+
+/// Render every static route of the router into the static directory, and write a sitemap.xml if `dx build --ssg` was given a base url
+#[cfg(all(feature = "ssr", feature = "router"))]
+async fn generate_static_site<R>(
+    mut cfg: ServeConfig<crate::router::FullstackRouterConfig<R>>,
+    static_dir: std::path::PathBuf,
+) -> Result<(), dioxus_ssr::incremental::IncrementalRendererError>
+where
+    <R as std::str::FromStr>::Err: std::fmt::Display,
+    R: Routable + serde::Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    // The static directory is the out dir of the app which holds the index.html and assets, so it must not be cleared
+    cfg.incremental = Some(std::sync::Arc::new(
+        IncrementalRendererConfig::new()
+            .static_dir(&static_dir)
+            .clear_cache(false),
+    ));
+    pre_cache_static_routes_with_props(&cfg).await?;
+
+    if let Ok(base_url) = std::env::var(dioxus_cli_config::__private::SSG_BASE_URL_ENV) {
+        std::fs::write(
+            static_dir.join("sitemap.xml"),
+            dioxus_router::incremental::generate_sitemap::<R>(&base_url),
+        )?;
+    }
+
+    Ok(())
+}
@@ -45,7 +45,7 @@ pub mod prelude {
     #[cfg(all(feature = "ssr", feature = "router"))]
     pub use crate::render::pre_cache_static_routes_with_props;
     #[cfg(feature = "ssr")]
-    pub use crate::render::SSRState;
+    pub use crate::render::{RenderStream, SSRState};
     #[cfg(feature = "router")]
     pub use crate::router::FullstackRouterConfig;
     #[cfg(feature = "ssr")]
@@ -15,6 +15,14 @@ use tokio::task::spawn_blocking;
 use crate::prelude::*;
 use dioxus::prelude::*;
 
+/// Show the component tree of the last page rendered on the server in `dx devtools`
+#[cfg(all(feature = "inspector", debug_assertions))]
+fn update_devtools(vdom: &VirtualDom) {
+    static DEVTOOLS: once_cell::sync::Lazy<dioxus_devtools::Devtools> =
+        once_cell::sync::Lazy::new(|| dioxus_devtools::Devtools::new("fullstack server render"));
+    DEVTOOLS.update(vdom);
+}
+
 enum SsrRendererPool {
     Renderer(RwLock<Vec<Renderer>>),
     Incremental(RwLock<Vec<dioxus_ssr::incremental::IncrementalRenderer>>),
@@ -40,53 +48,51 @@ impl SsrRendererPool {
 
                 let (tx, rx) = tokio::sync::oneshot::channel();
 
+                // The VirtualDom is not Send, so it is rendered on a blocking thread driven by the runtime of the server
+                let runtime = tokio::runtime::Handle::current();
                 spawn_blocking(move || {
-                    tokio::runtime::Runtime::new()
-                        .expect("couldn't spawn runtime")
-                        .block_on(async move {
-                            let mut vdom = VirtualDom::new_with_props(component, props);
-                            // Make sure the evaluator is initialized
-                            dioxus_ssr::eval::init_eval(vdom.base_scope());
-                            let mut to = WriteBuffer { buffer: Vec::new() };
-                            // before polling the future, we need to set the context
-                            let prev_context =
-                                SERVER_CONTEXT.with(|ctx| ctx.replace(server_context));
-                            // poll the future, which may call server_context()
-                            tracing::info!("Rebuilding vdom");
-                            let _ = vdom.rebuild();
-                            vdom.wait_for_suspense().await;
-                            tracing::info!("Suspense resolved");
-                            // after polling the future, we need to restore the context
-                            SERVER_CONTEXT.with(|ctx| ctx.replace(prev_context));
-
-                            if let Err(err) = wrapper.render_before_body(&mut *to) {
-                                let _ = tx.send(Err(err));
-                                return;
+                    runtime.block_on(async move {
+                        let mut vdom = VirtualDom::new_with_props(component, props);
+                        // Make sure the evaluator is initialized
+                        dioxus_ssr::eval::init_eval(vdom.base_scope());
+                        let mut to = WriteBuffer { buffer: Vec::new() };
+                        // before polling the future, we need to set the context
+                        let prev_context = SERVER_CONTEXT.with(|ctx| ctx.replace(server_context));
+                        // poll the future, which may call server_context()
+                        tracing::info!("Rebuilding vdom");
+                        let _ = vdom.rebuild();
+                        vdom.wait_for_suspense().await;
+                        tracing::info!("Suspense resolved");
+                        // after polling the future, we need to restore the context
+                        SERVER_CONTEXT.with(|ctx| ctx.replace(prev_context));
+                        #[cfg(all(feature = "inspector", debug_assertions))]
+                        update_devtools(&vdom);
+
+                        if let Err(err) = wrapper.render_before_body(&mut *to) {
+                            let _ = tx.send(Err(err));
+                            return;
+                        }
+                        if let Err(err) = renderer.render_to(&mut to, &vdom) {
+                            let _ = tx.send(Err(
+                                dioxus_ssr::incremental::IncrementalRendererError::RenderError(err),
+                            ));
+                            return;
+                        }
+                        if let Err(err) = wrapper.render_after_body(&mut *to) {
+                            let _ = tx.send(Err(err));
+                            return;
+                        }
+                        match String::from_utf8(to.buffer) {
+                            Ok(html) => {
+                                let _ = tx.send(Ok((renderer, RenderFreshness::now(None), html)));
                             }
-                            if let Err(err) = renderer.render_to(&mut to, &vdom) {
-                                let _ = tx.send(Err(
-                                    dioxus_ssr::incremental::IncrementalRendererError::RenderError(
-                                        err,
-                                    ),
+                            Err(err) => {
+                                dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(
+                                    err,
                                 ));
-                                return;
                             }
-                            if let Err(err) = wrapper.render_after_body(&mut *to) {
-                                let _ = tx.send(Err(err));
-                                return;
-                            }
-                            match String::from_utf8(to.buffer) {
-                                Ok(html) => {
-                                    let _ =
-                                        tx.send(Ok((renderer, RenderFreshness::now(None), html)));
-                                }
-                                Err(err) => {
-                                    dioxus_ssr::incremental::IncrementalRendererError::Other(
-                                        Box::new(err),
-                                    );
-                                }
-                            }
-                        });
+                        }
+                    });
                 });
                 let (renderer, freshness, html) = rx.await.unwrap()?;
                 pool.write().unwrap().push(renderer);
@@ -101,54 +107,56 @@ impl SsrRendererPool {
                 let (tx, rx) = tokio::sync::oneshot::channel();
 
                 let server_context = server_context.clone();
+                // The VirtualDom is not Send, so it is rendered on a blocking thread driven by the runtime of the server
+                let runtime = tokio::runtime::Handle::current();
                 spawn_blocking(move || {
-                    tokio::runtime::Runtime::new()
-                        .expect("couldn't spawn runtime")
-                        .block_on(async move {
-                            let mut to = WriteBuffer { buffer: Vec::new() };
-                            match renderer
-                                .render(
-                                    route,
-                                    component,
-                                    props,
-                                    &mut *to,
-                                    |vdom| {
-                                        Box::pin(async move {
-                                            // before polling the future, we need to set the context
-                                            let prev_context = SERVER_CONTEXT
-                                                .with(|ctx| ctx.replace(Box::new(server_context)));
-                                            // poll the future, which may call server_context()
-                                            tracing::info!("Rebuilding vdom");
-                                            let _ = vdom.rebuild();
-                                            vdom.wait_for_suspense().await;
-                                            tracing::info!("Suspense resolved");
-                                            // after polling the future, we need to restore the context
-                                            SERVER_CONTEXT.with(|ctx| ctx.replace(prev_context));
-                                        })
-                                    },
-                                    &wrapper,
-                                )
-                                .await
-                            {
-                                Ok(freshness) => {
-                                    match String::from_utf8(to.buffer).map_err(|err| {
-                                        dioxus_ssr::incremental::IncrementalRendererError::Other(
-                                            Box::new(err),
-                                        )
-                                    }) {
-                                        Ok(html) => {
-                                            let _ = tx.send(Ok((freshness, html)));
-                                        }
-                                        Err(err) => {
-                                            let _ = tx.send(Err(err));
-                                        }
+                    runtime.block_on(async move {
+                        let mut to = WriteBuffer { buffer: Vec::new() };
+                        match renderer
+                            .render(
+                                route,
+                                component,
+                                props,
+                                &mut *to,
+                                |vdom| {
+                                    Box::pin(async move {
+                                        // before polling the future, we need to set the context
+                                        let prev_context = SERVER_CONTEXT
+                                            .with(|ctx| ctx.replace(Box::new(server_context)));
+                                        // poll the future, which may call server_context()
+                                        tracing::info!("Rebuilding vdom");
+                                        let _ = vdom.rebuild();
+                                        vdom.wait_for_suspense().await;
+                                        tracing::info!("Suspense resolved");
+                                        // after polling the future, we need to restore the context
+                                        SERVER_CONTEXT.with(|ctx| ctx.replace(prev_context));
+                                        #[cfg(all(feature = "inspector", debug_assertions))]
+                                        update_devtools(vdom);
+                                    })
+                                },
+                                &wrapper,
+                            )
+                            .await
+                        {
+                            Ok(freshness) => {
+                                match String::from_utf8(to.buffer).map_err(|err| {
+                                    dioxus_ssr::incremental::IncrementalRendererError::Other(
+                                        Box::new(err),
+                                    )
+                                }) {
+                                    Ok(html) => {
+                                        let _ = tx.send(Ok((freshness, html)));
+                                    }
+                                    Err(err) => {
+                                        let _ = tx.send(Err(err));
                                     }
-                                }
-                                Err(err) => {
-                                    let _ = tx.send(Err(err));
                                 }
                             }
-                        })
+                            Err(err) => {
+                                let _ = tx.send(Err(err));
+                            }
+                        }
+                    })
                 });
                 let (freshness, html) = rx.await.unwrap()?;
 
@@ -156,8 +164,29 @@ impl SsrRendererPool {
             }
         }
     }
+
+    /// Take a plain renderer out of the pool. Streaming bypasses the incremental cache, so the incremental pool creates a new renderer
+    fn take_renderer(&self) -> Renderer {
+        match self {
+            Self::Renderer(pool) => pool.write().unwrap().pop(),
+            Self::Incremental(_) => None,
+        }
+        .unwrap_or_else(pre_renderer)
+    }
+
+    /// Return a renderer taken with [`SsrRendererPool::take_renderer`] to the pool
+    fn return_renderer(&self, renderer: Renderer) {
+        if let Self::Renderer(pool) = self {
+            pool.write().unwrap().push(renderer);
+        }
+    }
 }
 
+/// A stream of HTML chunks produced by [`SSRState::render_stream`].
+pub type RenderStream = tokio_stream::wrappers::UnboundedReceiverStream<
+    Result<String, dioxus_ssr::incremental::IncrementalRendererError>,
+>;
+
 /// State used in server side rendering. This utilizes a pool of [`dioxus_ssr::Renderer`]s to cache static templates between renders.
 #[derive(Clone)]
 pub struct SSRState {
@@ -210,6 +239,110 @@ impl SSRState {
             Ok(RenderResponse { html, freshness })
         }
     }
+
+    /// Render the application to a stream of HTML chunks.
+    ///
+    /// The shell of the page is sent as soon as the first render finishes with a placeholder for every suspended
+    /// component. Each suspended component is sent in its own chunk once it resolves, and the serialized server data is
+    /// sent with the last chunk once every component has resolved.
+    ///
+    /// The response headers are sent before the page is rendered, so changes server functions make to the response
+    /// while rendering are ignored.
+    pub fn render_stream<P: 'static + Clone + serde::Serialize + Send + Sync>(
+        &self,
+        cfg: &ServeConfig<P>,
+        server_context: &DioxusServerContext,
+    ) -> RenderStream {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let pool = self.renderers.clone();
+        let wrapper = FullstackRenderer {
+            cfg: cfg.clone(),
+            server_context: server_context.clone(),
+        };
+        let ServeConfig { app, props, .. } = cfg;
+        let (app, props) = (*app, props.clone());
+        let server_context = Box::new(server_context.clone());
+
+        // The VirtualDom is not Send, so it is rendered on a blocking thread driven by the runtime of the server
+        let runtime = tokio::runtime::Handle::current();
+        spawn_blocking(move || {
+            runtime.block_on(async move {
+                let mut renderer = pool.take_renderer();
+                let mut vdom = VirtualDom::new_with_props(app, props);
+                // Make sure the evaluator is initialized
+                dioxus_ssr::eval::init_eval(vdom.base_scope());
+                // before polling the future, we need to set the context
+                let prev_context = SERVER_CONTEXT.with(|ctx| ctx.replace(server_context));
+                tracing::info!("Rebuilding vdom");
+                let _ = vdom.rebuild();
+
+                if let Err(err) = stream_suspense(&mut vdom, &mut renderer, &wrapper, &tx).await {
+                    let _ = tx.send(Err(err));
+                }
+                #[cfg(all(feature = "inspector", debug_assertions))]
+                update_devtools(&vdom);
+
+                // after polling the future, we need to restore the context
+                SERVER_CONTEXT.with(|ctx| ctx.replace(prev_context));
+                pool.return_renderer(renderer);
+            });
+        });
+
+        RenderStream::new(rx)
+    }
+}
+
+/// Stream the shell of the page and then each suspended component as it resolves
+async fn stream_suspense<P: Clone + Serialize + Send + Sync + 'static>(
+    vdom: &mut VirtualDom,
+    renderer: &mut Renderer,
+    wrapper: &FullstackRenderer<P>,
+    tx: &tokio::sync::mpsc::UnboundedSender<
+        Result<String, dioxus_ssr::incremental::IncrementalRendererError>,
+    >,
+) -> Result<(), dioxus_ssr::incremental::IncrementalRendererError> {
+    let mut to = WriteBuffer { buffer: Vec::new() };
+
+    wrapper.render_before_body(&mut *to)?;
+    renderer.render_shell(&mut to, vdom)?;
+
+    let deadline = tokio::time::Instant::now() + wrapper.cfg.streaming_timeout;
+    let mut streamed = StreamedData::default();
+
+    loop {
+        // The data the resolved components pushed must reach the client before they hydrate
+        wrapper.render_streamed_data(&mut *to, &mut streamed)?;
+        renderer.render_resolved_suspense(&mut to, vdom)?;
+
+        // If the client disconnected, there is no reason to keep rendering
+        if !to.send_chunk(tx)? {
+            return Ok(());
+        }
+
+        if !renderer.has_pending_suspense() {
+            tracing::info!("Suspense resolved");
+            break;
+        }
+
+        if tokio::time::timeout_at(deadline, vdom.wait_for_work())
+            .await
+            .is_err()
+        {
+            tracing::warn!(
+                "Suspended components did not resolve within {:?}, the client will render them after it hydrates",
+                wrapper.cfg.streaming_timeout
+            );
+            break;
+        }
+        _ = vdom.render_immediate();
+    }
+
+    renderer.finish_stream(&mut to)?;
+    wrapper.render_after_body(&mut *to)?;
+    to.send_chunk(tx)?;
+
+    Ok(())
 }
 
 struct FullstackRenderer<P: Clone + Send + Sync + 'static> {
@@ -217,6 +350,62 @@ struct FullstackRenderer<P: Clone + Send + Sync + 'static> {
     server_context: DioxusServerContext,
 }
 
+/// The server data that was already sent to the client while streaming
+#[derive(Default)]
+struct StreamedData {
+    data: usize,
+    route_loaders: std::collections::HashSet<String>,
+}
+
+impl<P: Clone + Send + Sync + 'static> FullstackRenderer<P> {
+    /// Send the server data and route loaders that were pushed since the last chunk so the client can hydrate the
+    /// components that resolved
+    fn render_streamed_data<R: std::io::Write>(
+        &self,
+        to: &mut R,
+        streamed: &mut StreamedData,
+    ) -> Result<(), dioxus_ssr::incremental::IncrementalRendererError> {
+        let html_data = self
+            .server_context
+            .html_data()
+            .map_err(|_| html_data_read_error())?;
+        let chunk = crate::html_storage::HTMLData {
+            data: html_data.data[streamed.data..].to_vec(),
+            route_loaders: html_data
+                .route_loaders
+                .iter()
+                .filter(|(route, _)| !streamed.route_loaders.contains(*route))
+                .map(|(route, data)| (route.clone(), data.clone()))
+                .collect(),
+        };
+        if !chunk.data.is_empty() || !chunk.route_loaders.is_empty() {
+            crate::html_storage::serialize::encode_streamed_data(&chunk, to).map_err(|err| {
+                dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(err))
+            })?;
+            streamed.data = html_data.data.len();
+            streamed
+                .route_loaders
+                .extend(chunk.route_loaders.into_keys());
+        }
+        Ok(())
+    }
+}
+
+fn html_data_read_error() -> dioxus_ssr::incremental::IncrementalRendererError {
+    #[derive(Debug)]
+    struct HTMLDataReadError;
+
+    impl std::fmt::Display for HTMLDataReadError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("Failed to read the server data to serialize it into the HTML")
+        }
+    }
+
+    impl std::error::Error for HTMLDataReadError {}
+
+    dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(HTMLDataReadError))
+}
+
 impl<P: Clone + Serialize + Send + Sync + 'static> dioxus_ssr::incremental::WrapBody
     for FullstackRenderer<P>
 {
@@ -241,24 +430,10 @@ impl<P: Clone + Serialize + Send + Sync + 'static> dioxus_ssr::incremental::Wrap
         )?;
         // serialize the server state
         crate::html_storage::serialize::encode_in_element(
-            &*self.server_context.html_data().map_err(|_| {
-                dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new({
-                    #[derive(Debug)]
-                    struct HTMLDataReadError;
-
-                    impl std::fmt::Display for HTMLDataReadError {
-                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                            f.write_str(
-                                "Failed to read the server data to serialize it into the HTML",
-                            )
-                        }
-                    }
-
-                    impl std::error::Error for HTMLDataReadError {}
-
-                    HTMLDataReadError
-                }))
-            })?,
+            &*self
+                .server_context
+                .html_data()
+                .map_err(|_| html_data_read_error())?,
             to,
         )
         .map_err(|err| dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(err)))?;
@@ -344,7 +519,7 @@ pub async fn pre_cache_static_routes_with_props<Rt>(
     cfg: &crate::prelude::ServeConfig<crate::router::FullstackRouterConfig<Rt>>,
 ) -> Result<(), dioxus_ssr::incremental::IncrementalRendererError>
 where
-    Rt: dioxus_router::prelude::Routable + Send + Sync + Serialize,
+    Rt: dioxus_router::prelude::Routable + Send + Sync + Serialize + serde::de::DeserializeOwned,
     <Rt as std::str::FromStr>::Err: std::fmt::Display,
 {
     let wrapper = FullstackRenderer {
@@ -364,6 +539,24 @@ struct WriteBuffer {
     buffer: Vec<u8>,
 }
 
+impl WriteBuffer {
+    /// Send everything written so far as a chunk. Returns false if the receiver has been dropped
+    fn send_chunk(
+        &mut self,
+        tx: &tokio::sync::mpsc::UnboundedSender<
+            Result<String, dioxus_ssr::incremental::IncrementalRendererError>,
+        >,
+    ) -> Result<bool, dioxus_ssr::incremental::IncrementalRendererError> {
+        if self.buffer.is_empty() {
+            return Ok(!tx.is_closed());
+        }
+        let chunk = String::from_utf8(std::mem::take(&mut self.buffer)).map_err(|err| {
+            dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(err))
+        })?;
+        Ok(tx.send(Ok(chunk)).is_ok())
+    }
+}
+
 impl std::fmt::Write for WriteBuffer {
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
         self.buffer.extend_from_slice(s.as_bytes());
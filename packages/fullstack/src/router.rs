@@ -6,7 +6,7 @@ use dioxus::prelude::*;
 #[doc(hidden)]
 pub fn RouteWithCfg<R>(cx: Scope<FullstackRouterConfig<R>>) -> Element
 where
-    R: dioxus_router::prelude::Routable,
+    R: dioxus_router::prelude::Routable + serde::Serialize + serde::de::DeserializeOwned,
     <R as std::str::FromStr>::Err: std::fmt::Display,
 {
     use dioxus_router::prelude::RouterConfig;
@@ -15,11 +15,18 @@ where
     let context = crate::prelude::server_context();
 
     let cfg = *cx.props;
+    #[cfg(feature = "ssr")]
+    let loader_cache = FullstackLoaderCache {
+        context: context.clone(),
+    };
+    #[cfg(not(feature = "ssr"))]
+    let loader_cache = FullstackLoaderCache {};
     render! {
         dioxus_router::prelude::Router::<R> {
             config: move || {
                 RouterConfig::default()
                     .failure_external_navigation(cfg.failure_external_navigation)
+                    .loader_cache(loader_cache)
                     .history({
                         #[cfg(feature = "ssr")]
                         let history = dioxus_router::prelude::MemoryHistory::with_initial_path(
@@ -49,6 +56,39 @@ where
     }
 }
 
+/// Sends the data of route loaders from the server to the client in the [`crate::html_storage::HTMLData`] of the page
+struct FullstackLoaderCache {
+    #[cfg(feature = "ssr")]
+    context: crate::prelude::DioxusServerContext,
+}
+
+impl dioxus_router::prelude::LoaderCache for FullstackLoaderCache {
+    fn take(&self, route: &str) -> Option<String> {
+        #[cfg(feature = "ssr")]
+        {
+            let _ = route;
+            None
+        }
+        #[cfg(not(feature = "ssr"))]
+        {
+            crate::html_storage::deserialize::take_route_loader_data(route)
+        }
+    }
+
+    fn store(&self, route: &str, data: String) {
+        #[cfg(feature = "ssr")]
+        {
+            if let Err(err) = self.context.push_route_loader_data(route, data) {
+                tracing::error!("Failed to push route loader data: {}", err);
+            }
+        }
+        #[cfg(not(feature = "ssr"))]
+        {
+            let _ = (route, data);
+        }
+    }
+}
+
 fn default_external_navigation_handler() -> fn(Scope) -> Element {
     dioxus_router::prelude::FailureExternalNavigation
 }
@@ -6,8 +6,11 @@ use crate::router::*;
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 use dioxus::prelude::*;
+use dioxus_cli_config::StaticAssetManifest;
 
 /// A ServeConfig is used to configure how to serve a Dioxus application. It contains information about how to serve static assets, and what content to render with [`dioxus-ssr`].
 #[derive(Clone)]
@@ -19,8 +22,13 @@ pub struct ServeConfigBuilder<P: Clone> {
     pub(crate) assets_path: Option<&'static str>,
     pub(crate) incremental:
         Option<std::sync::Arc<dioxus_ssr::incremental::IncrementalRendererConfig>>,
+    pub(crate) streaming: bool,
+    pub(crate) streaming_timeout: Duration,
 }
 
+/// The default amount of time a streamed page waits for suspended components to resolve
+const DEFAULT_STREAMING_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// A template for incremental rendering that does nothing.
 #[derive(Default, Clone)]
 pub struct EmptyIncrementalRenderTemplate;
@@ -44,7 +52,7 @@ impl dioxus_ssr::incremental::WrapBody for EmptyIncrementalRenderTemplate {
 #[cfg(feature = "router")]
 impl<R> ServeConfigBuilder<FullstackRouterConfig<R>>
 where
-    R: dioxus_router::prelude::Routable,
+    R: dioxus_router::prelude::Routable + serde::Serialize + serde::de::DeserializeOwned,
     <R as std::str::FromStr>::Err: std::fmt::Display,
 {
     /// Create a new ServeConfigBuilder to serve a router on the server.
@@ -63,6 +71,8 @@ impl<P: Clone> ServeConfigBuilder<P> {
             index_path: None,
             assets_path: None,
             incremental: None,
+            streaming: false,
+            streaming_timeout: DEFAULT_STREAMING_TIMEOUT,
         }
     }
 
@@ -72,6 +82,24 @@ impl<P: Clone> ServeConfigBuilder<P> {
         self
     }
 
+    /// Stream the page to the client instead of waiting for every suspended component to resolve.
+    ///
+    /// The shell of the page is sent immediately, and each suspended component is streamed in once it resolves. Streamed pages are never cached by the incremental renderer.
+    ///
+    /// Streaming is supported by the axum and warp adapters. The salvo adapter ignores this setting and renders the whole page before sending it.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    /// Set how long a streamed page waits for suspended components to resolve. (defaults to 10 seconds)
+    ///
+    /// Once the deadline passes the rest of the page is sent, and any components that are still suspended are rendered by the client after it hydrates.
+    pub fn streaming_timeout(mut self, timeout: Duration) -> Self {
+        self.streaming_timeout = timeout;
+        self
+    }
+
     /// Set the path of the index.html file to be served. (defaults to {assets_path}/index.html)
     pub fn index_path(mut self, index_path: &'static str) -> Self {
         self.index_path = Some(index_path);
@@ -85,6 +113,8 @@ impl<P: Clone> ServeConfigBuilder<P> {
     }
 
     /// Set the path of the assets folder generated by the Dioxus CLI. (defaults to dist)
+    ///
+    /// Release builds of the CLI fingerprint the assets in that folder, and those are served with immutable cache headers.
     pub fn assets_path(mut self, assets_path: &'static str) -> Self {
         self.assets_path = Some(assets_path);
         self
@@ -103,12 +133,17 @@ impl<P: Clone> ServeConfigBuilder<P> {
 
         let index = load_index_html(index_path, root_id);
 
+        let assets = Arc::new(StaticAssetManifest::load(assets_path));
+
         ServeConfig {
             app: self.app,
             props: self.props,
             index,
             assets_path,
+            assets,
             incremental: self.incremental,
+            streaming: self.streaming,
+            streaming_timeout: self.streaming_timeout,
         }
     }
 }
@@ -151,8 +186,12 @@ pub struct ServeConfig<P: Clone> {
     pub(crate) props: P,
     pub(crate) index: IndexHtml,
     pub(crate) assets_path: &'static str,
+    /// The fingerprinted assets of a release build, which are served with immutable cache headers
+    pub(crate) assets: Arc<StaticAssetManifest>,
     pub(crate) incremental:
         Option<std::sync::Arc<dioxus_ssr::incremental::IncrementalRendererConfig>>,
+    pub(crate) streaming: bool,
+    pub(crate) streaming_timeout: Duration,
 }
 
 impl<P: Clone> From<ServeConfigBuilder<P>> for ServeConfig<P> {
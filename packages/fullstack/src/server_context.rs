@@ -115,6 +115,17 @@ mod server_fn_impl {
             })
         }
 
+        /// Insert the data of the loader of a route into the html data store
+        pub(crate) fn push_route_loader_data(
+            &self,
+            route: &str,
+            data: String,
+        ) -> Result<(), PoisonError<RwLockWriteGuard<'_, HTMLData>>> {
+            self.html_data.write().map(|mut map| {
+                map.push_route_loader(route, data);
+            })
+        }
+
         /// Get the html data store
         pub(crate) fn html_data(&self) -> LockResult<RwLockReadGuard<'_, HTMLData>> {
             self.html_data.read()
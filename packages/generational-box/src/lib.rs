@@ -2,8 +2,7 @@
 #![warn(missing_docs)]
 
 use std::{
-    any::Any,
-    cell::{Cell, Ref, RefCell, RefMut},
+    cell::{Ref, RefCell, RefMut},
     error::Error,
     fmt::{Debug, Display},
     marker::PhantomData,
@@ -11,8 +10,17 @@ use std::{
     rc::Rc,
 };
 
+#[cfg(any(debug_assertions, feature = "check_generation"))]
+use std::sync::atomic::{AtomicU32, Ordering};
+
 use bumpalo::Bump;
 
+mod sync;
+mod unsync;
+
+pub use sync::*;
+pub use unsync::*;
+
 /// # Example
 ///
 /// ```compile_fail
@@ -32,12 +40,12 @@ fn reused() {
     let first_ptr;
     {
         let owner = store.owner();
-        first_ptr = owner.insert(1).raw.0.data.as_ptr();
+        first_ptr = owner.insert(1).raw.0.data.data_ptr();
         drop(owner);
     }
     {
         let owner = store.owner();
-        let second_ptr = owner.insert(1234).raw.0.data.as_ptr();
+        let second_ptr = owner.insert(1234).raw.0.data.data_ptr();
         assert_eq!(first_ptr, second_ptr);
         drop(owner);
     }
@@ -154,9 +162,85 @@ fn fuzz() {
     }
 }
 
+#[test]
+fn sync_works_across_threads() {
+    let store = Store::<SyncStorage>::new();
+    let owner = store.owner();
+    let key = owner.insert(0);
+
+    // Reading and writing wait for the other thread instead of failing
+    let writer = std::thread::spawn(move || {
+        for _ in 0..1000 {
+            *key.write() += 1;
+        }
+    });
+    let reader = std::thread::spawn(move || {
+        let mut last = 0;
+        for _ in 0..1000 {
+            let value = *key.read();
+            assert!(value >= last);
+            last = value;
+        }
+    });
+    writer.join().unwrap();
+    reader.join().unwrap();
+
+    assert_eq!(*key.read(), 1000);
+}
+
+#[test]
+fn sync_conflicting_borrows() {
+    let store = Store::<SyncStorage>::new();
+    let owner = store.owner();
+    let key = owner.insert(0);
+
+    let read = key.read();
+    assert!(key.try_read().is_ok());
+    assert!(matches!(
+        key.try_write(),
+        Err(BorrowMutError::AlreadyBorrowed(_))
+    ));
+    drop(read);
+
+    let write = key.write();
+    assert!(matches!(
+        key.try_read(),
+        Err(BorrowError::AlreadyBorrowedMut(_))
+    ));
+    assert!(matches!(
+        key.try_write(),
+        Err(BorrowMutError::AlreadyBorrowed(_))
+    ));
+    let other_thread = std::thread::spawn(move || key.try_read().is_err())
+        .join()
+        .unwrap();
+    assert!(other_thread);
+    drop(write);
+
+    assert_eq!(*key.read(), 0);
+}
+
+#[test]
+fn sync_drops() {
+    let store = Store::<SyncStorage>::new();
+    let key;
+    {
+        let owner = store.owner();
+        key = owner.insert(String::from("hello world"));
+    }
+    let result = std::thread::spawn(move || key.try_read().is_err())
+        .join()
+        .unwrap();
+    assert!(result);
+}
+
 /// The core Copy state type. The generational box will be dropped when the [Owner] is dropped.
-pub struct GenerationalBox<T> {
-    raw: MemoryLocation,
+///
+/// The storage decides which values the box can hold and which threads can use it. The default [`UnsyncStorage`] can
+/// hold any value, but only the thread that created the box can use it. [`SyncStorage`] holds values that are `Send +
+/// Sync`, and the box can be sent to and used from any thread.
+pub struct GenerationalBox<T, S: 'static = UnsyncStorage> {
+    raw: MemoryLocation<S>,
     #[cfg(any(debug_assertions, feature = "check_generation"))]
     generation: u32,
     #[cfg(any(debug_assertions, feature = "debug_ownership"))]
@@ -164,26 +248,26 @@ pub struct GenerationalBox<T> {
     _marker: PhantomData<T>,
 }
 
-impl<T: 'static> Debug for GenerationalBox<T> {
+impl<T: 'static, S: AnyStorage> Debug for GenerationalBox<T, S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         #[cfg(any(debug_assertions, feature = "check_generation"))]
         f.write_fmt(format_args!(
             "{:?}@{:?}",
-            self.raw.0.data.as_ptr(),
+            self.raw.0.data.data_ptr(),
             self.generation
         ))?;
         #[cfg(not(any(debug_assertions, feature = "check_generation")))]
-        f.write_fmt(format_args!("{:?}", self.raw.data.as_ptr()))?;
+        f.write_fmt(format_args!("{:?}", self.raw.0.data.data_ptr()))?;
         Ok(())
     }
 }
 
-impl<T: 'static> GenerationalBox<T> {
+impl<T: 'static, S: Storage<T>> GenerationalBox<T, S> {
     #[inline(always)]
     fn validate(&self) -> bool {
         #[cfg(any(debug_assertions, feature = "check_generation"))]
         {
-            self.raw.0.generation.get() == self.generation
+            self.raw.0.generation.load(Ordering::Acquire) == self.generation
         }
         #[cfg(not(any(debug_assertions, feature = "check_generation")))]
         {
@@ -193,50 +277,66 @@ impl<T: 'static> GenerationalBox<T> {
 
     /// Try to read the value. Returns None if the value is no longer valid.
     #[track_caller]
-    pub fn try_read(&self) -> Result<GenerationalRef<T>, BorrowError> {
-        if !self.validate() {
-            return Err(BorrowError::Dropped(ValueDroppedError {
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                created_at: self.created_at,
-            }));
-        }
-        self.raw.try_borrow(
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            self.created_at,
-        )
+    pub fn try_read(&self) -> Result<S::Ref<T>, BorrowError> {
+        self.borrow(false)
     }
 
     /// Read the value. Panics if the value is no longer valid.
+    ///
+    /// With [`SyncStorage`], this waits for writes on other threads to finish.
     #[track_caller]
-    pub fn read(&self) -> GenerationalRef<T> {
-        self.try_read().unwrap()
+    pub fn read(&self) -> S::Ref<T> {
+        self.borrow(true).unwrap()
     }
 
     /// Try to write the value. Returns None if the value is no longer valid.
     #[track_caller]
-    pub fn try_write(&self) -> Result<GenerationalRefMut<T>, BorrowMutError> {
+    pub fn try_write(&self) -> Result<S::Mut<T>, BorrowMutError> {
+        self.borrow_mut(false)
+    }
+
+    /// Write the value. Panics if the value is no longer valid.
+    ///
+    /// With [`SyncStorage`], this waits for borrows on other threads to end.
+    #[track_caller]
+    pub fn write(&self) -> S::Mut<T> {
+        self.borrow_mut(true).unwrap()
+    }
+
+    #[track_caller]
+    fn borrow(&self, wait: bool) -> Result<S::Ref<T>, BorrowError> {
         if !self.validate() {
-            return Err(BorrowMutError::Dropped(ValueDroppedError {
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            return Err(BorrowError::Dropped(ValueDroppedError {
+                #[cfg(any(debug_assertions, feature = "debug_ownership"))]
                 created_at: self.created_at,
             }));
         }
-        self.raw.try_borrow_mut(
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+        self.raw.borrow(
+            wait,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
             self.created_at,
         )
     }
 
-    /// Write the value. Panics if the value is no longer valid.
     #[track_caller]
-    pub fn write(&self) -> GenerationalRefMut<T> {
-        self.try_write().unwrap()
+    fn borrow_mut(&self, wait: bool) -> Result<S::Mut<T>, BorrowMutError> {
+        if !self.validate() {
+            return Err(BorrowMutError::Dropped(ValueDroppedError {
+                #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+                created_at: self.created_at,
+            }));
+        }
+        self.raw.borrow_mut(
+            wait,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            self.created_at,
+        )
     }
 
     /// Set the value. Panics if the value is no longer valid.
     pub fn set(&self, value: T) {
         self.validate().then(|| {
-            *self.raw.0.data.borrow_mut() = Some(Box::new(value));
+            self.raw.0.data.set(value);
         });
     }
 
@@ -244,46 +344,139 @@ impl<T: 'static> GenerationalBox<T> {
     pub fn ptr_eq(&self, other: &Self) -> bool {
         #[cfg(any(debug_assertions, feature = "check_generation"))]
         {
-            self.raw.0.data.as_ptr() == other.raw.0.data.as_ptr()
+            self.raw.0.data.data_ptr() == other.raw.0.data.data_ptr()
                 && self.generation == other.generation
         }
         #[cfg(not(any(debug_assertions, feature = "check_generation")))]
         {
-            self.raw.data.as_ptr() == other.raw.data.as_ptr()
+            self.raw.0.data.data_ptr() == other.raw.0.data.data_ptr()
         }
     }
 }
 
-impl<T> Copy for GenerationalBox<T> {}
+impl<T, S> Copy for GenerationalBox<T, S> {}
 
-impl<T> Clone for GenerationalBox<T> {
+impl<T, S> Clone for GenerationalBox<T, S> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-#[derive(Clone, Copy)]
-struct MemoryLocation(&'static MemoryLocationInner);
+/// A type that can hold the value of a [`GenerationalBox`].
+///
+/// The storage is recycled for values of any type, so this trait contains everything that does not depend on the type
+/// of the value. [`Storage`] reads and writes values of a specific type.
+pub trait AnyStorage: Default + 'static {
+    /// The reference this storage returns when a value is read.
+    type Ref<T: 'static>: Deref<Target = T> + 'static;
+
+    /// The mutable reference this storage returns when a value is written.
+    type Mut<T: 'static>: DerefMut<Target = T> + 'static;
+
+    /// Map one ref type to another.
+    fn map<T: 'static, U: 'static>(ref_: Self::Ref<T>, f: impl FnOnce(&T) -> &U) -> Self::Ref<U>;
+
+    /// Filter one ref type to another.
+    fn try_map<T: 'static, U: 'static>(
+        ref_: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>>;
+
+    /// Map one mutable ref type to another.
+    fn map_mut<T: 'static, U: 'static>(
+        mut_ref: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> Self::Mut<U>;
+
+    /// Filter one mutable ref type to another.
+    fn try_map_mut<T: 'static, U: 'static>(
+        mut_ref: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>>;
+
+    /// Get a pointer to the data of this storage. Two boxes point to the same value if their data pointers are equal.
+    fn data_ptr(&self) -> *const ();
+
+    /// Drop the value of this storage. Returns true if the storage held a value.
+    fn take(&self) -> bool;
+}
+
+/// A type that can hold a value of type `Data` in a [`GenerationalBox`].
+pub trait Storage<Data: 'static>: AnyStorage {
+    /// Try to read the value of this storage.
+    fn try_read(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Ref<Data>, BorrowError>;
 
-struct MemoryLocationInner {
-    data: RefCell<Option<Box<dyn std::any::Any>>>,
+    /// Try to write the value of this storage.
+    fn try_write(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Mut<Data>, BorrowMutError>;
+
+    /// Read the value of this storage. Storages that are shared between threads wait for writes on other threads to
+    /// finish instead of failing.
+    fn read(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Ref<Data>, BorrowError> {
+        self.try_read(
+            borrow_info,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        )
+    }
+
+    /// Write the value of this storage. Storages that are shared between threads wait for other borrows on other
+    /// threads to end instead of failing.
+    fn write(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Mut<Data>, BorrowMutError> {
+        self.try_write(
+            borrow_info,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        )
+    }
+
+    /// Replace the value of this storage.
+    fn set(&self, value: Data);
+}
+
+struct MemoryLocation<S: 'static>(&'static MemoryLocationInner<S>);
+
+impl<S> Clone for MemoryLocation<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for MemoryLocation<S> {}
+
+struct MemoryLocationInner<S> {
+    data: S,
     #[cfg(any(debug_assertions, feature = "check_generation"))]
-    generation: Cell<u32>,
-    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-    borrowed_at: RefCell<Vec<&'static std::panic::Location<'static>>>,
-    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-    borrowed_mut_at: Cell<Option<&'static std::panic::Location<'static>>>,
+    generation: AtomicU32,
+    borrow: MemoryLocationBorrowInfo,
 }
 
-impl MemoryLocation {
+impl<S: AnyStorage> MemoryLocation<S> {
     #[allow(unused)]
     fn drop(&self) {
-        let old = self.0.data.borrow_mut().take();
+        let old = self.0.data.take();
         #[cfg(any(debug_assertions, feature = "check_generation"))]
-        if old.is_some() {
-            drop(old);
-            let new_generation = self.0.generation.get() + 1;
-            self.0.generation.set(new_generation);
+        if old {
+            self.0.generation.fetch_add(1, Ordering::AcqRel);
         }
     }
 
@@ -292,87 +485,120 @@ impl MemoryLocation {
         value: T,
         #[cfg(any(debug_assertions, feature = "debug_ownership"))]
         caller: &'static std::panic::Location<'static>,
-    ) -> GenerationalBox<T> {
-        let mut inner_mut = self.0.data.borrow_mut();
-
-        let raw = Box::new(value);
-        let old = inner_mut.replace(raw);
-        assert!(old.is_none());
+    ) -> GenerationalBox<T, S>
+    where
+        S: Storage<T>,
+    {
+        self.0.data.set(value);
         GenerationalBox {
             raw: *self,
             #[cfg(any(debug_assertions, feature = "check_generation"))]
-            generation: self.0.generation.get(),
+            generation: self.0.generation.load(Ordering::Acquire),
             #[cfg(any(debug_assertions, feature = "debug_ownership"))]
             created_at: caller,
             _marker: PhantomData,
         }
     }
 
+    /// Borrow the value, waiting for borrows on other threads to end if `wait` is true
     #[track_caller]
-    fn try_borrow<T: Any>(
+    fn borrow<T: 'static>(
         &self,
+        wait: bool,
         #[cfg(any(debug_assertions, feature = "debug_ownership"))]
         created_at: &'static std::panic::Location<'static>,
-    ) -> Result<GenerationalRef<T>, BorrowError> {
+    ) -> Result<S::Ref<T>, BorrowError>
+    where
+        S: Storage<T>,
+    {
         #[cfg(any(debug_assertions, feature = "debug_borrows"))]
         self.0
+            .borrow
             .borrowed_at
-            .borrow_mut()
+            .write()
             .push(std::panic::Location::caller());
-        match self.0.data.try_borrow() {
-            Ok(borrow) => match Ref::filter_map(borrow, |any| any.as_ref()?.downcast_ref::<T>()) {
-                Ok(reference) => Ok(GenerationalRef {
-                    inner: reference,
-                    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                    borrow: GenerationalRefBorrowInfo {
-                        borrowed_at: std::panic::Location::caller(),
-                        borrowed_from: self.0,
-                    },
-                }),
-                Err(_) => Err(BorrowError::Dropped(ValueDroppedError {
-                    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-                    created_at,
-                })),
-            },
-            Err(_) => Err(BorrowError::AlreadyBorrowedMut(AlreadyBorrowedMutError {
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrowed_mut_at: self.0.borrowed_mut_at.get().unwrap(),
-            })),
+        match wait {
+            true => self.0.data.read(
+                &self.0.borrow,
+                #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+                created_at,
+            ),
+            false => self.0.data.try_read(
+                &self.0.borrow,
+                #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+                created_at,
+            ),
         }
     }
 
+    /// Borrow the value mutably, waiting for borrows on other threads to end if `wait` is true
     #[track_caller]
-    fn try_borrow_mut<T: Any>(
+    fn borrow_mut<T: 'static>(
         &self,
+        wait: bool,
         #[cfg(any(debug_assertions, feature = "debug_ownership"))]
         created_at: &'static std::panic::Location<'static>,
-    ) -> Result<GenerationalRefMut<T>, BorrowMutError> {
+    ) -> Result<S::Mut<T>, BorrowMutError>
+    where
+        S: Storage<T>,
+    {
         #[cfg(any(debug_assertions, feature = "debug_borrows"))]
         {
-            self.0
-                .borrowed_mut_at
-                .set(Some(std::panic::Location::caller()));
+            *self.0.borrow.borrowed_mut_at.write() = Some(std::panic::Location::caller());
         }
-        match self.0.data.try_borrow_mut() {
-            Ok(borrow_mut) => {
-                match RefMut::filter_map(borrow_mut, |any| any.as_mut()?.downcast_mut::<T>()) {
-                    Ok(reference) => Ok(GenerationalRefMut {
-                        inner: reference,
-                        #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                        borrow: GenerationalRefMutBorrowInfo {
-                            borrowed_from: self.0,
-                        },
-                    }),
-                    Err(_) => Err(BorrowMutError::Dropped(ValueDroppedError {
-                        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
-                        created_at,
-                    })),
-                }
-            }
-            Err(_) => Err(BorrowMutError::AlreadyBorrowed(AlreadyBorrowedError {
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrowed_at: self.0.borrowed_at.borrow().clone(),
-            })),
+        match wait {
+            true => self.0.data.write(
+                &self.0.borrow,
+                #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+                created_at,
+            ),
+            false => self.0.data.try_write(
+                &self.0.borrow,
+                #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+                created_at,
+            ),
+        }
+    }
+}
+
+/// Information about the borrows of a memory location, used to explain borrow errors in debug builds.
+#[derive(Debug, Default)]
+pub struct MemoryLocationBorrowInfo {
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_at: parking_lot::RwLock<Vec<&'static std::panic::Location<'static>>>,
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_mut_at: parking_lot::RwLock<Option<&'static std::panic::Location<'static>>>,
+}
+
+impl MemoryLocationBorrowInfo {
+    #[track_caller]
+    fn borrow_guard(&'static self) -> GenerationalRefBorrowInfo {
+        GenerationalRefBorrowInfo {
+            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            borrowed_at: std::panic::Location::caller(),
+            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            borrowed_from: self,
+        }
+    }
+
+    fn borrow_mut_guard(&'static self) -> GenerationalRefMutBorrowInfo {
+        GenerationalRefMutBorrowInfo {
+            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            borrowed_from: self,
+        }
+    }
+
+    fn borrowed_error(&self) -> AlreadyBorrowedError {
+        AlreadyBorrowedError {
+            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            borrowed_at: self.borrowed_at.read().clone(),
+        }
+    }
+
+    fn borrowed_mut_error(&self) -> AlreadyBorrowedMutError {
+        AlreadyBorrowedMutError {
+            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            borrowed_mut_at: self.borrowed_mut_at.read().unwrap(),
         }
     }
 }
@@ -427,6 +653,18 @@ pub struct ValueDroppedError {
     created_at: &'static std::panic::Location<'static>,
 }
 
+impl ValueDroppedError {
+    fn new(
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        }
+    }
+}
+
 impl Display for ValueDroppedError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("Failed to borrow because the value was dropped.")?;
@@ -478,10 +716,9 @@ impl Display for AlreadyBorrowedError {
 
 impl std::error::Error for AlreadyBorrowedError {}
 
-/// A reference to a value in a generational box.
+/// A reference to a value in a generational box with [`UnsyncStorage`].
 pub struct GenerationalRef<T: 'static> {
     inner: Ref<'static, T>,
-    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
     borrow: GenerationalRefBorrowInfo,
 }
 
@@ -493,11 +730,7 @@ impl<T: 'static> GenerationalRef<T> {
     {
         GenerationalRef {
             inner: Ref::map(orig.inner, f),
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            borrow: GenerationalRefBorrowInfo {
-                borrowed_at: orig.borrow.borrowed_at,
-                borrowed_from: orig.borrow.borrowed_from,
-            },
+            borrow: orig.borrow,
         }
     }
 
@@ -506,19 +739,10 @@ impl<T: 'static> GenerationalRef<T> {
     where
         F: FnOnce(&T) -> Option<&U>,
     {
-        let Self {
-            inner,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            borrow,
-        } = orig;
-        Ref::filter_map(inner, f).ok().map(|inner| GenerationalRef {
-            inner,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            borrow: GenerationalRefBorrowInfo {
-                borrowed_at: borrow.borrowed_at,
-                borrowed_from: borrow.borrowed_from,
-            },
-        })
+        let Self { inner, borrow } = orig;
+        Ref::filter_map(inner, f)
+            .ok()
+            .map(|inner| GenerationalRef { inner, borrow })
     }
 }
 
@@ -530,10 +754,11 @@ impl<T: 'static> Deref for GenerationalRef<T> {
     }
 }
 
-#[cfg(any(debug_assertions, feature = "debug_borrows"))]
 struct GenerationalRefBorrowInfo {
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
     borrowed_at: &'static std::panic::Location<'static>,
-    borrowed_from: &'static MemoryLocationInner,
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_from: &'static MemoryLocationBorrowInfo,
 }
 
 #[cfg(any(debug_assertions, feature = "debug_borrows"))]
@@ -541,15 +766,14 @@ impl Drop for GenerationalRefBorrowInfo {
     fn drop(&mut self) {
         self.borrowed_from
             .borrowed_at
-            .borrow_mut()
-            .retain(|location| std::ptr::eq(*location, self.borrowed_at as *const _));
+            .write()
+            .retain(|location| !std::ptr::eq(*location, self.borrowed_at as *const _));
     }
 }
 
-/// A mutable reference to a value in a generational box.
+/// A mutable reference to a value in a generational box with [`UnsyncStorage`].
 pub struct GenerationalRefMut<T: 'static> {
     inner: RefMut<'static, T>,
-    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
     borrow: GenerationalRefMutBorrowInfo,
 }
 
@@ -561,7 +785,6 @@ impl<T: 'static> GenerationalRefMut<T> {
     {
         GenerationalRefMut {
             inner: RefMut::map(orig.inner, f),
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
             borrow: orig.borrow,
         }
     }
@@ -571,18 +794,10 @@ impl<T: 'static> GenerationalRefMut<T> {
     where
         F: FnOnce(&mut T) -> Option<&mut U>,
     {
-        let Self {
-            inner,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-            borrow,
-        } = orig;
+        let Self { inner, borrow } = orig;
         RefMut::filter_map(inner, f)
             .ok()
-            .map(|inner| GenerationalRefMut {
-                inner,
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrow,
-            })
+            .map(|inner| GenerationalRefMut { inner, borrow })
     }
 }
 
@@ -600,59 +815,69 @@ impl<T: 'static> DerefMut for GenerationalRefMut<T> {
     }
 }
 
-#[cfg(any(debug_assertions, feature = "debug_borrows"))]
 struct GenerationalRefMutBorrowInfo {
-    borrowed_from: &'static MemoryLocationInner,
+    #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+    borrowed_from: &'static MemoryLocationBorrowInfo,
 }
 
 #[cfg(any(debug_assertions, feature = "debug_borrows"))]
 impl Drop for GenerationalRefMutBorrowInfo {
     fn drop(&mut self) {
-        self.borrowed_from.borrowed_mut_at.take();
+        self.borrowed_from.borrowed_mut_at.write().take();
     }
 }
 
 /// Handles recycling generational boxes that have been dropped. Your application should have one store or one store per thread.
-#[derive(Clone)]
-pub struct Store {
+pub struct Store<S: 'static = UnsyncStorage> {
     bump: &'static Bump,
-    recycled: Rc<RefCell<Vec<MemoryLocation>>>,
+    recycled: Rc<RefCell<Vec<MemoryLocation<S>>>>,
+}
+
+impl<S> Clone for Store<S> {
+    fn clone(&self) -> Self {
+        Self {
+            bump: self.bump,
+            recycled: self.recycled.clone(),
+        }
+    }
 }
 
 impl Default for Store {
     fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: AnyStorage> Store<S> {
+    /// Create a new store for boxes with the storage `S`.
+    pub fn new() -> Self {
         Self {
             bump: Box::leak(Box::new(Bump::new())),
             recycled: Default::default(),
         }
     }
-}
 
-impl Store {
-    fn recycle(&self, location: MemoryLocation) {
+    fn recycle(&self, location: MemoryLocation<S>) {
         location.drop();
         self.recycled.borrow_mut().push(location);
     }
 
-    fn claim(&self) -> MemoryLocation {
+    fn claim(&self) -> MemoryLocation<S> {
         if let Some(location) = self.recycled.borrow_mut().pop() {
             location
         } else {
-            let data: &'static MemoryLocationInner = self.bump.alloc(MemoryLocationInner {
-                data: RefCell::new(None),
+            let data: &'static MemoryLocationInner<S> = self.bump.alloc(MemoryLocationInner {
+                data: S::default(),
                 #[cfg(any(debug_assertions, feature = "check_generation"))]
-                generation: Cell::new(0),
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrowed_at: Default::default(),
-                #[cfg(any(debug_assertions, feature = "debug_borrows"))]
-                borrowed_mut_at: Default::default(),
+                generation: AtomicU32::new(0),
+                borrow: Default::default(),
             });
             MemoryLocation(data)
         }
     }
 
     /// Create a new owner. The owner will be responsible for dropping all of the generational boxes that it creates.
-    pub fn owner(&self) -> Owner {
+    pub fn owner(&self) -> Owner<S> {
         Owner {
             store: self.clone(),
             owned: Default::default(),
@@ -661,19 +886,22 @@ impl Store {
 }
 
 /// Owner: Handles dropping generational boxes. The owner acts like a runtime lifetime guard. Any states that you create with an owner will be dropped when that owner is dropped.
-pub struct Owner {
-    store: Store,
-    owned: Rc<RefCell<Vec<MemoryLocation>>>,
+pub struct Owner<S: AnyStorage = UnsyncStorage> {
+    store: Store<S>,
+    owned: Rc<RefCell<Vec<MemoryLocation<S>>>>,
 }
 
-impl Owner {
+impl<S: AnyStorage> Owner<S> {
     /// Insert a value into the store. The value will be dropped when the owner is dropped.
     #[track_caller]
-    pub fn insert<T: 'static>(&self, value: T) -> GenerationalBox<T> {
+    pub fn insert<T: 'static>(&self, value: T) -> GenerationalBox<T, S>
+    where
+        S: Storage<T>,
+    {
         let mut location = self.store.claim();
         let key = location.replace_with_caller(
             value,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
             std::panic::Location::caller(),
         );
         self.owned.borrow_mut().push(location);
@@ -686,11 +914,14 @@ impl Owner {
         value: T,
         #[cfg(any(debug_assertions, feature = "debug_ownership"))]
         caller: &'static std::panic::Location<'static>,
-    ) -> GenerationalBox<T> {
+    ) -> GenerationalBox<T, S>
+    where
+        S: Storage<T>,
+    {
         let mut location = self.store.claim();
         let key = location.replace_with_caller(
             value,
-            #[cfg(any(debug_assertions, feature = "debug_borrows"))]
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
             caller,
         );
         self.owned.borrow_mut().push(location);
@@ -698,12 +929,13 @@ impl Owner {
     }
 
     /// Creates an invalid handle. This is useful for creating a handle that will be filled in later. If you use this before the value is filled in, you will get may get a panic or an out of date value.
-    pub fn invalid<T: 'static>(&self) -> GenerationalBox<T> {
+    #[track_caller]
+    pub fn invalid<T: 'static>(&self) -> GenerationalBox<T, S> {
         let location = self.store.claim();
         let key = GenerationalBox {
             raw: location,
             #[cfg(any(debug_assertions, feature = "check_generation"))]
-            generation: location.0.generation.get(),
+            generation: location.0.generation.load(Ordering::Acquire),
             #[cfg(any(debug_assertions, feature = "debug_ownership"))]
             created_at: std::panic::Location::caller(),
             _marker: PhantomData,
@@ -713,7 +945,7 @@ impl Owner {
     }
 }
 
-impl Drop for Owner {
+impl<S: AnyStorage> Drop for Owner<S> {
     fn drop(&mut self) {
         for location in self.owned.borrow().iter() {
             self.store.recycle(*location)
@@ -0,0 +1,250 @@
+use std::{
+    any::Any,
+    ops::{Deref, DerefMut},
+};
+
+use parking_lot::{
+    MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+
+use crate::{
+    AnyStorage, BorrowError, BorrowMutError, GenerationalRefBorrowInfo,
+    GenerationalRefMutBorrowInfo, MemoryLocationBorrowInfo, Storage, ValueDroppedError,
+};
+
+/// A storage of a [`crate::GenerationalBox`] that can be shared between threads. It holds values that are `Send + Sync`
+/// behind a lock, so the box can be sent to and used from any thread.
+///
+/// [`crate::GenerationalBox::read`] and [`crate::GenerationalBox::write`] wait for borrows on other threads to end.
+/// Like a [`std::cell::RefCell`], [`crate::GenerationalBox::try_read`] and [`crate::GenerationalBox::try_write`] fail
+/// instead of blocking while the value is borrowed.
+#[derive(Default)]
+pub struct SyncStorage(RwLock<Option<Box<dyn Any + Send + Sync>>>);
+
+impl AnyStorage for SyncStorage {
+    type Ref<T: 'static> = SyncRef<T>;
+    type Mut<T: 'static> = SyncRefMut<T>;
+
+    fn map<T: 'static, U: 'static>(ref_: Self::Ref<T>, f: impl FnOnce(&T) -> &U) -> Self::Ref<U> {
+        SyncRef::map(ref_, f)
+    }
+
+    fn try_map<T: 'static, U: 'static>(
+        ref_: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        SyncRef::filter_map(ref_, f)
+    }
+
+    fn map_mut<T: 'static, U: 'static>(
+        mut_ref: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> Self::Mut<U> {
+        SyncRefMut::map(mut_ref, f)
+    }
+
+    fn try_map_mut<T: 'static, U: 'static>(
+        mut_ref: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        SyncRefMut::filter_map(mut_ref, f)
+    }
+
+    fn data_ptr(&self) -> *const () {
+        self.0.data_ptr() as *const ()
+    }
+
+    fn take(&self) -> bool {
+        self.0.write().take().is_some()
+    }
+}
+
+impl<T: Send + Sync + 'static> Storage<T> for SyncStorage {
+    #[track_caller]
+    fn try_read(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Ref<T>, BorrowError> {
+        let read = self
+            .0
+            .try_read()
+            .ok_or_else(|| BorrowError::AlreadyBorrowedMut(borrow_info.borrowed_mut_error()))?;
+        map_read(
+            read,
+            borrow_info,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        )
+    }
+
+    fn try_write(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Mut<T>, BorrowMutError> {
+        let write = self
+            .0
+            .try_write()
+            .ok_or_else(|| BorrowMutError::AlreadyBorrowed(borrow_info.borrowed_error()))?;
+        map_write(
+            write,
+            borrow_info,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        )
+    }
+
+    #[track_caller]
+    fn read(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Ref<T>, BorrowError> {
+        map_read(
+            self.0.read(),
+            borrow_info,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        )
+    }
+
+    fn write(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Mut<T>, BorrowMutError> {
+        map_write(
+            self.0.write(),
+            borrow_info,
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        )
+    }
+
+    fn set(&self, value: T) {
+        *self.0.write() = Some(Box::new(value));
+    }
+}
+
+fn map_read<T: 'static>(
+    read: RwLockReadGuard<'static, Option<Box<dyn Any + Send + Sync>>>,
+    borrow_info: &'static MemoryLocationBorrowInfo,
+    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+    created_at: &'static std::panic::Location<'static>,
+) -> Result<SyncRef<T>, BorrowError> {
+    match RwLockReadGuard::try_map(read, |any| any.as_ref()?.downcast_ref::<T>()) {
+        Ok(inner) => Ok(SyncRef {
+            inner,
+            borrow: borrow_info.borrow_guard(),
+        }),
+        Err(_) => Err(BorrowError::Dropped(ValueDroppedError::new(
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        ))),
+    }
+}
+
+fn map_write<T: 'static>(
+    write: RwLockWriteGuard<'static, Option<Box<dyn Any + Send + Sync>>>,
+    borrow_info: &'static MemoryLocationBorrowInfo,
+    #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+    created_at: &'static std::panic::Location<'static>,
+) -> Result<SyncRefMut<T>, BorrowMutError> {
+    match RwLockWriteGuard::try_map(write, |any| any.as_mut()?.downcast_mut::<T>()) {
+        Ok(inner) => Ok(SyncRefMut {
+            inner,
+            borrow: borrow_info.borrow_mut_guard(),
+        }),
+        Err(_) => Err(BorrowMutError::Dropped(ValueDroppedError::new(
+            #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+            created_at,
+        ))),
+    }
+}
+
+/// A reference to a value in a generational box with [`SyncStorage`].
+pub struct SyncRef<T: 'static> {
+    inner: MappedRwLockReadGuard<'static, T>,
+    borrow: GenerationalRefBorrowInfo,
+}
+
+impl<T: 'static> SyncRef<T> {
+    /// Map one ref type to another.
+    pub fn map<U, F>(orig: SyncRef<T>, f: F) -> SyncRef<U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        SyncRef {
+            inner: MappedRwLockReadGuard::map(orig.inner, f),
+            borrow: orig.borrow,
+        }
+    }
+
+    /// Filter one ref type to another.
+    pub fn filter_map<U, F>(orig: SyncRef<T>, f: F) -> Option<SyncRef<U>>
+    where
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        let Self { inner, borrow } = orig;
+        MappedRwLockReadGuard::try_map(inner, f)
+            .ok()
+            .map(|inner| SyncRef { inner, borrow })
+    }
+}
+
+impl<T: 'static> Deref for SyncRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+/// A mutable reference to a value in a generational box with [`SyncStorage`].
+pub struct SyncRefMut<T: 'static> {
+    inner: MappedRwLockWriteGuard<'static, T>,
+    borrow: GenerationalRefMutBorrowInfo,
+}
+
+impl<T: 'static> SyncRefMut<T> {
+    /// Map one ref type to another.
+    pub fn map<U, F>(orig: SyncRefMut<T>, f: F) -> SyncRefMut<U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        SyncRefMut {
+            inner: MappedRwLockWriteGuard::map(orig.inner, f),
+            borrow: orig.borrow,
+        }
+    }
+
+    /// Filter one ref type to another.
+    pub fn filter_map<U, F>(orig: SyncRefMut<T>, f: F) -> Option<SyncRefMut<U>>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        let Self { inner, borrow } = orig;
+        MappedRwLockWriteGuard::try_map(inner, f)
+            .ok()
+            .map(|inner| SyncRefMut { inner, borrow })
+    }
+}
+
+impl<T: 'static> Deref for SyncRefMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.inner.deref()
+    }
+}
+
+impl<T: 'static> DerefMut for SyncRefMut<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.deref_mut()
+    }
+}
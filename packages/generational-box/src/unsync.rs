@@ -0,0 +1,102 @@
+use std::{
+    any::Any,
+    cell::{Ref, RefCell, RefMut},
+};
+
+use crate::{
+    AnyStorage, BorrowError, BorrowMutError, GenerationalRef, GenerationalRefMut,
+    MemoryLocationBorrowInfo, Storage, ValueDroppedError,
+};
+
+/// The default storage of a [`crate::GenerationalBox`]. It can hold any value, but only the thread that created the box can use it.
+#[derive(Default)]
+pub struct UnsyncStorage(RefCell<Option<Box<dyn Any>>>);
+
+impl AnyStorage for UnsyncStorage {
+    type Ref<T: 'static> = GenerationalRef<T>;
+    type Mut<T: 'static> = GenerationalRefMut<T>;
+
+    fn map<T: 'static, U: 'static>(ref_: Self::Ref<T>, f: impl FnOnce(&T) -> &U) -> Self::Ref<U> {
+        GenerationalRef::map(ref_, f)
+    }
+
+    fn try_map<T: 'static, U: 'static>(
+        ref_: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        GenerationalRef::filter_map(ref_, f)
+    }
+
+    fn map_mut<T: 'static, U: 'static>(
+        mut_ref: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> Self::Mut<U> {
+        GenerationalRefMut::map(mut_ref, f)
+    }
+
+    fn try_map_mut<T: 'static, U: 'static>(
+        mut_ref: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        GenerationalRefMut::filter_map(mut_ref, f)
+    }
+
+    fn data_ptr(&self) -> *const () {
+        self.0.as_ptr() as *const ()
+    }
+
+    fn take(&self) -> bool {
+        self.0.borrow_mut().take().is_some()
+    }
+}
+
+impl<T: 'static> Storage<T> for UnsyncStorage {
+    #[track_caller]
+    fn try_read(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Ref<T>, BorrowError> {
+        let borrow = self
+            .0
+            .try_borrow()
+            .map_err(|_| BorrowError::AlreadyBorrowedMut(borrow_info.borrowed_mut_error()))?;
+        match Ref::filter_map(borrow, |any| any.as_ref()?.downcast_ref::<T>()) {
+            Ok(inner) => Ok(GenerationalRef {
+                inner,
+                borrow: borrow_info.borrow_guard(),
+            }),
+            Err(_) => Err(BorrowError::Dropped(ValueDroppedError::new(
+                #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+                created_at,
+            ))),
+        }
+    }
+
+    fn try_write(
+        &'static self,
+        borrow_info: &'static MemoryLocationBorrowInfo,
+        #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+        created_at: &'static std::panic::Location<'static>,
+    ) -> Result<Self::Mut<T>, BorrowMutError> {
+        let borrow_mut = self
+            .0
+            .try_borrow_mut()
+            .map_err(|_| BorrowMutError::AlreadyBorrowed(borrow_info.borrowed_error()))?;
+        match RefMut::filter_map(borrow_mut, |any| any.as_mut()?.downcast_mut::<T>()) {
+            Ok(inner) => Ok(GenerationalRefMut {
+                inner,
+                borrow: borrow_info.borrow_mut_guard(),
+            }),
+            Err(_) => Err(BorrowMutError::Dropped(ValueDroppedError::new(
+                #[cfg(any(debug_assertions, feature = "debug_ownership"))]
+                created_at,
+            ))),
+        }
+    }
+
+    fn set(&self, value: T) {
+        *self.0.borrow_mut() = Some(Box::new(value));
+    }
+}
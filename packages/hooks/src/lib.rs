@@ -94,3 +94,6 @@ mod use_on_create;
 pub use use_on_create::*;
 mod use_root_context;
 pub use use_root_context::*;
+
+mod use_transition;
+pub use use_transition::*;
@@ -0,0 +1,56 @@
+use dioxus_core::{prelude::start_transition_in_scope, ScopeId, ScopeState};
+
+/// Render an update after user input, keeping the app responsive while it renders.
+///
+/// Updates started with [`UseTransition::start`] are rendered with the transition priority, so renderers can render them
+/// in between handling input. [`UseTransition::is_pending`] returns `true` until the transition is rendered.
+///
+/// ```rust, ignore
+/// fn app(cx: Scope) -> Element {
+///     let text = use_state(cx, String::new);
+///     let filter = use_state(cx, String::new);
+///     let transition = use_transition(cx);
+///
+///     render! {
+///         input {
+///             value: "{text}",
+///             oninput: move |evt| {
+///                 text.set(evt.value.clone());
+///                 transition.start(|| filter.set(evt.value.clone()));
+///             }
+///         }
+///         if transition.is_pending() {
+///             "Filtering..."
+///         }
+///         Table { filter: filter.get().clone() }
+///     }
+/// }
+/// ```
+pub fn use_transition(cx: &ScopeState) -> &UseTransition {
+    let pending = cx.is_transition_pending();
+    let hook = cx.use_hook(|| UseTransition {
+        scope: cx.scope_id(),
+        pending,
+    });
+    hook.pending = pending;
+    hook
+}
+
+/// A handle to start transitions, returned by [`use_transition`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UseTransition {
+    scope: ScopeId,
+    pending: bool,
+}
+
+impl UseTransition {
+    /// Run a function and render the updates it schedules as a transition
+    pub fn start<O>(&self, f: impl FnOnce() -> O) -> O {
+        start_transition_in_scope(self.scope, f)
+    }
+
+    /// Check if a transition was still rendering when the component was last rendered
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+}
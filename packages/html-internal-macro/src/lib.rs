@@ -5,7 +5,7 @@ use quote::{quote, ToTokens, TokenStreamExt};
 use syn::__private::TokenStream2;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{braced, parse_macro_input, Ident, Token};
+use syn::{braced, parse_macro_input, Ident, LitStr, Token, Type};
 
 #[proc_macro]
 pub fn impl_extension_attributes(input: TokenStream) -> TokenStream {
@@ -13,6 +13,13 @@ pub fn impl_extension_attributes(input: TokenStream) -> TokenStream {
     input.to_token_stream().into()
 }
 
+/// Create the capture phase listener of an event: `oncaptureclick` for `onclick`
+#[proc_macro]
+pub fn impl_capture_listener(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as ImplCaptureListener);
+    input.to_token_stream().into()
+}
+
 struct ImplExtensionAttributes {
     is_element: bool,
     name: Ident,
@@ -74,3 +81,64 @@ impl ToTokens for ImplExtensionAttributes {
         });
     }
 }
+
+struct ImplCaptureListener {
+    data: Type,
+    name: Ident,
+    js_name: Option<LitStr>,
+}
+
+impl Parse for ImplCaptureListener {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let data = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let name = input.parse()?;
+        let js_name = input.parse()?;
+
+        Ok(ImplCaptureListener {
+            data,
+            name,
+            js_name,
+        })
+    }
+}
+
+impl ToTokens for ImplCaptureListener {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let name_string = self.name.to_string();
+        let event = name_string.trim_start_matches("on");
+
+        // Mounted events are not dispatched through the tree, so there is nothing to capture
+        if event == "mounted" {
+            return;
+        }
+
+        let data = &self.data;
+        let capture_name = Ident::new(&format!("oncapture{event}"), self.name.span());
+        let js_name = match &self.js_name {
+            Some(js_name) => js_name.value(),
+            None => name_string.clone(),
+        };
+        let capture_js_name = format!("oncapture{}", js_name.trim_start_matches("on"));
+        let doc = format!(
+            "Execute a callback when the `{}` event is dispatched to this element or any of its children, before the \
+            listeners of the target element are called. See [`{name_string}`] for more information about the event.",
+            js_name.trim_start_matches("on")
+        );
+
+        tokens.append_all(quote! {
+            #[doc = #doc]
+            #[inline]
+            pub fn #capture_name<'a, E: crate::EventReturn<T>, T>(_cx: &'a ::dioxus_core::ScopeState, mut _f: impl FnMut(::dioxus_core::Event<#data>) -> E + 'a) -> ::dioxus_core::MountedAttribute<'a> {
+                ::dioxus_core::Attribute::new(
+                    #capture_js_name,
+                    _cx.listener(move |e: ::dioxus_core::Event<crate::PlatformEventData>| {
+                        _f(e.map(|e|e.into())).spawn(_cx);
+                    }),
+                    None,
+                    false,
+                ).into()
+            }
+        });
+    }
+}
@@ -0,0 +1,130 @@
+//! The elements and attributes defined in dioxus-html, with their documentation.
+//!
+//! Editor tooling like `dx lsp` uses these to complete element and attribute names and to show their docs.
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Every element, with the attributes only it accepts.
+pub const ELEMENTS: &[ElementDefinition] = crate::elements::ELEMENTS;
+
+/// The attributes every html element accepts.
+pub const GLOBAL_ATTRIBUTES: &[Definition] = crate::global_attributes::GLOBAL_ATTRIBUTES;
+
+/// The attributes every svg element accepts.
+pub const SVG_ATTRIBUTES: &[Definition] = crate::global_attributes::SVG_ATTRIBUTES;
+
+/// An element or attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Definition {
+    /// The name used in rsx
+    pub name: &'static str,
+    /// The attributes of the definition as they are written in the source, like `doc = r" ..."`
+    pub(crate) metas: &'static [&'static str],
+}
+
+impl Definition {
+    /// The doc comment of the definition, as markdown.
+    pub fn docs(&self) -> String {
+        let lines = self.metas.iter().filter_map(|meta| {
+            let literal = meta.strip_prefix("doc")?.trim_start().strip_prefix('=')?;
+            parse_str_literal(literal.trim())
+        });
+
+        let mut docs = String::new();
+        for line in lines {
+            // Doc comments keep the space after the slashes
+            docs.push_str(line.strip_prefix(' ').unwrap_or(&line));
+            docs.push('\n');
+        }
+        docs.trim().to_string()
+    }
+}
+
+/// An element and the attributes it accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ElementDefinition {
+    pub element: Definition,
+    pub namespace: Option<&'static str>,
+    /// The attributes that only this element accepts
+    pub attributes: &'static [Definition],
+}
+
+impl ElementDefinition {
+    /// Every attribute the element accepts, including the global or svg attributes.
+    pub fn all_attributes(&self) -> impl Iterator<Item = &'static Definition> {
+        let shared = match self.namespace {
+            Some(SVG_NAMESPACE) => SVG_ATTRIBUTES,
+            _ => GLOBAL_ATTRIBUTES,
+        };
+        self.attributes.iter().chain(shared)
+    }
+
+    /// Find an attribute the element accepts by its rsx name.
+    pub fn attribute(&self, name: &str) -> Option<&'static Definition> {
+        self.all_attributes()
+            .find(|attribute| attribute.name == name)
+    }
+}
+
+/// Find an element by its rsx name.
+pub fn element(name: &str) -> Option<&'static ElementDefinition> {
+    ELEMENTS.iter().find(|element| element.element.name == name)
+}
+
+/// Parse a string literal as `stringify!` prints it. Doc comments are usually raw strings.
+fn parse_str_literal(literal: &str) -> Option<String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let contents = raw
+            .get(hashes..raw.len().checked_sub(hashes)?)?
+            .strip_prefix('"')?
+            .strip_suffix('"')?;
+        return Some(contents.to_string());
+    }
+
+    let contents = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut unescaped = String::with_capacity(contents.len());
+    let mut chars = contents.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => unescaped.push('\n'),
+            't' => unescaped.push('\t'),
+            other => unescaped.push(other),
+        }
+    }
+    Some(unescaped)
+}
+
+#[test]
+fn docs_are_unescaped() {
+    let definition = Definition {
+        name: "test",
+        metas: &[
+            r#"doc = r" The first line""#,
+            r##"doc = r#" A "quoted" line"#"##,
+            "deprecated",
+            r#"doc = " An \"escaped\" line""#,
+        ],
+    };
+
+    assert_eq!(
+        definition.docs(),
+        "The first line\nA \"quoted\" line\nAn \"escaped\" line"
+    );
+}
+
+#[test]
+fn elements_have_attributes() {
+    let a = element("a").unwrap();
+    assert!(a.attribute("href").is_some());
+    assert!(a.attribute("class").is_some());
+    assert!(!a.element.docs().is_empty());
+
+    let circle = element("circle").unwrap();
+    assert!(circle.attribute("cx").is_some());
+    assert!(circle.attribute("download").is_none());
+}
@@ -6,7 +6,7 @@ use dioxus_html_internal_macro::impl_extension_attributes;
 #[cfg(feature = "hot-reload-context")]
 use dioxus_rsx::HotReloadingContext;
 
-#[cfg(feature = "hot-reload-context")]
+#[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
 use crate::{map_global_attributes, map_svg_attributes};
 use crate::{GlobalAttributes, SvgAttributes};
 
@@ -49,7 +49,7 @@ macro_rules! impl_attribute {
     };
 }
 
-#[cfg(feature = "hot-reload-context")]
+#[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
 macro_rules! impl_attribute_match {
     (
         $attr:ident $fil:ident: $vil:ident (DEFAULT),
@@ -189,7 +189,7 @@ macro_rules! impl_element {
     }
 }
 
-#[cfg(feature = "hot-reload-context")]
+#[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
 macro_rules! impl_element_match {
     (
         $el:ident $name:ident None {
@@ -228,7 +228,7 @@ macro_rules! impl_element_match {
     };
 }
 
-#[cfg(feature = "hot-reload-context")]
+#[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
 macro_rules! impl_element_match_attributes {
     (
         $el:ident $attr:ident $name:ident None {
@@ -267,7 +267,7 @@ macro_rules! impl_element_match_attributes {
     }
 }
 
-#[cfg(feature = "hot-reload-context")]
+#[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
 macro_rules! impl_map_global_attributes {
     (
         $el:ident $attr:ident $element:ident None
@@ -296,6 +296,21 @@ macro_rules! impl_map_global_attributes {
     };
 }
 
+#[cfg(feature = "hot-reload-context")]
+macro_rules! impl_element_namespace {
+    (None) => {
+        None
+    };
+
+    ($namespace:literal) => {
+        Some($namespace)
+    };
+
+    ([$name:literal, $namespace:tt]) => {
+        Some($namespace)
+    };
+}
+
 macro_rules! builder_constructors {
     (
         $(
@@ -314,32 +329,65 @@ macro_rules! builder_constructors {
         #[cfg(feature = "hot-reload-context")]
         impl HotReloadingContext for HtmlCtx {
             fn map_attribute(element: &str, attribute: &str) -> Option<(&'static str, Option<&'static str>)> {
-                $(
-                    impl_element_match_attributes!(
-                        element attribute $name $namespace {
-                            $(
-                                $fil: $vil $extra,
-                            )*
-                        }
-                    );
-                )*
-                None
+                map_rsx_attribute_to_html(element, attribute)
             }
 
             fn map_element(element: &str) -> Option<(&'static str, Option<&'static str>)> {
-                $(
-                    impl_element_match!(
-                        element $name $namespace {
-                            $(
-                                $fil: $vil $extra,
-                            )*
-                        }
-                    );
-                )*
-                None
+                map_rsx_element_to_html(element)
             }
         }
 
+        /// Converts an RSX attribute of an element to its HTML name and namespace
+        #[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
+        pub fn map_rsx_attribute_to_html(element: &str, attribute: &str) -> Option<(&'static str, Option<&'static str>)> {
+            $(
+                impl_element_match_attributes!(
+                    element attribute $name $namespace {
+                        $(
+                            $fil: $vil $extra,
+                        )*
+                    }
+                );
+            )*
+            None
+        }
+
+        /// Converts an RSX element to its HTML tag and namespace
+        #[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
+        pub fn map_rsx_element_to_html(element: &str) -> Option<(&'static str, Option<&'static str>)> {
+            $(
+                impl_element_match!(
+                    element $name $namespace {
+                        $(
+                            $fil: $vil $extra,
+                        )*
+                    }
+                );
+            )*
+            None
+        }
+
+        #[cfg(feature = "hot-reload-context")]
+        pub(crate) const ELEMENTS: &[crate::definitions::ElementDefinition] = &[
+            $(
+                crate::definitions::ElementDefinition {
+                    element: crate::definitions::Definition {
+                        name: stringify!($name),
+                        metas: &[$(stringify!($attr)),*],
+                    },
+                    namespace: impl_element_namespace!($namespace),
+                    attributes: &[
+                        $(
+                            crate::definitions::Definition {
+                                name: stringify!($fil),
+                                metas: &[$(stringify!($attr_method)),*],
+                            },
+                        )*
+                    ],
+                },
+            )*
+        ];
+
         #[cfg(feature = "html-to-rsx")]
         pub fn map_html_attribute_to_rsx(html: &str) -> Option<&'static str> {
             $(
@@ -361,6 +409,19 @@ macro_rules! builder_constructors {
             None
         }
 
+        #[cfg(feature = "html-to-rsx")]
+        pub fn map_html_style_to_rsx(css: &str) -> Option<&'static str> {
+            if let Some(name) = crate::map_html_global_styles_to_rsx(css) {
+                return Some(name);
+            }
+
+            if let Some(name) = crate::map_html_svg_styles_to_rsx(css) {
+                return Some(name);
+            }
+
+            None
+        }
+
         #[cfg(feature = "html-to-rsx")]
         pub fn map_html_element_to_rsx(html: &str) -> Option<&'static str> {
             $(
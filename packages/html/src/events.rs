@@ -22,6 +22,8 @@ macro_rules! impl_event {
                     false,
                 ).into()
             }
+
+            ::dioxus_html_internal_macro::impl_capture_listener!($data; $name $($js_name)?);
         )*
     };
 
@@ -6,7 +6,7 @@ use dioxus_html_internal_macro::impl_extension_attributes;
 
 use crate::AttributeDiscription;
 
-#[cfg(feature = "hot-reload-context")]
+#[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
 macro_rules! trait_method_mapping {
     (
         $matching:ident;
@@ -53,19 +53,34 @@ macro_rules! html_to_rsx_attribute_mapping {
         $(#[$attr:meta])*
         $name:ident: $lit:literal;
     ) => {
-        if $matching == stringify!($lit) {
+        if $matching == $lit {
             return Some(stringify!($name));
         }
     };
+    // Attributes in a namespace, like style properties, aren't html attributes
+    (
+        $matching:ident;
+        $(#[$attr:meta])*
+        $name:ident: $lit:literal, $ns:literal;
+    ) => {};
+}
+
+#[cfg(feature = "html-to-rsx")]
+macro_rules! html_to_rsx_style_mapping {
     (
         $matching:ident;
         $(#[$attr:meta])*
         $name:ident: $lit:literal, $ns:literal;
     ) => {
-        if $matching == stringify!($lit) {
+        if $ns == "style" && $matching == $lit {
             return Some(stringify!($name));
         }
     };
+    (
+        $matching:ident;
+        $(#[$attr:meta])*
+        $name:ident $(: $lit:literal)?;
+    ) => {};
 }
 
 macro_rules! trait_methods {
@@ -75,6 +90,8 @@ macro_rules! trait_methods {
         $trait:ident;
         $fn:ident;
         $fn_html_to_rsx:ident;
+        $fn_style_to_rsx:ident;
+        $definitions:ident;
         $(
             $(#[$attr:meta])*
             $name:ident $(: $($arg:literal),*)*;
@@ -88,7 +105,7 @@ macro_rules! trait_methods {
             )*
         }
 
-        #[cfg(feature = "hot-reload-context")]
+        #[cfg(any(feature = "hot-reload-context", feature = "html-to-rsx"))]
         pub(crate) fn $fn(attr: &str) -> Option<(&'static str, Option<&'static str>)> {
             $(
                 trait_method_mapping! {
@@ -99,6 +116,16 @@ macro_rules! trait_methods {
             None
         }
 
+        #[cfg(feature = "hot-reload-context")]
+        pub(crate) const $definitions: &[crate::definitions::Definition] = &[
+            $(
+                crate::definitions::Definition {
+                    name: stringify!($name),
+                    metas: &[$(stringify!($attr)),*],
+                },
+            )*
+        ];
+
         #[cfg(feature = "html-to-rsx")]
         #[doc = "Converts an HTML attribute to an RSX attribute"]
         pub(crate) fn $fn_html_to_rsx(html: &str) -> Option<&'static str> {
@@ -111,6 +138,19 @@ macro_rules! trait_methods {
             None
         }
 
+        #[cfg(feature = "html-to-rsx")]
+        #[doc = "Converts a CSS property to an RSX style attribute"]
+        #[allow(unused_variables)]
+        pub(crate) fn $fn_style_to_rsx(css: &str) -> Option<&'static str> {
+            $(
+                html_to_rsx_style_mapping! {
+                    css;
+                    $name$(: $($arg),*)*;
+                }
+            )*
+            None
+        }
+
         impl_extension_attributes![GLOBAL $trait { $($name,)* }];
     };
 
@@ -130,6 +170,8 @@ trait_methods! {
     GlobalAttributes;
     map_global_attributes;
     map_html_global_attributes_to_rsx;
+    map_html_global_styles_to_rsx;
+    GLOBAL_ATTRIBUTES;
 
     /// Prevent the default action for this element.
     ///
@@ -1645,6 +1687,8 @@ trait_methods! {
     SvgAttributes;
     map_svg_attributes;
     map_html_svg_attributes_to_rsx;
+    map_html_svg_styles_to_rsx;
+    SVG_ATTRIBUTES;
 
     /// Prevent the default action for this element.
     ///
@@ -0,0 +1,44 @@
+//! Islands of interactivity inside of a page that is otherwise static.
+//!
+//! When a page is pre-rendered with islands enabled, only components marked with [`use_island`] are hydrated on the
+//! client. Everything else is left as static HTML without any event listeners or templates.
+
+use dioxus_core::ScopeState;
+use serde::Serialize;
+
+/// A marker that [`use_island`] provides as context so renderers can find the islands in a page.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IslandMarker {
+    /// The name the client uses to find the component that should be hydrated for this island
+    pub name: &'static str,
+    /// The props of the component serialized as JSON
+    pub props: String,
+}
+
+/// Mark the current component as an island that should be hydrated on the client.
+///
+/// The name must match the name the component is registered with on the client, and the props are serialized into the
+/// page so the client can recreate the component.
+///
+/// ```rust, ignore
+/// #[derive(Props, PartialEq, Serialize, Deserialize)]
+/// struct CounterProps {
+///     start: i32,
+/// }
+///
+/// fn Counter(cx: Scope<CounterProps>) -> Element {
+///     use_island(cx, "counter", cx.props);
+///     let count = use_state(cx, || cx.props.start);
+///
+///     render! {
+///         button { onclick: move |_| count += 1, "{count}" }
+///     }
+/// }
+/// ```
+pub fn use_island<T: Serialize>(cx: &ScopeState, name: &'static str, props: &T) {
+    cx.use_hook(|| {
+        let props =
+            serde_json::to_string(props).expect("Failed to serialize the props of an island");
+        cx.provide_context(IslandMarker { name, props });
+    });
+}
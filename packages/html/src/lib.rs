@@ -16,11 +16,16 @@
 //!
 //! Currently, we don't validate for structures, but do validate attributes.
 
+#[cfg(feature = "hot-reload-context")]
+pub mod definitions;
 mod elements;
 #[cfg(feature = "hot-reload-context")]
 pub use elements::HtmlCtx;
 #[cfg(feature = "html-to-rsx")]
-pub use elements::{map_html_attribute_to_rsx, map_html_element_to_rsx};
+pub use elements::{
+    map_html_attribute_to_rsx, map_html_element_to_rsx, map_html_style_to_rsx,
+    map_rsx_attribute_to_html, map_rsx_element_to_html,
+};
 pub mod events;
 pub(crate) mod file_data;
 pub use file_data::*;
@@ -48,6 +53,9 @@ pub use render_template::*;
 #[cfg(feature = "eval")]
 pub mod eval;
 
+#[cfg(feature = "islands")]
+pub mod islands;
+
 pub mod extensions {
     pub use crate::elements::extensions::*;
     pub use crate::global_attributes::{GlobalAttributesExtension, SvgAttributesExtension};
@@ -59,6 +67,8 @@ pub mod prelude {
     pub use crate::eval::*;
     pub use crate::events::*;
     pub use crate::global_attributes::{GlobalAttributesExtension, SvgAttributesExtension};
+    #[cfg(feature = "islands")]
+    pub use crate::islands::use_island;
     pub use crate::point_interaction::*;
     pub use keyboard_types::{self, Code, Key, Location, Modifiers};
 }
@@ -77,3 +77,134 @@ const bool_attrs = {
 function truthy(val) {
   return val === "true" || val === true;
 }
+
+// The elements of animated lists that moved, keyed by the element with the position they had before the edits
+const movedElements = new Map();
+const enteredElements = [];
+let transitionsScheduled = false;
+
+// Start or record the transition of an element of an animated list. 0 is enter, 1 is move, and 2 is leave
+function Transition(node, kind) {
+  if (!(node instanceof Element)) {
+    return;
+  }
+  switch (kind) {
+    case 0:
+      node.classList.add("dx-enter-from", "dx-enter-active");
+      enteredElements.push(node);
+      break;
+    case 1:
+      if (!movedElements.has(node)) {
+        movedElements.set(node, node.getBoundingClientRect());
+      }
+      break;
+    case 2:
+      // The element is removed once the leave animation ends
+      node.dxLeaving = true;
+      break;
+  }
+  if (!transitionsScheduled) {
+    transitionsScheduled = true;
+    // Microtasks run after the current edits are applied, but before the browser paints
+    queueMicrotask(runTransitions);
+  }
+}
+
+function runTransitions() {
+  transitionsScheduled = false;
+  const moved = [];
+  for (const [node, first] of movedElements) {
+    if (!node.isConnected || node.dxLeaving) {
+      continue;
+    }
+    const last = node.getBoundingClientRect();
+    const dx = first.left - last.left;
+    const dy = first.top - last.top;
+    if (dx || dy) {
+      node.classList.remove("dx-move");
+      node.style.transform = `translate(${dx}px, ${dy}px)`;
+      node.style.transitionDuration = "0s";
+      moved.push(node);
+    }
+  }
+  movedElements.clear();
+  if (moved.length > 0) {
+    // Force a layout so the elements start from their old position
+    document.body.offsetHeight;
+    for (const node of moved) {
+      node.classList.add("dx-move");
+      node.style.transform = "";
+      node.style.transitionDuration = "";
+      whenTransitionEnds(node, () => node.classList.remove("dx-move"));
+    }
+  }
+  const entered = enteredElements.splice(0);
+  nextFrame(() => {
+    for (const node of entered) {
+      node.classList.remove("dx-enter-from");
+      node.classList.add("dx-enter-to");
+      whenTransitionEnds(node, () =>
+        node.classList.remove("dx-enter-active", "dx-enter-to")
+      );
+    }
+  });
+}
+
+// Remove an element that is leaving an animated list once its leave animation ends
+function LeaveElement(node) {
+  // The ids of the element are reused by the next elements, so it should no longer dispatch events
+  node.removeAttribute("data-dioxus-id");
+  for (const child of node.querySelectorAll("[data-dioxus-id]")) {
+    child.removeAttribute("data-dioxus-id");
+  }
+  node.style.pointerEvents = "none";
+  node.classList.remove("dx-enter-from", "dx-enter-active", "dx-enter-to", "dx-move");
+  node.classList.add("dx-leave-from", "dx-leave-active");
+  nextFrame(() => {
+    node.classList.remove("dx-leave-from");
+    node.classList.add("dx-leave-to");
+    whenTransitionEnds(node, () => node.remove());
+  });
+}
+
+function nextFrame(callback) {
+  requestAnimationFrame(() => requestAnimationFrame(callback));
+}
+
+// Call the callback once the transitions and animations of the element end, or right away if it has none
+function whenTransitionEnds(node, callback) {
+  const style = getComputedStyle(node);
+  const timeout = Math.max(
+    longestTime(style.transitionDuration, style.transitionDelay),
+    longestTime(style.animationDuration, style.animationDelay)
+  );
+  if (timeout === 0) {
+    callback();
+    return;
+  }
+  let done = false;
+  const end = (event) => {
+    if (done || (event && event.target !== node)) {
+      return;
+    }
+    done = true;
+    node.removeEventListener("transitionend", end);
+    node.removeEventListener("animationend", end);
+    callback();
+  };
+  node.addEventListener("transitionend", end);
+  node.addEventListener("animationend", end);
+  // Transitions of properties that don't change never fire an event
+  setTimeout(end, timeout + 50);
+}
+
+function longestTime(durations, delays) {
+  const toMs = (time) =>
+    time.endsWith("ms") ? parseFloat(time) : parseFloat(time) * 1000;
+  const durationList = durations.split(", ").map(toMs);
+  const delayList = delays.split(", ").map(toMs);
+  return Math.max(
+    0,
+    ...durationList.map((duration, i) => duration + (delayList[i % delayList.length] || 0))
+  );
+}
@@ -14,6 +14,12 @@ async function handler(event, name, bubbles, config) {
     if (target instanceof Element) {
       preventDefaultRequests = target.getAttribute(`dioxus-prevent-default`);
     }
+    // Rust listeners run after the event is dispatched, so the policy their elements registered is applied here
+    const preventDefaultRegistered = prevent_default_registered(
+      target,
+      event.type,
+      bubbles
+    );
 
     if (event.type === "click") {
       // todo call prevent default if it's the right type of event
@@ -31,7 +37,11 @@ async function handler(event, name, bubbles, config) {
             aElementShouldPreventDefault &&
             aElementShouldPreventDefault.includes(`onclick`);
 
-          if (!elementShouldPreventDefault && !linkShouldPreventDefault) {
+          if (
+            !elementShouldPreventDefault &&
+            !linkShouldPreventDefault &&
+            !preventDefaultRegistered
+          ) {
             const href = a_element.getAttribute("href");
             if (href !== "" && href !== null && href !== undefined) {
               window.ipc.postMessage(
@@ -51,8 +61,9 @@ async function handler(event, name, bubbles, config) {
     const realId = find_real_id(target);
 
     if (
-      preventDefaultRequests &&
-      preventDefaultRequests.includes(`on${event.type}`)
+      (preventDefaultRequests &&
+        preventDefaultRequests.includes(`on${event.type}`)) ||
+      preventDefaultRegistered
     ) {
       event.preventDefault();
     }
@@ -152,6 +163,24 @@ async function handler(event, name, bubbles, config) {
   }
 }
 
+// Elements register the events their listeners prevent the default action of with the `prevent_default` attribute,
+// which is created with the listeners. The attribute of the target applies, and the attributes of its parents apply if
+// the event bubbles to their listeners.
+function prevent_default_registered(target, event_type, bubbles) {
+  let node = target instanceof Element ? target : target.parentElement;
+  while (node !== null) {
+    const requests = node.getAttribute(`dioxus-prevent-default`);
+    if (requests && requests.includes(`on${event_type}`)) {
+      return true;
+    }
+    if (!bubbles) {
+      return false;
+    }
+    node = node.parentElement;
+  }
+  return false;
+}
+
 function find_real_id(target) {
   let realId = null;
   if (target instanceof Element) {
@@ -179,6 +208,8 @@ class ListenerMap {
     // non bubbling events listen at the element the listener was created at
     this.local = {};
     this.root = null;
+    // portals render outside of the root, so bubbling events also listen at their targets
+    this.portals = [];
   }
 
   create(event_name, element, bubbles, handler) {
@@ -186,7 +217,11 @@ class ListenerMap {
       if (this.global[event_name] === undefined) {
         this.global[event_name] = {};
         this.global[event_name].active = 1;
+        this.global[event_name].callback = handler;
         this.root.addEventListener(event_name, handler);
+        for (const portal of this.portals) {
+          portal.addEventListener(event_name, handler);
+        }
       } else {
         this.global[event_name].active++;
       }
@@ -205,6 +240,9 @@ class ListenerMap {
       this.global[event_name].active--;
       if (this.global[event_name].active === 0) {
         this.root.removeEventListener(event_name, this.global[event_name].callback);
+        for (const portal of this.portals) {
+          portal.removeEventListener(event_name, this.global[event_name].callback);
+        }
         delete this.global[event_name];
       }
     }
@@ -222,6 +260,28 @@ class ListenerMap {
     const id = element.getAttribute("data-dioxus-id");
     delete this.local[id];
   }
+
+  addPortal(element) {
+    if (
+      this.root.contains(element) ||
+      this.portals.some((portal) => portal.contains(element))
+    ) {
+      return;
+    }
+    this.portals.push(element);
+    for (const event_name in this.global) {
+      element.addEventListener(event_name, this.global[event_name].callback);
+    }
+  }
+}
+function PortalTarget(target) {
+  node = document.getElementById(target);
+  if (node === null) {
+    console.error(`There is no element with the id "${target}" to render the portal into`);
+    node = document.createElement("div");
+  }
+  listeners.addPortal(node);
+  return node;
 }
 function LoadChild(array) {
   // iterate through each number and get that child
@@ -20,6 +20,8 @@ mod js {
             // non bubbling events listen at the element the listener was created at
             this.local = {};
             this.root = null;
+            // portals render outside of the root, so bubbling events also listen at their targets
+            this.portals = [];
             this.handler = null;
         }
 
@@ -29,6 +31,9 @@ mod js {
                     this.global[event_name] = {};
                     this.global[event_name].active = 1;
                     this.root.addEventListener(event_name, this.handler);
+                    for (const portal of this.portals) {
+                        portal.addEventListener(event_name, this.handler);
+                    }
                 } else {
                     this.global[event_name].active++;
                 }
@@ -46,7 +51,10 @@ mod js {
             if (bubbles) {
                 this.global[event_name].active--;
                 if (this.global[event_name].active === 0) {
-                    this.root.removeEventListener(event_name, this.global[event_name].callback);
+                    this.root.removeEventListener(event_name, this.handler);
+                    for (const portal of this.portals) {
+                        portal.removeEventListener(event_name, this.handler);
+                    }
                     delete this.global[event_name];
                 }
             }
@@ -64,6 +72,25 @@ mod js {
             const id = element.getAttribute("data-dioxus-id");
             delete this.local[id];
         }
+
+        addPortal(element) {
+            if (this.root.contains(element) || this.portals.some((portal) => portal.contains(element))) {
+                return;
+            }
+            this.portals.push(element);
+            for (const event_name in this.global) {
+                element.addEventListener(event_name, this.handler);
+            }
+        }
+    }
+    function PortalTarget(target) {
+        node = document.getElementById(target);
+        if (node === null) {
+            console.error('There is no element with the id "' + target + '" to render the portal into');
+            node = document.createElement("div");
+        }
+        listeners.addPortal(node);
+        return node;
     }
     function LoadChild(ptr, len) {
         // iterate through each number and get that child
@@ -169,7 +196,7 @@ mod js {
         "{stack.pop();}"
     }
     fn replace_with(id: u32, n: u16) {
-        "{root = nodes[$id$]; els = stack.splice(stack.length-$n$); if (root.listening) { listeners.removeAllNonBubbling(root); } root.replaceWith(...els);}"
+        "{root = nodes[$id$]; els = stack.splice(stack.length-$n$); if (root.listening) { listeners.removeAllNonBubbling(root); } if (root.dxLeaving) { root.before(...els); LeaveElement(root); } else { root.replaceWith(...els); }}"
     }
     fn insert_after(id: u32, n: u16) {
         "{nodes[$id$].after(...stack.splice(stack.length-$n$));}"
@@ -178,7 +205,7 @@ mod js {
         "{nodes[$id$].before(...stack.splice(stack.length-$n$));}"
     }
     fn remove(id: u32) {
-        "{node = nodes[$id$]; if (node !== undefined) { if (node.listening) { listeners.removeAllNonBubbling(node); } node.remove(); }}"
+        "{node = nodes[$id$]; if (node !== undefined) { if (node.listening) { listeners.removeAllNonBubbling(node); } if (node.dxLeaving) { LeaveElement(node); } else { node.remove(); } }}"
     }
     fn create_raw_text(text: &str) {
         "{stack.push(document.createTextNode($text$));}"
@@ -201,6 +228,12 @@ mod js {
     fn set_attribute(id: u32, field: &str<u8, attr>, value: &str, ns: &str<u8, ns_cache>) {
         "{node = nodes[$id$]; setAttributeInner(node, $field$, $value$, $ns$);}"
     }
+    fn transition(id: u32, kind: u8) {
+        "{Transition(nodes[$id$], $kind$);}"
+    }
+    fn assign_portal_target(target: &str, id: u32) {
+        "{nodes[$id$] = PortalTarget($target$);}"
+    }
     fn remove_attribute(id: u32, field: &str<u8, attr>, ns: &str<u8, ns_cache>) {
         r#"{
             node = nodes[$id$];
@@ -285,7 +318,7 @@ pub mod binary_protocol {
             "{stack.pop();}"
         }
         fn replace_with(id: u32, n: u16) {
-            "{root = nodes[$id$]; els = stack.splice(stack.length-$n$); if (root.listening) { listeners.removeAllNonBubbling(root); } root.replaceWith(...els);}"
+            "{root = nodes[$id$]; els = stack.splice(stack.length-$n$); if (root.listening) { listeners.removeAllNonBubbling(root); } if (root.dxLeaving) { root.before(...els); LeaveElement(root); } else { root.replaceWith(...els); }}"
         }
         fn insert_after(id: u32, n: u16) {
             "{nodes[$id$].after(...stack.splice(stack.length-$n$));}"
@@ -294,7 +327,7 @@ pub mod binary_protocol {
             "{nodes[$id$].before(...stack.splice(stack.length-$n$));}"
         }
         fn remove(id: u32) {
-            "{node = nodes[$id$]; if (node !== undefined) { if (node.listening) { listeners.removeAllNonBubbling(node); } node.remove(); }}"
+            "{node = nodes[$id$]; if (node !== undefined) { if (node.listening) { listeners.removeAllNonBubbling(node); } if (node.dxLeaving) { LeaveElement(node); } else { node.remove(); } }}"
         }
         fn create_raw_text(text: &str) {
             "{stack.push(document.createTextNode($text$));}"
@@ -354,6 +387,12 @@ pub mod binary_protocol {
         fn set_top_attribute(field: &str<u8, attr>, value: &str, ns: &str<u8, ns_cache>) {
             "{setAttributeInner(stack[stack.length-1], $field$, $value$, $ns$);}"
         }
+        fn transition(id: u32, kind: u8) {
+            "{Transition(nodes[$id$], $kind$);}"
+        }
+        fn assign_portal_target(target: &str, id: u32) {
+            "{nodes[$id$] = PortalTarget($target$);}"
+        }
         fn remove_attribute(id: u32, field: &str<u8, attr>, ns: &str<u8, ns_cache>) {
             r#"{
                 node = nodes[$id$];
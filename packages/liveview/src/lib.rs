@@ -30,8 +30,10 @@ pub use adapters::*;
 mod element;
 pub mod pool;
 mod query;
+mod session;
 use futures_util::{SinkExt, StreamExt};
 pub use pool::*;
+pub use session::{SessionSnapshot, SnapshotStore};
 mod eval;
 mod events;
 
@@ -10,6 +10,17 @@ function main() {
 class IPC {
   constructor(root) {
     window.interpreter.initialize(root);
+    // The session we can resume if the connection drops
+    this.session = null;
+    // The number of edit frames we applied in the current session
+    this.editsApplied = 0;
+    // Messages we couldn't send while reconnecting
+    this.pending = [];
+    this.retries = 0;
+    this.connect();
+  }
+
+  connect() {
     const ws = new WebSocket(WS_ADDR);
     ws.binaryType = "arraybuffer";
 
@@ -17,14 +28,40 @@ class IPC {
       ws.send("__ping__");
     }
 
+    let pingInterval = null;
+
     ws.onopen = () => {
+      this.retries = 0;
       // we ping every 30 seconds to keep the websocket alive
-      setInterval(ping, 30000);
-      ws.send(window.interpreter.serializeIpcMessage("initialize"));
+      pingInterval = setInterval(ping, 30000);
+      // ask for the binary edit protocol which is decoded by the interpreter and try to resume our session if we have one
+      ws.send(
+        window.interpreter.serializeIpcMessage("initialize", {
+          edits: "binary",
+          session: this.session,
+          applied: this.editsApplied,
+        })
+      );
+      // send the events that happened while we were reconnecting
+      for (const msg of this.pending) {
+        ws.send(msg);
+      }
+      this.pending = [];
     };
 
     ws.onerror = (err) => {
-      // todo: retry the connection
+      // the close event will follow and try to reconnect
+    };
+
+    ws.onclose = () => {
+      clearInterval(pingInterval);
+      if (this.session === null) {
+        return;
+      }
+      // try to resume the session with an exponential backoff
+      const delay = Math.min(250 * 2 ** this.retries, 5000);
+      this.retries++;
+      setTimeout(() => this.connect(), delay);
     };
 
     ws.onmessage = (message) => {
@@ -35,13 +72,14 @@ class IPC {
       if (binaryFrame) {
         // binary frame
         run_from_bytes(messageData);
+        this.editsApplied++;
       }
       else {
         // text frame
 
         let decoder = new TextDecoder("utf-8");
 
-        // Using decode method to get string output 
+        // Using decode method to get string output
         let str = decoder.decode(messageData);
         // Ignore pongs
         if (str != "__pong__") {
@@ -50,6 +88,20 @@ class IPC {
             case "query":
               Function("Eval", `"use strict";${event.data};`)();
               break;
+            case "edits":
+              // edits the server sent as JSON instead of the binary protocol
+              applyJsonEdits(event.data);
+              this.editsApplied++;
+              break;
+            case "session":
+              // a new session starts counting edit frames from zero, even if it was restored from a snapshot
+              this.session = event.data;
+              this.editsApplied = 0;
+              break;
+            case "reload":
+              // the server no longer has our session
+              window.location.reload();
+              break;
           }
         }
       }
@@ -59,8 +111,206 @@ class IPC {
   }
 
   postMessage(msg) {
-    this.ws.send(msg);
+    if (this.ws.readyState === WebSocket.OPEN) {
+      this.ws.send(msg);
+    } else if (this.session !== null) {
+      // events that happen while we are reconnecting are sent once the session is resumed
+      this.pending.push(msg);
+    }
+  }
+}
+
+// The templates of JSON edits by name
+const jsonTemplates = {};
+
+// Apply the JSON representation of the mutations of a render. Each edit does the same thing as the matching
+// operation of the binary protocol.
+function applyJsonEdits(mutations) {
+  for (const template of mutations.templates) {
+    jsonTemplates[template.name] = template.roots.map(createJsonTemplateNode);
+  }
+  for (const edit of mutations.edits) {
+    applyJsonEdit(edit);
+  }
+}
+
+function createJsonTemplateNode(templateNode) {
+  switch (templateNode.type) {
+    case "Element": {
+      const element = templateNode.namespace
+        ? document.createElementNS(templateNode.namespace, templateNode.tag)
+        : document.createElement(templateNode.tag);
+      for (const attr of templateNode.attrs) {
+        if (attr.type === "Static") {
+          setAttributeInner(element, attr.name, attr.value, attr.namespace || "");
+        }
+      }
+      for (const child of templateNode.children) {
+        element.appendChild(createJsonTemplateNode(child));
+      }
+      return element;
+    }
+    case "Text":
+      return document.createTextNode(templateNode.text);
+    case "DynamicText":
+      return document.createTextNode("p");
+    case "Dynamic": {
+      const placeholder = document.createElement("pre");
+      placeholder.hidden = true;
+      return placeholder;
+    }
+  }
+}
+
+const transitionKinds = { Enter: 0, Move: 1, Leave: 2 };
+
+function applyJsonEdit(edit) {
+  switch (edit.type) {
+    case "AppendChildren":
+      AppendChildren(edit.id, edit.m);
+      break;
+    case "AssignId":
+      nodes[edit.id] = LoadChild(edit.path);
+      break;
+    case "CreatePlaceholder":
+      node = document.createElement("pre");
+      node.hidden = true;
+      stack.push(node);
+      nodes[edit.id] = node;
+      break;
+    case "CreateTextNode":
+      node = document.createTextNode(edit.value);
+      nodes[edit.id] = node;
+      stack.push(node);
+      break;
+    case "HydrateText":
+      node = LoadChild(edit.path);
+      if (node.nodeType == Node.TEXT_NODE) {
+        node.textContent = edit.value;
+      } else {
+        const text = document.createTextNode(edit.value);
+        node.replaceWith(text);
+        node = text;
+      }
+      nodes[edit.id] = node;
+      break;
+    case "LoadTemplate":
+      node = jsonTemplates[edit.name][edit.index].cloneNode(true);
+      nodes[edit.id] = node;
+      stack.push(node);
+      break;
+    case "ReplaceWith":
+      root = nodes[edit.id];
+      els = stack.splice(stack.length - edit.m);
+      if (root.listening) {
+        listeners.removeAllNonBubbling(root);
+      }
+      if (root.dxLeaving) {
+        root.before(...els);
+        LeaveElement(root);
+      } else {
+        root.replaceWith(...els);
+      }
+      break;
+    case "ReplacePlaceholder":
+      els = stack.splice(stack.length - edit.m);
+      LoadChild(edit.path).replaceWith(...els);
+      break;
+    case "InsertAfter":
+      nodes[edit.id].after(...stack.splice(stack.length - edit.m));
+      break;
+    case "InsertBefore":
+      nodes[edit.id].before(...stack.splice(stack.length - edit.m));
+      break;
+    case "SetAttribute":
+      if (edit.value === null) {
+        removeJsonAttribute(nodes[edit.id], edit.name, edit.ns || "");
+      } else {
+        setAttributeInner(nodes[edit.id], edit.name, String(edit.value), edit.ns || "");
+      }
+      break;
+    case "SetText":
+      nodes[edit.id].textContent = edit.value;
+      break;
+    case "NewEventListener": {
+      const bubbles = event_bubbles(edit.name);
+      const id = edit.id;
+      const event_name = edit.name;
+      node = nodes[id];
+      node.listening = node.listening ? node.listening + 1 : 1;
+      node.setAttribute("data-dioxus-id", `${id}`);
+      // if this is a mounted listener, we send the event immediately
+      if (event_name === "mounted") {
+        window.ipc.postMessage(
+          window.interpreter.serializeIpcMessage("user_event", {
+            name: event_name,
+            element: id,
+            data: null,
+            bubbles,
+          })
+        );
+      } else {
+        listeners.create(event_name, node, bubbles, (event) => {
+          handler(event, event_name, bubbles, config);
+        });
+      }
+      break;
+    }
+    case "RemoveEventListener":
+      node = nodes[edit.id];
+      node.listening -= 1;
+      node.removeAttribute("data-dioxus-id");
+      listeners.remove(node, edit.name, event_bubbles(edit.name));
+      break;
+    case "Remove":
+      node = nodes[edit.id];
+      if (node !== undefined) {
+        if (node.listening) {
+          listeners.removeAllNonBubbling(node);
+        }
+        if (node.dxLeaving) {
+          LeaveElement(node);
+        } else {
+          node.remove();
+        }
+      }
+      break;
+    case "PushRoot":
+      stack.push(nodes[edit.id]);
+      break;
+    case "AssignPortalTarget":
+      nodes[edit.id] = PortalTarget(edit.target);
+      break;
+    case "Transition":
+      Transition(nodes[edit.id], transitionKinds[edit.kind]);
+      break;
+  }
+}
+
+function removeJsonAttribute(node, field, ns) {
+  if (!ns) {
+    switch (field) {
+      case "value":
+        node.value = "";
+        break;
+      case "checked":
+        node.checked = false;
+        break;
+      case "selected":
+        node.selected = false;
+        break;
+      case "dangerous_inner_html":
+        node.innerHTML = "";
+        break;
+      default:
+        node.removeAttribute(field);
+        break;
+    }
+  } else if (ns == "style") {
+    node.style.removeProperty(field);
+  } else {
+    node.removeAttributeNS(ns, field);
   }
 }
 
-main();
\ No newline at end of file
+main();
@@ -3,20 +3,29 @@ use crate::{
     eval::init_eval,
     events::SerializedHtmlEventConverter,
     query::{QueryEngine, QueryResult},
+    session::{BoxedSocket, Connection, FrameLog, Resume, Session, Sessions, SnapshotStore},
     LiveViewError,
 };
-use dioxus_core::{prelude::*, BorrowedAttributeValue, Mutations};
+use dioxus_core::{prelude::*, BorrowedAttributeValue, Mutations, VirtualDomSnapshot};
 use dioxus_html::{event_bubbles, EventData, HtmlEvent, PlatformEventData};
 use dioxus_interpreter_js::binary_protocol::Channel;
-use futures_util::{pin_mut, SinkExt, StreamExt};
+use futures_util::{SinkExt, StreamExt};
 use rustc_hash::FxHashMap;
 use serde::Serialize;
-use std::{rc::Rc, time::Duration};
+use std::{rc::Rc, sync::Arc, time::Duration};
+use tokio::time::Instant;
 use tokio_util::task::LocalPoolHandle;
 
+/// A pool of threads that run the VirtualDoms of liveview clients
+///
+/// If the websocket of a client drops, the VirtualDom is kept alive for a grace period so the client can reconnect
+/// without losing any state. Set the grace period with [`LiveViewPool::grace_period`].
 #[derive(Clone)]
 pub struct LiveViewPool {
     pub(crate) pool: LocalPoolHandle,
+    sessions: Sessions,
+    grace_period: Duration,
+    snapshots: Option<Arc<dyn SnapshotStore>>,
 }
 
 impl Default for LiveViewPool {
@@ -32,9 +41,29 @@ impl LiveViewPool {
 
         LiveViewPool {
             pool: LocalPoolHandle::new(16),
+            sessions: Default::default(),
+            grace_period: Duration::from_secs(30),
+            snapshots: None,
         }
     }
 
+    /// Set how long the VirtualDom of a client that lost its connection is kept alive. Defaults to 30 seconds.
+    ///
+    /// A grace period of zero drops the VirtualDom as soon as the connection is lost.
+    pub fn grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Save a snapshot of every session to a store after each render. Clients that try to resume a session this pool
+    /// doesn't know about, for example after the server restarted, are restored from the snapshot instead of reloading.
+    ///
+    /// Sessions are only kept while the grace period is not zero.
+    pub fn snapshots(mut self, store: impl SnapshotStore) -> Self {
+        self.snapshots = Some(Arc::new(store));
+        self
+    }
+
     pub async fn launch(
         &self,
         ws: impl LiveViewSocket,
@@ -58,7 +87,56 @@ impl LiveViewPool {
         ws: impl LiveViewSocket,
         make_app: F,
     ) -> Result<(), LiveViewError> {
-        match self.pool.spawn_pinned(move || run(make_app(), ws)).await {
+        let mut ws: BoxedSocket = Box::pin(ws);
+        let Some(params) = read_initialize(&mut ws).await else {
+            return Ok(());
+        };
+
+        // Hand the connection over to the session the client is trying to resume
+        let mut restore = None;
+        if let Some(id) = &params.session {
+            let session = self.sessions.lock().unwrap().get(id).cloned();
+            if let Some(session) = session {
+                let resume = Resume {
+                    ws,
+                    applied: params.applied,
+                };
+                match session.send(resume) {
+                    Ok(()) => return Ok(()),
+                    Err(err) => ws = err.0.ws,
+                }
+            }
+
+            // The session isn't running in this pool, but it may have been saved by another one. The snapshot is only
+            // the page of the client if it applied the same frames
+            restore = self
+                .snapshots
+                .as_ref()
+                .and_then(|store| store.take(id))
+                .and_then(|snapshot| snapshot.restore(params.applied));
+            if restore.is_none() {
+                // The session expired or the client is out of sync with the snapshot, so the client has to start over
+                ws.send(text_frame(
+                    &serde_json::to_string(&ClientUpdate::Reload).unwrap(),
+                ))
+                .await?;
+                return Ok(());
+            }
+        }
+
+        let session = (!self.grace_period.is_zero()).then(|| {
+            Session::new(
+                self.sessions.clone(),
+                self.grace_period,
+                self.snapshots.clone(),
+            )
+        });
+
+        match self
+            .pool
+            .spawn_pinned(move || run_session(make_app(), ws, params.encoding(), session, restore))
+            .await
+        {
             Ok(Ok(_)) => Ok(()),
             Ok(Err(e)) => Err(e),
             Err(_) => Err(LiveViewError::SendingFailed),
@@ -116,7 +194,48 @@ impl<S> LiveViewSocket for S where
 /// As long as your framework can provide a Sink and Stream of Bytes, you can use this function.
 ///
 /// You might need to transform the error types of the web backend into the LiveView error type.
-pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
+///
+/// The VirtualDom is dropped as soon as the connection is lost. Use [`LiveViewPool`] to let clients resume their session.
+pub async fn run(vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
+    let mut ws: BoxedSocket = Box::pin(ws);
+    match read_initialize(&mut ws).await {
+        Some(params) => run_session(vdom, ws, params.encoding(), None, None).await,
+        None => Ok(()),
+    }
+}
+
+// desktop uses this wrapper struct thing around the actual event itself
+// this is sorta driven by tao/wry
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "method", content = "params")]
+enum IpcMessage {
+    #[serde(rename = "initialize")]
+    Initialize(InitializeParams),
+    #[serde(rename = "user_event")]
+    Event(HtmlEvent),
+    #[serde(rename = "query")]
+    Query(QueryResult),
+}
+
+/// Wait for the `initialize` message the client sends when it connects. Returns [`None`] if the socket closed first
+async fn read_initialize(ws: &mut BoxedSocket) -> Option<InitializeParams> {
+    let params = match ws.next().await? {
+        Ok(msg) => match serde_json::from_slice::<IpcMessage>(&msg) {
+            Ok(IpcMessage::Initialize(params)) => params,
+            _ => InitializeParams::default(),
+        },
+        Err(_) => InitializeParams::default(),
+    };
+    Some(params)
+}
+
+async fn run_session(
+    mut vdom: VirtualDom,
+    ws: BoxedSocket,
+    encoding: EditEncoding,
+    mut session: Option<Session>,
+    restore: Option<VirtualDomSnapshot>,
+) -> Result<(), LiveViewError> {
     #[cfg(all(feature = "hot-reload", debug_assertions))]
     let mut hot_reload_rx = {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -126,8 +245,12 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
         rx
     };
 
-    let mut templates: FxHashMap<String, u16> = Default::default();
-    let mut max_template_count = 0;
+    // Serve the component tree of the session to `dx devtools`
+    #[cfg(all(feature = "inspector", debug_assertions))]
+    let devtools = dioxus_devtools::Devtools::new(match &session {
+        Some(session) => format!("liveview session {}", session.id),
+        None => "liveview".to_string(),
+    });
 
     // Create the a proxy for query engine
     let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
@@ -135,35 +258,48 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
     vdom.base_scope().provide_context(query_engine.clone());
     init_eval(vdom.base_scope());
 
-    // pin the futures so we can use select!
-    pin_mut!(ws);
-
-    let mut edit_channel = Channel::default();
-    if let Some(edits) = {
-        let mutations = vdom.rebuild();
-        apply_edits(
-            mutations,
-            &mut edit_channel,
-            &mut templates,
-            &mut max_template_count,
-        )
-    } {
-        // send the initial render to the client
-        ws.send(edits).await?;
+    let mut ws = Connection::new(ws);
+    let mut frames = FrameLog::default();
+    let mut encoder = EditEncoder::new(encoding);
+
+    if let Some(session) = &session {
+        // Tell the client which session to resume if it loses the connection
+        let update = ClientUpdate::Session(session.id.clone());
+        ws.send(text_frame(&serde_json::to_string(&update).unwrap()))
+            .await;
     }
 
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize, Debug)]
-    #[serde(tag = "method", content = "params")]
-    enum IpcMessage {
-        #[serde(rename = "user_event")]
-        Event(HtmlEvent),
-        #[serde(rename = "query")]
-        Query(QueryResult),
+    // A restored client is still showing the tree of the snapshot, so only the differences are sent
+    let mutations = match &restore {
+        Some(snapshot) => vdom.rebuild_from_snapshot(snapshot),
+        None => vdom.rebuild(),
+    };
+    if let Some(edits) = encoder.encode(mutations) {
+        // send the initial render to the client
+        if session.is_some() {
+            frames.push(edits.clone(), ws.is_connected());
+        }
+        ws.send(edits).await;
+    }
+    if let Some(session) = &session {
+        session.save_snapshot(&vdom, &frames);
     }
+    #[cfg(all(feature = "inspector", debug_assertions))]
+    devtools.update(&vdom);
+
+    // The session is dropped if the client doesn't reconnect before this deadline
+    let mut deadline = None;
 
     loop {
+        if !ws.is_connected() {
+            match &session {
+                Some(session) => {
+                    deadline.get_or_insert_with(|| Instant::now() + session.grace_period);
+                }
+                None => return ws.error.take().map_or(Ok(()), Err),
+            }
+        }
+
         #[cfg(all(feature = "hot-reload", debug_assertions))]
         let hot_reload_wait = hot_reload_rx.recv();
         #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
@@ -177,13 +313,14 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
                 match evt.as_ref().map(|o| o.as_deref()) {
                     // respond with a pong every ping to keep the websocket alive
                     Some(Ok(b"__ping__")) => {
-                        ws.send(text_frame("__pong__")).await?;
+                        ws.send(text_frame("__pong__")).await;
                     }
                     Some(Ok(evt)) => {
                         if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
                             match message {
                                 IpcMessage::Event(evt) => {
                                     // Intercept the mounted event and insert a custom element type
+                                    // The browser already dispatched this event, so it was prevented by the `prevent_default` attribute if at all
                                     if let EventData::Mounted = &evt.data {
                                         let element = LiveviewElement::new(evt.element, query_engine.clone());
                                         vdom.handle_event(
@@ -204,18 +341,45 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
                                 IpcMessage::Query(result) => {
                                     query_engine.send(result);
                                 },
+                                IpcMessage::Initialize(_) => {}
                             }
                         }
                     }
                     // log this I guess? when would we get an error here?
                     Some(Err(_e)) => {}
-                    None => return Ok(()),
+                    None => ws.disconnect(),
+                }
+            }
+
+            // a client reconnected to this session
+            Some(resume) = Session::resumed(&mut session) => {
+                let mut new_ws = Connection::new(resume.ws);
+                match frames.missed(resume.applied) {
+                    Some(missed) => {
+                        for frame in missed {
+                            new_ws.send(frame).await;
+                        }
+                        ws = new_ws;
+                        deadline = None;
+                    }
+                    // The client missed edits that are no longer in the log, so it has to start over
+                    None => {
+                        new_ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Reload).unwrap())).await;
+                    }
                 }
             }
 
+            // the client didn't reconnect in time
+            _ = expired(deadline) => {
+                if let Some(session) = &session {
+                    session.expire();
+                }
+                return Ok(());
+            },
+
             // handle any new queries
             Some(query) = query_rx.recv() => {
-                ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Query(query)).unwrap())).await?;
+                ws.send(text_frame(&serde_json::to_string(&ClientUpdate::Query(query)).unwrap())).await;
             }
 
             Some(msg) = hot_reload_wait => {
@@ -237,15 +401,98 @@ pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), Li
             .render_with_deadline(tokio::time::sleep(Duration::from_millis(10)))
             .await;
 
-        if let Some(edits) = {
-            apply_edits(
-                edits,
-                &mut edit_channel,
-                &mut templates,
-                &mut max_template_count,
-            )
-        } {
-            ws.send(edits).await?;
+        if let Some(edits) = encoder.encode(edits) {
+            if let Some(session) = &session {
+                frames.push(edits.clone(), ws.is_connected());
+                session.save_snapshot(&vdom, &frames);
+            }
+            ws.send(edits).await;
+        }
+        #[cfg(all(feature = "inspector", debug_assertions))]
+        devtools.update(&vdom);
+    }
+}
+
+/// Wait until the deadline passes. This never resolves without a deadline
+async fn expired(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// How the edits are encoded before they are sent to the client
+///
+/// The client picks the encoding in the `initialize` message it sends when it connects. Clients that don't ask for an
+/// encoding get JSON, which every version of the client glue can apply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EditEncoding {
+    /// The compact binary protocol the interpreter glue decodes
+    Binary,
+
+    /// The serde representation of [`Mutations`] as JSON text frames, for clients that can't decode the binary protocol
+    Json,
+}
+
+#[derive(serde::Deserialize, Debug, Default)]
+#[serde(default)]
+struct InitializeParams {
+    /// The encoding the client asked for
+    edits: Option<EditEncoding>,
+
+    /// The session the client wants to resume
+    session: Option<String>,
+
+    /// The number of edit frames the client applied in the session it wants to resume
+    applied: u64,
+}
+
+impl InitializeParams {
+    /// The encoding the edits of this connection are sent in
+    fn encoding(&self) -> EditEncoding {
+        self.edits.unwrap_or_else(|| {
+            tracing::warn!(
+                "The liveview client did not ask for an edit encoding, sending JSON edits"
+            );
+            EditEncoding::Json
+        })
+    }
+}
+
+/// Encodes the edits of one connection
+struct EditEncoder {
+    encoding: EditEncoding,
+    channel: Channel,
+    templates: FxHashMap<String, u16>,
+    max_template_count: u16,
+}
+
+impl EditEncoder {
+    fn new(encoding: EditEncoding) -> Self {
+        Self {
+            encoding,
+            channel: Channel::default(),
+            templates: Default::default(),
+            max_template_count: 0,
+        }
+    }
+
+    fn encode(&mut self, mutations: Mutations) -> Option<Vec<u8>> {
+        match self.encoding {
+            EditEncoding::Binary => apply_edits(
+                mutations,
+                &mut self.channel,
+                &mut self.templates,
+                &mut self.max_template_count,
+            ),
+            EditEncoding::Json => {
+                if mutations.templates.is_empty() && mutations.edits.is_empty() {
+                    return None;
+                }
+                let update = ClientUpdate::Edits(mutations);
+                Some(text_frame(&serde_json::to_string(&update).unwrap()))
+            }
         }
     }
 }
@@ -374,6 +621,8 @@ fn apply_edits(
             }
             Remove { id } => channel.remove(id.0 as u32),
             PushRoot { id } => channel.push_root(id.0 as u32),
+            Transition { id, kind } => channel.transition(id.0 as u32, kind as u8),
+            AssignPortalTarget { target, id } => channel.assign_portal_target(&target, id.0 as u32),
         }
     }
 
@@ -386,7 +635,188 @@ fn apply_edits(
 
 #[derive(Serialize)]
 #[serde(tag = "type", content = "data")]
-enum ClientUpdate {
+enum ClientUpdate<'a> {
     #[serde(rename = "query")]
     Query(String),
+    #[serde(rename = "edits")]
+    Edits(Mutations<'a>),
+    #[serde(rename = "session")]
+    Session(String),
+    #[serde(rename = "reload")]
+    Reload,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::prelude::render;
+    use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use futures_util::{Sink, Stream};
+    use std::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    /// The server side of an in memory websocket
+    struct TestSocket {
+        rx: UnboundedReceiver<Result<Vec<u8>, LiveViewError>>,
+        tx: UnboundedSender<Vec<u8>>,
+    }
+
+    impl Stream for TestSocket {
+        type Item = Result<Vec<u8>, LiveViewError>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.rx.poll_next_unpin(cx)
+        }
+    }
+
+    impl Sink<Vec<u8>> for TestSocket {
+        type Error = LiveViewError;
+
+        fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, frame: Vec<u8>) -> Result<(), Self::Error> {
+            self.tx
+                .unbounded_send(frame)
+                .map_err(|_| LiveViewError::SendingFailed)
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn app(cx: Scope) -> Element {
+        let count = 1;
+        render! { div { "count: {count}" } }
+    }
+
+    /// Connect a client that sends `initialize` and return the first frame the server sends back
+    async fn first_frame(initialize: &str) -> Vec<u8> {
+        let (client_tx, rx) = unbounded();
+        let (tx, mut client_rx) = unbounded();
+        client_tx
+            .unbounded_send(Ok(initialize.as_bytes().to_vec()))
+            .unwrap();
+
+        let local = tokio::task::LocalSet::new();
+        local
+            .run_until(async move {
+                tokio::task::spawn_local(run(VirtualDom::new(app), TestSocket { rx, tx }));
+                let frame = client_rx.next().await.unwrap();
+                drop(client_tx);
+                frame
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn json_edits_round_trip() {
+        let frame = first_frame(r#"{"method":"initialize","params":{"edits":"json"}}"#).await;
+
+        // Text frames start with a zero
+        assert_eq!(frame[0], 0);
+        let update: serde_json::Value = serde_json::from_slice(&frame[1..]).unwrap();
+        assert_eq!(update["type"], "edits");
+
+        let mut dom = VirtualDom::new(app);
+        let expected = serde_json::to_value(dom.rebuild()).unwrap();
+        assert_eq!(update["data"], expected);
+    }
+
+    #[tokio::test]
+    async fn binary_edits_round_trip() {
+        let frame = first_frame(r#"{"method":"initialize","params":{"edits":"binary"}}"#).await;
+
+        let mut dom = VirtualDom::new(app);
+        let expected = EditEncoder::new(EditEncoding::Binary)
+            .encode(dom.rebuild())
+            .unwrap();
+        // Binary frames start with a one
+        assert_eq!(frame[0], 1);
+        assert_eq!(frame, expected);
+    }
+
+    /// Connect a client to a pool and send `initialize`. Returns the frames the client receives and the sender that keeps
+    /// the connection open
+    fn connect(
+        pool: &LiveViewPool,
+        initialize: serde_json::Value,
+    ) -> (
+        UnboundedSender<Result<Vec<u8>, LiveViewError>>,
+        UnboundedReceiver<Vec<u8>>,
+    ) {
+        let (client_tx, rx) = unbounded();
+        let (tx, client_rx) = unbounded();
+        client_tx
+            .unbounded_send(Ok(initialize.to_string().into_bytes()))
+            .unwrap();
+
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.launch(TestSocket { rx, tx }, app).await });
+
+        (client_tx, client_rx)
+    }
+
+    fn text(frame: &[u8]) -> serde_json::Value {
+        assert_eq!(frame[0], 0);
+        serde_json::from_slice(&frame[1..]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn resumed_sessions_replay_missed_frames() {
+        let pool = LiveViewPool::new();
+
+        let (client_tx, mut client_rx) = connect(
+            &pool,
+            serde_json::json!({ "method": "initialize", "params": { "edits": "json" } }),
+        );
+        let session = text(&client_rx.next().await.unwrap());
+        assert_eq!(session["type"], "session");
+        let edits = client_rx.next().await.unwrap();
+        assert_eq!(text(&edits)["type"], "edits");
+
+        // The connection drops before the client applies the edits
+        drop(client_tx);
+        drop(client_rx);
+
+        let (_client_tx, mut client_rx) = connect(
+            &pool,
+            serde_json::json!({
+                "method": "initialize",
+                "params": { "edits": "json", "session": session["data"], "applied": 0 }
+            }),
+        );
+        assert_eq!(client_rx.next().await.unwrap(), edits);
+    }
+
+    #[tokio::test]
+    async fn unknown_sessions_reload() {
+        let pool = LiveViewPool::new();
+
+        let (_client_tx, mut client_rx) = connect(
+            &pool,
+            serde_json::json!({
+                "method": "initialize",
+                "params": { "edits": "json", "session": "missing", "applied": 3 }
+            }),
+        );
+        assert_eq!(text(&client_rx.next().await.unwrap())["type"], "reload");
+    }
+
+    #[tokio::test]
+    async fn clients_without_an_encoding_get_json() {
+        let frame = first_frame(r#"{"method":"initialize","params":{}}"#).await;
+
+        assert_eq!(frame[0], 0);
+        let update: serde_json::Value = serde_json::from_slice(&frame[1..]).unwrap();
+        assert_eq!(update["type"], "edits");
+    }
 }
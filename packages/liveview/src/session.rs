@@ -0,0 +1,285 @@
+//! Sessions that keep the VirtualDom of a client alive while it reconnects.
+//!
+//! When a session is created, the client is sent a session id. If the websocket drops, the VirtualDom keeps running for
+//! a grace period. A client that reconnects in time sends the session id and the number of edit frames it applied
+//! in its `initialize` message, and the session replays the frames the client missed over the new socket.
+//!
+//! If a [`SnapshotStore`] is configured, sessions also save a snapshot of their VirtualDom after every render. A client
+//! that tries to resume a session this process doesn't know about (because the server restarted, or the client
+//! reconnected to another worker) gets a new session that is rebuilt from the snapshot. The snapshot only matches the
+//! page of the client if the client applied every frame that was sent before the snapshot was saved, otherwise the
+//! client reloads the page.
+
+use crate::LiveViewError;
+use dioxus_core::{VirtualDom, VirtualDomSnapshot};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+/// The number of edit frames kept around while the client is connected, in case the last frames were lost in flight
+const MAX_REPLAY_FRAMES: usize = 64;
+
+/// A [`crate::LiveViewSocket`] with its type erased so it can be handed over to a session that is resumed
+pub(crate) trait DynSocket:
+    Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+impl<S> DynSocket for S where
+    S: Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+pub(crate) type BoxedSocket = Pin<Box<dyn DynSocket>>;
+
+pub(crate) type Sessions = Arc<Mutex<FxHashMap<String, UnboundedSender<Resume>>>>;
+
+/// Stores snapshots of the VirtualDoms of liveview sessions so they can be restored after the server restarts, or by
+/// another process
+///
+/// Component state is not part of a snapshot. A restored session runs every component again and only keeps the
+/// elements the client is already showing. See [`VirtualDom::rebuild_from_snapshot`].
+pub trait SnapshotStore: Send + Sync + 'static {
+    /// Save the latest snapshot of a session
+    fn save(&self, session: &str, snapshot: SessionSnapshot);
+
+    /// Take the snapshot of a session out of the store to restore it
+    fn take(&self, session: &str) -> Option<SessionSnapshot>;
+
+    /// Remove the snapshot of a session the client didn't resume in time
+    fn remove(&self, session: &str);
+}
+
+/// A snapshot of a liveview session saved to a [`SnapshotStore`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    /// The number of edit frames that were sent to the client when the snapshot was saved
+    pub frames: u64,
+
+    /// The tree the client shows after it applied those frames
+    pub dom: VirtualDomSnapshot,
+}
+
+impl SessionSnapshot {
+    /// Get the tree the client is showing if it applied `applied` frames. A client that missed frames or applied frames
+    /// that were sent after the snapshot was saved shows a different tree, so it can't be restored.
+    pub(crate) fn restore(self, applied: u64) -> Option<VirtualDomSnapshot> {
+        (self.frames == applied).then_some(self.dom)
+    }
+}
+
+/// A client that reconnected to a session
+pub(crate) struct Resume {
+    pub(crate) ws: BoxedSocket,
+
+    /// The number of edit frames the client applied before it lost the connection
+    pub(crate) applied: u64,
+}
+
+/// A session that can be resumed by a client that lost its connection
+pub(crate) struct Session {
+    pub(crate) id: String,
+    pub(crate) grace_period: Duration,
+    resume: UnboundedReceiver<Resume>,
+    sessions: Sessions,
+    snapshots: Option<Arc<dyn SnapshotStore>>,
+}
+
+impl Session {
+    /// Create a session with a random id and register it so clients can resume it
+    pub(crate) fn new(
+        sessions: Sessions,
+        grace_period: Duration,
+        snapshots: Option<Arc<dyn SnapshotStore>>,
+    ) -> Self {
+        let id = format!("{:032x}", rand::random::<u128>());
+        let (tx, resume) = tokio::sync::mpsc::unbounded_channel();
+        sessions.lock().unwrap().insert(id.clone(), tx);
+
+        Self {
+            id,
+            grace_period,
+            resume,
+            sessions,
+            snapshots,
+        }
+    }
+
+    /// Save a snapshot of the VirtualDom of this session if snapshots are enabled
+    pub(crate) fn save_snapshot(&self, vdom: &VirtualDom, frames: &FrameLog) {
+        if let Some(snapshots) = &self.snapshots {
+            let snapshot = SessionSnapshot {
+                frames: frames.sent(),
+                dom: vdom.snapshot(),
+            };
+            snapshots.save(&self.id, snapshot);
+        }
+    }
+
+    /// The client didn't resume the session in time, so it can't be restored anymore
+    pub(crate) fn expire(&self) {
+        if let Some(snapshots) = &self.snapshots {
+            snapshots.remove(&self.id);
+        }
+    }
+
+    /// Wait for a client to resume this session
+    pub(crate) async fn resumed(session: &mut Option<Session>) -> Option<Resume> {
+        match session {
+            Some(session) => session.resume.recv().await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.sessions.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// The socket of the client that is currently connected
+pub(crate) struct Connection {
+    socket: Option<BoxedSocket>,
+
+    /// The error that disconnected the socket, if sending a frame failed
+    pub(crate) error: Option<LiveViewError>,
+}
+
+impl Connection {
+    pub(crate) fn new(socket: BoxedSocket) -> Self {
+        Self {
+            socket: Some(socket),
+            error: None,
+        }
+    }
+
+    pub(crate) fn is_connected(&self) -> bool {
+        self.socket.is_some()
+    }
+
+    /// Wait for the next message from the client. This never resolves while disconnected
+    pub(crate) async fn next(&mut self) -> Option<Result<Vec<u8>, LiveViewError>> {
+        match &mut self.socket {
+            Some(socket) => socket.next().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Send a frame to the client. Frames sent while disconnected are dropped
+    pub(crate) async fn send(&mut self, frame: Vec<u8>) {
+        if let Some(socket) = &mut self.socket {
+            if let Err(err) = socket.send(frame).await {
+                self.socket = None;
+                self.error = Some(err);
+            }
+        }
+    }
+
+    pub(crate) fn disconnect(&mut self) {
+        self.socket = None;
+    }
+}
+
+/// The edit frames that were sent to the client, kept so they can be replayed when the client reconnects
+#[derive(Default)]
+pub(crate) struct FrameLog {
+    frames: VecDeque<Vec<u8>>,
+
+    /// The number of frames that were dropped from the front of the log
+    first: u64,
+}
+
+impl FrameLog {
+    /// Add a frame to the log. All frames sent while disconnected are kept until the client reconnects
+    pub(crate) fn push(&mut self, frame: Vec<u8>, connected: bool) {
+        self.frames.push_back(frame);
+        if connected {
+            while self.frames.len() > MAX_REPLAY_FRAMES {
+                self.frames.pop_front();
+                self.first += 1;
+            }
+        }
+    }
+
+    /// The number of frames that were sent to the client in this session
+    pub(crate) fn sent(&self) -> u64 {
+        self.first + self.frames.len() as u64
+    }
+
+    /// Get the frames a client that applied `applied` frames missed, if they are still in the log
+    pub(crate) fn missed(&self, applied: u64) -> Option<Vec<Vec<u8>>> {
+        if applied < self.first || applied > self.sent() {
+            return None;
+        }
+
+        Some(
+            self.frames
+                .iter()
+                .skip((applied - self.first) as usize)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_missed_frames() {
+        let mut log = FrameLog::default();
+        for frame in 0..3 {
+            log.push(vec![frame], true);
+        }
+
+        assert_eq!(log.missed(0), Some(vec![vec![0], vec![1], vec![2]]));
+        assert_eq!(log.missed(1), Some(vec![vec![1], vec![2]]));
+        assert_eq!(log.missed(3), Some(vec![]));
+        // The client can't have applied frames that were never sent
+        assert_eq!(log.missed(4), None);
+    }
+
+    #[test]
+    fn keeps_every_frame_while_disconnected() {
+        let mut log = FrameLog::default();
+        for frame in 0..MAX_REPLAY_FRAMES + 1 {
+            log.push(vec![frame as u8], true);
+        }
+        // The oldest frame was dropped while the client was connected
+        assert_eq!(log.missed(0), None);
+
+        for frame in 0..MAX_REPLAY_FRAMES {
+            log.push(vec![frame as u8], false);
+        }
+        assert_eq!(log.missed(1).unwrap().len(), MAX_REPLAY_FRAMES * 2);
+    }
+
+    #[test]
+    fn snapshots_only_restore_clients_that_applied_every_frame() {
+        let mut dom = VirtualDom::new(|cx| dioxus::prelude::render! { div {} });
+        _ = dom.rebuild();
+        let mut log = FrameLog::default();
+        for frame in 0..3 {
+            log.push(vec![frame], true);
+        }
+        let snapshot = SessionSnapshot {
+            frames: log.sent(),
+            dom: dom.snapshot(),
+        };
+
+        assert_eq!(snapshot.clone().restore(3), Some(dom.snapshot()));
+        // The client lost frames that were sent before the snapshot
+        assert_eq!(snapshot.clone().restore(2), None);
+        // The client applied frames that were sent after the snapshot
+        assert_eq!(snapshot.restore(4), None);
+    }
+}
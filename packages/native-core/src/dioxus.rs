@@ -23,6 +23,7 @@ pub struct DioxusState {
     templates: FxHashMap<String, Vec<NodeId>>,
     stack: Vec<NodeId>,
     node_id_mapping: Vec<Option<NodeId>>,
+    portal_targets: FxHashSet<NodeId>,
 }
 
 impl DioxusState {
@@ -35,6 +36,7 @@ impl DioxusState {
             templates: FxHashMap::default(),
             stack: vec![root_id],
             node_id_mapping: vec![Some(root_id)],
+            portal_targets: FxHashSet::default(),
         }
     }
 
@@ -57,8 +59,9 @@ impl DioxusState {
         node.insert(ElementIdComponent(element_id));
         if self.node_id_mapping.len() <= element_id.0 {
             self.node_id_mapping.resize(element_id.0 + 1, None);
-        } else if let Some(mut node) =
-            self.node_id_mapping[element_id.0].and_then(|id| node.real_dom_mut().get_mut(id))
+        } else if let Some(mut node) = self.node_id_mapping[element_id.0]
+            .filter(|id| !self.portal_targets.contains(id))
+            .and_then(|id| node.real_dom_mut().get_mut(id))
         {
             node.remove();
         }
@@ -230,11 +233,49 @@ impl DioxusState {
                     let node_id = self.element_to_node_id(id);
                     self.stack.push(node_id);
                 }
+                // The real dom doesn't animate lists
+                Transition { .. } => {}
+                AssignPortalTarget { target, id } => {
+                    // If the target doesn't exist, the children of the portal are rendered into a node outside of the tree
+                    let node_id = find_portal_target(rdom, &target)
+                        .unwrap_or_else(|| rdom.create_node(NodeType::Placeholder).id());
+                    // The target is not owned by the portal, so it is kept when the id is reused
+                    self.portal_targets.insert(node_id);
+                    if self.node_id_mapping.len() <= id.0 {
+                        self.node_id_mapping.resize(id.0 + 1, None);
+                    }
+                    self.node_id_mapping[id.0] = Some(node_id);
+                }
             }
         }
     }
 }
 
+/// Find the element with the given id attribute
+fn find_portal_target<V: FromAnyValue + Send + Sync>(
+    rdom: &RealDom<V>,
+    target: &str,
+) -> Option<NodeId> {
+    let mut found = None;
+    rdom.traverse_depth_first(|node| {
+        if found.is_some() {
+            return;
+        }
+        if let NodeType::Element(element) = &*node.node_type() {
+            let id = element.attributes.get(&OwnedAttributeDiscription {
+                name: "id".to_string(),
+                namespace: None,
+            });
+            if let Some(OwnedAttributeValue::Text(id)) = id {
+                if id == target {
+                    found = Some(node.id());
+                }
+            }
+        }
+    });
+    found
+}
+
 fn create_template_node<V: FromAnyValue + Send + Sync>(
     rdom: &mut RealDom<V>,
     node: &TemplateNode,
@@ -17,6 +17,7 @@ use proc_macro2::TokenStream as TokenStream2;
 
 use crate::{layout::LayoutId, route_tree::RouteTree};
 
+mod hash;
 mod layout;
 mod nest;
 mod query;
@@ -37,6 +38,7 @@ mod segment;
 /// 2. Dynamic Segments: "/:dynamic" (where dynamic has a type that is FromStr in all child Variants)
 /// 3. Catch all Segments: "/:..segments" (where segments has a type that is FromSegments in all child Variants)
 /// 4. Query Segments: "/?:..query" (where query has a type that is FromQuery in all child Variants) or "/?:query&:other_query" (where query and other_query has a type that is FromQueryArgument in all child Variants)
+/// 5. Hash Fragments: "/#:section" (where section has a type that is FromHashFragment in all child Variants)
 ///
 /// Routes are matched:
 /// 1. By there specificity this order: Query Routes ("/?:query"), Static Routes ("/route"), Dynamic Routes ("/:route"), Catch All Routes ("/:..route")
@@ -197,9 +199,57 @@ mod segment;
 ///     Home {},
 /// }
 /// ```
+///
+/// # `#[static_routes(routes)]`
+///
+/// The `#[static_routes]` attribute lists the values of a route with dynamic segments that should be rendered ahead of time. It takes 1 parameter:
+/// - `routes`: An expression that evaluates to an iterator of routes
+///
+/// Routes without dynamic segments are always included in `Routable::static_routes`. Static site generation renders every route returned by that method.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     // Render /post/0, /post/1 and /post/2 ahead of time
+///     #[route("/post/:id")]
+///     #[static_routes((0..3).map(|id| Route::Post { id }))]
+///     Post { id: usize },
+/// }
+/// ```
+///
+/// # `#[loader(future)]`
+///
+/// The `#[loader]` attribute declares the data a route needs. It takes 1 parameter:
+/// - `future`: An expression that evaluates to a future. The fields of the route are in scope. The output of the future must implement `Serialize` and `DeserializeOwned`
+///
+/// The router starts the loader when the route is entered, so the data is fetched while the route and its layouts render. The route and its layouts can read the data with `use_route_loader`.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     // get_post is a server function that returns Result<Post, ServerFnError>
+///     #[route("/post/:id")]
+///     #[loader(get_post(id))]
+///     Post { id: usize },
+/// }
+/// ```
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(
+        route,
+        nest,
+        end_nest,
+        layout,
+        end_layout,
+        redirect,
+        child,
+        static_routes,
+        loader
+    )
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -484,7 +534,7 @@ impl RouteEnum {
 
                 fn from_str(s: &str) -> Result<Self, Self::Err> {
                     let route = s;
-                    let (route, _hash) = route.split_once('#').unwrap_or((route, ""));
+                    let (route, hash) = route.split_once('#').unwrap_or((route, ""));
                     let (route, query) = route.split_once('?').unwrap_or((route, ""));
                     let query = dioxus_router::exports::urlencoding::decode(query).unwrap_or(query.into());
                     let mut segments = route.split('/').map(|s| dioxus_router::exports::urlencoding::decode(s).unwrap_or(s.into()));
@@ -583,6 +633,40 @@ impl RouteEnum {
             matches.push(route.routable_match(&self.layouts, &self.nests));
         }
 
+        // Routes with dynamic segments can list the values they should be pre-rendered with
+        let static_routes = self
+            .routes
+            .iter()
+            .filter_map(|route| route.static_routes.as_ref())
+            .collect::<Vec<_>>();
+        let static_routes_impl = (!static_routes.is_empty()).then(|| {
+            quote! {
+                fn static_routes() -> Vec<Self> {
+                    let mut routes = dioxus_router::routable::static_site_map_routes::<Self>();
+                    #(routes.extend(#static_routes);)*
+                    routes
+                }
+            }
+        });
+
+        // Routes can declare a loader that fetches their data while the route renders
+        let loaders = self
+            .routes
+            .iter()
+            .filter_map(|route| route.loader_match())
+            .collect::<Vec<_>>();
+        let loader_impl = (!loaders.is_empty()).then(|| {
+            quote! {
+                fn loader(&self) -> Option<dioxus_router::loader::RouteLoader> {
+                    match self.clone() {
+                        #(#loaders)*
+                        #[allow(unreachable_patterns)]
+                        _ => None
+                    }
+                }
+            }
+        });
+
         quote! {
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
@@ -596,6 +680,10 @@ impl RouteEnum {
                         _ => None
                     }
                 }
+
+                #static_routes_impl
+
+                #loader_impl
             }
         }
     }
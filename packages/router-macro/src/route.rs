@@ -9,6 +9,7 @@ use syn::{Ident, LitStr};
 
 use proc_macro2::TokenStream as TokenStream2;
 
+use crate::hash::HashFragment;
 use crate::layout::Layout;
 use crate::layout::LayoutId;
 use crate::nest::Nest;
@@ -56,8 +57,11 @@ pub(crate) struct Route {
     pub route: String,
     pub segments: Vec<RouteSegment>,
     pub query: Option<QuerySegment>,
+    pub hash: Option<HashFragment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub static_routes: Option<syn::Expr>,
+    pub loader: Option<syn::Expr>,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -144,7 +148,27 @@ impl Route {
             _ => Vec::new(),
         };
 
-        let (route_segments, query) = {
+        let static_routes = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("static_routes"))
+            .map(|attr| attr.parse_args::<syn::Expr>())
+            .transpose()?;
+
+        let loader = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path().is_ident("loader"))
+            .map(|attr| attr.parse_args::<syn::Expr>())
+            .transpose()?;
+        if let (Some(loader), RouteType::Child(_)) = (&loader, &ty) {
+            return Err(syn::Error::new_spanned(
+                loader,
+                "Routable variants with a #[child(..)] attribute use the loader of the child route",
+            ));
+        }
+
+        let (route_segments, query, hash) = {
             parse_route_segments(
                 variant.ident.span(),
                 fields.iter().map(|f| (&f.0, &f.1)),
@@ -158,8 +182,11 @@ impl Route {
             segments: route_segments,
             route,
             query,
+            hash,
             nests,
             layouts,
+            static_routes,
+            loader,
             fields,
         })
     }
@@ -168,6 +195,7 @@ impl Route {
         let name = &self.route_name;
         let dynamic_segments = self.dynamic_segments();
         let write_query = self.query.as_ref().map(|q| q.write());
+        let write_hash = self.hash.as_ref().map(|h| h.write());
 
         match &self.ty {
             RouteType::Child(field) => {
@@ -200,6 +228,7 @@ impl Route {
                         #(#write_nests)*
                         #(#write_segments)*
                         #write_query
+                        #write_hash
                     }
                 }
             }
@@ -260,6 +289,29 @@ impl Route {
         tokens
     }
 
+    pub fn loader_match(&self) -> Option<TokenStream2> {
+        let name = &self.route_name;
+
+        match &self.ty {
+            RouteType::Child(field) => {
+                let field_name = field.ident.as_ref().unwrap();
+                Some(quote! {
+                    Self::#name { #field_name, .. } => #field_name.loader(),
+                })
+            }
+            RouteType::Leaf { .. } => {
+                let loader = self.loader.as_ref()?;
+                let dynamic_segments = self.dynamic_segments();
+                Some(quote! {
+                    #[allow(unused)]
+                    Self::#name { #(#dynamic_segments,)* } => {
+                        Some(dioxus_router::loader::RouteLoader::new(#loader))
+                    }
+                })
+            }
+        }
+    }
+
     fn dynamic_segments(&self) -> impl Iterator<Item = TokenStream2> + '_ {
         self.fields.iter().map(|(name, _)| {
             quote! {#name}
@@ -286,6 +338,11 @@ impl Route {
                     from_route = true
                 }
             }
+            if let Some(hash) = &self.hash {
+                if hash.contains_ident(name) {
+                    from_route = true
+                }
+            }
 
             if from_route {
                 quote! {#name}
@@ -337,6 +394,13 @@ impl Route {
             None => quote! {},
         }
     }
+
+    pub fn parse_hash(&self) -> TokenStream2 {
+        match &self.hash {
+            Some(hash) => hash.parse(),
+            None => quote! {},
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -337,6 +337,7 @@ impl<'a> RouteTreeSegmentData<'a> {
 
                 let construct_variant = route.construct(nests, enum_name);
                 let parse_query = route.parse_query();
+                let parse_hash = route.parse_hash();
 
                 let insure_not_trailing = match route.ty {
                     RouteType::Leaf { .. } => route
@@ -356,6 +357,7 @@ impl<'a> RouteTreeSegmentData<'a> {
                         enum_varient,
                         &varient_parse_error,
                         parse_query,
+                        parse_hash,
                     ),
                     &error_enum_name,
                     enum_varient,
@@ -374,6 +376,11 @@ impl<'a> RouteTreeSegmentData<'a> {
                                 trailing += "/";
                             }
                             trailing.pop();
+                            // the hash fragment belongs to the child route
+                            if !hash.is_empty() {
+                                trailing += "#";
+                                trailing += hash;
+                            }
                             match #ty::from_str(&trailing).map_err(|err| #error_enum_name::#enum_varient(#varient_parse_error::ChildRoute(err))) {
                                 Ok(#child_name) => {
                                     #print_route_segment
@@ -426,6 +433,7 @@ impl<'a> RouteTreeSegmentData<'a> {
                     .skip_while(|(_, seg)| matches!(seg, RouteSegment::Static(_)));
 
                 let parse_query = redirect.parse_query();
+                let parse_hash = redirect.parse_hash();
 
                 let insure_not_trailing = redirect
                     .segments
@@ -454,6 +462,7 @@ impl<'a> RouteTreeSegmentData<'a> {
                         enum_varient,
                         &varient_parse_error,
                         parse_query,
+                        parse_hash,
                     ),
                     &error_enum_name,
                     enum_varient,
@@ -501,6 +510,7 @@ fn return_constructed(
     enum_varient: &Ident,
     varient_parse_error: &Ident,
     parse_query: TokenStream,
+    parse_hash: TokenStream,
 ) -> TokenStream {
     if insure_not_trailing {
         quote! {
@@ -514,6 +524,7 @@ fn return_constructed(
                 // This is the last segment, return the parsed route
                 (None, _) | (Some(""), None) => {
                     #parse_query
+                    #parse_hash
                     return Ok(#construct_variant);
                 }
                 _ => {
@@ -530,6 +541,7 @@ fn return_constructed(
     } else {
         quote! {
             #parse_query
+            #parse_hash
             return Ok(#construct_variant);
         }
     }
@@ -3,6 +3,7 @@ use syn::{Ident, Type};
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
 
+use crate::hash::HashFragment;
 use crate::query::{FullQuerySegment, QueryArgument, QuerySegment};
 
 #[derive(Debug, Clone)]
@@ -132,13 +133,21 @@ pub fn parse_route_segments<'a>(
     route_span: Span,
     mut fields: impl Iterator<Item = (&'a Ident, &'a Type)>,
     route: &str,
-) -> syn::Result<(Vec<RouteSegment>, Option<QuerySegment>)> {
+) -> syn::Result<(
+    Vec<RouteSegment>,
+    Option<QuerySegment>,
+    Option<HashFragment>,
+)> {
     let mut route_segments = Vec::new();
 
-    let (route_string, query) = match route.rsplit_once('?') {
-        Some((route, query)) => (route, Some(query)),
+    let (route_string, hash) = match route.rsplit_once('#') {
+        Some((route, hash)) => (route, Some(hash)),
         None => (route, None),
     };
+    let (route_string, query) = match route_string.rsplit_once('?') {
+        Some((route, query)) => (route, Some(query)),
+        None => (route_string, None),
+    };
     let mut iterator = route_string.split('/');
 
     // skip the first empty segment
@@ -257,7 +266,37 @@ pub fn parse_route_segments<'a>(
         None => None,
     };
 
-    Ok((route_segments, parsed_query))
+    // check if the route has a hash fragment
+    let parsed_hash = match hash {
+        Some(hash) => {
+            if let Some(hash) = hash.strip_prefix(':') {
+                let hash_ident = Ident::new(hash, Span::call_site());
+                let field = fields.find(|(name, _)| *name == &hash_ident);
+
+                let ty = if let Some((_, ty)) = field {
+                    ty.clone()
+                } else {
+                    return Err(syn::Error::new(
+                        route_span,
+                        format!("Could not find a field with the name '{}'", hash_ident),
+                    ));
+                };
+
+                Some(HashFragment {
+                    ident: hash_ident,
+                    ty,
+                })
+            } else {
+                return Err(syn::Error::new(
+                    route_span,
+                    "Hash fragments should be a : followed by the name of the hash argument",
+                ));
+            }
+        }
+        None => None,
+    };
+
+    Ok((route_segments, parsed_query, parsed_hash))
 }
 
 pub(crate) fn create_error_type(
@@ -50,6 +50,12 @@ async fn main() {
     )
     .await
     .unwrap();
+
+    std::fs::write(
+        "./static/sitemap.xml",
+        generate_sitemap::<Route>("https://example.com"),
+    )
+    .unwrap();
 }
 
 #[component]
@@ -97,6 +103,7 @@ enum Route {
         #[route("/post/index")]
         PostHome {},
         #[route("/post/:id")]
+        #[static_routes((0..3).map(|id| Route::Post { id }))]
         Post {
             id: usize,
         },
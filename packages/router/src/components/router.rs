@@ -9,7 +9,7 @@ pub struct RouterConfigFactory<R: Routable> {
     config: RefCell<Option<Box<dyn FnOnce() -> RouterConfig<R>>>>,
 }
 
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", target_arch = "wasm32", feature = "web"))]
 impl<R: Routable> Default for RouterConfigFactory<R>
 where
     <R as FromStr>::Err: std::fmt::Display,
@@ -20,7 +20,7 @@ where
     }
 }
 
-#[cfg(not(feature = "serde"))]
+#[cfg(not(all(feature = "serde", target_arch = "wasm32", feature = "web")))]
 impl<R: Routable> Default for RouterConfigFactory<R>
 where
     <R as FromStr>::Err: std::fmt::Display,
@@ -38,7 +38,7 @@ impl<R: Routable, F: FnOnce() -> RouterConfig<R> + 'static> From<F> for RouterCo
     }
 }
 
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", target_arch = "wasm32", feature = "web"))]
 /// The props for [`Router`].
 #[derive(Props)]
 pub struct RouterProps<R: Routable>
@@ -50,7 +50,7 @@ where
     config: RouterConfigFactory<R>,
 }
 
-#[cfg(not(feature = "serde"))]
+#[cfg(not(all(feature = "serde", target_arch = "wasm32", feature = "web")))]
 /// The props for [`Router`].
 #[derive(Props)]
 pub struct RouterProps<R: Routable>
@@ -61,7 +61,7 @@ where
     config: RouterConfigFactory<R>,
 }
 
-#[cfg(not(feature = "serde"))]
+#[cfg(not(all(feature = "serde", target_arch = "wasm32", feature = "web")))]
 impl<R: Routable> Default for RouterProps<R>
 where
     <R as FromStr>::Err: std::fmt::Display,
@@ -73,7 +73,7 @@ where
     }
 }
 
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", target_arch = "wasm32", feature = "web"))]
 impl<R: Routable> Default for RouterProps<R>
 where
     <R as FromStr>::Err: std::fmt::Display,
@@ -86,7 +86,7 @@ where
     }
 }
 
-#[cfg(not(feature = "serde"))]
+#[cfg(not(all(feature = "serde", target_arch = "wasm32", feature = "web")))]
 impl<R: Routable> PartialEq for RouterProps<R>
 where
     <R as FromStr>::Err: std::fmt::Display,
@@ -97,7 +97,7 @@ where
     }
 }
 
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", target_arch = "wasm32", feature = "web"))]
 impl<R: Routable> PartialEq for RouterProps<R>
 where
     <R as FromStr>::Err: std::fmt::Display,
@@ -109,7 +109,7 @@ where
     }
 }
 
-#[cfg(not(feature = "serde"))]
+#[cfg(not(all(feature = "serde", target_arch = "wasm32", feature = "web")))]
 /// A component that renders the current route.
 pub fn Router<R: Routable + Clone>(cx: Scope<RouterProps<R>>) -> Element
 where
@@ -125,6 +125,7 @@ where
                 .take()
                 .expect("use_context_provider ran twice"))(),
             cx.schedule_update_any(),
+            cx.scope_id(),
         )
     });
     use_context_provider(cx, || OutletContext::<R> {
@@ -137,13 +138,15 @@ where
     }
 }
 
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", target_arch = "wasm32", feature = "web"))]
 /// A component that renders the current route.
 pub fn Router<R: Routable + Clone>(cx: Scope<RouterProps<R>>) -> Element
 where
     <R as FromStr>::Err: std::fmt::Display,
     R: serde::Serialize + serde::de::DeserializeOwned,
 {
+    use crate::prelude::{outlet::OutletContext, RouterContext};
+
     use_context_provider(cx, || {
         RouterContext::new(
             (cx.props
@@ -152,6 +155,7 @@ where
                 .take()
                 .expect("use_context_provider ran twice"))(),
             cx.schedule_update_any(),
+            cx.scope_id(),
         )
     });
     use_context_provider(cx, || OutletContext::<R> {
@@ -0,0 +1,33 @@
+use crate::prelude::RouterContext;
+
+/// A handle to a navigation blocker registered with [`crate::prelude::use_navigation_blocker`].
+///
+/// While the blocker is blocking, navigations through the router are held back instead of being
+/// performed, so the component can ask the user to confirm them.
+#[derive(Clone)]
+pub struct NavigationBlocker {
+    pub(crate) router: RouterContext,
+    pub(crate) id: usize,
+}
+
+impl NavigationBlocker {
+    /// Check whether a navigation was blocked and is waiting for [`Self::proceed`] or [`Self::reset`].
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        self.router.is_blocked(self.id)
+    }
+
+    /// Perform the blocked navigation.
+    ///
+    /// Other blockers are skipped, but the guards of the router still run.
+    pub fn proceed(&self) {
+        if let Some(navigation) = self.router.take_blocked(self.id) {
+            self.router.navigate(navigation, false);
+        }
+    }
+
+    /// Stay on the current route and discard the blocked navigation.
+    pub fn reset(&self) {
+        self.router.take_blocked(self.id);
+    }
+}
@@ -51,6 +51,13 @@ impl<R> OutletContext<R> {
             }
         }
 
-        router.current::<R>().render(cx, current_level)
+        let current = router.current::<R>();
+
+        // Start loading the data of the route before we render it, so the route and its layouts don't have to wait for each other
+        if current_level == 0 {
+            router.load(current.to_string(), current.loader());
+        }
+
+        current.render(cx, current_level)
     }
 }
@@ -1,14 +1,22 @@
 use std::{
     any::Any,
-    collections::HashSet,
-    rc::Rc,
+    collections::{BTreeMap, HashSet},
+    future::Future,
+    pin::Pin,
+    rc::{Rc, Weak},
     sync::{Arc, RwLock},
+    task::{Context, Poll},
 };
 
-use dioxus::prelude::*;
+use dioxus::{
+    core::prelude::{remove_future, spawn_at, Runtime, RuntimeGuard},
+    prelude::*,
+};
+use futures_util::task::noop_waker_ref;
 
 use crate::{
-    navigation::NavigationTarget,
+    loader::{LoaderCache, RouteLoader},
+    navigation::{NavigationDecision, NavigationKind, NavigationTarget, PendingNavigation},
     prelude::{AnyHistoryProvider, IntoRoutable},
     routable::Routable,
     router_cfg::RouterConfig,
@@ -24,6 +32,54 @@ pub(crate) type RoutingCallback<R> =
 pub(crate) type AnyRoutingCallback =
     Arc<dyn Fn(RouterContext) -> Option<NavigationTarget<Rc<dyn Any>>>>;
 
+/// A guard the router will call before every navigation.
+pub(crate) type NavigationGuard<R> =
+    Arc<dyn Fn(PendingNavigation<R>) -> Pin<Box<dyn Future<Output = NavigationDecision<R>>>>>;
+pub(crate) type AnyNavigationGuard = NavigationGuard<Rc<dyn Any>>;
+
+/// A navigation the router was asked to perform.
+#[derive(Clone)]
+pub(crate) enum NavigationRequest {
+    Push(NavigationTarget<Rc<dyn Any>>),
+    Replace(NavigationTarget<Rc<dyn Any>>),
+    GoBack,
+    GoForward,
+}
+
+impl NavigationRequest {
+    fn kind(&self) -> NavigationKind {
+        match self {
+            Self::Push(_) => NavigationKind::Push,
+            Self::Replace(_) => NavigationKind::Replace,
+            Self::GoBack => NavigationKind::GoBack,
+            Self::GoForward => NavigationKind::GoForward,
+        }
+    }
+
+    fn target(&self) -> Option<NavigationTarget<Rc<dyn Any>>> {
+        match self {
+            Self::Push(target) | Self::Replace(target) => Some(target.clone()),
+            Self::GoBack | Self::GoForward => None,
+        }
+    }
+}
+
+/// A blocker registered with [`crate::prelude::use_navigation_blocker`].
+struct BlockerState {
+    blocking: bool,
+    scope: ScopeId,
+    /// The navigation that was blocked, if any.
+    blocked: Option<NavigationRequest>,
+}
+
+/// The data of the loader of the current route.
+struct LoaderState {
+    /// The route the loader was started for.
+    route: String,
+    data: Option<Rc<dyn Any>>,
+    task: Option<TaskId>,
+}
+
 struct MutableRouterState {
     /// The current prefix.
     prefix: Option<String>,
@@ -31,6 +87,56 @@ struct MutableRouterState {
     history: Box<dyn AnyHistoryProvider>,
 
     unresolved_error: Option<ExternalNavigationFailure>,
+
+    blockers: BTreeMap<usize, BlockerState>,
+    next_blocker_id: usize,
+
+    /// Incremented for every navigation, so guards that resolve late don't override a newer navigation.
+    navigation_generation: usize,
+
+    loader: Option<LoaderState>,
+}
+
+/// A [`RouterContext`] that doesn't keep the router state alive, for callbacks the history provider
+/// owns.
+#[derive(Clone)]
+struct WeakRouterContext {
+    state: Weak<RefCell<MutableRouterState>>,
+    subscribers: Arc<RwLock<HashSet<ScopeId>>>,
+    subscriber_update: Arc<dyn Fn(ScopeId)>,
+    routing_callback: Option<AnyRoutingCallback>,
+    guards: Rc<[AnyNavigationGuard]>,
+    loader_cache: Option<Rc<dyn LoaderCache>>,
+    scope: ScopeId,
+    failure_external_navigation: fn(Scope) -> Element,
+    any_route_to_string: fn(&dyn Any) -> String,
+}
+
+impl WeakRouterContext {
+    fn upgrade(&self) -> Option<RouterContext> {
+        let Self {
+            state,
+            subscribers,
+            subscriber_update,
+            routing_callback,
+            guards,
+            loader_cache,
+            scope,
+            failure_external_navigation,
+            any_route_to_string,
+        } = self.clone();
+        Some(RouterContext {
+            state: state.upgrade()?,
+            subscribers,
+            subscriber_update,
+            routing_callback,
+            guards,
+            loader_cache,
+            scope,
+            failure_external_navigation,
+            any_route_to_string,
+        })
+    }
 }
 
 /// A collection of router data that manages all routing functionality.
@@ -41,6 +147,11 @@ pub struct RouterContext {
     subscribers: Arc<RwLock<HashSet<ScopeId>>>,
     subscriber_update: Arc<dyn Fn(ScopeId)>,
     routing_callback: Option<AnyRoutingCallback>,
+    guards: Rc<[AnyNavigationGuard]>,
+    loader_cache: Option<Rc<dyn LoaderCache>>,
+
+    /// The scope of the router, which runs guards that don't resolve immediately and loaders.
+    scope: ScopeId,
 
     failure_external_navigation: fn(Scope) -> Element,
 
@@ -51,6 +162,7 @@ impl RouterContext {
     pub(crate) fn new<R: Routable + 'static>(
         mut cfg: RouterConfig<R>,
         mark_dirty: Arc<dyn Fn(ScopeId) + Sync + Send>,
+        scope: ScopeId,
     ) -> Self
     where
         R: Clone,
@@ -60,6 +172,10 @@ impl RouterContext {
             prefix: Default::default(),
             history: cfg.take_history(),
             unresolved_error: None,
+            blockers: BTreeMap::new(),
+            next_blocker_id: 0,
+            navigation_generation: 0,
+            loader: None,
         }));
 
         let subscriber_update = mark_dirty.clone();
@@ -86,6 +202,44 @@ impl RouterContext {
                     as Arc<dyn Fn(RouterContext) -> Option<NavigationTarget<Rc<dyn Any>>>>
             }),
 
+            guards: cfg
+                .guards
+                .into_iter()
+                .map(|guard| {
+                    Arc::new(move |navigation: PendingNavigation<Rc<dyn Any>>| {
+                        let navigation = PendingNavigation {
+                            kind: navigation.kind,
+                            from: navigation.from.downcast::<R>().unwrap().as_ref().clone(),
+                            to: navigation.to.map(|to| match to {
+                                NavigationTarget::Internal(r) => NavigationTarget::Internal(
+                                    r.downcast::<R>().unwrap().as_ref().clone(),
+                                ),
+                                NavigationTarget::External(s) => NavigationTarget::External(s),
+                            }),
+                        };
+                        let decision = guard(navigation);
+                        Box::pin(async move {
+                            match decision.await {
+                                NavigationDecision::Allow => NavigationDecision::Allow,
+                                NavigationDecision::Cancel => NavigationDecision::Cancel,
+                                NavigationDecision::Redirect(NavigationTarget::Internal(r)) => {
+                                    NavigationDecision::Redirect(NavigationTarget::Internal(
+                                        Rc::new(r) as Rc<dyn Any>,
+                                    ))
+                                }
+                                NavigationDecision::Redirect(NavigationTarget::External(s)) => {
+                                    NavigationDecision::Redirect(NavigationTarget::External(s))
+                                }
+                            }
+                        })
+                            as Pin<Box<dyn Future<Output = NavigationDecision<Rc<dyn Any>>>>>
+                    }) as AnyNavigationGuard
+                })
+                .collect(),
+            loader_cache: cfg.loader_cache,
+
+            scope,
+
             failure_external_navigation: cfg.failure_external_navigation,
 
             any_route_to_string: |route| {
@@ -112,6 +266,7 @@ impl RouterContext {
                 }
             }));
         }
+        myself.update_blocked();
 
         myself
     }
@@ -137,37 +292,21 @@ impl RouterContext {
     ///
     /// Will fail silently if there is no previous location to go to.
     pub fn go_back(&self) {
-        {
-            self.state.borrow_mut().history.go_back();
-        }
-
-        self.change_route();
+        self.navigate(NavigationRequest::GoBack, true);
     }
 
     /// Go back to the next location.
     ///
     /// Will fail silently if there is no next location to go to.
     pub fn go_forward(&self) {
-        {
-            self.state.borrow_mut().history.go_forward();
-        }
-
-        self.change_route();
+        self.navigate(NavigationRequest::GoForward, true);
     }
 
     pub(crate) fn push_any(
         &self,
         target: NavigationTarget<Rc<dyn Any>>,
     ) -> Option<ExternalNavigationFailure> {
-        match target {
-            NavigationTarget::Internal(p) => {
-                let mut state = self.state_mut();
-                state.history.push(p)
-            }
-            NavigationTarget::External(e) => return self.external(e),
-        }
-
-        self.change_route()
+        self.navigate(NavigationRequest::Push(target), true)
     }
 
     /// Push a new location.
@@ -175,15 +314,7 @@ impl RouterContext {
     /// The previous location will be available to go back to.
     pub fn push(&self, target: impl Into<IntoRoutable>) -> Option<ExternalNavigationFailure> {
         let target = self.resolve_into_routable(target.into());
-        match target {
-            NavigationTarget::Internal(p) => {
-                let mut state = self.state_mut();
-                state.history.push(p)
-            }
-            NavigationTarget::External(e) => return self.external(e),
-        }
-
-        self.change_route()
+        self.navigate(NavigationRequest::Push(target), true)
     }
 
     /// Replace the current location.
@@ -191,18 +322,315 @@ impl RouterContext {
     /// The previous location will **not** be available to go back to.
     pub fn replace(&self, target: impl Into<IntoRoutable>) -> Option<ExternalNavigationFailure> {
         let target = self.resolve_into_routable(target.into());
+        self.navigate(NavigationRequest::Replace(target), true)
+    }
+
+    /// Run the blockers and guards for a navigation, and perform it if they allow it.
+    pub(crate) fn navigate(
+        &self,
+        request: NavigationRequest,
+        check_blockers: bool,
+    ) -> Option<ExternalNavigationFailure> {
+        if check_blockers && self.block(&request) {
+            return None;
+        }
 
+        let generation = {
+            let mut state = self.state_mut();
+            state.navigation_generation += 1;
+            state.navigation_generation
+        };
+
+        if self.guards.is_empty() {
+            return self.apply(request);
+        }
+
+        // Most guards resolve immediately, so we only spawn a task if one of them is still pending
+        let mut navigation = Box::pin(self.clone().guarded(request, generation));
+        match navigation
+            .as_mut()
+            .poll(&mut Context::from_waker(noop_waker_ref()))
+        {
+            Poll::Ready(failure) => failure,
+            Poll::Pending => {
+                spawn_at(
+                    async move {
+                        navigation.await;
+                    },
+                    self.scope,
+                );
+                None
+            }
+        }
+    }
+
+    async fn guarded(
+        self,
+        mut request: NavigationRequest,
+        generation: usize,
+    ) -> Option<ExternalNavigationFailure> {
+        let guards = self.guards.clone();
+        for guard in guards.iter() {
+            let navigation = PendingNavigation {
+                kind: request.kind(),
+                from: self.state.borrow().history.current_route(),
+                to: request.target(),
+            };
+            let decision = guard(navigation).await;
+
+            // Another navigation started while the guard was pending
+            if self.state.borrow().navigation_generation != generation {
+                return None;
+            }
+
+            match decision {
+                NavigationDecision::Allow => {}
+                NavigationDecision::Cancel => return None,
+                NavigationDecision::Redirect(target) => {
+                    request = match request {
+                        NavigationRequest::Replace(_) => NavigationRequest::Replace(target),
+                        _ => NavigationRequest::Push(target),
+                    };
+                    break;
+                }
+            }
+        }
+
+        self.apply(request)
+    }
+
+    fn apply(&self, request: NavigationRequest) -> Option<ExternalNavigationFailure> {
         {
             let mut state = self.state_mut();
-            match target {
-                NavigationTarget::Internal(p) => state.history.replace(p),
-                NavigationTarget::External(e) => return self.external(e),
+            match request {
+                NavigationRequest::Push(NavigationTarget::Internal(p)) => state.history.push(p),
+                NavigationRequest::Replace(NavigationTarget::Internal(p)) => {
+                    state.history.replace(p)
+                }
+                NavigationRequest::Push(NavigationTarget::External(e))
+                | NavigationRequest::Replace(NavigationTarget::External(e)) => {
+                    drop(state);
+                    return self.external(e);
+                }
+                NavigationRequest::GoBack => state.history.go_back(),
+                NavigationRequest::GoForward => state.history.go_forward(),
             }
         }
 
         self.change_route()
     }
 
+    /// Hand the navigation to the first blocker that is blocking, if there is one.
+    fn block(&self, request: &NavigationRequest) -> bool {
+        let scope = {
+            let mut state = self.state_mut();
+            let Some(blocker) = state.blockers.values_mut().find(|b| b.blocking) else {
+                return false;
+            };
+            blocker.blocked = Some(request.clone());
+            blocker.scope
+        };
+
+        (self.subscriber_update)(scope);
+
+        true
+    }
+
+    pub(crate) fn register_blocker(&self, scope: ScopeId, blocking: bool) -> usize {
+        let id = {
+            let mut state = self.state_mut();
+            let id = state.next_blocker_id;
+            state.next_blocker_id += 1;
+            state.blockers.insert(
+                id,
+                BlockerState {
+                    blocking,
+                    scope,
+                    blocked: None,
+                },
+            );
+            id
+        };
+        self.update_blocked();
+        id
+    }
+
+    pub(crate) fn unregister_blocker(&self, id: usize) {
+        self.state_mut().blockers.remove(&id);
+        self.update_blocked();
+    }
+
+    pub(crate) fn set_blocking(&self, id: usize, blocking: bool) {
+        {
+            let mut state = self.state_mut();
+            match state.blockers.get_mut(&id) {
+                Some(blocker) if blocker.blocking != blocking => blocker.blocking = blocking,
+                _ => return,
+            }
+        }
+        self.update_blocked();
+    }
+
+    pub(crate) fn is_blocked(&self, id: usize) -> bool {
+        self.state
+            .borrow()
+            .blockers
+            .get(&id)
+            .is_some_and(|b| b.blocked.is_some())
+    }
+
+    /// Take the navigation a blocker blocked, and rerender the component that owns it.
+    pub(crate) fn take_blocked(&self, id: usize) -> Option<NavigationRequest> {
+        let (scope, blocked) = {
+            let mut state = self.state_mut();
+            let blocker = state.blockers.get_mut(&id)?;
+            (blocker.scope, blocker.blocked.take())
+        };
+        (self.subscriber_update)(scope);
+        blocked
+    }
+
+    /// Tell the history provider whether any blocker is blocking, and whether it should hand
+    /// navigations that don't go through the router to the guards and blockers.
+    fn update_blocked(&self) {
+        let mut state = self.state_mut();
+        let blocked = state.blockers.values().any(|b| b.blocking);
+        state.history.set_blocked(blocked);
+        let intercepting = blocked || !self.guards.is_empty();
+        state
+            .history
+            .intercept(intercepting.then(|| self.interceptor()));
+    }
+
+    /// A callback that runs the blockers and guards for a navigation the history provider undid.
+    fn interceptor(&self) -> Rc<dyn Fn(NavigationKind)> {
+        let router = self.downgrade();
+        // The history provider calls this outside of the virtual dom, which guards that don't
+        // resolve immediately need to spawn their task
+        let runtime = Runtime::current().map(|runtime| Rc::downgrade(&runtime));
+        Rc::new(move |kind| {
+            let Some(router) = router.upgrade() else {
+                return;
+            };
+            let _runtime = runtime
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .map(RuntimeGuard::new);
+            let request = match kind {
+                NavigationKind::GoForward => NavigationRequest::GoForward,
+                _ => NavigationRequest::GoBack,
+            };
+            router.navigate(request, true);
+        })
+    }
+
+    fn downgrade(&self) -> WeakRouterContext {
+        let Self {
+            state,
+            subscribers,
+            subscriber_update,
+            routing_callback,
+            guards,
+            loader_cache,
+            scope,
+            failure_external_navigation,
+            any_route_to_string,
+        } = self.clone();
+        WeakRouterContext {
+            state: Rc::downgrade(&state),
+            subscribers,
+            subscriber_update,
+            routing_callback,
+            guards,
+            loader_cache,
+            scope,
+            failure_external_navigation,
+            any_route_to_string,
+        }
+    }
+
+    /// Start the loader of the current route, unless it was already started for that route.
+    pub(crate) fn load(&self, route: String, loader: Option<RouteLoader>) {
+        let previous = {
+            let mut state = self.state_mut();
+            if state.loader.as_ref().is_some_and(|l| l.route == route) {
+                return;
+            }
+            state.loader.take()
+        };
+        if let Some(task) = previous.and_then(|l| l.task) {
+            remove_future(task);
+        }
+
+        let Some(loader) = loader else {
+            return;
+        };
+
+        // The data may have been loaded by the renderer that rendered the page, e.g. a server
+        let cached = self
+            .loader_cache
+            .as_ref()
+            .and_then(|cache| cache.take(&route))
+            .and_then(|data| (loader.deserialize)(&data));
+        if let Some(data) = cached {
+            self.state_mut().loader = Some(LoaderState {
+                route,
+                data: Some(data),
+                task: None,
+            });
+            return;
+        }
+
+        self.state_mut().loader = Some(LoaderState {
+            route: route.clone(),
+            data: None,
+            task: None,
+        });
+
+        let myself = self.clone();
+        let loaded_route = route.clone();
+        let task = spawn_at(
+            async move {
+                let data = loader.future.await;
+                if let Some(cache) = &myself.loader_cache {
+                    if let Some(serialized) = (loader.serialize)(&*data) {
+                        cache.store(&loaded_route, serialized);
+                    }
+                }
+                {
+                    let mut state = myself.state_mut();
+                    match &mut state.loader {
+                        Some(current) if current.route == loaded_route => {
+                            current.data = Some(data);
+                            current.task = None;
+                        }
+                        _ => return,
+                    }
+                }
+                myself.update_subscribers();
+            },
+            self.scope,
+        );
+
+        // The task is polled once when it is spawned, so the loader may already be done
+        if let Some(current) = &mut self.state_mut().loader {
+            if current.route == route && current.data.is_none() {
+                current.task = task;
+            }
+        }
+    }
+
+    /// Get the data of the loader of the current route.
+    ///
+    /// Returns [`None`] if the current route has no loader, and `Some(None)` while the loader is pending.
+    pub(crate) fn loader_data(&self) -> Option<Option<Rc<dyn Any>>> {
+        self.state
+            .borrow()
+            .loader
+            .as_ref()
+            .map(|loader| loader.data.clone())
+    }
+
     /// The route that is currently active.
     pub fn current<R: Routable>(&self) -> R {
         self.state
@@ -32,7 +32,7 @@ pub use liveview::*;
 // #[cfg(feature = "web")]
 // pub use web_hash::*;
 
-use crate::routable::Routable;
+use crate::{navigation::NavigationKind, routable::Routable};
 
 #[cfg(feature = "web")]
 pub(crate) mod web_scroll;
@@ -284,6 +284,25 @@ pub trait HistoryProvider<R: Routable> {
     /// updates are received, they should call `callback`, which will cause the router to update.
     #[allow(unused_variables)]
     fn updater(&mut self, callback: Arc<dyn Fn() + Send + Sync>) {}
+
+    /// Tell the [`HistoryProvider`] whether a navigation blocker is active.
+    ///
+    /// The router calls this whenever a component starts or stops blocking navigation with
+    /// [`crate::prelude::use_navigation_blocker`]. [`HistoryProvider`]s that can be left without
+    /// going through the router (e.g. by closing a browser tab) should ask the user to confirm
+    /// leaving while navigation is blocked.
+    #[allow(unused_variables)]
+    fn set_blocked(&mut self, blocked: bool) {}
+
+    /// Provide the [`HistoryProvider`] with a callback for navigations that don't go through the
+    /// router, e.g. the back and forward buttons of a browser.
+    ///
+    /// The router only provides a callback while it has guards or an active navigation blocker.
+    /// While it is set, [`HistoryProvider`]s should undo such navigations and call `callback` with
+    /// their direction instead. If the guards and blockers allow the navigation, the router performs
+    /// it again through [`HistoryProvider::go_back`] or [`HistoryProvider::go_forward`].
+    #[allow(unused_variables)]
+    fn intercept(&mut self, callback: Option<Rc<dyn Fn(NavigationKind)>>) {}
 }
 
 pub(crate) trait AnyHistoryProvider {
@@ -325,6 +344,12 @@ pub(crate) trait AnyHistoryProvider {
 
     #[allow(unused_variables)]
     fn updater(&mut self, callback: Arc<dyn Fn() + Send + Sync>) {}
+
+    #[allow(unused_variables)]
+    fn set_blocked(&mut self, blocked: bool) {}
+
+    #[allow(unused_variables)]
+    fn intercept(&mut self, callback: Option<Rc<dyn Fn(NavigationKind)>>) {}
 }
 
 pub(crate) struct AnyHistoryProviderImplWrapper<R, H> {
@@ -405,4 +430,12 @@ where
     fn updater(&mut self, callback: Arc<dyn Fn() + Send + Sync>) {
         self.inner.updater(callback)
     }
+
+    fn set_blocked(&mut self, blocked: bool) {
+        self.inner.set_blocked(blocked)
+    }
+
+    fn intercept(&mut self, callback: Option<Rc<dyn Fn(NavigationKind)>>) {
+        self.inner.intercept(callback)
+    }
 }
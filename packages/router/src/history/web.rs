@@ -1,15 +1,23 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
 
-use gloo::{console::error, events::EventListener, render::AnimationFrame};
+use gloo::{
+    console::error,
+    events::{EventListener, EventListenerOptions},
+    render::AnimationFrame,
+};
 
 use wasm_bindgen::JsValue;
 use web_sys::{window, History, ScrollRestoration, Window};
 
-use crate::routable::Routable;
+use crate::{navigation::NavigationKind, routable::Routable};
 
 use super::{
     web_history::{get_current, push_state_and_url, replace_state_with_url},
-    web_scroll::ScrollPosition,
+    web_scroll::{scroll_to_fragment, ScrollPosition},
     HistoryProvider,
 };
 
@@ -26,8 +34,9 @@ fn base_path() -> Option<&'static str> {
 #[cfg(not(feature = "serde"))]
 #[allow(clippy::extra_unused_type_parameters)]
 fn update_scroll<R>(window: &Window, history: &History) {
+    let index = current_index::<R>(history).unwrap_or_default() as f64;
     let scroll = ScrollPosition::of_window(window);
-    if let Err(err) = replace_state_with_url(history, &[scroll.x, scroll.y], None) {
+    if let Err(err) = replace_state_with_url(history, &[scroll.x, scroll.y, index], None) {
         error!(err);
     }
 }
@@ -37,9 +46,13 @@ fn update_scroll<R: serde::Serialize + serde::de::DeserializeOwned + Routable>(
     window: &Window,
     history: &History,
 ) {
-    if let Some(WebHistoryState { state, .. }) = get_current::<WebHistoryState<R>>(history) {
+    if let Some(WebHistoryState { state, index, .. }) = get_current::<WebHistoryState<R>>(history) {
         let scroll = ScrollPosition::of_window(window);
-        let state = WebHistoryState { state, scroll };
+        let state = WebHistoryState {
+            state,
+            scroll,
+            index,
+        };
         if let Err(err) = replace_state_with_url(history, &state, None) {
             error!(err);
         }
@@ -51,6 +64,92 @@ fn update_scroll<R: serde::Serialize + serde::de::DeserializeOwned + Routable>(
 struct WebHistoryState<R> {
     state: R,
     scroll: ScrollPosition,
+    /// The position of the entry in the session history.
+    index: usize,
+}
+
+/// Get the position of the current entry in the session history, if the router created it.
+#[cfg(not(feature = "serde"))]
+#[allow(clippy::extra_unused_type_parameters)]
+fn current_index<R>(history: &History) -> Option<usize> {
+    get_current(history).map(|[_, _, index]| index as usize)
+}
+
+/// Get the position of the current entry in the session history, if the router created it.
+#[cfg(feature = "serde")]
+fn current_index<R: serde::de::DeserializeOwned + Routable>(history: &History) -> Option<usize> {
+    get_current::<WebHistoryState<R>>(history).map(|state| state.index)
+}
+
+/// The state of a [`WebHistory`] that is shared with its popstate listener.
+#[derive(Default)]
+struct NavigationState {
+    /// The position of the current entry in the session history.
+    index: usize,
+    /// The popstate event the history is waiting for.
+    expected: Option<ExpectedPopstate>,
+    /// How far the user moved through the history before the navigation was undone. The history
+    /// moves that far again if the router allows the navigation.
+    intercepted: Option<isize>,
+    /// Hands navigations that don't go through the router to the router.
+    intercept: Option<Rc<dyn Fn(NavigationKind)>>,
+}
+
+enum ExpectedPopstate {
+    /// The router moved through the history to the entry at this position.
+    Traversal(usize),
+    /// A navigation of the user that moved this far through the history was undone.
+    Undo(isize),
+}
+
+/// Handle a popstate event. Returns `true` if the router should show the new entry.
+///
+/// While the router intercepts navigations, the user moving through the history (e.g. with the
+/// back button of the browser) is undone, and handed to the router once the previous entry is
+/// restored. The router runs its blockers and guards, and moves through the history again if they
+/// allow it.
+fn handle_popstate(
+    navigation: &RefCell<NavigationState>,
+    history: &History,
+    index: Option<usize>,
+) -> bool {
+    let mut state = navigation.borrow_mut();
+    match state.expected.take() {
+        Some(ExpectedPopstate::Undo(delta)) => {
+            state.intercepted = Some(delta);
+            let intercept = state.intercept.clone();
+            // The router moves through the history again if it allows the navigation
+            drop(state);
+            if let Some(intercept) = intercept {
+                intercept(match delta < 0 {
+                    true => NavigationKind::GoBack,
+                    false => NavigationKind::GoForward,
+                });
+            }
+            return false;
+        }
+        Some(ExpectedPopstate::Traversal(target)) if index == Some(target) => {}
+        _ => {
+            // Entries that weren't created by the router have no index, so they can't be undone
+            if let (Some(index), Some(_)) = (index, &state.intercept) {
+                let delta = index as isize - state.index as isize;
+                if delta != 0 {
+                    match history.go_with_delta(-delta as i32) {
+                        Ok(_) => {
+                            state.expected = Some(ExpectedPopstate::Undo(delta));
+                            return false;
+                        }
+                        Err(e) => error!("failed to undo navigation: ", e),
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(index) = index {
+        state.index = index;
+    }
+    true
 }
 
 /// A [`HistoryProvider`] that integrates with a browser via the [History API](https://developer.mozilla.org/en-US/docs/Web/API/History_API).
@@ -70,6 +169,8 @@ pub struct WebHistory<R: Routable> {
     history: History,
     listener_navigation: Option<EventListener>,
     listener_animation_frame: Arc<Mutex<Option<AnimationFrame>>>,
+    listener_unload: Option<EventListener>,
+    navigation: Rc<RefCell<NavigationState>>,
     prefix: Option<String>,
     window: Window,
     phantom: std::marker::PhantomData<R>,
@@ -107,11 +208,14 @@ impl<R: Routable> WebHistory<R> {
         <R as std::str::FromStr>::Err: std::fmt::Display,
     {
         let myself = Self::new_inner(prefix, do_scroll_restoration);
+        myself.navigation.borrow_mut().index =
+            current_index::<R>(&myself.history).unwrap_or_default();
 
         let current_route = myself.current_route();
         let current_url = current_route.to_string();
         let state = myself.create_state(current_route);
         let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
+        myself.scroll_to_fragment(&current_url);
 
         myself
     }
@@ -126,33 +230,15 @@ impl<R: Routable> WebHistory<R> {
         <R as std::str::FromStr>::Err: std::fmt::Display,
         R: serde::Serialize + serde::de::DeserializeOwned,
     {
-        let w = window().expect("access to `window`");
-        let h = w.history().expect("`window` has access to `history`");
-        let document = w.document().expect("`window` has access to `document`");
-
-        let myself = Self::new_inner(
-            prefix,
-            do_scroll_restoration,
-            EventListener::new(&document, "scroll", {
-                let mut last_updated = 0.0;
-                move |evt| {
-                    // the time stamp in milliseconds
-                    let time_stamp = evt.time_stamp();
-                    // throttle the scroll event to 100ms
-                    if (time_stamp - last_updated) < 100.0 {
-                        return;
-                    }
-                    update_scroll::<R>(&w, &h);
-                    last_updated = time_stamp;
-                }
-            }),
-        );
+        let myself = Self::new_inner(prefix, do_scroll_restoration);
+        myself.navigation.borrow_mut().index =
+            current_index::<R>(&myself.history).unwrap_or_default();
 
         let current_route = myself.current_route();
-        tracing::trace!("initial route: {:?}", current_route);
         let current_url = current_route.to_string();
         let state = myself.create_state(current_route);
         let _ = replace_state_with_url(&myself.history, &state, Some(&current_url));
+        myself.scroll_to_fragment(&current_url);
 
         myself
     }
@@ -179,12 +265,29 @@ impl<R: Routable> WebHistory<R> {
             history,
             listener_navigation: None,
             listener_animation_frame: Default::default(),
+            listener_unload: None,
+            navigation: Default::default(),
             prefix,
             window,
             phantom: Default::default(),
         }
     }
 
+    /// Scroll to the element the hash fragment of a path points to. Returns `false` if the path has no fragment.
+    fn scroll_to_fragment(&self, path: &str) -> bool {
+        match path.split_once('#') {
+            Some((_, fragment)) if !fragment.is_empty() => {
+                let frame = scroll_to_fragment(self.window.clone(), fragment.to_string());
+                *self
+                    .listener_animation_frame
+                    .lock()
+                    .expect("unpoisoned scroll mutex") = Some(frame);
+                true
+            }
+            _ => false,
+        }
+    }
+
     fn scroll_pos(&self) -> ScrollPosition {
         self.do_scroll_restoration
             .then(|| ScrollPosition::of_window(&self.window))
@@ -192,15 +295,47 @@ impl<R: Routable> WebHistory<R> {
     }
 
     #[cfg(not(feature = "serde"))]
-    fn create_state(&self, _state: R) -> [f64; 2] {
+    fn create_state(&self, _state: R) -> [f64; 3] {
         let scroll = self.scroll_pos();
-        [scroll.x, scroll.y]
+        let index = self.navigation.borrow().index;
+        [scroll.x, scroll.y, index as f64]
     }
 
     #[cfg(feature = "serde")]
     fn create_state(&self, state: R) -> WebHistoryState<R> {
         let scroll = self.scroll_pos();
-        WebHistoryState { state, scroll }
+        let index = self.navigation.borrow().index;
+        WebHistoryState {
+            state,
+            scroll,
+            index,
+        }
+    }
+
+    /// Move through the session history. If a navigation of the user in the same direction was
+    /// undone, this moves as far as the user did.
+    fn traverse(&mut self, delta: isize) {
+        let mut navigation = self.navigation.borrow_mut();
+        let delta = match navigation.intercepted.take() {
+            Some(intercepted) if intercepted.signum() == delta.signum() => intercepted,
+            _ => delta,
+        };
+        // Moving before the first entry leaves the app, so there is no popstate to wait for
+        navigation.expected = navigation
+            .index
+            .checked_add_signed(delta)
+            .map(ExpectedPopstate::Traversal);
+        if let Err(e) = self.history.go_with_delta(delta as i32) {
+            navigation.expected = None;
+            error!("failed to move through the history: ", e)
+        }
+    }
+
+    /// Add a new entry after the current entry of the session history.
+    fn push_entry(&mut self) {
+        let mut navigation = self.navigation.borrow_mut();
+        navigation.index += 1;
+        navigation.intercepted = None;
     }
 }
 
@@ -211,7 +346,8 @@ where
     fn route_from_location(&self) -> R {
         let location = self.window.location();
         let path = location.pathname().unwrap_or_else(|_| "/".into())
-            + &location.search().unwrap_or("".into());
+            + &location.search().unwrap_or("".into())
+            + &location.hash().unwrap_or("".into());
         let path = match self.prefix {
             None => path,
             Some(ref prefix) => {
@@ -232,10 +368,10 @@ where
         }
     }
 
-    fn handle_nav(&self, result: Result<(), JsValue>) {
+    fn handle_nav(&self, result: Result<(), JsValue>, path: &str) {
         match result {
             Ok(_) => {
-                if self.do_scroll_restoration {
+                if !self.scroll_to_fragment(path) && self.do_scroll_restoration {
                     self.window.scroll_to_with_x_and_y(0.0, 0.0)
                 }
             }
@@ -243,6 +379,21 @@ where
         }
     }
 
+    fn block_unload(&mut self, blocked: bool) {
+        self.listener_unload = blocked.then(|| {
+            EventListener::new_with_options(
+                &self.window,
+                "beforeunload",
+                EventListenerOptions::enable_prevent_default(),
+                |event| {
+                    // ask the browser to confirm leaving the page
+                    event.prevent_default();
+                    let _ = js_sys::Reflect::set(event, &"returnValue".into(), &"".into());
+                },
+            )
+        });
+    }
+
     fn navigate_external(&mut self, url: String) -> bool {
         match self.window.location().set_href(&url) {
             Ok(_) => true,
@@ -274,20 +425,15 @@ where
     }
 
     fn go_back(&mut self) {
-        if let Err(e) = self.history.back() {
-            error!("failed to go back: ", e)
-        }
+        self.traverse(-1);
     }
 
     fn go_forward(&mut self) {
-        if let Err(e) = self.history.forward() {
-            error!("failed to go forward: ", e)
-        }
+        self.traverse(1);
     }
 
     fn push(&mut self, state: R) {
-        use gloo_utils::format::JsValueSerdeExt;
-        if JsValue::from_serde(&state) != JsValue::from_serde(&self.current_route()) {
+        if state.to_string() == self.current_route().to_string() {
             // don't push the same state twice
             return;
         }
@@ -300,9 +446,13 @@ where
 
         let path = self.full_path(&state);
 
+        self.push_entry();
         let state = self.create_state(state);
 
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+        self.handle_nav(
+            push_state_and_url(&self.history, &state, path.clone()),
+            &path,
+        );
     }
 
     fn replace(&mut self, state: R) {
@@ -313,24 +463,40 @@ where
 
         let state = self.create_state(state);
 
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+        self.handle_nav(
+            replace_state_with_url(&self.history, &state, Some(&path)),
+            &path,
+        );
     }
 
     fn external(&mut self, url: String) -> bool {
         self.navigate_external(url)
     }
 
+    fn set_blocked(&mut self, blocked: bool) {
+        self.block_unload(blocked)
+    }
+
+    fn intercept(&mut self, callback: Option<Rc<dyn Fn(NavigationKind)>>) {
+        self.navigation.borrow_mut().intercept = callback;
+    }
+
     fn updater(&mut self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
         let w = self.window.clone();
         let h = self.history.clone();
         let s = self.listener_animation_frame.clone();
         let d = self.do_scroll_restoration;
+        let navigation = self.navigation.clone();
 
         self.listener_navigation = Some(EventListener::new(&self.window, "popstate", move |_| {
+            let current_state = get_current::<WebHistoryState<R>>(&h);
+            if !handle_popstate(&navigation, &h, current_state.as_ref().map(|s| s.index)) {
+                return;
+            }
             (*callback)();
             if d {
                 let mut s = s.lock().expect("unpoisoned scroll mutex");
-                if let Some(current_state) = get_current::<WebHistoryState<R>>(&h) {
+                if let Some(current_state) = current_state {
                     *s = Some(current_state.scroll.scroll_to(w.clone()));
                 }
             }
@@ -352,15 +518,11 @@ where
     }
 
     fn go_back(&mut self) {
-        if let Err(e) = self.history.back() {
-            error!("failed to go back: ", e)
-        }
+        self.traverse(-1);
     }
 
     fn go_forward(&mut self) {
-        if let Err(e) = self.history.forward() {
-            error!("failed to go forward: ", e)
-        }
+        self.traverse(1);
     }
 
     fn push(&mut self, state: R) {
@@ -377,8 +539,12 @@ where
 
         let path = self.full_path(&state);
 
-        let state: [f64; 2] = self.create_state(state);
-        self.handle_nav(push_state_and_url(&self.history, &state, path));
+        self.push_entry();
+        let state: [f64; 3] = self.create_state(state);
+        self.handle_nav(
+            push_state_and_url(&self.history, &state, path.clone()),
+            &path,
+        );
     }
 
     fn replace(&mut self, state: R) {
@@ -388,24 +554,40 @@ where
         };
 
         let state = self.create_state(state);
-        self.handle_nav(replace_state_with_url(&self.history, &state, Some(&path)));
+        self.handle_nav(
+            replace_state_with_url(&self.history, &state, Some(&path)),
+            &path,
+        );
     }
 
     fn external(&mut self, url: String) -> bool {
         self.navigate_external(url)
     }
 
+    fn set_blocked(&mut self, blocked: bool) {
+        self.block_unload(blocked)
+    }
+
+    fn intercept(&mut self, callback: Option<Rc<dyn Fn(NavigationKind)>>) {
+        self.navigation.borrow_mut().intercept = callback;
+    }
+
     fn updater(&mut self, callback: std::sync::Arc<dyn Fn() + Send + Sync>) {
         let w = self.window.clone();
         let h = self.history.clone();
         let s = self.listener_animation_frame.clone();
         let d = self.do_scroll_restoration;
+        let navigation = self.navigation.clone();
 
         self.listener_navigation = Some(EventListener::new(&self.window, "popstate", move |_| {
+            let current_state = get_current(&h);
+            if !handle_popstate(&navigation, &h, current_state.map(|[_, _, i]| i as usize)) {
+                return;
+            }
             (*callback)();
             if d {
                 let mut s = s.lock().expect("unpoisoned scroll mutex");
-                if let Some([x, y]) = get_current(&h) {
+                if let Some([x, y, _]) = current_state {
                     *s = Some(ScrollPosition { x, y }.scroll_to(w.clone()));
                 }
             }
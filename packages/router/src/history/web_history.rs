@@ -7,12 +7,13 @@ use web_sys::History;
 #[cfg(not(feature = "serde"))]
 pub(crate) fn replace_state_with_url(
     history: &History,
-    value: &[f64; 2],
+    value: &[f64; 3],
     url: Option<&str>,
 ) -> Result<(), JsValue> {
     let position = js_sys::Array::new();
     position.push(&JsValue::from(value[0]));
     position.push(&JsValue::from(value[1]));
+    position.push(&JsValue::from(value[2]));
 
     history.replace_state_with_url(&position, "", url)
 }
@@ -31,12 +32,13 @@ pub(crate) fn replace_state_with_url<V: serde::Serialize>(
 #[cfg(not(feature = "serde"))]
 pub(crate) fn push_state_and_url(
     history: &History,
-    value: &[f64; 2],
+    value: &[f64; 3],
     url: String,
 ) -> Result<(), JsValue> {
     let position = js_sys::Array::new();
     position.push(&JsValue::from(value[0]));
     position.push(&JsValue::from(value[1]));
+    position.push(&JsValue::from(value[2]));
 
     history.push_state_with_url(&position, "", Some(&url))
 }
@@ -68,7 +70,7 @@ pub(crate) fn get_current<V: serde::de::DeserializeOwned>(history: &History) ->
 }
 
 #[cfg(not(feature = "serde"))]
-pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
+pub(crate) fn get_current(history: &History) -> Option<[f64; 3]> {
     use wasm_bindgen::JsCast;
 
     let state = history.state();
@@ -79,6 +81,7 @@ pub(crate) fn get_current(history: &History) -> Option<[f64; 2]> {
         let state = state.dyn_into::<js_sys::Array>().ok()?;
         let x = state.get(0).as_f64()?;
         let y = state.get(1).as_f64()?;
-        Some([x, y])
+        let index = state.get(2).as_f64()?;
+        Some([x, y, index])
     })
 }
@@ -21,3 +21,15 @@ impl ScrollPosition {
         request_animation_frame(move |_| window.scroll_to_with_x_and_y(x, y))
     }
 }
+
+/// Scroll the element with the id of a url hash fragment into view once the next frame is rendered.
+pub(crate) fn scroll_to_fragment(window: Window, fragment: String) -> AnimationFrame {
+    request_animation_frame(move |_| {
+        let id = urlencoding::decode(&fragment)
+            .map(|id| id.into_owned())
+            .unwrap_or(fragment);
+        if let Some(element) = window.document().and_then(|d| d.get_element_by_id(&id)) {
+            element.scroll_into_view();
+        }
+    })
+}
@@ -0,0 +1,67 @@
+use dioxus::prelude::ScopeState;
+
+use crate::prelude::{NavigationBlocker, RouterContext};
+
+/// A hook that blocks navigation while `blocking` is true, e.g. to prompt the user about unsaved
+/// changes.
+///
+/// Blocked navigations are held back until [`NavigationBlocker::proceed`] or
+/// [`NavigationBlocker::reset`] is called. The component rerenders when a navigation is blocked.
+///
+/// On the web, the browser also asks the user to confirm leaving the page while navigation is
+/// blocked.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// # #[component]
+/// # fn Index(cx: Scope) -> Element { todo!() }
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     #[route("/editor")]
+///     Editor {},
+/// }
+///
+/// #[component]
+/// fn Editor(cx: Scope) -> Element {
+///     let unsaved_changes = use_state(cx, || false);
+///     let blocker = use_navigation_blocker(cx, **unsaved_changes);
+///
+///     if blocker.is_blocked() {
+///         return render! {
+///             p { "You have unsaved changes. Leave anyway?" }
+///             button { onclick: move |_| blocker.proceed(), "Leave" }
+///             button { onclick: move |_| blocker.reset(), "Stay" }
+///         };
+///     }
+///
+///     render! {
+///         textarea { oninput: move |_| unsaved_changes.set(true) }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_navigation_blocker(cx: &ScopeState, blocking: bool) -> &NavigationBlocker {
+    let registration = cx.use_hook(|| {
+        let router = cx
+            .consume_context::<RouterContext>()
+            .expect("Must be called in a descendant of a Router component");
+        let id = router.register_blocker(cx.scope_id(), blocking);
+
+        BlockerRegistration(NavigationBlocker { router, id })
+    });
+
+    let blocker = &registration.0;
+    blocker.router.set_blocking(blocker.id, blocking);
+    blocker
+}
+
+struct BlockerRegistration(NavigationBlocker);
+
+impl Drop for BlockerRegistration {
+    fn drop(&mut self) {
+        self.0.router.unregister_blocker(self.0.id);
+    }
+}
@@ -0,0 +1,71 @@
+use std::rc::Rc;
+
+use dioxus::prelude::ScopeState;
+
+use crate::utils::use_router_internal::use_router_internal;
+
+/// A hook that provides access to the data of the loader of the current route.
+///
+/// The loader is declared with the `#[loader(..)]` attribute of the [`crate::prelude::Routable`] derive. The router
+/// starts it when the route is entered, so the route and all of its layouts can use this hook without waiting for
+/// each other.
+///
+/// # Return values
+/// - [`None`], while the loader is pending, if the current route has no loader, or if the data is not of type `T`.
+/// - Otherwise the data the loader produced.
+///
+/// The component is suspended while the loader is pending, so servers wait for the data before they send the page if
+/// the component returns [`None`].
+///
+/// # Panic
+/// - When the calling component is not nested within a [`crate::prelude::Router`] component during a debug build.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// # async fn get_post(id: usize) -> String { format!("Post {id}") }
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/post/:id")]
+///     #[loader(get_post(id))]
+///     Post { id: usize },
+/// }
+///
+/// #[component]
+/// fn Post(cx: Scope, id: usize) -> Element {
+///     let post = use_route_loader::<String>(cx)?;
+///     render! {
+///         h1 { "{post}" }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_route_loader<T: 'static>(cx: &ScopeState) -> Option<Rc<T>> {
+    let router = match use_router_internal(cx) {
+        Some(r) => r,
+        None => {
+            #[cfg(debug_assertions)]
+            panic!("`use_route_loader` must have access to a parent router");
+            #[allow(unreachable_code)]
+            return None;
+        }
+    };
+
+    match router.loader_data()? {
+        Some(data) => match data.downcast::<T>() {
+            Ok(data) => Some(data),
+            Err(_) => {
+                tracing::error!(
+                    "The loader of the current route does not produce a {}",
+                    std::any::type_name::<T>()
+                );
+                None
+            }
+        },
+        None => {
+            cx.suspend();
+            None
+        }
+    }
+}
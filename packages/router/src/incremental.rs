@@ -11,6 +11,9 @@ use dioxus_ssr::incremental::{
 use crate::prelude::*;
 
 /// Pre-cache all static routes.
+///
+/// This renders every route returned by [`Routable::static_routes`], including the values of dynamic routes that are
+/// listed with the `#[static_routes(..)]` attribute.
 pub async fn pre_cache_static_routes<Rt, R: WrapBody + Send + Sync>(
     renderer: &mut IncrementalRenderer,
     wrapper: &R,
@@ -19,54 +22,56 @@ where
     Rt: Routable,
     <Rt as FromStr>::Err: std::fmt::Display,
 {
-    for route in Rt::SITE_MAP
-        .iter()
-        .flat_map(|seg| seg.flatten().into_iter())
-    {
-        // check if this is a static segment
-        let mut is_static = true;
-        let mut full_path = String::new();
-        for segment in &route {
-            match segment {
-                SegmentType::Child => {}
-                SegmentType::Static(s) => {
-                    full_path += "/";
-                    full_path += s;
-                }
-                _ => {
-                    // skip routes with any dynamic segments
-                    is_static = false;
-                    break;
-                }
-            }
-        }
+    for route in Rt::static_routes() {
+        tracing::trace!("pre-caching route: {}", route);
+        render_route(
+            renderer,
+            route,
+            &mut tokio::io::sink(),
+            |vdom| {
+                Box::pin(async move {
+                    let _ = vdom.rebuild();
+                    vdom.wait_for_suspense().await;
+                })
+            },
+            wrapper,
+        )
+        .await?;
+    }
 
-        if is_static {
-            match Rt::from_str(&full_path) {
-                Ok(route) => {
-                    render_route(
-                        renderer,
-                        route,
-                        &mut tokio::io::sink(),
-                        |vdom| {
-                            Box::pin(async move {
-                                let _ = vdom.rebuild();
-                                vdom.wait_for_suspense().await;
-                            })
-                        },
-                        wrapper,
-                    )
-                    .await?;
-                }
-                Err(e) => {
-                    tracing::info!("@ route: {}", full_path);
-                    tracing::error!("Error pre-caching static route: {}", e);
-                }
+    Ok(())
+}
+
+/// Generate a `sitemap.xml` with every route returned by [`Routable::static_routes`].
+///
+/// The base url is the origin the site is hosted at, for example `https://dioxuslabs.com`.
+pub fn generate_sitemap<Rt>(base_url: &str) -> String
+where
+    Rt: Routable,
+    <Rt as FromStr>::Err: std::fmt::Display,
+{
+    let base_url = base_url.trim_end_matches('/');
+
+    let mut sitemap = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    for route in Rt::static_routes() {
+        sitemap += "  <url><loc>";
+        for c in format!("{base_url}{route}").chars() {
+            match c {
+                '&' => sitemap += "&amp;",
+                '<' => sitemap += "&lt;",
+                '>' => sitemap += "&gt;",
+                '"' => sitemap += "&quot;",
+                '\'' => sitemap += "&apos;",
+                c => sitemap.push(c),
             }
         }
+        sitemap += "</loc></url>\n";
     }
+    sitemap += "</urlset>\n";
 
-    Ok(())
+    sitemap
 }
 
 /// Render a route to a writer.
@@ -5,6 +5,7 @@
 #![deny(missing_docs)]
 #![allow(non_snake_case)]
 
+pub mod loader;
 pub mod navigation;
 pub mod routable;
 
@@ -30,9 +31,11 @@ pub mod components {
 }
 
 mod contexts {
+    pub(crate) mod blocker;
     pub(crate) mod navigator;
     pub(crate) mod outlet;
     pub(crate) mod router;
+    pub use blocker::*;
     pub use navigator::*;
     pub(crate) use router::*;
 }
@@ -51,6 +54,12 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_navigation_blocker;
+    pub use use_navigation_blocker::*;
+
+    mod use_route_loader;
+    pub use use_route_loader::*;
 }
 
 pub use hooks::router;
@@ -61,6 +70,7 @@ pub mod prelude {
     pub use crate::contexts::*;
     pub use crate::history::*;
     pub use crate::hooks::*;
+    pub use crate::loader::*;
     pub use crate::navigation::*;
     pub use crate::routable::*;
     pub use crate::router_cfg::RouterConfig;
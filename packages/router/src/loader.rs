@@ -0,0 +1,74 @@
+//! Loaders that fetch the data of a route while it renders.
+
+use std::{any::Any, future::Future, pin::Pin, rc::Rc};
+
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
+
+/// The loader of a route, declared with the `#[loader(..)]` attribute of the [`crate::prelude::Routable`] derive.
+///
+/// The router starts the loader when the route is entered, so the data is fetched in parallel with rendering the route
+/// and its layouts. The route and its layouts can read the data with [`crate::prelude::use_route_loader`].
+pub struct RouteLoader {
+    pub(crate) future: Pin<Box<dyn Future<Output = Rc<dyn Any>>>>,
+    pub(crate) serialize: fn(&dyn Any) -> Option<String>,
+    pub(crate) deserialize: fn(&str) -> Option<Rc<dyn Any>>,
+}
+
+impl RouteLoader {
+    /// Create a loader from the future that loads the data of a route.
+    ///
+    /// The data is not shared through the [`LoaderCache`] without the `serde` feature.
+    #[cfg(not(feature = "serde"))]
+    pub fn new<T, F>(future: F) -> Self
+    where
+        T: 'static,
+        F: Future<Output = T> + 'static,
+    {
+        Self {
+            future: Box::pin(async move { Rc::new(future.await) as Rc<dyn Any> }),
+            serialize: |_| None,
+            deserialize: |_| None,
+        }
+    }
+
+    /// Create a loader from the future that loads the data of a route.
+    ///
+    /// The data must be serializable so a server can send it to the client with the rendered page.
+    #[cfg(feature = "serde")]
+    pub fn new<T, F>(future: F) -> Self
+    where
+        T: Serialize + DeserializeOwned + 'static,
+        F: Future<Output = T> + 'static,
+    {
+        Self {
+            future: Box::pin(async move { Rc::new(future.await) as Rc<dyn Any> }),
+            serialize: |data| {
+                let data = data.downcast_ref::<T>()?;
+                serde_json::to_string(data)
+                    .map_err(|err| tracing::error!("Failed to serialize loader data: {err}"))
+                    .ok()
+            },
+            deserialize: |data| {
+                serde_json::from_str::<T>(data)
+                    .map(|data| Rc::new(data) as Rc<dyn Any>)
+                    .map_err(|err| tracing::error!("Failed to deserialize loader data: {err}"))
+                    .ok()
+            },
+        }
+    }
+}
+
+/// A place to share the data of loaders between renderers.
+///
+/// A server stores the data its loaders produced with the page it renders, and the client takes that data instead
+/// of running the loader of the initial route again. Set the cache with [`crate::prelude::RouterConfig::loader_cache`].
+///
+/// Loader data is only serialized with the `serde` feature.
+pub trait LoaderCache {
+    /// Take the serialized data of the loader of a route, if another renderer already loaded it.
+    fn take(&self, route: &str) -> Option<String>;
+
+    /// Store the serialized data the loader of a route produced.
+    fn store(&self, route: &str, data: String);
+}
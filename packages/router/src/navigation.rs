@@ -123,3 +123,43 @@ impl<R: Routable> FromStr for NavigationTarget<R> {
         }
     }
 }
+
+/// The kind of navigation the router is about to perform.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NavigationKind {
+    /// A new location is pushed onto the history.
+    Push,
+    /// The current location is replaced.
+    Replace,
+    /// The router goes back to the previous location.
+    GoBack,
+    /// The router goes forward to the next location.
+    GoForward,
+}
+
+/// A navigation that the router is about to perform.
+///
+/// This is passed to the guards registered with [`crate::prelude::RouterConfig::guard`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct PendingNavigation<R> {
+    /// The kind of the navigation.
+    pub kind: NavigationKind,
+    /// The route that is currently active.
+    pub from: R,
+    /// The target of the navigation.
+    ///
+    /// This is [`None`] when going back or forward, because only the history provider knows the
+    /// location it will go to.
+    pub to: Option<NavigationTarget<R>>,
+}
+
+/// What a navigation guard decided to do with a [`PendingNavigation`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum NavigationDecision<R> {
+    /// Continue with the navigation.
+    Allow,
+    /// Stay on the current route.
+    Cancel,
+    /// Navigate to another target instead. Guards are not run again for the new target.
+    Redirect(NavigationTarget<R>),
+}
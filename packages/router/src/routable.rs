@@ -75,6 +75,37 @@ where
     }
 }
 
+/// Something that can be created from a hash fragment.
+///
+/// This trait must be implemented for every type used as a hash fragment in the router macro. The fragment is
+/// written back into the url with [`Display`], an empty fragment is not written at all.
+pub trait FromHashFragment {
+    /// Create an instance of `Self` from a hash fragment.
+    fn from_hash_fragment(hash: &str) -> Self;
+}
+
+impl<T: Default + FromStr> FromHashFragment for T
+where
+    <T as FromStr>::Err: Display,
+{
+    fn from_hash_fragment(hash: &str) -> Self {
+        let result = match urlencoding::decode(hash) {
+            Ok(hash) => T::from_str(&hash),
+            Err(err) => {
+                tracing::error!("Failed to decode url encoding: {}", err);
+                T::from_str(hash)
+            }
+        };
+        match result {
+            Ok(result) => result,
+            Err(err) => {
+                tracing::error!("Failed to parse hash fragment: {}", err);
+                Default::default()
+            }
+        }
+    }
+}
+
 /// Something that can be created from a route segment.
 pub trait FromRouteSegment: Sized {
     /// The error that can occur when parsing a route segment.
@@ -294,21 +325,35 @@ pub trait Routable: FromStr + Display + Clone + 'static {
 
     /// Gets a list of all the static routes.
     /// Example static route: `#[route("/static/route")]`
+    ///
+    /// Routes with dynamic segments are included if they list their values with the `#[static_routes(..)]` attribute.
     fn static_routes() -> Vec<Self> {
-        Self::flatten_site_map()
-            .filter_map(|route| {
-                let route_if_static = &route
-                    .iter()
-                    .map(|segment| match segment {
-                        SegmentType::Static(s) => Some(*s),
-                        _ => None,
-                    })
-                    .collect::<Option<Vec<_>>>();
-
-                seg_strs_to_route(route_if_static)
-            })
-            .collect()
+        static_site_map_routes()
     }
+
+    /// Get the loader of this route, if it declares one with the `#[loader(..)]` attribute.
+    ///
+    /// The router starts the loader when the route is entered. Read the data with [`crate::prelude::use_route_loader`].
+    fn loader(&self) -> Option<crate::loader::RouteLoader> {
+        None
+    }
+}
+
+/// Gets a list of all the routes in [`Routable::SITE_MAP`] without any dynamic segments.
+pub fn static_site_map_routes<R: Routable>() -> Vec<R> {
+    R::flatten_site_map()
+        .filter_map(|route| {
+            let route_if_static = &route
+                .iter()
+                .map(|segment| match segment {
+                    SegmentType::Static(s) => Some(*s),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+
+            seg_strs_to_route(route_if_static)
+        })
+        .collect()
 }
 
 trait RoutableFactory {
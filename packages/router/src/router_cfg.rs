@@ -1,7 +1,10 @@
+use std::future::Future;
+use std::rc::Rc;
 use std::sync::Arc;
 
-use crate::contexts::router::RoutingCallback;
+use crate::contexts::router::{NavigationGuard, RoutingCallback};
 use crate::history::HistoryProvider;
+use crate::loader::LoaderCache;
 use crate::routable::Routable;
 use dioxus::prelude::*;
 
@@ -27,10 +30,16 @@ use crate::prelude::*;
 pub struct RouterConfig<R: Routable> {
     pub(crate) failure_external_navigation: fn(Scope) -> Element,
     pub(crate) history: Option<Box<dyn AnyHistoryProvider>>,
+    /// Creates the history if none was set.
+    pub(crate) default_history: fn() -> Box<dyn AnyHistoryProvider>,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) guards: Vec<NavigationGuard<R>>,
+    pub(crate) loader_cache: Option<Rc<dyn LoaderCache>>,
 }
 
-#[cfg(feature = "serde")]
+// With the `serde` feature the web history stores the route in the history state, so the router can only create it
+// for routes that can be serialized.
+#[cfg(all(feature = "serde", target_arch = "wasm32", feature = "web"))]
 impl<R: Routable + Clone> Default for RouterConfig<R>
 where
     <R as std::str::FromStr>::Err: std::fmt::Display,
@@ -38,36 +47,17 @@ where
 {
     fn default() -> Self {
         Self {
-            failure_external_navigation: FailureExternalNavigation::<R>,
+            failure_external_navigation: FailureExternalNavigation,
             history: None,
+            default_history: || Box::<AnyHistoryProviderImplWrapper<R, WebHistory<R>>>::default(),
             on_update: None,
+            guards: Vec::new(),
+            loader_cache: None,
         }
     }
 }
 
-#[cfg(feature = "serde")]
-impl<R: Routable + Clone> RouterConfig<R>
-where
-    <R as std::str::FromStr>::Err: std::fmt::Display,
-    R: serde::Serialize + serde::de::DeserializeOwned,
-{
-    pub(crate) fn get_history(self) -> Box<dyn HistoryProvider<R>> {
-        self.history.unwrap_or_else(|| {
-            #[cfg(all(not(feature = "liveview"), target_arch = "wasm32", feature = "web"))]
-            let history = Box::<WebHistory<R>>::default();
-            #[cfg(all(
-                not(feature = "liveview"),
-                any(not(target_arch = "wasm32"), not(feature = "web"))
-            ))]
-            let history = Box::<MemoryHistory<R>>::default();
-            #[cfg(feature = "liveview")]
-            let history = Box::<LiveviewHistory<R>>::default();
-            history
-        })
-    }
-}
-
-#[cfg(not(feature = "serde"))]
+#[cfg(not(all(feature = "serde", target_arch = "wasm32", feature = "web")))]
 impl<R: Routable + Clone> Default for RouterConfig<R>
 where
     <R as std::str::FromStr>::Err: std::fmt::Display,
@@ -76,32 +66,32 @@ where
         Self {
             failure_external_navigation: FailureExternalNavigation,
             history: None,
+            default_history: || {
+                // If we are on wasm32 and the web feature is enabled, use the web history.
+                #[cfg(all(target_arch = "wasm32", feature = "web"))]
+                let history = Box::<AnyHistoryProviderImplWrapper<R, WebHistory<R>>>::default();
+                // If we are not on wasm32 and the liveview feature is enabled, use the liveview history.
+                #[cfg(all(feature = "liveview", not(target_arch = "wasm32")))]
+                let history =
+                    Box::<AnyHistoryProviderImplWrapper<R, LiveviewHistory<R>>>::default();
+                // If neither of the above are true, use the memory history.
+                #[cfg(all(
+                    not(all(target_arch = "wasm32", feature = "web")),
+                    not(all(feature = "liveview", not(target_arch = "wasm32"))),
+                ))]
+                let history = Box::<AnyHistoryProviderImplWrapper<R, MemoryHistory<R>>>::default();
+                history
+            },
             on_update: None,
+            guards: Vec::new(),
+            loader_cache: None,
         }
     }
 }
 
-#[cfg(not(feature = "serde"))]
-impl<R: Routable + Clone> RouterConfig<R>
-where
-    <R as std::str::FromStr>::Err: std::fmt::Display,
-{
+impl<R: Routable> RouterConfig<R> {
     pub(crate) fn take_history(&mut self) -> Box<dyn AnyHistoryProvider> {
-        self.history.take().unwrap_or_else(|| {
-            // If we are on wasm32 and the web feature is enabled, use the web history.
-            #[cfg(all(target_arch = "wasm32", feature = "web"))]
-            let history = Box::<AnyHistoryProviderImplWrapper<R, WebHistory<R>>>::default();
-            // If we are not on wasm32 and the liveview feature is enabled, use the liveview history.
-            #[cfg(all(feature = "liveview", not(target_arch = "wasm32")))]
-            let history = Box::<AnyHistoryProviderImplWrapper<R, LiveviewHistory<R>>>::default();
-            // If neither of the above are true, use the memory history.
-            #[cfg(all(
-                not(all(target_arch = "wasm32", feature = "web")),
-                not(all(feature = "liveview", not(target_arch = "wasm32"))),
-            ))]
-            let history = Box::<AnyHistoryProviderImplWrapper<R, MemoryHistory<R>>>::default();
-            history
-        })
+        self.history.take().unwrap_or_else(self.default_history)
     }
 }
 
@@ -133,6 +123,67 @@ where
         }
     }
 
+    /// Add a guard that is run before every navigation.
+    ///
+    /// The guard is invoked before the router changes the route in `push`, `replace`, `go_back`
+    /// and `go_forward`. It can allow the navigation, cancel it, or redirect it to another target
+    /// (e.g. a login route). If multiple guards are added, they run in the order they were added
+    /// until one of them doesn't allow the navigation.
+    ///
+    /// Guards are async. While a guard is pending, the router stays on the current route. If
+    /// another navigation starts before the guard resolves, the pending navigation is dropped.
+    ///
+    /// ```rust,no_run
+    /// # use dioxus_router::prelude::*;
+    /// # use dioxus::prelude::*;
+    /// # #[component]
+    /// # fn Index(cx: Scope) -> Element { todo!() }
+    /// # #[component]
+    /// # fn Login(cx: Scope) -> Element { todo!() }
+    /// # #[component]
+    /// # fn Settings(cx: Scope) -> Element { todo!() }
+    /// # fn logged_in() -> bool { false }
+    /// #[derive(Clone, Routable)]
+    /// enum Route {
+    ///     #[route("/")]
+    ///     Index {},
+    ///     #[route("/login")]
+    ///     Login {},
+    ///     #[route("/settings")]
+    ///     Settings {},
+    /// }
+    ///
+    /// let cfg = RouterConfig::default().guard(|navigation: PendingNavigation<Route>| async move {
+    ///     match navigation.to {
+    ///         Some(NavigationTarget::Internal(Route::Settings {})) if !logged_in() => {
+    ///             NavigationDecision::Redirect(Route::Login {}.into())
+    ///         }
+    ///         _ => NavigationDecision::Allow,
+    ///     }
+    /// });
+    /// ```
+    pub fn guard<F>(mut self, guard: impl Fn(PendingNavigation<R>) -> F + 'static) -> Self
+    where
+        F: Future<Output = NavigationDecision<R>> + 'static,
+    {
+        self.guards
+            .push(Arc::new(move |navigation| Box::pin(guard(navigation))));
+        self
+    }
+
+    /// The [`LoaderCache`] the router shares the data of route loaders with.
+    ///
+    /// Renderers that hydrate a page rendered on a server, like `dioxus-fullstack`, use this to send the data the
+    /// server loaded to the client.
+    ///
+    /// Defaults to [`None`].
+    pub fn loader_cache(self, cache: impl LoaderCache + 'static) -> Self {
+        Self {
+            loader_cache: Some(Rc::new(cache)),
+            ..self
+        }
+    }
+
     /// The [`HistoryProvider`] the router should use.
     ///
     /// Defaults to a default [`MemoryHistory`].
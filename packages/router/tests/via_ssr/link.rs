@@ -116,6 +116,75 @@ fn href_external() {
     assert_eq!(prepare::<Route>(), expected);
 }
 
+#[test]
+fn href_hash_fragment() {
+    #[derive(Routable, Clone, Debug, PartialEq)]
+    enum Route {
+        #[route("/")]
+        Root {},
+        #[route("/docs/:page#:section")]
+        Docs { page: String, section: String },
+    }
+
+    #[component]
+    fn Docs(cx: Scope, page: String, section: String) -> Element {
+        render! { "{page}#{section}" }
+    }
+
+    #[component]
+    fn Root(cx: Scope) -> Element {
+        render! {
+            Link {
+                to: Route::Docs { page: "router".to_string(), section: "hash-fragments".to_string() },
+                "Link"
+            }
+        }
+    }
+
+    let expected = format!(
+        "<h1>App</h1><a {href} {default} {class} {id} {rel} {target}>Link</a>",
+        href = r#"href="/docs/router#hash-fragments""#,
+        default = r#"dioxus-prevent-default="onclick""#,
+        class = r#"class="""#,
+        id = r#"id="""#,
+        rel = r#"rel="""#,
+        target = r#"target="""#
+    );
+
+    assert_eq!(prepare::<Route>(), expected);
+
+    assert_eq!(
+        "/docs/router#hash-fragments".parse::<Route>().unwrap(),
+        Route::Docs {
+            page: "router".to_string(),
+            section: "hash-fragments".to_string()
+        }
+    );
+    assert_eq!(
+        "/docs/router".parse::<Route>().unwrap(),
+        Route::Docs {
+            page: "router".to_string(),
+            section: String::new()
+        }
+    );
+    assert_eq!(
+        Route::Docs {
+            page: "router".to_string(),
+            section: String::new()
+        }
+        .to_string(),
+        "/docs/router"
+    );
+
+    // Hash fragments are encoded so they survive a round trip through the url
+    let route = Route::Docs {
+        page: "router".to_string(),
+        section: "100% hash fragments".to_string(),
+    };
+    assert_eq!(route.to_string(), "/docs/router#100%25%20hash%20fragments");
+    assert_eq!(route.to_string().parse::<Route>().unwrap(), route);
+}
+
 #[test]
 fn with_class() {
     #[derive(Routable, Clone)]
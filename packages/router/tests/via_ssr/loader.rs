@@ -0,0 +1,127 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use std::{cell::RefCell, rc::Rc};
+
+#[rustfmt::skip]
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[layout(Layout)]
+        #[route("/post/:id")]
+        #[loader(async move {
+            tokio::task::yield_now().await;
+            format!("Post {id}")
+        })]
+        Post { id: usize },
+        #[route("/")]
+        Home {},
+}
+
+#[component]
+fn Layout(cx: Scope) -> Element {
+    let title = use_route_loader::<String>(cx);
+    let title = title.as_deref().map_or("Loading", String::as_str);
+    render! {
+        h1 { "{title}" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Post(cx: Scope, id: usize) -> Element {
+    let post = use_route_loader::<String>(cx)?;
+    render! { p { "{post} ({id})" } }
+}
+
+#[component]
+fn Home(cx: Scope) -> Element {
+    render! { "Home" }
+}
+
+#[derive(Default, Clone)]
+struct TestCache(Rc<RefCell<Vec<(String, String)>>>);
+
+impl LoaderCache for TestCache {
+    fn take(&self, route: &str) -> Option<String> {
+        let mut data = self.0.borrow_mut();
+        let index = data.iter().position(|(r, _)| r == route)?;
+        Some(data.remove(index).1)
+    }
+
+    fn store(&self, route: &str, data: String) {
+        self.0.borrow_mut().push((route.to_string(), data));
+    }
+}
+
+fn app(path: Route, cache: TestCache) -> VirtualDom {
+    #[derive(Props)]
+    struct AppProps {
+        path: Route,
+        cache: TestCache,
+    }
+
+    impl PartialEq for AppProps {
+        fn eq(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    #[component]
+    fn App(cx: Scope<AppProps>) -> Element {
+        let path = cx.props.path.clone();
+        let cache = cx.props.cache.clone();
+        render! {
+            Router::<Route> {
+                config: move || RouterConfig::default()
+                    .history(MemoryHistory::with_initial_path(path))
+                    .loader_cache(cache)
+            }
+        }
+    }
+
+    VirtualDom::new_with_props(App, AppProps { path, cache })
+}
+
+#[tokio::test]
+async fn loads_data_for_route_and_layouts() {
+    let cache = TestCache::default();
+    let mut vdom = app(Route::Post { id: 1 }, cache.clone());
+    let _ = vdom.rebuild();
+    assert!(vdom.has_suspended_work());
+
+    vdom.wait_for_suspense().await;
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<h1>Post 1</h1><p>Post 1 (1)</p>"
+    );
+
+    // The data is stored so the client doesn't have to load it again
+    #[cfg(feature = "serde")]
+    assert_eq!(
+        *cache.0.borrow(),
+        vec![("/post/1".to_string(), "\"Post 1\"".to_string())]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn uses_cached_data() {
+    let cache = TestCache::default();
+    cache.store("/post/2", "\"Cached post\"".to_string());
+    let mut vdom = app(Route::Post { id: 2 }, cache.clone());
+    let _ = vdom.rebuild();
+
+    assert_eq!(
+        dioxus_ssr::render(&vdom),
+        "<h1>Cached post</h1><p>Cached post (2)</p>"
+    );
+    assert!(cache.0.borrow().is_empty());
+}
+
+#[test]
+fn routes_without_loader() {
+    let mut vdom = app(Route::Home {}, TestCache::default());
+    let _ = vdom.rebuild();
+
+    assert!(!vdom.has_suspended_work());
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Loading</h1>Home");
+}
@@ -1,2 +1,5 @@
 mod link;
+mod loader;
+mod navigation_guards;
 mod outlet;
+mod static_routes;
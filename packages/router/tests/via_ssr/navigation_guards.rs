@@ -0,0 +1,215 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use std::{cell::Cell, cell::RefCell, rc::Rc, time::Duration};
+
+thread_local! {
+    static NAVIGATOR: RefCell<Option<Navigator>> = RefCell::new(None);
+    static BLOCKER: RefCell<Option<NavigationBlocker>> = RefCell::new(None);
+    static INTERCEPT: RefCell<Option<Rc<dyn Fn(NavigationKind)>>> = RefCell::new(None);
+}
+
+fn navigator() -> Navigator {
+    NAVIGATOR.with(|n| n.borrow().clone().unwrap())
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/login")]
+    Login {},
+    #[route("/private")]
+    Private {},
+    #[route("/editor")]
+    Editor {},
+}
+
+#[component]
+fn Home(cx: Scope) -> Element {
+    let navigator = use_navigator(cx);
+    NAVIGATOR.with(|n| *n.borrow_mut() = Some(navigator.clone()));
+    render! { "Home" }
+}
+
+#[component]
+fn Login(cx: Scope) -> Element {
+    render! { "Login" }
+}
+
+#[component]
+fn Private(cx: Scope) -> Element {
+    render! { "Private" }
+}
+
+#[component]
+fn Editor(cx: Scope) -> Element {
+    let navigator = use_navigator(cx);
+    NAVIGATOR.with(|n| *n.borrow_mut() = Some(navigator.clone()));
+    let blocker = use_navigation_blocker(cx, true);
+    BLOCKER.with(|b| *b.borrow_mut() = Some(blocker.clone()));
+    render! {
+        if blocker.is_blocked() {
+            "Leave?"
+        } else {
+            "Editor"
+        }
+    }
+}
+
+fn render(cfg: fn() -> RouterConfig<Route>) -> VirtualDom {
+    #[component]
+    fn App(cx: Scope<fn() -> RouterConfig<Route>>) -> Element {
+        let cfg = *cx.props;
+        render! {
+            Router::<Route> {
+                config: cfg
+            }
+        }
+    }
+
+    let mut vdom = VirtualDom::new_with_props(App, cfg);
+    let _ = vdom.rebuild();
+    vdom
+}
+
+fn rerender(vdom: &mut VirtualDom) -> String {
+    let _ = vdom.render_immediate();
+    dioxus_ssr::render(vdom)
+}
+
+#[test]
+fn guard_redirects() {
+    let mut vdom = render(|| {
+        RouterConfig::default()
+            .history(MemoryHistory::default())
+            .guard(|navigation: PendingNavigation<Route>| async move {
+                match navigation.to {
+                    Some(NavigationTarget::Internal(Route::Private {})) => {
+                        NavigationDecision::Redirect(Route::Login {}.into())
+                    }
+                    _ => NavigationDecision::Allow,
+                }
+            })
+    });
+    assert_eq!(dioxus_ssr::render(&vdom), "Home");
+
+    navigator().push(Route::Private {});
+    assert_eq!(rerender(&mut vdom), "Login");
+}
+
+#[test]
+fn guard_cancels() {
+    let mut vdom = render(|| {
+        RouterConfig::default()
+            .history(MemoryHistory::default())
+            .guard(|navigation: PendingNavigation<Route>| async move {
+                assert_eq!(navigation.from, Route::Home {});
+                assert_eq!(navigation.kind, NavigationKind::Push);
+                NavigationDecision::Cancel
+            })
+    });
+
+    navigator().push(Route::Private {});
+    assert_eq!(rerender(&mut vdom), "Home");
+}
+
+#[test]
+fn blocker_holds_navigation() {
+    let mut vdom = render(|| {
+        RouterConfig::default().history(MemoryHistory::with_initial_path(Route::Editor {}))
+    });
+    assert_eq!(dioxus_ssr::render(&vdom), "Editor");
+
+    navigator().push(Route::Home {});
+    assert_eq!(rerender(&mut vdom), "Leave?");
+
+    let blocker = BLOCKER.with(|b| b.borrow().clone().unwrap());
+    blocker.reset();
+    assert_eq!(rerender(&mut vdom), "Editor");
+
+    navigator().push(Route::Home {});
+    assert_eq!(rerender(&mut vdom), "Leave?");
+    blocker.proceed();
+    assert_eq!(rerender(&mut vdom), "Home");
+}
+
+/// A history the user can move through without going through the router, like the history of a browser
+struct BrowserHistory(MemoryHistory<Route>);
+
+impl HistoryProvider<Route> for BrowserHistory {
+    fn current_route(&self) -> Route {
+        self.0.current_route()
+    }
+
+    fn can_go_back(&self) -> bool {
+        self.0.can_go_back()
+    }
+
+    fn go_back(&mut self) {
+        self.0.go_back()
+    }
+
+    fn can_go_forward(&self) -> bool {
+        self.0.can_go_forward()
+    }
+
+    fn go_forward(&mut self) {
+        self.0.go_forward()
+    }
+
+    fn push(&mut self, route: Route) {
+        self.0.push(route)
+    }
+
+    fn replace(&mut self, route: Route) {
+        self.0.replace(route)
+    }
+
+    fn intercept(&mut self, callback: Option<Rc<dyn Fn(NavigationKind)>>) {
+        INTERCEPT.with(|i| *i.borrow_mut() = callback);
+    }
+}
+
+/// Press the back button of the browser. The history undid the navigation and hands it to the router.
+fn browser_back() {
+    let intercept = INTERCEPT.with(|i| i.borrow().clone());
+    (intercept.expect("the router intercepts navigations while it has guards"))(
+        NavigationKind::GoBack,
+    );
+}
+
+#[tokio::test]
+async fn async_guard_runs_for_browser_navigation() {
+    thread_local! {
+        static ALLOW_BACK: Cell<bool> = Cell::new(false);
+    }
+
+    let mut vdom = render(|| {
+        RouterConfig::default()
+            .history(BrowserHistory(MemoryHistory::default()))
+            .guard(|navigation: PendingNavigation<Route>| async move {
+                tokio::task::yield_now().await;
+                match navigation.kind {
+                    NavigationKind::GoBack if !ALLOW_BACK.with(Cell::get) => {
+                        NavigationDecision::Cancel
+                    }
+                    _ => NavigationDecision::Allow,
+                }
+            })
+    });
+
+    // Guards that don't resolve immediately spawn a task, like they would in an event handler
+    RuntimeGuard::with(vdom.runtime(), None, || navigator().push(Route::Private {}));
+    vdom.wait_for_work().await;
+    assert_eq!(rerender(&mut vdom), "Private");
+
+    // The guard rejects the navigation, so the router stays on the current route
+    browser_back();
+    let _ = tokio::time::timeout(Duration::from_millis(100), vdom.wait_for_work()).await;
+    assert_eq!(rerender(&mut vdom), "Private");
+
+    ALLOW_BACK.with(|allow| allow.set(true));
+    browser_back();
+    vdom.wait_for_work().await;
+    assert_eq!(rerender(&mut vdom), "Home");
+}
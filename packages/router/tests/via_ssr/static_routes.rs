@@ -0,0 +1,50 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[component]
+fn Home(cx: Scope) -> Element {
+    render! { "Home" }
+}
+
+#[component]
+fn Post(cx: Scope, id: usize) -> Element {
+    render! { "Post {id}" }
+}
+
+#[component]
+fn User(cx: Scope, name: String) -> Element {
+    render! { "User {name}" }
+}
+
+#[derive(Routable, Clone, Debug, PartialEq)]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/post/:id")]
+    #[static_routes((0..2).map(|id| Route::Post { id }))]
+    Post { id: usize },
+    #[route("/user/:name")]
+    User { name: String },
+}
+
+#[test]
+fn dynamic_static_routes() {
+    assert_eq!(
+        Route::static_routes(),
+        [Route::Home {}, Route::Post { id: 0 }, Route::Post { id: 1 }]
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn sitemap() {
+    assert_eq!(
+        generate_sitemap::<Route>("https://example.com/"),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n  \
+         <url><loc>https://example.com/</loc></url>\n  \
+         <url><loc>https://example.com/post/0</loc></url>\n  \
+         <url><loc>https://example.com/post/1</loc></url>\n\
+         </urlset>\n"
+    );
+}
@@ -0,0 +1,848 @@
+//! Translate JSX, the markup React components are written in, into rsx.
+//!
+//! The markup translates directly. The javascript in between only translates where rsx has an equivalent, like
+//! `cond && <div />`, ternaries and `.map` calls that return JSX, or where it is also valid rust. Anything else is
+//! kept in a `todo!()` so it is easy to find.
+
+use std::fmt::{Display, Formatter};
+
+use convert_case::{Case, Casing};
+use dioxus_html::{map_html_attribute_to_rsx, map_html_style_to_rsx};
+use dioxus_rsx::ElementAttrName;
+use proc_macro2::Span;
+use syn::LitStr;
+
+use crate::writer::{escape_braces, text_literal, RsxWriter};
+use crate::{builtin_attribute, element_name};
+
+/// CSS properties that React doesn't add `px` to when they are numbers
+const UNITLESS_PROPERTIES: &[&str] = &[
+    "animation-iteration-count",
+    "column-count",
+    "flex",
+    "flex-grow",
+    "flex-shrink",
+    "font-weight",
+    "grid-column",
+    "grid-row",
+    "line-height",
+    "opacity",
+    "order",
+    "orphans",
+    "tab-size",
+    "widows",
+    "z-index",
+    "zoom",
+];
+
+/// JSX that couldn't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsxError {
+    pub message: String,
+    /// The line of the error, starting at 1
+    pub line: usize,
+    /// The column of the error in characters, starting at 1
+    pub column: usize,
+}
+
+impl Display for JsxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}:{}", self.message, self.line, self.column)
+    }
+}
+
+impl std::error::Error for JsxError {}
+
+/// Convert JSX or TSX into the source of an rsx body, keeping its comments
+///
+/// The input can be JSX, or a component that returns JSX. Elements with capitalized names become components, and their
+/// props are renamed to snake_case.
+///
+/// The source isn't formatted, parse it and write it out with `dioxus_autofmt::write_block_out_with_source` to format
+/// it with the comments.
+pub fn rsx_source_from_jsx(jsx: &str) -> Result<String, JsxError> {
+    let mut parser = Parser {
+        src: jsx,
+        pos: jsx_start(jsx).unwrap_or_default(),
+    };
+    if parser.peek() != Some('<') {
+        return Err(parser.error("expected JSX"));
+    }
+    let node = parser.element()?;
+
+    let mut writer = RsxWriter::new();
+    write_children(&mut writer, std::slice::from_ref(&node));
+    Ok(writer.finish())
+}
+
+/// Where the JSX starts: at the start of the input, or after the `return` or `=>` of a component
+fn jsx_start(source: &str) -> Option<usize> {
+    let trimmed = source.trim_start();
+    if trimmed.starts_with('<') {
+        return Some(source.len() - trimmed.len());
+    }
+
+    for keyword in ["return", "=>"] {
+        for (index, _) in source.match_indices(keyword) {
+            let after = &source[index + keyword.len()..];
+            let jsx = after.trim_start_matches(|c: char| c == '(' || c.is_whitespace());
+            if jsx.starts_with('<') {
+                return Some(source.len() - jsx.len());
+            }
+        }
+    }
+
+    None
+}
+
+enum Node {
+    Element(JsxElement),
+    Fragment(Vec<Node>),
+    Text(String),
+    Comment(String),
+    Expression(Expression),
+}
+
+struct JsxElement {
+    name: String,
+    attributes: Vec<Attribute>,
+    children: Vec<Node>,
+}
+
+enum Attribute {
+    Named(String, Value),
+    /// `{...props}`
+    Spread(Expression),
+}
+
+enum Value {
+    /// An attribute without a value, like `disabled`
+    True,
+    Text(String),
+    Expression(Expression),
+}
+
+/// A javascript expression, with the JSX inside it parsed
+struct Expression {
+    parts: Vec<Part>,
+    source: String,
+}
+
+enum Part {
+    Code(String),
+    Jsx(Node),
+}
+
+impl Expression {
+    /// The code of the expression, if there is no JSX inside it
+    fn code(&self) -> Option<&str> {
+        match self.parts.as_slice() {
+            [] => Some(""),
+            [Part::Code(code)] => Some(code.trim()),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), JsxError> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(format!("expected `{}`", token))),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn error(&self, message: impl Into<String>) -> JsxError {
+        let before = &self.src[..self.pos];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        JsxError {
+            message: message.into(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// An element or a fragment, starting at its `<`
+    fn element(&mut self) -> Result<Node, JsxError> {
+        self.expect("<")?;
+        self.skip_whitespace();
+        if self.eat(">") {
+            let children = self.children()?;
+            self.closing_tag("")?;
+            return Ok(Node::Fragment(children));
+        }
+
+        let name = self.name()?;
+        let mut attributes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.eat("/>") {
+                return Ok(Node::Element(JsxElement {
+                    name,
+                    attributes,
+                    children: Vec::new(),
+                }));
+            }
+            if self.eat(">") {
+                break;
+            }
+            if self.eat("{") {
+                self.skip_whitespace();
+                self.expect("...")?;
+                attributes.push(Attribute::Spread(self.expression()?));
+                continue;
+            }
+
+            let attribute = self.name()?;
+            self.skip_whitespace();
+            let value = match self.eat("=") {
+                true => {
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(quote @ ('"' | '\'')) => {
+                            self.bump();
+                            let len = self
+                                .rest()
+                                .find(quote)
+                                .ok_or_else(|| self.error("unclosed string"))?;
+                            let text = decode_entities(&self.rest()[..len]);
+                            self.pos += len + 1;
+                            Value::Text(text)
+                        }
+                        Some('{') => {
+                            self.bump();
+                            Value::Expression(self.expression()?)
+                        }
+                        _ => return Err(self.error("expected an attribute value")),
+                    }
+                }
+                false => Value::True,
+            };
+            attributes.push(Attribute::Named(attribute, value));
+        }
+
+        let children = self.children()?;
+        self.closing_tag(&name)?;
+        Ok(Node::Element(JsxElement {
+            name,
+            attributes,
+            children,
+        }))
+    }
+
+    fn closing_tag(&mut self, name: &str) -> Result<(), JsxError> {
+        self.expect("</")?;
+        self.skip_whitespace();
+        if !name.is_empty() && self.name()? != name {
+            return Err(self.error(format!("expected `</{}>`", name)));
+        }
+        self.skip_whitespace();
+        self.expect(">")
+    }
+
+    /// The name of an element or attribute, like `div`, `Foo.Bar` or `aria-label`
+    fn name(&mut self) -> Result<String, JsxError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':' | '$')))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        self.pos += len;
+        Ok(rest[..len].to_string())
+    }
+
+    /// The children of an element, up to its closing tag
+    fn children(&mut self) -> Result<Vec<Node>, JsxError> {
+        let mut children = Vec::new();
+        loop {
+            if self.rest().starts_with("</") {
+                return Ok(children);
+            }
+
+            match self.peek() {
+                None => return Err(self.error("expected a closing tag")),
+                Some('<') => children.push(self.element()?),
+                Some('{') => {
+                    self.bump();
+                    let expression = self.expression()?;
+                    match expression.code() {
+                        Some("") => {}
+                        Some(code) if comment_text(code).is_some() => {
+                            children.push(Node::Comment(comment_text(code).unwrap()))
+                        }
+                        _ => children.push(Node::Expression(expression)),
+                    }
+                }
+                Some(_) => {
+                    let rest = self.rest();
+                    let len = rest.find(['<', '{']).unwrap_or(rest.len());
+                    let text = jsx_text(&rest[..len]);
+                    self.pos += len;
+                    if !text.is_empty() {
+                        children.push(Node::Text(text));
+                    }
+                }
+            }
+        }
+    }
+
+    /// A javascript expression up to the `}` that closes it
+    fn expression(&mut self) -> Result<Expression, JsxError> {
+        let start = self.pos;
+        let mut parts = Vec::new();
+        let mut code = String::new();
+        let mut depth = 0usize;
+
+        loop {
+            let rest = self.rest();
+            let Some(c) = self.peek() else {
+                return Err(self.error("expected `}`"));
+            };
+
+            if let Some(comment) = rest.strip_prefix("//").or_else(|| rest.strip_prefix("/*")) {
+                let end = if rest.starts_with("//") { "\n" } else { "*/" };
+                let len = comment
+                    .find(end)
+                    .map(|i| 2 + i + end.len())
+                    .unwrap_or(rest.len());
+                code.push_str(&rest[..len]);
+                self.pos += len;
+                continue;
+            }
+
+            match c {
+                '"' | '\'' | '`' => {
+                    let len = string_len(rest).ok_or_else(|| self.error("unclosed string"))?;
+                    code.push_str(&rest[..len]);
+                    self.pos += len;
+                }
+                '(' | '[' | '{' => {
+                    depth += 1;
+                    code.push(c);
+                    self.bump();
+                }
+                '}' if depth == 0 => break,
+                ')' | ']' | '}' => {
+                    depth = depth.saturating_sub(1);
+                    code.push(c);
+                    self.bump();
+                }
+                '<' if jsx_can_start(&code, &parts)
+                    && rest[1..].starts_with(|c: char| c.is_alphabetic() || c == '>') =>
+                {
+                    if !code.is_empty() {
+                        parts.push(Part::Code(std::mem::take(&mut code)));
+                    }
+                    parts.push(Part::Jsx(self.element()?));
+                }
+                _ => {
+                    code.push(c);
+                    self.bump();
+                }
+            }
+        }
+
+        let source = self.src[start..self.pos].trim().to_string();
+        self.bump();
+        if !code.is_empty() {
+            parts.push(Part::Code(code));
+        }
+        Ok(Expression { parts, source })
+    }
+}
+
+/// Whether a `<` after some code starts JSX, instead of comparing two values
+fn jsx_can_start(code: &str, parts: &[Part]) -> bool {
+    let code = code.trim_end();
+    if code.is_empty() {
+        return parts.is_empty();
+    }
+    code.ends_with(['(', '[', '{', ',', '?', ':', '&', '|', '=', '>', '!'])
+        || code.ends_with("return")
+}
+
+/// The length of the string literal at the start of some code, including its quotes
+fn string_len(code: &str) -> Option<usize> {
+    let quote = code.chars().next()?;
+    let mut chars = code.char_indices().skip(1);
+    let mut depth = 0usize;
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            // Template literals can have strings inside their `${}`
+            '{' if quote == '`' && code[..index].ends_with('$') => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            c if c == quote && depth == 0 => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Collapse the whitespace in JSX text like React does: lines are trimmed and joined with a space, and empty lines
+/// are dropped
+fn jsx_text(raw: &str) -> String {
+    let lines: Vec<_> = raw.split('\n').collect();
+    let last = lines.len() - 1;
+
+    let mut text = String::new();
+    for (index, line) in lines.into_iter().enumerate() {
+        let line = line.trim_end_matches('\r');
+        let line = if index > 0 { line.trim_start() } else { line };
+        let line = if index < last { line.trim_end() } else { line };
+        if line.is_empty() {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(line);
+    }
+
+    decode_entities(&text)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", "\u{a0}")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The text of a comment like `/* comment */` or `// comment`
+fn comment_text(code: &str) -> Option<String> {
+    let lines: Vec<_> = if let Some(inner) = code.strip_prefix("/*") {
+        let inner = inner.strip_suffix("*/")?;
+        if inner.contains("*/") {
+            return None;
+        }
+        inner
+            .lines()
+            .map(|line| line.trim().trim_start_matches('*').trim())
+            .collect()
+    } else {
+        code.lines()
+            .map(|line| line.trim().strip_prefix("//").map(str::trim))
+            .collect::<Option<_>>()?
+    };
+
+    Some(lines.join("\n").trim().to_string())
+}
+
+/// The contents of a string literal as the format string of rsx text
+fn format_contents(code: &str) -> Option<String> {
+    let quote = code.chars().next()?;
+    let inner = code.get(1..code.len().checked_sub(1)?)?;
+    if !matches!(quote, '"' | '\'' | '`') || !code.ends_with(quote) || inner.contains(quote) {
+        return None;
+    }
+    match quote {
+        '`' => Some(inner.replace("${", "{")),
+        _ => Some(escape_braces(inner)),
+    }
+}
+
+/// Whether some code is a variable or a field, like `name` or `user.name`, which rsx can format
+fn is_path(code: &str) -> bool {
+    code.split('.').all(|segment| {
+        segment.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && segment.chars().all(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+fn is_event(attribute: &str) -> bool {
+    attribute
+        .strip_prefix("on")
+        .is_some_and(|event| event.starts_with(char::is_uppercase))
+}
+
+/// Javascript code as rust, if it is also valid rust, or a `todo!()` with the javascript
+fn rust_code(code: &str) -> String {
+    let rust = code.trim().replace("===", "==").replace("!==", "!=");
+    match syn::parse_str::<syn::Expr>(&rust) {
+        Ok(_) => rust,
+        Err(_) => todo(code),
+    }
+}
+
+fn rust_expression(expression: &Expression) -> String {
+    match expression.code() {
+        Some(code) => rust_code(code),
+        None => todo(&expression.source),
+    }
+}
+
+fn todo(javascript: &str) -> String {
+    format!("todo!({:?})", escape_braces(javascript.trim()))
+}
+
+/// Split code at the commas that aren't nested in brackets or strings
+fn split_top_level(code: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut start = 0;
+
+    for (index, c) in code.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(c),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                parts.push(&code[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&code[start..]);
+    parts
+}
+
+fn write_children(writer: &mut RsxWriter, children: &[Node]) {
+    // Text, strings and variables next to each other become one formatted string
+    let mut format = String::new();
+
+    for child in children {
+        match child {
+            Node::Text(text) => format.push_str(&escape_braces(text)),
+            Node::Expression(expression) => match expression.code() {
+                Some(code) if is_path(code) => {
+                    format.push('{');
+                    format.push_str(code);
+                    format.push('}');
+                }
+                Some(code) if format_contents(code).is_some() => {
+                    format.push_str(&format_contents(code).unwrap())
+                }
+                _ => {
+                    flush_text(writer, &mut format);
+                    write_expression(writer, expression);
+                }
+            },
+            _ => {
+                flush_text(writer, &mut format);
+                write_node(writer, child);
+            }
+        }
+    }
+
+    flush_text(writer, &mut format);
+}
+
+fn flush_text(writer: &mut RsxWriter, format: &mut String) {
+    if !format.trim().is_empty() {
+        writer.formatted(format);
+    }
+    format.clear();
+}
+
+fn write_node(writer: &mut RsxWriter, node: &Node) {
+    match node {
+        Node::Text(text) => writer.text(text),
+        Node::Comment(comment) => writer.comment(comment),
+        Node::Fragment(children) => write_children(writer, children),
+        Node::Element(el) if el.name == "Fragment" || el.name == "React.Fragment" => {
+            write_children(writer, &el.children)
+        }
+        Node::Element(el) if el.name.starts_with(char::is_uppercase) || el.name.contains('.') => {
+            write_component(writer, el)
+        }
+        Node::Element(el) => write_element(writer, el),
+        Node::Expression(expression) => write_expression(writer, expression),
+    }
+}
+
+fn write_element(writer: &mut RsxWriter, el: &JsxElement) {
+    writer.open_element(&element_name(&el.name));
+
+    for attribute in &el.attributes {
+        match attribute {
+            Attribute::Named(name, Value::Expression(expression)) if name == "style" => {
+                write_style(writer, expression)
+            }
+            Attribute::Named(name, value) => {
+                let value = match value {
+                    Value::True => "true".to_string(),
+                    Value::Text(text) => text_literal(text),
+                    Value::Expression(expression) => match expression.code() {
+                        // Event handlers take the function, other attributes format the value
+                        Some(code) if is_path(code) && !is_event(name) => {
+                            format!("{:?}", format!("{{{}}}", code))
+                        }
+                        _ => rust_expression(expression),
+                    },
+                };
+                writer.attribute(&attribute_name(name), &value);
+            }
+            Attribute::Spread(expression) => {
+                writer.field(&format!("..{}", rust_expression(expression)))
+            }
+        }
+    }
+
+    write_children(writer, &el.children);
+    writer.close();
+}
+
+/// The rsx name of a JSX attribute. React spells html attributes in camelCase, like `tabIndex` and `strokeWidth`.
+fn attribute_name(name: &str) -> ElementAttrName {
+    let name = match name {
+        "className" => "class",
+        "htmlFor" => "for",
+        "key" => return builtin_attribute("key"),
+        name if is_event(name) => return builtin_attribute(&name.to_lowercase()),
+        name => name,
+    };
+
+    [
+        name.to_string(),
+        name.to_lowercase(),
+        name.to_case(Case::Kebab),
+    ]
+    .iter()
+    .find_map(|name| map_html_attribute_to_rsx(name))
+    .map(builtin_attribute)
+    .unwrap_or_else(|| ElementAttrName::Custom(LitStr::new(name, Span::call_site())))
+}
+
+/// Style objects like `style={{ fontSize: 12 }}` become style attributes like `font_size: "12px"`
+fn write_style(writer: &mut RsxWriter, expression: &Expression) {
+    let style = builtin_attribute("style");
+    let Some(object) = expression
+        .code()
+        .and_then(|code| code.strip_prefix('{')?.strip_suffix('}'))
+    else {
+        writer.attribute(&style, &rust_expression(expression));
+        return;
+    };
+
+    // Properties rsx doesn't know stay in a `style` attribute
+    let mut unknown = Vec::new();
+    let mut translated = true;
+
+    for entry in split_top_level(object) {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let (key, raw) = entry.split_once(':').unwrap_or((entry, entry));
+        let property = key.trim().trim_matches(['"', '\'']).to_case(Case::Kebab);
+        let value = style_value(&property, raw.trim());
+
+        match (map_html_style_to_rsx(&property), value) {
+            (Some(name), Some(value)) => {
+                writer.attribute(&builtin_attribute(name), &format!("{:?}", value))
+            }
+            (Some(name), None) => writer.attribute(&builtin_attribute(name), &todo(raw)),
+            (None, Some(value)) => unknown.push(format!("{}: {}", property, value)),
+            (None, None) => translated = false,
+        }
+    }
+
+    if !translated {
+        writer.attribute(&style, &rust_expression(expression));
+    } else if !unknown.is_empty() {
+        writer.attribute(&style, &format!("{:?}", unknown.join("; ")));
+    }
+}
+
+/// The value of a style property as a format string, if it is a string, a number or a variable
+fn style_value(property: &str, value: &str) -> Option<String> {
+    if let Some(contents) = format_contents(value) {
+        return Some(contents);
+    }
+    if value.parse::<f64>().is_ok() {
+        return Some(
+            match UNITLESS_PROPERTIES.contains(&property) || value == "0" {
+                true => value.to_string(),
+                false => format!("{}px", value),
+            },
+        );
+    }
+    if is_path(value) {
+        return Some(format!("{{{}}}", value));
+    }
+    None
+}
+
+fn write_component(writer: &mut RsxWriter, el: &JsxElement) {
+    writer.open(&el.name.replace('.', "::"));
+
+    for attribute in &el.attributes {
+        match attribute {
+            Attribute::Named(name, value) => {
+                let value = match value {
+                    Value::True => "true".to_string(),
+                    Value::Text(text) => text_literal(text),
+                    Value::Expression(expression) => rust_expression(expression),
+                };
+                writer.field(&format!("{}: {}", prop_name(name), value));
+            }
+            Attribute::Spread(expression) => {
+                writer.field(&format!("..{}", rust_expression(expression)))
+            }
+        }
+    }
+
+    write_children(writer, &el.children);
+    writer.close();
+}
+
+/// Props are struct fields, which are snake_case in rust
+fn prop_name(name: &str) -> String {
+    let name = match name {
+        "className" => "class".to_string(),
+        name => name.to_case(Case::Snake),
+    };
+    match syn::parse_str::<syn::Ident>(&name) {
+        Ok(_) => name,
+        Err(_) => format!("r#{}", name),
+    }
+}
+
+fn write_expression(writer: &mut RsxWriter, expression: &Expression) {
+    if !write_control_flow(writer, expression) {
+        writer.expression(&rust_expression(expression));
+    }
+}
+
+/// Translate javascript with JSX inside it into rsx control flow:
+///
+/// - `cond && <div />` becomes `if cond { div {} }`
+/// - `cond ? <a /> : <b />` becomes `if cond { a {} } else { b {} }`
+/// - `items.map(item => <li />)` becomes `for item in items { li {} }`
+fn write_control_flow(writer: &mut RsxWriter, expression: &Expression) -> bool {
+    let [Part::Code(before), Part::Jsx(node), rest @ ..] = expression.parts.as_slice() else {
+        return false;
+    };
+    let before = before.trim_end_matches(|c: char| c == '(' || c.is_whitespace());
+
+    if let [Part::Code(middle), Part::Jsx(otherwise), rest @ ..] = rest {
+        let Some(cond) = before.strip_suffix('?') else {
+            return false;
+        };
+        if trim_parens(middle) != ":" || !only_parens(rest) {
+            return false;
+        }
+        writer.open(&format!("if {}", rust_code(cond)));
+        write_node(writer, node);
+        writer.open_else();
+        write_node(writer, otherwise);
+        writer.close();
+        return true;
+    }
+
+    let after = match rest {
+        [] => "",
+        [Part::Code(after)] => trim_parens(after),
+        _ => return false,
+    };
+    let head = match after {
+        "" => match before.strip_suffix("&&") {
+            Some(cond) => Some(format!("if {}", rust_code(cond))),
+            None => map_loop(before),
+        },
+        // `cond ? <div /> : null`
+        _ => match (
+            before.strip_suffix('?'),
+            after.strip_prefix(':').map(str::trim),
+        ) {
+            (Some(cond), Some("null" | "undefined" | "false")) => {
+                Some(format!("if {}", rust_code(cond)))
+            }
+            _ => None,
+        },
+    };
+
+    let Some(head) = head else {
+        return false;
+    };
+    writer.open(&head);
+    write_node(writer, node);
+    writer.close();
+    true
+}
+
+fn trim_parens(code: &str) -> &str {
+    code.trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+}
+
+fn only_parens(parts: &[Part]) -> bool {
+    parts.iter().all(|part| match part {
+        Part::Code(code) => trim_parens(code).is_empty(),
+        Part::Jsx(_) => false,
+    })
+}
+
+/// The head of a `for` loop for the start of a `.map` call, like `items.map((item, index) =>`
+fn map_loop(code: &str) -> Option<String> {
+    let code = code.trim_end().strip_suffix("=>")?;
+    let index = code.rfind(".map(")?;
+    let list = rust_code(&code[..index]);
+
+    let params = code[index + ".map(".len()..].trim();
+    let params = params
+        .strip_prefix('(')
+        .and_then(|params| params.strip_suffix(')'))
+        .unwrap_or(params);
+    let params = split_top_level(params)
+        .into_iter()
+        .map(|param| {
+            // TSX params can have types
+            let name = param.split(':').next().unwrap_or_default().trim();
+            syn::parse_str::<syn::Ident>(name).ok().map(|_| name)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    match params.as_slice() {
+        [item] => Some(format!("for {} in {}", item, list)),
+        [item, index] => Some(format!(
+            "for ({}, {}) in {}.iter().enumerate()",
+            index, item, list
+        )),
+        _ => None,
+    }
+}
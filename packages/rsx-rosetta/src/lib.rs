@@ -3,14 +3,22 @@
 #![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
 
 use convert_case::{Case, Casing};
-use dioxus_html::{map_html_attribute_to_rsx, map_html_element_to_rsx};
+use dioxus_html::{map_html_attribute_to_rsx, map_html_element_to_rsx, map_html_style_to_rsx};
 use dioxus_rsx::{
-    AttributeType, BodyNode, CallBody, Component, Element, ElementAttr, ElementAttrNamed,
-    ElementName, IfmtInput,
+    AttributeType, BodyNode, CallBody, Component, Element, ElementAttr, ElementAttrName,
+    ElementAttrNamed, ElementAttrValue, ElementName, IfmtInput,
 };
 pub use html_parser::{Dom, Node};
 use proc_macro2::{Ident, Span};
 use syn::{punctuated::Punctuated, LitStr};
+use writer::{text_literal, RsxWriter};
+
+mod jsx;
+mod to_html;
+mod writer;
+
+pub use jsx::{rsx_source_from_jsx, JsxError};
+pub use to_html::html_from_rsx;
 
 /// Convert an HTML DOM tree into an RSX CallBody
 pub fn rsx_from_html(dom: &Dom) -> CallBody {
@@ -26,78 +34,21 @@ pub fn rsx_node_from_html(node: &Node) -> Option<BodyNode> {
     match node {
         Node::Text(text) => Some(BodyNode::Text(ifmt_from_text(text))),
         Node::Element(el) => {
-            let el_name = if let Some(name) = map_html_element_to_rsx(&el.name) {
-                ElementName::Ident(Ident::new(name, Span::call_site()))
-            } else {
-                // if we don't recognize it and it has a dash, we assume it's a web component
-                if el.name.contains('-') {
-                    ElementName::Custom(LitStr::new(&el.name, Span::call_site()))
-                } else {
-                    // otherwise, it might be an element that isn't supported yet
-                    ElementName::Ident(Ident::new(&el.name.to_case(Case::Snake), Span::call_site()))
-                }
-            };
-
-            let mut attributes: Vec<_> = el
-                .attributes
-                .iter()
-                .map(|(name, value)| {
-                    let value = ifmt_from_text(value.as_deref().unwrap_or("false"));
-                    let attr = if let Some(name) = map_html_attribute_to_rsx(name) {
-                        let ident = if let Some(name) = name.strip_prefix("r#") {
-                            Ident::new_raw(name, Span::call_site())
-                        } else {
-                            Ident::new(name, Span::call_site())
-                        };
-                        ElementAttr {
-                            value: dioxus_rsx::ElementAttrValue::AttrLiteral(value),
-                            name: dioxus_rsx::ElementAttrName::BuiltIn(ident),
-                        }
-                    } else {
-                        // If we don't recognize the attribute, we assume it's a custom attribute
-                        ElementAttr {
-                            value: dioxus_rsx::ElementAttrValue::AttrLiteral(value),
-                            name: dioxus_rsx::ElementAttrName::Custom(LitStr::new(
-                                name,
-                                Span::call_site(),
-                            )),
-                        }
-                    };
+            let el_name = element_name(&el.name);
 
+            let attributes = html_attributes(el)
+                .into_iter()
+                .map(|(name, value)| {
                     AttributeType::Named(ElementAttrNamed {
                         el_name: el_name.clone(),
-                        attr,
+                        attr: ElementAttr {
+                            name,
+                            value: ElementAttrValue::AttrLiteral(ifmt_from_text(&value)),
+                        },
                     })
                 })
                 .collect();
 
-            let class = el.classes.join(" ");
-            if !class.is_empty() {
-                attributes.push(AttributeType::Named(ElementAttrNamed {
-                    el_name: el_name.clone(),
-                    attr: ElementAttr {
-                        name: dioxus_rsx::ElementAttrName::BuiltIn(Ident::new(
-                            "class",
-                            Span::call_site(),
-                        )),
-                        value: dioxus_rsx::ElementAttrValue::AttrLiteral(ifmt_from_text(&class)),
-                    },
-                }));
-            }
-
-            if let Some(id) = &el.id {
-                attributes.push(AttributeType::Named(ElementAttrNamed {
-                    el_name: el_name.clone(),
-                    attr: ElementAttr {
-                        name: dioxus_rsx::ElementAttrName::BuiltIn(Ident::new(
-                            "id",
-                            Span::call_site(),
-                        )),
-                        value: dioxus_rsx::ElementAttrValue::AttrLiteral(ifmt_from_text(id)),
-                    },
-                }));
-            }
-
             let children = el.children.iter().filter_map(rsx_node_from_html).collect();
 
             Some(BodyNode::Element(Element {
@@ -115,6 +66,114 @@ pub fn rsx_node_from_html(node: &Node) -> Option<BodyNode> {
     }
 }
 
+/// Convert an HTML DOM tree into the source of an rsx body, keeping its comments
+///
+/// The source isn't formatted, parse it and write it out with `dioxus_autofmt::write_block_out_with_source` to format
+/// it with the comments.
+pub fn rsx_source_from_html(dom: &Dom) -> String {
+    let mut writer = RsxWriter::new();
+    for node in &dom.children {
+        write_html_node(&mut writer, node);
+    }
+    writer.finish()
+}
+
+fn write_html_node(writer: &mut RsxWriter, node: &Node) {
+    match node {
+        Node::Text(text) => writer.text(text),
+        Node::Comment(comment) => writer.comment(comment),
+        Node::Element(el) => {
+            writer.open_element(&element_name(&el.name));
+            for (name, value) in html_attributes(el) {
+                writer.attribute(&name, &text_literal(&value));
+            }
+            for child in &el.children {
+                write_html_node(writer, child);
+            }
+            writer.close();
+        }
+    }
+}
+
+fn element_name(name: &str) -> ElementName {
+    if let Some(name) = map_html_element_to_rsx(name) {
+        ElementName::Ident(Ident::new(name, Span::call_site()))
+    } else {
+        // if we don't recognize it and it has a dash, we assume it's a web component
+        if name.contains('-') {
+            ElementName::Custom(LitStr::new(name, Span::call_site()))
+        } else {
+            // otherwise, it might be an element that isn't supported yet
+            ElementName::Ident(Ident::new(&name.to_case(Case::Snake), Span::call_site()))
+        }
+    }
+}
+
+/// The attributes of an HTML element with their rsx names. Inline styles are split into style attributes.
+fn html_attributes(el: &html_parser::Element) -> Vec<(ElementAttrName, String)> {
+    let mut attributes = Vec::new();
+
+    for (name, value) in &el.attributes {
+        let value = value.as_deref().unwrap_or("false");
+        if name == "style" {
+            attributes.extend(style_attributes(value));
+        } else {
+            attributes.push((attribute_name(name), value.to_string()));
+        }
+    }
+
+    let class = el.classes.join(" ");
+    if !class.is_empty() {
+        attributes.push((builtin_attribute("class"), class));
+    }
+
+    if let Some(id) = &el.id {
+        attributes.push((builtin_attribute("id"), id.clone()));
+    }
+
+    attributes
+}
+
+fn attribute_name(name: &str) -> ElementAttrName {
+    match map_html_attribute_to_rsx(name) {
+        Some(name) => builtin_attribute(name),
+        // If we don't recognize the attribute, we assume it's a custom attribute
+        None => ElementAttrName::Custom(LitStr::new(name, Span::call_site())),
+    }
+}
+
+fn builtin_attribute(name: &str) -> ElementAttrName {
+    let ident = if let Some(name) = name.strip_prefix("r#") {
+        Ident::new_raw(name, Span::call_site())
+    } else {
+        Ident::new(name, Span::call_site())
+    };
+    ElementAttrName::BuiltIn(ident)
+}
+
+/// Split an inline style into the style attributes rsx has. Properties rsx doesn't know stay in a `style` attribute.
+fn style_attributes(style: &str) -> Vec<(ElementAttrName, String)> {
+    let mut attributes = Vec::new();
+    let mut unknown = Vec::new();
+
+    for declaration in style.split(';') {
+        let Some((property, value)) = declaration.split_once(':') else {
+            continue;
+        };
+        let (property, value) = (property.trim(), value.trim());
+        match map_html_style_to_rsx(property) {
+            Some(name) => attributes.push((builtin_attribute(name), value.to_string())),
+            None => unknown.push(format!("{}: {}", property, value)),
+        }
+    }
+
+    if !unknown.is_empty() {
+        attributes.push((builtin_attribute("style"), unknown.join("; ")));
+    }
+
+    attributes
+}
+
 /// Pull out all the svgs from the body and replace them with components of the same name
 pub fn collect_svgs(children: &mut [BodyNode], out: &mut Vec<BodyNode>) {
     for child in children {
@@ -0,0 +1,217 @@
+//! Render rsx back into HTML, for designers and tools that only understand HTML.
+
+use std::fmt::Write;
+
+use dioxus_html::{map_rsx_attribute_to_html, map_rsx_element_to_html};
+use dioxus_rsx::{
+    AttributeType, BodyNode, CallBody, Element, ElementAttrName, ElementAttrValue, ElementName,
+    IfChain, IfmtInput,
+};
+use quote::ToTokens;
+use syn::spanned::Spanned;
+
+/// Elements that can't have children, which HTML writes without a closing tag
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Render the static parts of an rsx body as HTML
+///
+/// Style attributes are merged into a `style` attribute, and event handlers are left out. The parts that are only
+/// known when the app runs are kept as HTML comments: components, loops, conditionals and expressions. The children of
+/// components and loops and every branch of a conditional are rendered once, so their markup can still be seen.
+pub fn html_from_rsx(body: &CallBody) -> String {
+    let mut out = String::new();
+    for node in &body.roots {
+        write_node(&mut out, node, 0);
+    }
+    out
+}
+
+fn write_node(out: &mut String, node: &BodyNode, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match node {
+        BodyNode::Element(el) => write_element(out, el, depth),
+        BodyNode::Text(text) => {
+            writeln!(out, "{}{}", indent, escape_text(&ifmt_text(text))).unwrap()
+        }
+        BodyNode::Component(component) => {
+            let name = component
+                .name
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+            write_section(out, &name, &name, &component.children, depth);
+        }
+        BodyNode::ForLoop(for_loop) => {
+            let head = format!(
+                "for {} in {}",
+                source(&for_loop.pat),
+                source(&for_loop.expr)
+            );
+            write_section(out, &head, "for", &for_loop.body, depth);
+        }
+        BodyNode::IfChain(chain) => {
+            write_if_chain(out, chain, "if", depth);
+            writeln!(out, "{}{}", indent, comment("/if")).unwrap();
+        }
+        BodyNode::RawExpr(expr) => writeln!(out, "{}{}", indent, comment(&source(expr))).unwrap(),
+    }
+}
+
+/// Write children between an opening and closing comment, like `<!-- for item in items -->` and `<!-- /for -->`
+fn write_section(out: &mut String, head: &str, end: &str, children: &[BodyNode], depth: usize) {
+    let indent = "  ".repeat(depth);
+    writeln!(out, "{}{}", indent, comment(head)).unwrap();
+    if children.is_empty() {
+        return;
+    }
+    for child in children {
+        write_node(out, child, depth + 1);
+    }
+    writeln!(out, "{}{}", indent, comment(&format!("/{}", end))).unwrap();
+}
+
+fn write_if_chain(out: &mut String, chain: &IfChain, keyword: &str, depth: usize) {
+    let indent = "  ".repeat(depth);
+    writeln!(
+        out,
+        "{}{}",
+        indent,
+        comment(&format!("{} {}", keyword, source(&chain.cond)))
+    )
+    .unwrap();
+    for child in &chain.then_branch {
+        write_node(out, child, depth + 1);
+    }
+
+    if let Some(chain) = &chain.else_if_branch {
+        write_if_chain(out, chain, "else if", depth);
+    } else if let Some(children) = &chain.else_branch {
+        writeln!(out, "{}{}", indent, comment("else")).unwrap();
+        for child in children {
+            write_node(out, child, depth + 1);
+        }
+    }
+}
+
+fn write_element(out: &mut String, el: &Element, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let (tag, rsx_name) = match &el.name {
+        ElementName::Ident(ident) => {
+            let name = ident.to_string();
+            let tag = map_rsx_element_to_html(&name).map(|(tag, _)| tag.to_string());
+            (tag.unwrap_or_else(|| name.clone()), name)
+        }
+        ElementName::Custom(name) => (name.value(), name.value()),
+    };
+
+    let mut attributes = Vec::new();
+    let mut styles = Vec::new();
+    for attribute in &el.attributes {
+        // Spread attributes are only known when the app runs
+        let AttributeType::Named(named) = attribute else {
+            continue;
+        };
+        let Some(value) = attribute_value(&named.attr.value) else {
+            continue;
+        };
+
+        let name = match &named.attr.name {
+            ElementAttrName::BuiltIn(ident) => {
+                let name = ident.to_string();
+                match map_rsx_attribute_to_html(&rsx_name, &name) {
+                    Some((property, Some("style"))) => {
+                        styles.push(format!("{}: {}", property, value));
+                        continue;
+                    }
+                    Some((html, _)) => html.trim_start_matches("r#").to_string(),
+                    None => name.trim_start_matches("r#").to_string(),
+                }
+            }
+            ElementAttrName::Custom(name) => name.value(),
+        };
+
+        match name.as_str() {
+            "style" => styles.push(value),
+            _ => attributes.push((name, value)),
+        }
+    }
+    if !styles.is_empty() {
+        attributes.push(("style".to_string(), styles.join("; ")));
+    }
+
+    write!(out, "{}<{}", indent, tag).unwrap();
+    for (name, value) in attributes {
+        write!(out, " {}=\"{}\"", name, escape_attribute(&value)).unwrap();
+    }
+    out.push('>');
+
+    match el.children.as_slice() {
+        [] if VOID_ELEMENTS.contains(&tag.as_str()) => {
+            out.push('\n');
+            return;
+        }
+        [] => {}
+        [BodyNode::Text(text)] => out.push_str(&escape_text(&ifmt_text(text))),
+        children => {
+            out.push('\n');
+            for child in children {
+                write_node(out, child, depth + 1);
+            }
+            out.push_str(&indent);
+        }
+    }
+
+    writeln!(out, "</{}>", tag).unwrap();
+}
+
+/// The value of an attribute as HTML shows it, or `None` if the attribute isn't shown
+fn attribute_value(value: &ElementAttrValue) -> Option<String> {
+    match value {
+        ElementAttrValue::AttrLiteral(text) => Some(ifmt_text(text)),
+        ElementAttrValue::AttrOptionalExpr { value, .. } => attribute_value(value),
+        ElementAttrValue::AttrExpr(syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Bool(value),
+            ..
+        })) => value.value.then(|| "true".to_string()),
+        ElementAttrValue::AttrExpr(expr) => Some(format!("{{{}}}", source(expr))),
+        ElementAttrValue::Shorthand(ident) => Some(format!("{{{}}}", ident)),
+        // Event handlers only run in the app
+        ElementAttrValue::EventTokens(_) => None,
+    }
+}
+
+/// The text of an rsx string. Formatted values stay as placeholders like `{name}`.
+fn ifmt_text(text: &IfmtInput) -> String {
+    let source = text.source.as_ref().map(|source| source.value());
+    source
+        .unwrap_or_default()
+        .replace("{{", "{")
+        .replace("}}", "}")
+}
+
+/// The source of some rust code, or its tokens if it wasn't parsed from source
+fn source(code: &(impl ToTokens + Spanned)) -> String {
+    code.span()
+        .source_text()
+        .unwrap_or_else(|| code.to_token_stream().to_string())
+}
+
+fn comment(text: &str) -> String {
+    // Comments can't contain `--`
+    format!("<!-- {} -->", text.replace("--", "- -"))
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn escape_attribute(value: &str) -> String {
+    value.replace('&', "&amp;").replace('"', "&quot;")
+}
@@ -0,0 +1,87 @@
+use std::fmt::Write;
+
+use dioxus_rsx::{ElementAttrName, ElementName};
+
+/// Writes the source of an rsx body, one node or attribute per line.
+///
+/// A [`dioxus_rsx::CallBody`] can't hold comments, so translations that keep them write source instead. The source
+/// isn't formatted, parse it and write it out with `dioxus_autofmt::write_block_out_with_source` to format it.
+pub(crate) struct RsxWriter {
+    out: String,
+}
+
+impl RsxWriter {
+    pub fn new() -> Self {
+        // dioxus-autofmt never looks for comments on the first line
+        Self { out: "\n".into() }
+    }
+
+    pub fn comment(&mut self, comment: &str) {
+        for line in comment.trim().lines() {
+            writeln!(self.out, "// {}", line.trim()).unwrap();
+        }
+    }
+
+    /// Text that is shown as it is.
+    pub fn text(&mut self, text: &str) {
+        writeln!(self.out, "{}", text_literal(text)).unwrap();
+    }
+
+    /// Text that may format values, like `"hello {name}"`.
+    pub fn formatted(&mut self, format: &str) {
+        writeln!(self.out, "{:?}", format).unwrap();
+    }
+
+    pub fn open_element(&mut self, name: &ElementName) {
+        writeln!(self.out, "{} {{", name).unwrap();
+    }
+
+    /// Open a component, a `for` loop or an `if`, like `for item in items`.
+    pub fn open(&mut self, head: &str) {
+        writeln!(self.out, "{} {{", head).unwrap();
+    }
+
+    pub fn close(&mut self) {
+        // Keep empty bodies on one line, dioxus-autofmt leaves them as they are written
+        if self.out.ends_with("{\n") {
+            self.out.pop();
+        }
+        self.out.push_str("}\n");
+    }
+
+    /// Close an `if` and open its `else` branch.
+    pub fn open_else(&mut self) {
+        self.out.push_str("} else {\n");
+    }
+
+    pub fn attribute(&mut self, name: &ElementAttrName, value: &str) {
+        match name {
+            ElementAttrName::BuiltIn(ident) => writeln!(self.out, "{}: {},", ident, value),
+            ElementAttrName::Custom(name) => writeln!(self.out, "{:?}: {},", name.value(), value),
+        }
+        .unwrap();
+    }
+
+    /// A component prop or spread attributes, written as they are.
+    pub fn field(&mut self, field: &str) {
+        writeln!(self.out, "{},", field).unwrap();
+    }
+
+    /// A rust expression as a child, like `{children}`.
+    pub fn expression(&mut self, expr: &str) {
+        writeln!(self.out, "{{{}}}", expr).unwrap();
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// A string literal that shows text as it is, instead of formatting values into it.
+pub(crate) fn text_literal(text: &str) -> String {
+    format!("{:?}", escape_braces(text))
+}
+
+pub(crate) fn escape_braces(text: &str) -> String {
+    text.replace('{', "{{").replace('}', "}}")
+}
@@ -0,0 +1,86 @@
+use dioxus_rsx::CallBody;
+
+fn translate(jsx: &str) -> String {
+    let source = rsx_rosetta::rsx_source_from_jsx(jsx).unwrap();
+    let body = syn::parse_str::<CallBody>(&source).unwrap();
+    dioxus_autofmt::write_block_out_with_source(body, &source).unwrap()
+}
+
+#[test]
+fn elements_and_attributes() {
+    let jsx = r#"
+    <div className="card" style={{ fontSize: 12, marginTop: "4px", opacity: 0.5 }}>
+        {/* The title */}
+        <label htmlFor="name" tabIndex={0}>Hello {user.name}!</label>
+        <input disabled data-id={id} onClick={handleClick} />
+    </div>
+    "#;
+
+    let expected = r#"
+    div {
+        class: "card",
+        font_size: "12px",
+        margin_top: "4px",
+        opacity: "0.5",
+        // The title
+        label { r#for: "name", tabindex: 0, "Hello {user.name}!" }
+        input { disabled: true, "data-id": "{id}", onclick: handleClick }
+    }"#;
+    pretty_assertions::assert_eq!(&translate(jsx), &expected);
+}
+
+#[test]
+fn control_flow() {
+    let jsx = r#"
+    <ul>
+        {open && <li>Open</li>}
+        {done ? <li>Done</li> : <li>Todo</li>}
+        {todos.map((todo) => (
+            <li key={todo.id}>{todo.text}</li>
+        ))}
+    </ul>
+    "#;
+
+    let expected = r#"
+    ul {
+        if open {
+            li { "Open" }
+        }
+        if done {
+            li { "Done" }
+        } else {
+            li { "Todo" }
+        }
+        for todo in todos {
+            li { key: "{todo.id}", "{todo.text}" }
+        }
+    }"#;
+    pretty_assertions::assert_eq!(&translate(jsx), &expected);
+}
+
+#[test]
+fn components() {
+    let jsx = r#"
+    export default function App({ items }: Props) {
+        const [count, setCount] = useState(0);
+        return (
+            <>
+                <Counter initialCount={count} showLabel />
+                {items.filter(item => item.visible).length}
+            </>
+        );
+    }
+    "#;
+
+    let expected = r#"
+    Counter { initial_count: count, show_label: true }
+    {todo!("items.filter(item => item.visible).length")}"#;
+    pretty_assertions::assert_eq!(&translate(jsx), &expected);
+}
+
+#[test]
+fn invalid_jsx() {
+    let error = rsx_rosetta::rsx_source_from_jsx("<div>\n  <p>\n</div>").unwrap_err();
+    assert_eq!(error.message, "expected `</p>`");
+    assert_eq!((error.line, error.column), (3, 6));
+}
@@ -0,0 +1,34 @@
+use dioxus_rsx::CallBody;
+
+#[test]
+fn static_rsx_to_html() {
+    let rsx = r#"
+    div { class: "card", font_size: "12px", onclick: move |_| {},
+        h1 { "Hello {name}!" }
+        img { src: "logo.png" }
+        for item in items {
+            li { "{item}" }
+        }
+        if open {
+            p { "Open" }
+        }
+        Footer {}
+    }
+    "#;
+
+    let body = syn::parse_str::<CallBody>(rsx).unwrap();
+
+    let expected = r#"<div class="card" style="font-size: 12px">
+  <h1>Hello {name}!</h1>
+  <img src="logo.png">
+  <!-- for item in items -->
+    <li>{item}</li>
+  <!-- /for -->
+  <!-- if open -->
+    <p>Open</p>
+  <!-- /if -->
+  <!-- Footer -->
+</div>
+"#;
+    pretty_assertions::assert_eq!(&rsx_rosetta::html_from_rsx(&body), &expected);
+}
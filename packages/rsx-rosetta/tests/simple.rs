@@ -62,3 +62,23 @@ fn deeply_nested() {
     }"#;
     pretty_assertions::assert_eq!(&out, &expected);
 }
+
+#[test]
+fn comments_and_styles() {
+    let html =
+        r#"<div style="color: red; font-size: 12px"><!-- a comment --><p>hello world!</p></div>"#;
+
+    let dom = Dom::parse(html).unwrap();
+
+    let source = rsx_rosetta::rsx_source_from_html(&dom);
+    let body = syn::parse_str::<dioxus_rsx::CallBody>(&source).unwrap();
+
+    let out = dioxus_autofmt::write_block_out_with_source(body, &source).unwrap();
+
+    let expected = r#"
+    div { color: "red", font_size: "12px",
+        // a comment
+        p { "hello world!" }
+    }"#;
+    pretty_assertions::assert_eq!(&out, &expected);
+}
@@ -1,21 +1,56 @@
 use core::{self, fmt::Debug};
+use std::collections::HashMap;
 use std::fmt::{self, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
 //
 use dioxus_core::prelude::*;
+use futures_channel::mpsc::UnboundedSender;
+use futures_util::StreamExt;
 
 use crate::use_signal;
 use crate::{dependency::Dependency, CopyValue};
 
+/// A unique id of an effect. Signals store the ids of the effects that read them so the signal can be shared between threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct EffectId(usize);
+
+impl EffectId {
+    fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 #[derive(Copy, Clone, PartialEq)]
 pub(crate) struct EffectStack {
     pub(crate) effects: CopyValue<Vec<Effect>>,
+    pub(crate) effect_mapping: CopyValue<HashMap<EffectId, Effect>>,
+    rerun_effect: CopyValue<UnboundedSender<EffectId>>,
 }
 
 impl Default for EffectStack {
     fn default() -> Self {
-        Self {
+        let (sender, mut receiver) = futures_channel::mpsc::unbounded();
+        let myself = Self {
             effects: CopyValue::new_in_scope(Vec::new(), ScopeId::ROOT),
-        }
+            effect_mapping: CopyValue::new_in_scope(HashMap::new(), ScopeId::ROOT),
+            rerun_effect: CopyValue::new_in_scope(sender, ScopeId::ROOT),
+        };
+
+        // Effects triggered from other threads are queued here and run on the thread of the virtual dom
+        spawn_at(
+            async move {
+                while let Some(id) = receiver.next().await {
+                    let effect = myself.effect_mapping.read().get(&id).copied();
+                    if let Some(effect) = effect {
+                        effect.try_run();
+                    }
+                }
+            },
+            ScopeId::ROOT,
+        );
+
+        myself
     }
 }
 
@@ -23,6 +58,34 @@ impl EffectStack {
     pub(crate) fn current(&self) -> Option<Effect> {
         self.effects.read().last().copied()
     }
+
+    pub(crate) fn effect_ref(&self) -> EffectStackRef {
+        EffectStackRef {
+            rerun_effect: self.rerun_effect.read().clone(),
+        }
+    }
+}
+
+/// A handle to the effect stack of a virtual dom that can be sent to other threads.
+#[derive(Clone)]
+pub(crate) struct EffectStackRef {
+    rerun_effect: UnboundedSender<EffectId>,
+}
+
+impl EffectStackRef {
+    /// Rerun an effect. The effect runs immediately if it belongs to the virtual dom that is running on this thread, otherwise it is queued for its virtual dom.
+    pub(crate) fn rerun_effect(&self, id: EffectId) {
+        if current_scope_id().is_some() {
+            let effect = get_effect_stack().effect_mapping.read().get(&id).copied();
+            if let Some(effect) = effect {
+                tracing::trace!("Rerunning effect {:?}", effect);
+                effect.try_run();
+                return;
+            }
+        }
+        tracing::trace!("Queueing effect {:?} for its virtual dom", id);
+        let _ = self.rerun_effect.unbounded_send(id);
+    }
 }
 
 pub(crate) fn get_effect_stack() -> EffectStack {
@@ -68,45 +131,83 @@ pub fn use_effect_with_dependencies<D: Dependency>(
 #[derive(Copy, Clone, PartialEq)]
 pub struct Effect {
     pub(crate) source: ScopeId,
-    pub(crate) callback: CopyValue<Box<dyn FnMut()>>,
+    pub(crate) id: EffectId,
+    pub(crate) inner: CopyValue<EffectInner>,
+}
+
+pub(crate) struct EffectInner {
+    pub(crate) callback: Box<dyn FnMut()>,
+    pub(crate) id: EffectId,
     pub(crate) effect_stack: EffectStack,
 }
 
+impl Drop for EffectInner {
+    fn drop(&mut self) {
+        // The effect stack may already be dropped if the whole virtual dom is dropped
+        if let Ok(mut effect_mapping) = self.effect_stack.effect_mapping.try_write() {
+            effect_mapping.remove(&self.id);
+        }
+    }
+}
+
 impl Debug for Effect {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.write_fmt(format_args!("{:?}", self.callback.value))
+        f.write_fmt(format_args!("{:?}", self.inner.value))
     }
 }
 
 impl Effect {
+    /// Get the effect that is currently running in the virtual dom of this thread.
     pub(crate) fn current() -> Option<Self> {
-        get_effect_stack().effects.read().last().copied()
+        current_scope_id()?;
+        get_effect_stack().current()
     }
 
     /// Create a new effect. The effect will be run immediately and whenever any signal it reads changes.
     ///
     /// The signal will be owned by the current component and will be dropped when the component is dropped.
     pub fn new(callback: impl FnMut() + 'static) -> Self {
+        let myself = Self::invalid();
+        myself.set_callback(callback);
+
+        myself.try_run();
+
+        myself
+    }
+
+    /// Create an effect without a callback. The callback must be set before the effect runs.
+    pub(crate) fn invalid() -> Self {
         let myself = Self {
             source: current_scope_id().expect("in a virtual dom"),
-            callback: CopyValue::new(Box::new(callback)),
-            effect_stack: get_effect_stack(),
+            id: EffectId::new(),
+            inner: CopyValue::invalid(),
         };
-
-        myself.try_run();
+        get_effect_stack()
+            .effect_mapping
+            .write()
+            .insert(myself.id, myself);
 
         myself
     }
 
+    pub(crate) fn set_callback(&self, callback: impl FnMut() + 'static) {
+        self.inner.value.set(EffectInner {
+            callback: Box::new(callback),
+            id: self.id,
+            effect_stack: get_effect_stack(),
+        });
+    }
+
     /// Run the effect callback immediately. Returns `true` if the effect was run. Returns `false` is the effect is dead.
     pub fn try_run(&self) {
-        if let Ok(mut callback) = self.callback.try_write() {
+        if let Ok(mut inner) = self.inner.try_write() {
+            let effect_stack = inner.effect_stack;
             {
-                self.effect_stack.effects.write().push(*self);
+                effect_stack.effects.write().push(*self);
             }
-            callback();
+            (inner.callback)();
             {
-                self.effect_stack.effects.write().pop();
+                effect_stack.effects.write().pop();
             }
         }
     }
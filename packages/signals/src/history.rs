@@ -0,0 +1,404 @@
+use std::any::Any;
+use std::collections::VecDeque;
+
+use dioxus_core::prelude::*;
+use generational_box::GenerationalRef;
+
+use crate::{Signal, Write};
+
+/// The number of undo steps a history created with [`use_signal_with_history`] keeps.
+pub const DEFAULT_HISTORY_CAPACITY: usize = 100;
+
+/// Creates a new [`History`] that keeps at most `capacity` undo steps. Track signals with [`History::track`] to record their writes.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     let history = use_history(cx, 50);
+///     let title = use_tracked_signal(cx, history, || "Untitled".to_string());
+///     let body = use_tracked_signal(cx, history, String::new);
+///
+///     render! {
+///         button {
+///             // Both writes are undone in a single step
+///             onclick: move |_| history.transaction(|| {
+///                 title.set("Empty".to_string());
+///                 body.set(String::new());
+///             }),
+///             "Clear"
+///         }
+///         button {
+///             disabled: !history.can_undo(),
+///             onclick: move |_| { history.undo(); },
+///             "Undo"
+///         }
+///         button {
+///             disabled: !history.can_redo(),
+///             onclick: move |_| { history.redo(); },
+///             "Redo"
+///         }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_history(cx: &ScopeState, capacity: usize) -> History {
+    *cx.use_hook(|| History::new(capacity))
+}
+
+/// Creates a new Signal that is tracked by `history`. Every write to the signal can be undone with [`History::undo`].
+#[track_caller]
+#[must_use]
+pub fn use_tracked_signal<T: Clone + 'static>(
+    cx: &ScopeState,
+    history: History,
+    f: impl FnOnce() -> T,
+) -> TrackedSignal<T> {
+    let signal = crate::use_signal(cx, f);
+    *cx.use_hook(|| history.track(signal))
+}
+
+/// Creates a new Signal with its own [`History`]. Every write to the signal can be undone with [`TrackedSignal::undo`].
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     let mut count = use_signal_with_history(cx, || 0);
+///
+///     render! {
+///         button { onclick: move |_| count += 1, "{count}" }
+///         button { onclick: move |_| { count.undo(); }, "Undo" }
+///     }
+/// }
+/// ```
+#[track_caller]
+#[must_use]
+pub fn use_signal_with_history<T: Clone + 'static>(
+    cx: &ScopeState,
+    f: impl FnOnce() -> T,
+) -> TrackedSignal<T> {
+    let history = use_history(cx, DEFAULT_HISTORY_CAPACITY);
+    use_tracked_signal(cx, history, f)
+}
+
+/// The value of a signal before a write. Restoring the snapshot swaps it with the current value of the signal, so the
+/// returned snapshot can restore the value the signal had before the restore.
+trait Snapshot {
+    fn restore(self: Box<Self>) -> Box<dyn Snapshot>;
+
+    fn as_any(&self) -> &dyn Any;
+}
+
+struct SignalSnapshot<T: 'static> {
+    signal: Signal<T>,
+    value: T,
+}
+
+impl<T: 'static> Snapshot for SignalSnapshot<T> {
+    fn restore(mut self: Box<Self>) -> Box<dyn Snapshot> {
+        std::mem::swap(&mut *self.signal.write(), &mut self.value);
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+type Step = Vec<Box<dyn Snapshot>>;
+
+struct HistoryState {
+    undo: VecDeque<Step>,
+    redo: Vec<Step>,
+    transaction: Option<Step>,
+    capacity: usize,
+}
+
+impl HistoryState {
+    fn push_undo(&mut self, step: Step) {
+        self.undo.push_back(step);
+        while self.undo.len() > self.capacity {
+            self.undo.pop_front();
+        }
+    }
+}
+
+/// An undo history shared by any number of signals.
+///
+/// Writes to a [`TrackedSignal`] record the value the signal had before the write. [`History::undo`] and
+/// [`History::redo`] write the recorded values back to the signals, so components and effects that read the signals
+/// rerun like they would for any other write.
+///
+/// Components that read [`History::can_undo`] or [`History::can_redo`] rerun when the history changes.
+#[derive(Clone, Copy, PartialEq)]
+pub struct History {
+    state: Signal<HistoryState>,
+}
+
+impl History {
+    /// Create a new history that keeps at most `capacity` undo steps. When the history is full, the oldest step is forgotten.
+    #[track_caller]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Signal::new(HistoryState {
+                undo: VecDeque::new(),
+                redo: Vec::new(),
+                transaction: None,
+                capacity,
+            }),
+        }
+    }
+
+    /// Track the writes of a signal with this history.
+    pub fn track<T: Clone + 'static>(self, signal: Signal<T>) -> TrackedSignal<T> {
+        TrackedSignal {
+            signal,
+            history: self,
+        }
+    }
+
+    /// Run a closure and group every tracked write inside of it into a single undo step.
+    ///
+    /// Transactions can be nested. The writes of a nested transaction are part of the outermost transaction.
+    pub fn transaction<O>(&self, f: impl FnOnce() -> O) -> O {
+        let nested = {
+            // Starting a transaction doesn't change what can be undone, so subscribers are not notified
+            let mut state = self.state.inner.write();
+            let state = &mut state.value;
+            let nested = state.transaction.is_some();
+            if !nested {
+                state.transaction = Some(Vec::new());
+            }
+            nested
+        };
+
+        let output = f();
+
+        if !nested {
+            let mut state = self.state.write();
+            if let Some(step) = state.transaction.take() {
+                if !step.is_empty() {
+                    state.push_undo(step);
+                }
+            }
+        }
+
+        output
+    }
+
+    /// Undo the last step. Returns `false` if there is nothing to undo.
+    pub fn undo(&self) -> bool {
+        let step = self.state.write().undo.pop_back();
+        match step {
+            Some(step) => {
+                let redo = restore(step);
+                self.state.write().redo.push(redo);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redo the last step that was undone. Returns `false` if there is nothing to redo.
+    pub fn redo(&self) -> bool {
+        let step = self.state.write().redo.pop();
+        match step {
+            Some(step) => {
+                let undo = restore(step);
+                self.state.write().push_undo(undo);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if there is a step to undo. This will subscribe the current scope to the history.
+    pub fn can_undo(&self) -> bool {
+        !self.state.read().undo.is_empty()
+    }
+
+    /// Check if there is a step to redo. This will subscribe the current scope to the history.
+    pub fn can_redo(&self) -> bool {
+        !self.state.read().redo.is_empty()
+    }
+
+    /// Get the maximum number of undo steps this history keeps.
+    pub fn capacity(&self) -> usize {
+        self.state.peek().capacity
+    }
+
+    /// Change the maximum number of undo steps this history keeps. The oldest steps are forgotten if the history is longer than the new capacity.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut state = self.state.write();
+        state.capacity = capacity;
+        while state.undo.len() > capacity {
+            state.undo.pop_front();
+        }
+    }
+
+    /// Forget every undo and redo step.
+    pub fn clear(&self) {
+        let mut state = self.state.write();
+        state.undo.clear();
+        state.redo.clear();
+    }
+
+    /// Record the current value of a signal before it is written.
+    fn record<T: Clone + 'static>(&self, signal: Signal<T>) {
+        let mut state = self.state.write();
+        state.redo.clear();
+        match &mut state.transaction {
+            Some(step) => {
+                // Only the value before the first write of a transaction is restored
+                let recorded = step.iter().any(|snapshot| {
+                    snapshot
+                        .as_any()
+                        .downcast_ref::<SignalSnapshot<T>>()
+                        .map_or(false, |snapshot| snapshot.signal == signal)
+                });
+                if !recorded {
+                    let value = signal.peek().clone();
+                    step.push(Box::new(SignalSnapshot { signal, value }));
+                }
+            }
+            None => {
+                let value = signal.peek().clone();
+                state.push_undo(vec![Box::new(SignalSnapshot { signal, value })]);
+            }
+        }
+    }
+}
+
+fn restore(step: Step) -> Step {
+    // A step holds at most one snapshot per signal, so the order the snapshots are restored in doesn't matter
+    step.into_iter()
+        .map(|snapshot| snapshot.restore())
+        .collect()
+}
+
+/// A signal whose writes are recorded in a [`History`]. Create one with [`use_signal_with_history`],
+/// [`use_tracked_signal`] or [`History::track`].
+pub struct TrackedSignal<T: 'static> {
+    signal: Signal<T>,
+    history: History,
+}
+
+impl<T: Clone + 'static> TrackedSignal<T> {
+    /// Get the signal this tracked signal writes to. Writes to the returned signal are not recorded in the history.
+    pub fn signal(&self) -> Signal<T> {
+        self.signal
+    }
+
+    /// Get the history this signal is tracked by.
+    pub fn history(&self) -> History {
+        self.history
+    }
+
+    /// Get the current value of the signal. This will subscribe the current scope to the signal.
+    ///
+    /// If the signal has been dropped, this will panic.
+    #[track_caller]
+    pub fn read(&self) -> GenerationalRef<T> {
+        self.signal.read()
+    }
+
+    /// Get the current value of the signal without subscribing the current scope to the signal.
+    ///
+    /// If the signal has been dropped, this will panic.
+    pub fn peek(&self) -> GenerationalRef<T> {
+        self.signal.peek()
+    }
+
+    /// Get a mutable reference to the signal's value. The value before the write is recorded in the history.
+    ///
+    /// If the signal has been dropped, this will panic.
+    #[track_caller]
+    pub fn write(&self) -> Write<T> {
+        self.history.record(self.signal);
+        self.signal.write()
+    }
+
+    /// Set the value of the signal. The previous value is recorded in the history.
+    #[track_caller]
+    pub fn set(&self, value: T) {
+        *self.write() = value;
+    }
+
+    /// Run a closure with a reference to the signal's value.
+    #[track_caller]
+    pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        self.signal.with(f)
+    }
+
+    /// Run a closure with a mutable reference to the signal's value. The value before the write is recorded in the history.
+    #[track_caller]
+    pub fn with_mut<O>(&self, f: impl FnOnce(&mut T) -> O) -> O {
+        let mut write = self.write();
+        f(&mut *write)
+    }
+
+    /// Get the current value of the signal. This will subscribe the current scope to the signal.
+    #[track_caller]
+    pub fn value(&self) -> T {
+        self.read().clone()
+    }
+
+    /// Undo the last step of the history of this signal. Returns `false` if there is nothing to undo.
+    pub fn undo(&self) -> bool {
+        self.history.undo()
+    }
+
+    /// Redo the last step of the history of this signal that was undone. Returns `false` if there is nothing to redo.
+    pub fn redo(&self) -> bool {
+        self.history.redo()
+    }
+}
+
+impl<T: 'static> Clone for TrackedSignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for TrackedSignal<T> {}
+
+impl<T: 'static> PartialEq for TrackedSignal<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signal == other.signal && self.history == other.history
+    }
+}
+
+impl<T: std::fmt::Display + Clone + 'static> std::fmt::Display for TrackedSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with(|v| std::fmt::Display::fmt(v, f))
+    }
+}
+
+impl<T: std::fmt::Debug + Clone + 'static> std::fmt::Debug for TrackedSignal<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with(|v| std::fmt::Debug::fmt(v, f))
+    }
+}
+
+impl<T: std::ops::Add<Output = T> + Copy + 'static> std::ops::AddAssign<T> for TrackedSignal<T> {
+    fn add_assign(&mut self, rhs: T) {
+        self.with_mut(|v| *v = *v + rhs)
+    }
+}
+
+impl<T: std::ops::Sub<Output = T> + Copy + 'static> std::ops::SubAssign<T> for TrackedSignal<T> {
+    fn sub_assign(&mut self, rhs: T) {
+        self.with_mut(|v| *v = *v - rhs)
+    }
+}
+
+impl<T: 'static> From<TrackedSignal<T>> for Signal<T> {
+    fn from(tracked: TrackedSignal<T>) -> Self {
+        tracked.signal
+    }
+}
@@ -1,7 +1,6 @@
 use crate::rt::CopyValue;
-use crate::signal::{ReadOnlySignal, Signal, Write};
-use generational_box::GenerationalRef;
-use generational_box::GenerationalRefMut;
+use crate::signal::{ReadOnlySignal, Signal, SignalData, Write};
+use generational_box::{Storage, UnsyncStorage};
 
 use std::{
     fmt::{Debug, Display},
@@ -9,41 +8,35 @@ use std::{
 };
 
 macro_rules! read_impls {
-    ($ty:ident) => {
-        impl<T: Default + 'static> Default for $ty<T> {
-            fn default() -> Self {
-                Self::new(Default::default())
-            }
-        }
-
-        impl<T> std::clone::Clone for $ty<T> {
+    ($ty:ident, $bound:path, $vec_bound:path, $option_bound:path) => {
+        impl<T, S: $bound> std::clone::Clone for $ty<T, S> {
             fn clone(&self) -> Self {
                 *self
             }
         }
 
-        impl<T> Copy for $ty<T> {}
+        impl<T, S: $bound> Copy for $ty<T, S> {}
 
-        impl<T: Display + 'static> Display for $ty<T> {
+        impl<T: Display + 'static, S: $bound> Display for $ty<T, S> {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 self.with(|v| Display::fmt(v, f))
             }
         }
 
-        impl<T: Debug + 'static> Debug for $ty<T> {
+        impl<T: Debug + 'static, S: $bound> Debug for $ty<T, S> {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 self.with(|v| Debug::fmt(v, f))
             }
         }
 
-        impl<T: 'static> $ty<Vec<T>> {
+        impl<T: 'static, S: $vec_bound> $ty<Vec<T>, S> {
             /// Read a value from the inner vector.
-            pub fn get(&self, index: usize) -> Option<GenerationalRef<T>> {
-                GenerationalRef::filter_map(self.read(), |v| v.get(index))
+            pub fn get(&self, index: usize) -> Option<S::Ref<T>> {
+                S::try_map(self.read(), |v| v.get(index))
             }
         }
 
-        impl<T: 'static> $ty<Option<T>> {
+        impl<T: 'static, S: $option_bound> $ty<Option<T>, S> {
             /// Unwraps the inner value and clones it.
             pub fn unwrap(&self) -> T
             where
@@ -53,16 +46,16 @@ macro_rules! read_impls {
             }
 
             /// Attempts to read the inner value of the Option.
-            pub fn as_ref(&self) -> Option<GenerationalRef<T>> {
-                GenerationalRef::filter_map(self.read(), |v| v.as_ref())
+            pub fn as_ref(&self) -> Option<S::Ref<T>> {
+                S::try_map(self.read(), |v| v.as_ref())
             }
         }
     };
 }
 
 macro_rules! write_impls {
-    ($ty:ident) => {
-        impl<T: Add<Output = T> + Copy + 'static> std::ops::Add<T> for $ty<T> {
+    ($ty:ident, $bound:path, $vec_bound:path, $option_bound:path) => {
+        impl<T: Add<Output = T> + Copy + 'static, S: $bound> std::ops::Add<T> for $ty<T, S> {
             type Output = T;
 
             fn add(self, rhs: T) -> Self::Output {
@@ -70,19 +63,19 @@ macro_rules! write_impls {
             }
         }
 
-        impl<T: Add<Output = T> + Copy + 'static> std::ops::AddAssign<T> for $ty<T> {
+        impl<T: Add<Output = T> + Copy + 'static, S: $bound> std::ops::AddAssign<T> for $ty<T, S> {
             fn add_assign(&mut self, rhs: T) {
                 self.with_mut(|v| *v = *v + rhs)
             }
         }
 
-        impl<T: Sub<Output = T> + Copy + 'static> std::ops::SubAssign<T> for $ty<T> {
+        impl<T: Sub<Output = T> + Copy + 'static, S: $bound> std::ops::SubAssign<T> for $ty<T, S> {
             fn sub_assign(&mut self, rhs: T) {
                 self.with_mut(|v| *v = *v - rhs)
             }
         }
 
-        impl<T: Sub<Output = T> + Copy + 'static> std::ops::Sub<T> for $ty<T> {
+        impl<T: Sub<Output = T> + Copy + 'static, S: $bound> std::ops::Sub<T> for $ty<T, S> {
             type Output = T;
 
             fn sub(self, rhs: T) -> Self::Output {
@@ -90,13 +83,13 @@ macro_rules! write_impls {
             }
         }
 
-        impl<T: Mul<Output = T> + Copy + 'static> std::ops::MulAssign<T> for $ty<T> {
+        impl<T: Mul<Output = T> + Copy + 'static, S: $bound> std::ops::MulAssign<T> for $ty<T, S> {
             fn mul_assign(&mut self, rhs: T) {
                 self.with_mut(|v| *v = *v * rhs)
             }
         }
 
-        impl<T: Mul<Output = T> + Copy + 'static> std::ops::Mul<T> for $ty<T> {
+        impl<T: Mul<Output = T> + Copy + 'static, S: $bound> std::ops::Mul<T> for $ty<T, S> {
             type Output = T;
 
             fn mul(self, rhs: T) -> Self::Output {
@@ -104,13 +97,13 @@ macro_rules! write_impls {
             }
         }
 
-        impl<T: Div<Output = T> + Copy + 'static> std::ops::DivAssign<T> for $ty<T> {
+        impl<T: Div<Output = T> + Copy + 'static, S: $bound> std::ops::DivAssign<T> for $ty<T, S> {
             fn div_assign(&mut self, rhs: T) {
                 self.with_mut(|v| *v = *v / rhs)
             }
         }
 
-        impl<T: Div<Output = T> + Copy + 'static> std::ops::Div<T> for $ty<T> {
+        impl<T: Div<Output = T> + Copy + 'static, S: $bound> std::ops::Div<T> for $ty<T, S> {
             type Output = T;
 
             fn div(self, rhs: T) -> Self::Output {
@@ -118,7 +111,7 @@ macro_rules! write_impls {
             }
         }
 
-        impl<T: 'static> $ty<Vec<T>> {
+        impl<T: 'static, S: $vec_bound> $ty<Vec<T>, S> {
             /// Pushes a new value to the end of the vector.
             pub fn push(&self, value: T) {
                 self.with_mut(|v| v.push(value))
@@ -170,7 +163,7 @@ macro_rules! write_impls {
             }
         }
 
-        impl<T: 'static> $ty<Option<T>> {
+        impl<T: 'static, S: $option_bound> $ty<Option<T>, S> {
             /// Takes the value out of the Option.
             pub fn take(&self) -> Option<T> {
                 self.with_mut(|v| v.take())
@@ -182,38 +175,71 @@ macro_rules! write_impls {
             }
 
             /// Gets the value out of the Option, or inserts the given value if the Option is empty.
-            pub fn get_or_insert(&self, default: T) -> GenerationalRef<T> {
+            pub fn get_or_insert(&self, default: T) -> S::Ref<T> {
                 self.get_or_insert_with(|| default)
             }
 
             /// Gets the value out of the Option, or inserts the value returned by the given function if the Option is empty.
-            pub fn get_or_insert_with(&self, default: impl FnOnce() -> T) -> GenerationalRef<T> {
+            pub fn get_or_insert_with(&self, default: impl FnOnce() -> T) -> S::Ref<T> {
                 let borrow = self.read();
                 if borrow.is_none() {
                     drop(borrow);
                     self.with_mut(|v| *v = Some(default()));
-                    GenerationalRef::map(self.read(), |v| v.as_ref().unwrap())
+                    S::map(self.read(), |v| v.as_ref().unwrap())
                 } else {
-                    GenerationalRef::map(borrow, |v| v.as_ref().unwrap())
+                    S::map(borrow, |v| v.as_ref().unwrap())
                 }
             }
         }
     };
 }
 
-read_impls!(CopyValue);
-write_impls!(CopyValue);
-read_impls!(Signal);
-write_impls!(Signal);
-read_impls!(ReadOnlySignal);
+read_impls!(CopyValue, Storage<T>, Storage<Vec<T>>, Storage<Option<T>>);
+write_impls!(CopyValue, Storage<T>, Storage<Vec<T>>, Storage<Option<T>>);
+read_impls!(
+    Signal,
+    Storage<SignalData<T>>,
+    Storage<SignalData<Vec<T>>>,
+    Storage<SignalData<Option<T>>>
+);
+write_impls!(
+    Signal,
+    Storage<SignalData<T>>,
+    Storage<SignalData<Vec<T>>>,
+    Storage<SignalData<Option<T>>>
+);
+read_impls!(
+    ReadOnlySignal,
+    Storage<SignalData<T>>,
+    Storage<SignalData<Vec<T>>>,
+    Storage<SignalData<Option<T>>>
+);
+
+impl<T: Default + 'static, S: Storage<T>> Default for CopyValue<T, S> {
+    fn default() -> Self {
+        Self::new_maybe_sync(Default::default())
+    }
+}
+
+impl<T: Default + 'static, S: Storage<SignalData<T>>> Default for Signal<T, S> {
+    fn default() -> Self {
+        Self::new_maybe_sync(Default::default())
+    }
+}
+
+impl<T: Default + 'static, S: Storage<SignalData<T>>> Default for ReadOnlySignal<T, S> {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
 
 /// An iterator over the values of a `CopyValue<Vec<T>>`.
-pub struct CopyValueIterator<T: 'static> {
+pub struct CopyValueIterator<T: 'static, S: Storage<Vec<T>> = UnsyncStorage> {
     index: usize,
-    value: CopyValue<Vec<T>>,
+    value: CopyValue<Vec<T>, S>,
 }
 
-impl<T: Clone> Iterator for CopyValueIterator<T> {
+impl<T: Clone, S: Storage<Vec<T>>> Iterator for CopyValueIterator<T, S> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -223,8 +249,8 @@ impl<T: Clone> Iterator for CopyValueIterator<T> {
     }
 }
 
-impl<T: Clone + 'static> IntoIterator for CopyValue<Vec<T>> {
-    type IntoIter = CopyValueIterator<T>;
+impl<T: Clone + 'static, S: Storage<Vec<T>>> IntoIterator for CopyValue<Vec<T>, S> {
+    type IntoIter = CopyValueIterator<T, S>;
 
     type Item = T;
 
@@ -236,27 +262,27 @@ impl<T: Clone + 'static> IntoIterator for CopyValue<Vec<T>> {
     }
 }
 
-impl<T: 'static> CopyValue<Vec<T>> {
+impl<T: 'static, S: Storage<Vec<T>>> CopyValue<Vec<T>, S> {
     /// Write to an element in the inner vector.
-    pub fn get_mut(&self, index: usize) -> Option<GenerationalRefMut<T>> {
-        GenerationalRefMut::filter_map(self.write(), |v| v.get_mut(index))
+    pub fn get_mut(&self, index: usize) -> Option<S::Mut<T>> {
+        S::try_map_mut(self.write(), |v| v.get_mut(index))
     }
 }
 
-impl<T: 'static> CopyValue<Option<T>> {
+impl<T: 'static, S: Storage<Option<T>>> CopyValue<Option<T>, S> {
     /// Deref the inner value mutably.
-    pub fn as_mut(&self) -> Option<GenerationalRefMut<T>> {
-        GenerationalRefMut::filter_map(self.write(), |v| v.as_mut())
+    pub fn as_mut(&self) -> Option<S::Mut<T>> {
+        S::try_map_mut(self.write(), |v| v.as_mut())
     }
 }
 
 /// An iterator over items in a `Signal<Vec<T>>`.
-pub struct SignalIterator<T: 'static> {
+pub struct SignalIterator<T: 'static, S: Storage<SignalData<Vec<T>>> = UnsyncStorage> {
     index: usize,
-    value: Signal<Vec<T>>,
+    value: Signal<Vec<T>, S>,
 }
 
-impl<T: Clone> Iterator for SignalIterator<T> {
+impl<T: Clone, S: Storage<SignalData<Vec<T>>>> Iterator for SignalIterator<T, S> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -266,8 +292,8 @@ impl<T: Clone> Iterator for SignalIterator<T> {
     }
 }
 
-impl<T: Clone + 'static> IntoIterator for Signal<Vec<T>> {
-    type IntoIter = SignalIterator<T>;
+impl<T: Clone + 'static, S: Storage<SignalData<Vec<T>>>> IntoIterator for Signal<Vec<T>, S> {
+    type IntoIter = SignalIterator<T, S>;
 
     type Item = T;
 
@@ -279,16 +305,16 @@ impl<T: Clone + 'static> IntoIterator for Signal<Vec<T>> {
     }
 }
 
-impl<T: 'static> Signal<Vec<T>> {
+impl<T: 'static, S: Storage<SignalData<Vec<T>>>> Signal<Vec<T>, S> {
     /// Returns a reference to an element or `None` if out of bounds.
-    pub fn get_mut(&self, index: usize) -> Option<Write<T, Vec<T>>> {
+    pub fn get_mut(&self, index: usize) -> Option<Write<T, Vec<T>, S>> {
         Write::filter_map(self.write(), |v| v.get_mut(index))
     }
 }
 
-impl<T: 'static> Signal<Option<T>> {
+impl<T: 'static, S: Storage<SignalData<Option<T>>>> Signal<Option<T>, S> {
     /// Returns a reference to an element or `None` if out of bounds.
-    pub fn as_mut(&self) -> Option<Write<T, Option<T>>> {
+    pub fn as_mut(&self) -> Option<Write<T, Option<T>, S>> {
         Write::filter_map(self.write(), |v| v.as_mut())
     }
 }
@@ -14,3 +14,9 @@ pub(crate) mod signal;
 pub use signal::*;
 mod dependency;
 pub use dependency::*;
+mod history;
+pub use history::*;
+mod resource;
+pub use resource::*;
+
+pub use generational_box::{AnyStorage, Storage, SyncStorage, UnsyncStorage};
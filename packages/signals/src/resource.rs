@@ -0,0 +1,236 @@
+use std::future::Future;
+
+use dioxus_core::prelude::*;
+use generational_box::GenerationalRef;
+
+use crate::{CopyValue, Effect, ReadOnlySignal, Signal};
+
+/// The state of a [`Resource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceState<T, E> {
+    /// The future of the resource is running.
+    Pending,
+    /// The future of the resource finished successfully.
+    Ready(T),
+    /// The future of the resource failed.
+    Errored(E),
+}
+
+impl<T, E> ResourceState<T, E> {
+    /// Check if the future of the resource is running.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending)
+    }
+
+    /// Check if the future of the resource finished successfully.
+    pub fn is_ready(&self) -> bool {
+        matches!(self, Self::Ready(_))
+    }
+
+    /// Check if the future of the resource failed.
+    pub fn is_errored(&self) -> bool {
+        matches!(self, Self::Errored(_))
+    }
+
+    /// Get the value of the resource if the future finished successfully.
+    pub fn ready(&self) -> Option<&T> {
+        match self {
+            Self::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the error of the resource if the future failed.
+    pub fn errored(&self) -> Option<&E> {
+        match self {
+            Self::Errored(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Creates a new Resource. The future will be started immediately and restarted whenever any signal it reads before its first `.await` changes.
+///
+/// Resources are the async counterpart of selectors. When a signal the resource depends on changes, the future that
+/// is still running is cancelled before the new future starts, so a stale future never overwrites the state.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// async fn fetch_user(id: usize) -> Result<String, String> {
+///     Ok(format!("User {id}"))
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     let mut id = use_signal(cx, || 0);
+///     let user = use_resource(cx, move || {
+///         // Read the signal before the first await to restart the resource when it changes
+///         let id = id.value();
+///         async move { fetch_user(id).await }
+///     });
+///
+///     // Suspend the component until the user is loaded
+///     let user = user.suspend(cx)?;
+///
+///     render! {
+///         button { onclick: move |_| id += 1, "Next user" }
+///         match user {
+///             Ok(name) => rsx! { "{name}" },
+///             Err(err) => rsx! { "Failed to load the user: {err}" },
+///         }
+///     }
+/// }
+/// ```
+#[must_use]
+pub fn use_resource<T: 'static, E: 'static, F: Future<Output = Result<T, E>> + 'static>(
+    cx: &ScopeState,
+    f: impl FnMut() -> F + 'static,
+) -> Resource<T, E> {
+    *cx.use_hook(|| resource(f))
+}
+
+/// Creates a new Resource. The future will be started immediately and restarted whenever any signal it reads before its first `.await` changes.
+///
+/// The future is owned by the current component and will be cancelled when the component is dropped.
+pub fn resource<T: 'static, E: 'static, F: Future<Output = Result<T, E>> + 'static>(
+    mut f: impl FnMut() -> F + 'static,
+) -> Resource<T, E> {
+    let scope = current_scope_id().expect("in a virtual dom");
+    let myself = Resource {
+        state: Signal::new(ResourceState::Pending),
+        task: CopyValue::new(None),
+        generation: CopyValue::new(0),
+        effect: Effect::invalid(),
+    };
+
+    myself.effect.set_callback(move || {
+        myself.cancel();
+        if !myself.state.peek().is_pending() {
+            myself.state.set(ResourceState::Pending);
+        }
+
+        let generation = {
+            let mut generation = myself.generation.write();
+            *generation += 1;
+            *generation
+        };
+        let future = f();
+
+        // The future is polled for the first time while the effect is running, so the signals it reads before the first await are tracked
+        let task = spawn_at(
+            async move {
+                let result = future.await;
+                // A future that was replaced may still finish the poll it is in when it is cancelled
+                if *myself.generation.read() != generation {
+                    return;
+                }
+                *myself.task.write() = None;
+                myself.state.set(match result {
+                    Ok(value) => ResourceState::Ready(value),
+                    Err(error) => ResourceState::Errored(error),
+                });
+            },
+            scope,
+        );
+
+        if myself.state.peek().is_pending() {
+            *myself.task.write() = task;
+        }
+    });
+
+    myself.effect.try_run();
+
+    myself
+}
+
+/// An async value derived from signals. Create one with [`use_resource`] or [`resource`].
+///
+/// The state of the resource is stored in a signal, so components and effects that read the state rerun when the
+/// future finishes.
+pub struct Resource<T: 'static, E: 'static> {
+    state: Signal<ResourceState<T, E>>,
+    task: CopyValue<Option<TaskId>>,
+    generation: CopyValue<u64>,
+    effect: Effect,
+}
+
+impl<T: 'static, E: 'static> Resource<T, E> {
+    /// Get the state of the resource as a read only signal.
+    pub fn state(&self) -> ReadOnlySignal<ResourceState<T, E>> {
+        ReadOnlySignal::new(self.state)
+    }
+
+    /// Get the current state of the resource. This will subscribe the current scope to the resource.
+    ///
+    /// If the resource has been dropped, this will panic.
+    #[track_caller]
+    pub fn read(&self) -> GenerationalRef<ResourceState<T, E>> {
+        self.state.read()
+    }
+
+    /// Get the current state of the resource without subscribing the current scope to the resource.
+    ///
+    /// If the resource has been dropped, this will panic.
+    pub fn peek(&self) -> GenerationalRef<ResourceState<T, E>> {
+        self.state.peek()
+    }
+
+    /// Check if the future of the resource is running. This will subscribe the current scope to the resource.
+    pub fn is_pending(&self) -> bool {
+        self.state.read().is_pending()
+    }
+
+    /// Cancel the running future and start the future again.
+    pub fn restart(&self) {
+        self.effect.try_run();
+    }
+
+    /// Cancel the running future. If the future was running, the state of the resource stays [`ResourceState::Pending`] until the resource is restarted.
+    pub fn cancel(&self) {
+        let task = self.task.write().take();
+        if let Some(task) = task {
+            remove_future(task);
+        }
+    }
+
+    /// Get the result of the resource, or suspend the component if the future is still running.
+    ///
+    /// This will subscribe the component to the resource, so it is rendered again when the future finishes.
+    pub fn suspend(&self, cx: &ScopeState) -> Option<Result<T, E>>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        match &*self.state.read() {
+            ResourceState::Pending => {
+                cx.suspend();
+                None
+            }
+            ResourceState::Ready(value) => Some(Ok(value.clone())),
+            ResourceState::Errored(error) => Some(Err(error.clone())),
+        }
+    }
+}
+
+impl<T: Clone + 'static, E: Clone + 'static> Resource<T, E> {
+    /// Get the current state of the resource. This will subscribe the current scope to the resource.
+    pub fn value(&self) -> ResourceState<T, E> {
+        self.state.read().clone()
+    }
+}
+
+impl<T: 'static, E: 'static> Clone for Resource<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static, E: 'static> Copy for Resource<T, E> {}
+
+impl<T: 'static, E: 'static> PartialEq for Resource<T, E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.state == other.state
+    }
+}
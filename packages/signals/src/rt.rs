@@ -6,22 +6,22 @@ use dioxus_core::prelude::*;
 use dioxus_core::ScopeId;
 
 use generational_box::{
-    BorrowError, BorrowMutError, GenerationalBox, GenerationalRef, GenerationalRefMut, Owner, Store,
+    AnyStorage, BorrowError, BorrowMutError, GenerationalBox, Owner, Storage, Store, UnsyncStorage,
 };
 
 use crate::Effect;
 
-fn current_store() -> Store {
+fn current_store<S: AnyStorage>() -> Store<S> {
     match consume_context() {
         Some(rt) => rt,
         None => {
-            let store = Store::default();
+            let store = Store::<S>::new();
             provide_root_context(store).expect("in a virtual dom")
         }
     }
 }
 
-fn current_owner() -> Rc<Owner> {
+fn current_owner<S: AnyStorage>() -> Rc<Owner<S>> {
     match Effect::current() {
         // If we are inside of an effect, we should use the owner of the effect as the owner of the value.
         Some(effect) => {
@@ -39,7 +39,7 @@ fn current_owner() -> Rc<Owner> {
     }
 }
 
-fn owner_in_scope(scope: ScopeId) -> Rc<Owner> {
+fn owner_in_scope<S: AnyStorage>(scope: ScopeId) -> Rc<Owner<S>> {
     match consume_context_from_scope(scope) {
         Some(rt) => rt,
         None => {
@@ -51,14 +51,16 @@ fn owner_in_scope(scope: ScopeId) -> Rc<Owner> {
 
 /// CopyValue is a wrapper around a value to make the value mutable and Copy.
 ///
-/// It is internally backed by [`generational_box::GenerationalBox`].
-pub struct CopyValue<T: 'static> {
-    pub(crate) value: GenerationalBox<T>,
+/// It is internally backed by [`generational_box::GenerationalBox`]. The storage `S` decides which threads can use the
+/// value: the default [`UnsyncStorage`] keeps it on the thread of the virtual dom, while
+/// [`generational_box::SyncStorage`] lets other threads read and write it.
+pub struct CopyValue<T: 'static, S: Storage<T> = UnsyncStorage> {
+    pub(crate) value: GenerationalBox<T, S>,
     origin_scope: ScopeId,
 }
 
 #[cfg(feature = "serde")]
-impl<T: 'static> serde::Serialize for CopyValue<T>
+impl<T: 'static, St: Storage<T>> serde::Serialize for CopyValue<T, St>
 where
     T: serde::Serialize,
 {
@@ -68,14 +70,14 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<'de, T: 'static> serde::Deserialize<'de> for CopyValue<T>
+impl<'de, T: 'static, St: Storage<T>> serde::Deserialize<'de> for CopyValue<T, St>
 where
     T: serde::Deserialize<'de>,
 {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let value = T::deserialize(deserializer)?;
 
-        Ok(Self::new(value))
+        Ok(Self::new_maybe_sync(value))
     }
 }
 
@@ -85,6 +87,21 @@ impl<T: 'static> CopyValue<T> {
     /// Once the component this value is created in is dropped, the value will be dropped.
     #[track_caller]
     pub fn new(value: T) -> Self {
+        Self::new_maybe_sync(value)
+    }
+
+    /// Create a new CopyValue. The value will be stored in the given scope. When the specified scope is dropped, the value will be dropped.
+    pub fn new_in_scope(value: T, scope: ScopeId) -> Self {
+        Self::new_maybe_sync_in_scope(value, scope)
+    }
+}
+
+impl<T: 'static, S: Storage<T>> CopyValue<T, S> {
+    /// Create a new CopyValue with any storage. The value will be stored in the current component.
+    ///
+    /// Once the component this value is created in is dropped, the value will be dropped.
+    #[track_caller]
+    pub fn new_maybe_sync(value: T) -> Self {
         let owner = current_owner();
 
         Self {
@@ -109,8 +126,8 @@ impl<T: 'static> CopyValue<T> {
         }
     }
 
-    /// Create a new CopyValue. The value will be stored in the given scope. When the specified scope is dropped, the value will be dropped.
-    pub fn new_in_scope(value: T, scope: ScopeId) -> Self {
+    /// Create a new CopyValue with any storage. The value will be stored in the given scope. When the specified scope is dropped, the value will be dropped.
+    pub fn new_maybe_sync_in_scope(value: T, scope: ScopeId) -> Self {
         let owner = owner_in_scope(scope);
 
         Self {
@@ -135,25 +152,25 @@ impl<T: 'static> CopyValue<T> {
 
     /// Try to read the value. If the value has been dropped, this will return None.
     #[track_caller]
-    pub fn try_read(&self) -> Result<GenerationalRef<T>, BorrowError> {
+    pub fn try_read(&self) -> Result<S::Ref<T>, BorrowError> {
         self.value.try_read()
     }
 
     /// Read the value. If the value has been dropped, this will panic.
     #[track_caller]
-    pub fn read(&self) -> GenerationalRef<T> {
+    pub fn read(&self) -> S::Ref<T> {
         self.value.read()
     }
 
     /// Try to write the value. If the value has been dropped, this will return None.
     #[track_caller]
-    pub fn try_write(&self) -> Result<GenerationalRefMut<T>, BorrowMutError> {
+    pub fn try_write(&self) -> Result<S::Mut<T>, BorrowMutError> {
         self.value.try_write()
     }
 
     /// Write the value. If the value has been dropped, this will panic.
     #[track_caller]
-    pub fn write(&self) -> GenerationalRefMut<T> {
+    pub fn write(&self) -> S::Mut<T> {
         self.value.write()
     }
 
@@ -175,21 +192,21 @@ impl<T: 'static> CopyValue<T> {
     }
 }
 
-impl<T: Clone + 'static> CopyValue<T> {
+impl<T: Clone + 'static, S: Storage<T>> CopyValue<T, S> {
     /// Get the value. If the value has been dropped, this will panic.
     pub fn value(&self) -> T {
         self.read().clone()
     }
 }
 
-impl<T: 'static> PartialEq for CopyValue<T> {
+impl<T: 'static, S: Storage<T>> PartialEq for CopyValue<T, S> {
     fn eq(&self, other: &Self) -> bool {
         self.value.ptr_eq(&other.value)
     }
 }
 
-impl<T> Deref for CopyValue<T> {
-    type Target = dyn Fn() -> GenerationalRef<T>;
+impl<T, S: Storage<T>> Deref for CopyValue<T, S> {
+    type Target = dyn Fn() -> S::Ref<T>;
 
     fn deref(&self) -> &Self::Target {
         // https://github.com/dtolnay/case-studies/tree/master/callable-types
@@ -1,4 +1,5 @@
 use dioxus_core::prelude::*;
+use dioxus_core::RenderReason;
 
 use crate::dependency::Dependency;
 use crate::use_signal;
@@ -75,11 +76,7 @@ pub fn selector<R: PartialEq>(mut f: impl FnMut() -> R + 'static) -> ReadOnlySig
     let state = Signal::<R> {
         inner: CopyValue::invalid(),
     };
-    let effect = Effect {
-        source: current_scope_id().expect("in a virtual dom"),
-        callback: CopyValue::invalid(),
-        effect_stack: get_effect_stack(),
-    };
+    let effect = Effect::invalid();
 
     {
         get_effect_stack().effects.write().push(effect);
@@ -87,15 +84,16 @@ pub fn selector<R: PartialEq>(mut f: impl FnMut() -> R + 'static) -> ReadOnlySig
     state.inner.value.set(SignalData {
         subscribers: Default::default(),
         effect_subscribers: Default::default(),
-        update_any: schedule_update_any().expect("in a virtual dom"),
+        update_any: schedule_update_any_with_reason(RenderReason::Signal)
+            .expect("in a virtual dom"),
         value: f(),
-        effect_stack: get_effect_stack(),
+        effect_ref: get_effect_stack().effect_ref(),
     });
     {
         get_effect_stack().effects.write().pop();
     }
 
-    effect.callback.value.set(Box::new(move || {
+    effect.set_callback(move || {
         let value = f();
         let changed = {
             let old = state.inner.read();
@@ -104,7 +102,7 @@ pub fn selector<R: PartialEq>(mut f: impl FnMut() -> R + 'static) -> ReadOnlySig
         if changed {
             state.set(value)
         }
-    }));
+    });
 
     ReadOnlySignal::new(state)
 }
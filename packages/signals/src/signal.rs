@@ -7,12 +7,13 @@ use std::{
 };
 
 use dioxus_core::{
-    prelude::{current_scope_id, has_context, provide_context, schedule_update_any},
-    ScopeId, ScopeState,
+    prelude::{current_scope_id, has_context, provide_context, schedule_update_any_with_reason},
+    RenderReason, ScopeId, ScopeState,
 };
-use generational_box::{GenerationalRef, GenerationalRefMut};
+use generational_box::{Storage, SyncStorage, UnsyncStorage};
+use parking_lot::RwLock;
 
-use crate::{get_effect_stack, CopyValue, Effect, EffectStack};
+use crate::{get_effect_stack, CopyValue, Effect, EffectId, EffectStackRef};
 
 /// Creates a new Signal. Signals are a Copy state management solution with automatic dependency tracking.
 ///
@@ -60,18 +61,58 @@ pub fn use_signal<T: 'static>(cx: &ScopeState, f: impl FnOnce() -> T) -> Signal<
     })
 }
 
+/// Creates a new Signal that can be shared between threads. The value must be `Send + Sync`.
+///
+/// Writing to the signal from another thread, for example from a tokio worker or a rayon pool, updates the components and effects that read it on the thread of the virtual dom.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// fn App(cx: Scope) -> Element {
+///     let progress = use_signal_sync(cx, || 0);
+///
+///     cx.use_hook(|| {
+///         std::thread::spawn(move || {
+///             for i in 1..=100 {
+///                 progress.set(i);
+///             }
+///         })
+///     });
+///
+///     render! { "{progress}%" }
+/// }
+/// ```
+#[track_caller]
+#[must_use]
+pub fn use_signal_sync<T: Send + Sync + 'static>(
+    cx: &ScopeState,
+    f: impl FnOnce() -> T,
+) -> Signal<T, SyncStorage> {
+    #[cfg(debug_assertions)]
+    let caller = std::panic::Location::caller();
+
+    *cx.use_hook(|| {
+        Signal::new_with_caller(
+            f(),
+            #[cfg(debug_assertions)]
+            caller,
+        )
+    })
+}
+
 #[derive(Clone)]
 struct Unsubscriber {
     scope: ScopeId,
     subscribers: UnsubscriberArray,
 }
 
-type UnsubscriberArray = Rc<RefCell<Vec<Rc<RefCell<Vec<ScopeId>>>>>>;
+type UnsubscriberArray = Rc<RefCell<Vec<Arc<RwLock<Vec<ScopeId>>>>>>;
 
 impl Drop for Unsubscriber {
     fn drop(&mut self) {
         for subscribers in self.subscribers.borrow().iter() {
-            subscribers.borrow_mut().retain(|s| *s != self.scope);
+            subscribers.write().retain(|s| *s != self.scope);
         }
     }
 }
@@ -89,14 +130,28 @@ fn current_unsubscriber() -> Unsubscriber {
     }
 }
 
-pub(crate) struct SignalData<T> {
-    pub(crate) subscribers: Rc<RefCell<Vec<ScopeId>>>,
-    pub(crate) effect_subscribers: Rc<RefCell<Vec<Effect>>>,
-    pub(crate) update_any: Arc<dyn Fn(ScopeId)>,
-    pub(crate) effect_stack: EffectStack,
+/// The data of a signal. It is `Send + Sync` if the value is, so it can be stored in a [`SyncStorage`].
+pub struct SignalData<T> {
+    pub(crate) subscribers: Arc<RwLock<Vec<ScopeId>>>,
+    pub(crate) effect_subscribers: Arc<RwLock<Vec<EffectId>>>,
+    pub(crate) update_any: Arc<dyn Fn(ScopeId) + Send + Sync>,
+    pub(crate) effect_ref: EffectStackRef,
     pub(crate) value: T,
 }
 
+impl<T> SignalData<T> {
+    fn new(value: T) -> Self {
+        Self {
+            subscribers: Default::default(),
+            effect_subscribers: Default::default(),
+            update_any: schedule_update_any_with_reason(RenderReason::Signal)
+                .expect("in a virtual dom"),
+            effect_ref: get_effect_stack().effect_ref(),
+            value,
+        }
+    }
+}
+
 /// Creates a new Signal. Signals are a Copy state management solution with automatic dependency tracking.
 ///
 /// ```rust
@@ -129,21 +184,25 @@ pub(crate) struct SignalData<T> {
 ///     }
 /// }
 /// ```
-pub struct Signal<T: 'static> {
-    pub(crate) inner: CopyValue<SignalData<T>>,
+///
+/// The storage `S` decides which threads can use the signal. The default [`UnsyncStorage`] keeps it on the thread of the virtual dom, while a `Signal<T, SyncStorage>` (see [`use_signal_sync`]) can be written from other threads.
+pub struct Signal<T: 'static, S: Storage<SignalData<T>> = UnsyncStorage> {
+    pub(crate) inner: CopyValue<SignalData<T>, S>,
 }
 
 #[cfg(feature = "serde")]
-impl<T: serde::Serialize + 'static> serde::Serialize for Signal<T> {
+impl<T: serde::Serialize + 'static, St: Storage<SignalData<T>>> serde::Serialize for Signal<T, St> {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         self.read().serialize(serializer)
     }
 }
 
 #[cfg(feature = "serde")]
-impl<'de, T: serde::Deserialize<'de> + 'static> serde::Deserialize<'de> for Signal<T> {
+impl<'de, T: serde::Deserialize<'de> + 'static, St: Storage<SignalData<T>>> serde::Deserialize<'de>
+    for Signal<T, St>
+{
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        Ok(Self::new(T::deserialize(deserializer)?))
+        Ok(Self::new_maybe_sync(T::deserialize(deserializer)?))
     }
 }
 
@@ -151,15 +210,23 @@ impl<T: 'static> Signal<T> {
     /// Creates a new Signal. Signals are a Copy state management solution with automatic dependency tracking.
     #[track_caller]
     pub fn new(value: T) -> Self {
+        Self::new_maybe_sync(value)
+    }
+
+    /// Create a new signal with a custom owner scope. The signal will be dropped when the owner scope is dropped instead of the current scope.
+    pub fn new_in_scope(value: T, owner: ScopeId) -> Self {
+        Self::new_maybe_sync_in_scope(value, owner)
+    }
+}
+
+impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
+    /// Creates a new Signal with any storage. Signals are a Copy state management solution with automatic dependency tracking.
+    #[track_caller]
+    pub fn new_maybe_sync(value: T) -> Self {
         Self {
-            inner: CopyValue::new(SignalData {
-                subscribers: Default::default(),
-                effect_subscribers: Default::default(),
-                update_any: schedule_update_any().expect("in a virtual dom"),
-                value,
-                effect_stack: get_effect_stack(),
-            }),
+            inner: CopyValue::new_maybe_sync(SignalData::new(value)),
         }
+        .inspected()
     }
 
     /// Creates a new Signal. Signals are a Copy state management solution with automatic dependency tracking.
@@ -169,33 +236,38 @@ impl<T: 'static> Signal<T> {
     ) -> Self {
         Self {
             inner: CopyValue::new_with_caller(
-                SignalData {
-                    subscribers: Default::default(),
-                    effect_subscribers: Default::default(),
-                    update_any: schedule_update_any().expect("in a virtual dom"),
-                    value,
-                    effect_stack: get_effect_stack(),
-                },
+                SignalData::new(value),
                 #[cfg(debug_assertions)]
                 caller,
             ),
         }
+        .inspected()
     }
 
-    /// Create a new signal with a custom owner scope. The signal will be dropped when the owner scope is dropped instead of the current scope.
-    pub fn new_in_scope(value: T, owner: ScopeId) -> Self {
+    /// Create a new signal with any storage and a custom owner scope. The signal will be dropped when the owner scope is dropped instead of the current scope.
+    pub fn new_maybe_sync_in_scope(value: T, owner: ScopeId) -> Self {
         Self {
-            inner: CopyValue::new_in_scope(
-                SignalData {
-                    subscribers: Default::default(),
-                    effect_subscribers: Default::default(),
-                    update_any: schedule_update_any().expect("in a virtual dom"),
-                    value,
-                    effect_stack: get_effect_stack(),
-                },
-                owner,
-            ),
+            inner: CopyValue::new_maybe_sync_in_scope(SignalData::new(value), owner),
         }
+        .inspected()
+    }
+
+    /// Show the signal and the scopes subscribed to it in devtools
+    fn inspected(self) -> Self {
+        #[cfg(debug_assertions)]
+        dioxus_core::prelude::add_inspector(self.origin_scope(), move || {
+            // Once the signal is dropped, the inspector is removed
+            let inner = self.inner.try_read().ok()?;
+            let mut subscribers = inner.subscribers.read().clone();
+            subscribers.sort_unstable_by_key(|scope| scope.0);
+            subscribers.dedup();
+            Some(dioxus_core::InspectedValue {
+                label: format!("Signal<{}>", std::any::type_name::<T>()),
+                value: None,
+                subscribers,
+            })
+        });
+        self
     }
 
     /// Get the scope the signal was created in.
@@ -207,12 +279,12 @@ impl<T: 'static> Signal<T> {
     ///
     /// If the signal has been dropped, this will panic.
     #[track_caller]
-    pub fn read(&self) -> GenerationalRef<T> {
+    pub fn read(&self) -> S::Ref<T> {
         let inner = self.inner.read();
-        if let Some(effect) = inner.effect_stack.current() {
-            let mut effect_subscribers = inner.effect_subscribers.borrow_mut();
-            if !effect_subscribers.contains(&effect) {
-                effect_subscribers.push(effect);
+        if let Some(effect) = Effect::current() {
+            let mut effect_subscribers = inner.effect_subscribers.write();
+            if !effect_subscribers.contains(&effect.id) {
+                effect_subscribers.push(effect.id);
             }
         } else if let Some(current_scope_id) = current_scope_id() {
             // only subscribe if the vdom is rendering
@@ -222,33 +294,33 @@ impl<T: 'static> Signal<T> {
                     self.inner.value,
                     current_scope_id
                 );
-                let mut subscribers = inner.subscribers.borrow_mut();
+                let mut subscribers = inner.subscribers.write();
                 if !subscribers.contains(&current_scope_id) {
                     subscribers.push(current_scope_id);
                     drop(subscribers);
                     let unsubscriber = current_unsubscriber();
-                    inner.subscribers.borrow_mut().push(unsubscriber.scope);
+                    inner.subscribers.write().push(unsubscriber.scope);
                 }
             }
         }
-        GenerationalRef::map(inner, |v| &v.value)
+        S::map(inner, |v| &v.value)
     }
 
     /// Get the current value of the signal. **Unlike read, this will not subscribe the current scope to the signal which can cause parts of your UI to not update.**
     ///
     /// If the signal has been dropped, this will panic.
-    pub fn peek(&self) -> GenerationalRef<T> {
+    pub fn peek(&self) -> S::Ref<T> {
         let inner = self.inner.read();
-        GenerationalRef::map(inner, |v| &v.value)
+        S::map(inner, |v| &v.value)
     }
 
     /// Get a mutable reference to the signal's value.
     ///
     /// If the signal has been dropped, this will panic.
     #[track_caller]
-    pub fn write(&self) -> Write<T> {
+    pub fn write(&self) -> Write<T, T, S> {
         let inner = self.inner.write();
-        let borrow = GenerationalRefMut::map(inner, |v| &mut v.value);
+        let borrow = S::map_mut(inner, |v| &mut v.value);
         Write {
             write: borrow,
             signal: SignalSubscriberDrop { signal: *self },
@@ -258,7 +330,7 @@ impl<T: 'static> Signal<T> {
     fn update_subscribers(&self) {
         {
             let inner = self.inner.read();
-            for &scope_id in &*inner.subscribers.borrow() {
+            for &scope_id in &*inner.subscribers.read() {
                 tracing::trace!(
                     "Write on {:?} triggered update on {:?}",
                     self.inner.value,
@@ -268,10 +340,10 @@ impl<T: 'static> Signal<T> {
             }
         }
 
-        let subscribers = {
+        let (subscribers, effect_ref) = {
             let self_read = self.inner.read();
-            let mut effects = self_read.effect_subscribers.borrow_mut();
-            std::mem::take(&mut *effects)
+            let mut effects = self_read.effect_subscribers.write();
+            (std::mem::take(&mut *effects), self_read.effect_ref.clone())
         };
         for effect in subscribers {
             tracing::trace!(
@@ -279,7 +351,7 @@ impl<T: 'static> Signal<T> {
                 self.inner.value,
                 effect
             );
-            effect.try_run();
+            effect_ref.rerun_effect(effect);
         }
     }
 
@@ -306,7 +378,7 @@ impl<T: 'static> Signal<T> {
     }
 }
 
-impl<T: Clone + 'static> Signal<T> {
+impl<T: Clone + 'static, S: Storage<SignalData<T>>> Signal<T, S> {
     /// Get the current value of the signal. This will subscribe the current scope to the signal.
     /// If the signal has been dropped, this will panic.
     #[track_caller]
@@ -315,21 +387,21 @@ impl<T: Clone + 'static> Signal<T> {
     }
 }
 
-impl Signal<bool> {
+impl<S: Storage<SignalData<bool>>> Signal<bool, S> {
     /// Invert the boolean value of the signal. This will trigger an update on all subscribers.
     pub fn toggle(&self) {
         self.set(!self.value());
     }
 }
 
-impl<T: 'static> PartialEq for Signal<T> {
+impl<T: 'static, S: Storage<SignalData<T>>> PartialEq for Signal<T, S> {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
     }
 }
 
-impl<T> Deref for Signal<T> {
-    type Target = dyn Fn() -> GenerationalRef<T>;
+impl<T, S: Storage<SignalData<T>>> Deref for Signal<T, S> {
+    type Target = dyn Fn() -> S::Ref<T>;
 
     fn deref(&self) -> &Self::Target {
         // https://github.com/dtolnay/case-studies/tree/master/callable-types
@@ -361,44 +433,44 @@ impl<T> Deref for Signal<T> {
     }
 }
 
-struct SignalSubscriberDrop<T: 'static> {
-    signal: Signal<T>,
+struct SignalSubscriberDrop<T: 'static, S: Storage<SignalData<T>>> {
+    signal: Signal<T, S>,
 }
 
-impl<T: 'static> Drop for SignalSubscriberDrop<T> {
+impl<T: 'static, S: Storage<SignalData<T>>> Drop for SignalSubscriberDrop<T, S> {
     fn drop(&mut self) {
         self.signal.update_subscribers();
     }
 }
 
 /// A mutable reference to a signal's value.
-pub struct Write<T: 'static, I: 'static = T> {
-    write: GenerationalRefMut<T>,
-    signal: SignalSubscriberDrop<I>,
+pub struct Write<T: 'static, I: 'static = T, S: Storage<SignalData<I>> = UnsyncStorage> {
+    write: S::Mut<T>,
+    signal: SignalSubscriberDrop<I, S>,
 }
 
-impl<T: 'static, I: 'static> Write<T, I> {
+impl<T: 'static, I: 'static, S: Storage<SignalData<I>>> Write<T, I, S> {
     /// Map the mutable reference to the signal's value to a new type.
-    pub fn map<O>(myself: Self, f: impl FnOnce(&mut T) -> &mut O) -> Write<O, I> {
+    pub fn map<O: 'static>(myself: Self, f: impl FnOnce(&mut T) -> &mut O) -> Write<O, I, S> {
         let Self { write, signal } = myself;
         Write {
-            write: GenerationalRefMut::map(write, f),
+            write: S::map_mut(write, f),
             signal,
         }
     }
 
     /// Try to map the mutable reference to the signal's value to a new type
-    pub fn filter_map<O>(
+    pub fn filter_map<O: 'static>(
         myself: Self,
         f: impl FnOnce(&mut T) -> Option<&mut O>,
-    ) -> Option<Write<O, I>> {
+    ) -> Option<Write<O, I, S>> {
         let Self { write, signal } = myself;
-        let write = GenerationalRefMut::filter_map(write, f);
+        let write = S::try_map_mut(write, f);
         write.map(|write| Write { write, signal })
     }
 }
 
-impl<T: 'static, I: 'static> Deref for Write<T, I> {
+impl<T: 'static, I: 'static, S: Storage<SignalData<I>>> Deref for Write<T, I, S> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -406,20 +478,20 @@ impl<T: 'static, I: 'static> Deref for Write<T, I> {
     }
 }
 
-impl<T, I> DerefMut for Write<T, I> {
+impl<T, I, S: Storage<SignalData<I>>> DerefMut for Write<T, I, S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.write
     }
 }
 
 /// A signal that can only be read from.
-pub struct ReadOnlySignal<T: 'static> {
-    inner: Signal<T>,
+pub struct ReadOnlySignal<T: 'static, S: Storage<SignalData<T>> = UnsyncStorage> {
+    inner: Signal<T, S>,
 }
 
-impl<T: 'static> ReadOnlySignal<T> {
+impl<T: 'static, S: Storage<SignalData<T>>> ReadOnlySignal<T, S> {
     /// Create a new read-only signal.
-    pub fn new(signal: Signal<T>) -> Self {
+    pub fn new(signal: Signal<T, S>) -> Self {
         Self { inner: signal }
     }
 
@@ -432,14 +504,14 @@ impl<T: 'static> ReadOnlySignal<T> {
     ///
     /// If the signal has been dropped, this will panic.
     #[track_caller]
-    pub fn read(&self) -> GenerationalRef<T> {
+    pub fn read(&self) -> S::Ref<T> {
         self.inner.read()
     }
 
     /// Get the current value of the signal. **Unlike read, this will not subscribe the current scope to the signal which can cause parts of your UI to not update.**
     ///
     /// If the signal has been dropped, this will panic.
-    pub fn peek(&self) -> GenerationalRef<T> {
+    pub fn peek(&self) -> S::Ref<T> {
         self.inner.peek()
     }
 
@@ -450,21 +522,21 @@ impl<T: 'static> ReadOnlySignal<T> {
     }
 }
 
-impl<T: Clone + 'static> ReadOnlySignal<T> {
+impl<T: Clone + 'static, S: Storage<SignalData<T>>> ReadOnlySignal<T, S> {
     /// Get the current value of the signal. This will subscribe the current scope to the signal.
     pub fn value(&self) -> T {
         self.read().clone()
     }
 }
 
-impl<T: 'static> PartialEq for ReadOnlySignal<T> {
+impl<T: 'static, S: Storage<SignalData<T>>> PartialEq for ReadOnlySignal<T, S> {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
     }
 }
 
-impl<T> Deref for ReadOnlySignal<T> {
-    type Target = dyn Fn() -> GenerationalRef<T>;
+impl<T, S: Storage<SignalData<T>>> Deref for ReadOnlySignal<T, S> {
+    type Target = dyn Fn() -> S::Ref<T>;
 
     fn deref(&self) -> &Self::Target {
         // https://github.com/dtolnay/case-studies/tree/master/callable-types
@@ -496,8 +568,8 @@ impl<T> Deref for ReadOnlySignal<T> {
     }
 }
 
-impl<T> From<Signal<T>> for ReadOnlySignal<T> {
-    fn from(signal: Signal<T>) -> Self {
+impl<T, S: Storage<SignalData<T>>> From<Signal<T, S>> for ReadOnlySignal<T, S> {
+    fn from(signal: Signal<T, S>) -> Self {
         Self::new(signal)
     }
 }
@@ -0,0 +1,151 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+
+#[test]
+fn undo_and_redo() {
+    let mut dom = VirtualDom::new(|cx| {
+        let count = use_signal_with_history(cx, || 0);
+
+        if cx.generation() == 0 {
+            count.set(1);
+            count.set(2);
+            assert_eq!(count.value(), 2);
+
+            assert!(count.undo());
+            assert_eq!(count.value(), 1);
+            assert!(count.undo());
+            assert_eq!(count.value(), 0);
+            assert!(!count.undo());
+
+            assert!(count.redo());
+            assert_eq!(count.value(), 1);
+
+            // A new write forgets the steps that were undone
+            count.set(5);
+            assert!(!count.redo());
+            assert!(count.undo());
+            assert_eq!(count.value(), 1);
+        }
+
+        render! { "{count}" }
+    });
+
+    let _ = dom.rebuild().santize();
+}
+
+#[test]
+fn bounded_capacity() {
+    let mut dom = VirtualDom::new(|cx| {
+        let history = use_history(cx, 2);
+        let count = use_tracked_signal(cx, history, || 0);
+
+        if cx.generation() == 0 {
+            for i in 1..=5 {
+                count.set(i);
+            }
+            assert!(history.undo());
+            assert!(history.undo());
+            assert!(!history.undo());
+            assert_eq!(count.value(), 3);
+        }
+
+        render! { "{count}" }
+    });
+
+    let _ = dom.rebuild().santize();
+}
+
+#[test]
+fn transactions_group_writes_across_signals() {
+    let mut dom = VirtualDom::new(|cx| {
+        let history = use_history(cx, 10);
+        let first = use_tracked_signal(cx, history, || "a".to_string());
+        let second = use_tracked_signal(cx, history, || 0);
+
+        if cx.generation() == 0 {
+            history.transaction(|| {
+                first.set("b".to_string());
+                first.set("c".to_string());
+                second.with_mut(|v| *v += 1);
+            });
+            assert_eq!(first.value(), "c");
+            assert_eq!(second.value(), 1);
+
+            assert!(history.undo());
+            assert_eq!(first.value(), "a");
+            assert_eq!(second.value(), 0);
+            assert!(!history.can_undo());
+
+            assert!(history.redo());
+            assert_eq!(first.value(), "c");
+            assert_eq!(second.value(), 1);
+        }
+
+        render! { "{first} {second}" }
+    });
+
+    let _ = dom.rebuild().santize();
+}
+
+#[test]
+fn undo_notifies_subscribers() {
+    #[derive(Default)]
+    struct RunCounter {
+        child: usize,
+    }
+
+    let counter = Rc::new(RefCell::new(RunCounter::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let count = use_signal_with_history(cx, || 0);
+
+            match cx.generation() {
+                0 => count.set(1),
+                1 => {
+                    count.undo();
+                }
+                _ => {}
+            }
+
+            render! {
+                Child {
+                    signal: count.signal(),
+                    counter: cx.props.clone(),
+                }
+            }
+        },
+        counter.clone(),
+    );
+
+    #[derive(Props, Clone)]
+    struct ChildProps {
+        signal: Signal<usize>,
+        counter: Rc<RefCell<RunCounter>>,
+    }
+
+    impl PartialEq for ChildProps {
+        fn eq(&self, other: &Self) -> bool {
+            self.signal == other.signal
+        }
+    }
+
+    fn Child(cx: Scope<ChildProps>) -> Element {
+        cx.props.counter.borrow_mut().child += 1;
+
+        render! {
+            "{cx.props.signal}"
+        }
+    }
+
+    let _ = dom.rebuild().santize();
+    assert_eq!(counter.borrow().child, 1);
+
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate();
+    dom.render_immediate();
+
+    assert_eq!(counter.borrow().child, 2);
+}
@@ -0,0 +1,151 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+use futures_channel::oneshot;
+
+#[tokio::test]
+async fn resources_suspend_until_ready() {
+    #[derive(Default)]
+    struct State {
+        renders: usize,
+        sender: Option<oneshot::Sender<usize>>,
+        value: Option<Result<usize, ()>>,
+    }
+
+    fn Child(cx: Scope) -> Element {
+        let state = cx.consume_context::<Rc<RefCell<State>>>().unwrap();
+        state.borrow_mut().renders += 1;
+
+        let resource = use_resource(cx, move || {
+            let (sender, receiver) = oneshot::channel();
+            state.borrow_mut().sender = Some(sender);
+            async move { receiver.await.map_err(|_| ()) }
+        });
+        let value = resource.suspend(cx);
+        cx.consume_context::<Rc<RefCell<State>>>()
+            .unwrap()
+            .borrow_mut()
+            .value = value;
+        let value = value?;
+
+        render! {
+            "{value:?}"
+        }
+    }
+
+    let state = Rc::new(RefCell::new(State::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            cx.provide_context(cx.props.clone());
+
+            render! {
+                Child {}
+            }
+        },
+        state.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+
+    assert_eq!(state.borrow().renders, 1);
+    assert_eq!(state.borrow().value, None);
+    assert!(dom.has_suspended_work());
+
+    let sender = state.borrow_mut().sender.take().unwrap();
+    sender.send(2).unwrap();
+
+    dom.wait_for_work().await;
+    let _ = dom.render_immediate();
+
+    assert_eq!(state.borrow().renders, 2);
+    assert_eq!(state.borrow().value, Some(Ok(2)));
+    assert!(!dom.has_suspended_work());
+}
+
+#[tokio::test]
+async fn dependency_changes_cancel_running_future() {
+    #[derive(Default)]
+    struct State {
+        id: Option<Signal<usize>>,
+        senders: Vec<oneshot::Sender<usize>>,
+        value: Option<Result<usize, ()>>,
+    }
+
+    fn Child(cx: Scope) -> Element {
+        let state = cx.consume_context::<Rc<RefCell<State>>>().unwrap();
+        let id = use_signal(cx, || 0);
+        state.borrow_mut().id = Some(id);
+
+        let resource = use_resource(cx, {
+            let state = state.clone();
+            move || {
+                let (sender, receiver) = oneshot::channel();
+                state.borrow_mut().senders.push(sender);
+                async move {
+                    // Only the signals read before the first await are tracked
+                    let id = id.value();
+                    let multiplier = receiver.await.map_err(|_| ())?;
+                    Ok(id * multiplier)
+                }
+            }
+        });
+        let value = resource.suspend(cx);
+        state.borrow_mut().value = value;
+        let value = value?;
+
+        render! {
+            "{value:?}"
+        }
+    }
+
+    let state = Rc::new(RefCell::new(State::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            cx.provide_context(cx.props.clone());
+
+            render! {
+                Child {}
+            }
+        },
+        state.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+
+    let id = state.borrow().id.unwrap();
+    id.set(1);
+
+    dom.wait_for_work().await;
+    let _ = dom.render_immediate();
+
+    let mut senders = std::mem::take(&mut state.borrow_mut().senders);
+    assert_eq!(senders.len(), 2);
+    // The future of the first run was dropped when the id changed
+    assert!(senders.remove(0).send(10).is_err());
+    senders.remove(0).send(10).unwrap();
+
+    dom.wait_for_work().await;
+    let _ = dom.render_immediate();
+
+    assert_eq!(state.borrow().value, Some(Ok(10)));
+}
+
+#[test]
+fn futures_that_finish_immediately_are_ready() {
+    let mut dom = VirtualDom::new(|cx| {
+        let resource = use_resource(cx, || async { Err::<usize, _>("failed") });
+        assert_eq!(resource.value(), ResourceState::Errored("failed"));
+
+        resource.restart();
+        assert_eq!(resource.value(), ResourceState::Errored("failed"));
+
+        render! {
+            div {}
+        }
+    });
+
+    let _ = dom.rebuild().santize();
+    assert!(!dom.has_suspended_work());
+}
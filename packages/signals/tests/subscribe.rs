@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use dioxus::prelude::*;
-use dioxus_core::ElementId;
+use dioxus_core::{ElementId, RenderReason};
 use dioxus_signals::*;
 
 #[test]
@@ -90,3 +90,48 @@ fn reading_subscribes() {
         }
     }
 }
+
+#[test]
+fn signal_writes_are_profiled() {
+    let mut dom = VirtualDom::new(|cx| {
+        let mut signal = use_signal(cx, || 0);
+        if cx.generation() == 1 {
+            signal += 1;
+        }
+
+        render! {
+            "{signal}"
+        }
+    });
+    dom.start_profiling();
+
+    let _ = dom.rebuild().santize();
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate();
+    dom.render_immediate();
+
+    let profile = dom.stop_profiling();
+    let last = profile.passes.last().unwrap();
+    assert_eq!(last.dirty[0].reason, RenderReason::Signal);
+    assert_eq!(last.renders[0].reason, RenderReason::Signal);
+}
+
+#[test]
+fn signals_show_their_subscribers_in_devtools() {
+    let mut dom = VirtualDom::new(|cx| {
+        let signal = use_signal(cx, || 0);
+
+        render! {
+            "{signal}"
+        }
+    });
+    let _ = dom.rebuild().santize();
+
+    let root = &dom.inspect()[0];
+    let signal = root
+        .values
+        .iter()
+        .find(|value| value.label == "Signal<i32>")
+        .unwrap();
+    assert_eq!(signal.subscribers, [ScopeId::ROOT]);
+}
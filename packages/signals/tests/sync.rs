@@ -0,0 +1,51 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+
+#[tokio::test]
+async fn writes_from_other_threads() {
+    #[derive(Default)]
+    struct RunCounter {
+        component: usize,
+        effect: usize,
+        signal: Option<Signal<usize, SyncStorage>>,
+    }
+
+    let counter = Rc::new(RefCell::new(RunCounter::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            cx.props.borrow_mut().component += 1;
+
+            let signal = use_signal_sync(cx, || 0);
+            cx.use_hook(|| {
+                let counter = cx.props.clone();
+                counter.borrow_mut().signal = Some(signal);
+                Effect::new(move || {
+                    // Reading the signal subscribes the effect to it
+                    _ = signal();
+                    counter.borrow_mut().effect += 1;
+                })
+            });
+
+            render! {
+                "{signal}"
+            }
+        },
+        counter.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+
+    let signal = counter.borrow().signal.unwrap();
+    std::thread::spawn(move || signal.set(1)).join().unwrap();
+
+    dom.wait_for_work().await;
+    let _ = dom.render_immediate();
+
+    let current_counter = counter.borrow();
+    assert_eq!(current_counter.component, 2);
+    assert_eq!(current_counter.effect, 2);
+    assert_eq!(*signal.peek(), 1);
+}
@@ -12,6 +12,7 @@ mod incremental_cfg;
 
 pub mod eval;
 pub mod renderer;
+mod streaming;
 pub mod template;
 
 use dioxus_core::{Element, LazyNodes, Scope, VirtualDom};
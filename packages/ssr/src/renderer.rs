@@ -1,8 +1,11 @@
 use super::cache::Segment;
 use crate::cache::StringCache;
+use crate::streaming::SuspenseStream;
 
 use dioxus_core::Attribute;
 use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn};
+#[cfg(feature = "islands")]
+use dioxus_html::islands::IslandMarker;
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::Arc;
@@ -22,15 +25,27 @@ pub struct Renderer {
     /// Choose to write ElementIDs into elements so the page can be re-hydrated later on
     pub pre_render: bool,
 
+    /// Only write hydration data inside of components marked with [`dioxus_html::islands::use_island`]. The rest of
+    /// the page is rendered as static HTML. This has no effect unless `pre_render` is enabled. Requires the `islands` feature.
+    #[cfg(feature = "islands")]
+    pub islands: bool,
+
     // Currently not implemented
     // Don't proceed onto new components. Instead, put the name of the component.
     pub skip_components: bool,
 
-    /// A cache of templates that have been rendered
-    template_cache: HashMap<&'static str, Arc<StringCache>>,
+    /// A cache of templates that have been rendered, and if they were rendered with hydration data
+    template_cache: HashMap<(&'static str, bool), Arc<StringCache>>,
+
+    /// How many islands we are currently rendering inside of
+    #[cfg(feature = "islands")]
+    island_depth: usize,
 
     /// The current dynamic node id for hydration
-    dynamic_node_id: usize,
+    pub(crate) dynamic_node_id: usize,
+
+    /// The state of the current stream if we are streaming suspended components
+    pub(crate) suspense: Option<SuspenseStream>,
 }
 
 impl Renderer {
@@ -64,18 +79,23 @@ impl Renderer {
         Ok(())
     }
 
-    fn render_template(
+    pub(crate) fn render_template(
         &mut self,
         buf: &mut impl Write,
         dom: &VirtualDom,
         template: &VNode,
     ) -> std::fmt::Result {
+        // Outside of islands, the page is static and doesn't need any hydration data
+        #[cfg(feature = "islands")]
+        let pre_render = self.pre_render && (!self.islands || self.island_depth > 0);
+        #[cfg(not(feature = "islands"))]
+        let pre_render = self.pre_render;
+
         let entry = self
             .template_cache
-            .entry(template.template.get().name)
-            .or_insert_with({
-                let prerender = self.pre_render;
-                move || Arc::new(StringCache::from_template(template, prerender).unwrap())
+            .entry((template.template.get().name, pre_render))
+            .or_insert_with(move || {
+                Arc::new(StringCache::from_template(template, pre_render).unwrap())
             })
             .clone();
 
@@ -104,7 +124,7 @@ impl Renderer {
                             write_attribute(buf, attr)?;
                         }
 
-                        if self.pre_render {
+                        if pre_render {
                             if let AttributeValue::Listener(_) = &attr.value {
                                 // The onmounted event doesn't need a DOM listener
                                 if attr.name != "onmounted" {
@@ -122,20 +142,36 @@ impl Renderer {
                         } else {
                             let id = node.mounted_scope().unwrap();
                             let scope = dom.get_scope(id).unwrap();
+
+                            #[cfg(feature = "islands")]
+                            let island = self.enter_island(buf, scope)?;
+
                             let node = scope.root_node();
                             match node {
                                 RenderReturn::Ready(node) => {
                                     self.render_template(buf, dom, node)?
                                 }
+                                // While streaming, suspended components are rendered later in their own chunk
+                                RenderReturn::Aborted(_)
+                                    if self.suspense.is_some() && dom.is_suspended(id) =>
+                                {
+                                    self.render_suspense_placeholder(buf, id)?
+                                }
                                 _ => todo!(
                                     "generally, scopes should be sync, only if being traversed"
                                 ),
                             }
+
+                            #[cfg(feature = "islands")]
+                            if island {
+                                self.island_depth -= 1;
+                                write!(buf, "</div>")?;
+                            }
                         }
                     }
                     DynamicNode::Text(text) => {
                         // in SSR, we are concerned that we can't hunt down the right text node since they might get merged
-                        if self.pre_render {
+                        if pre_render {
                             write!(buf, "<!--node-id{}-->", self.dynamic_node_id)?;
                             self.dynamic_node_id += 1;
                         }
@@ -146,7 +182,7 @@ impl Renderer {
                             askama_escape::escape(text.value, askama_escape::Html)
                         )?;
 
-                        if self.pre_render {
+                        if pre_render {
                             write!(buf, "<!--#-->")?;
                         }
                     }
@@ -157,7 +193,7 @@ impl Renderer {
                     }
 
                     DynamicNode::Placeholder(_) => {
-                        if self.pre_render {
+                        if pre_render {
                             write!(
                                 buf,
                                 "<pre data-node-hydration={}></pre>",
@@ -223,6 +259,30 @@ impl Renderer {
 
         Ok(())
     }
+
+    /// Open the wrapper of an island if this component is the outermost island. Returns true if the wrapper was opened
+    #[cfg(feature = "islands")]
+    fn enter_island(
+        &mut self,
+        buf: &mut impl Write,
+        scope: &ScopeState,
+    ) -> Result<bool, std::fmt::Error> {
+        // Islands inside of other islands are hydrated with the outer island
+        if !self.pre_render || !self.islands || self.island_depth > 0 {
+            return Ok(false);
+        }
+        let Some(island) = scope.has_context::<IslandMarker>() else {
+            return Ok(false);
+        };
+        write!(
+            buf,
+            r#"<div data-dioxus-island="{}" data-dioxus-island-props="{}" style="display:contents;">"#,
+            island.name,
+            askama_escape::escape(&island.props, askama_escape::Html)
+        )?;
+        self.island_depth += 1;
+        Ok(true)
+    }
 }
 
 #[test]
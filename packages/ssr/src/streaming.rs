@@ -0,0 +1,280 @@
+//! Out-of-order streaming of suspended components.
+//!
+//! When streaming, the renderer writes everything that is ready immediately and leaves a placeholder for every
+//! component that is still suspended. Once a suspended component resolves, it is written as a `<template>` chunk
+//! followed by a small script that swaps the chunk into the placeholder.
+
+use crate::Renderer;
+use dioxus_core::{prelude::*, RenderReturn};
+use rustc_hash::FxHashMap;
+use std::fmt::Write;
+
+/// The script that moves streamed chunks into their placeholders and fixes up hydration ids once the stream is done
+const SUSPENSE_SCRIPT: &str = r#"window.__dioxus_resolve_suspense = function (id) {
+    const chunk = document.querySelector('template[data-dioxus-suspense-chunk="' + id + '"]');
+    const placeholder = document.querySelector('template[data-dioxus-suspense="' + id + '"]');
+    if (chunk && placeholder) {
+        placeholder.replaceWith(chunk.content);
+    }
+    if (chunk) {
+        chunk.remove();
+    }
+};
+window.__dioxus_remap_hydration = function (map) {
+    const hydrateNodes = document.querySelectorAll('[data-node-hydration]');
+    for (let i = 0; i < hydrateNodes.length; i++) {
+        const split = hydrateNodes[i].getAttribute('data-node-hydration').split(',');
+        split[0] = map[parseInt(split[0])];
+        hydrateNodes[i].setAttribute('data-node-hydration', split.join(','));
+    }
+    const treeWalker = document.createTreeWalker(document.body, NodeFilter.SHOW_COMMENT);
+    let currentNode = treeWalker.nextNode();
+    while (currentNode) {
+        const split = currentNode.textContent.split('node-id');
+        if (split.length > 1) {
+            currentNode.textContent = 'node-id' + map[parseInt(split[1])];
+        }
+        currentNode = treeWalker.nextNode();
+    }
+};"#;
+
+/// The state of a stream of suspended components
+#[derive(Default)]
+pub(crate) struct SuspenseStream {
+    /// Suspended scopes that have a placeholder in the document, but have not been streamed yet
+    pending: Vec<ScopeId>,
+
+    /// The hydration ids each chunk used. The shell of the page is stored under `None`
+    chunks: FxHashMap<Option<ScopeId>, StreamedChunk>,
+
+    /// The chunk that is currently being written
+    current: Option<ScopeId>,
+
+    /// If the script that resolves chunks has already been written
+    script_written: bool,
+}
+
+/// Hydration ids are assigned in the order chunks are written, not in the order the nodes appear in the document.
+/// We keep track of the range of ids each chunk used and where other chunks were inserted into it so we can
+/// map the ids back to document order once the stream is finished.
+#[derive(Default)]
+struct StreamedChunk {
+    start: usize,
+    end: usize,
+    /// The id the next node would have been given when a placeholder was written, and the scope of the placeholder
+    placeholders: Vec<(usize, ScopeId)>,
+}
+
+impl SuspenseStream {
+    /// Write the start of the chunk of a suspended scope
+    fn start_chunk(&mut self, buf: &mut impl Write, id: ScopeId) -> std::fmt::Result {
+        if !self.script_written {
+            self.script_written = true;
+            write!(
+                buf,
+                "<script>{SUSPENSE_SCRIPT}document.currentScript.remove()</script>"
+            )?;
+        }
+
+        write!(buf, r#"<template data-dioxus-suspense-chunk="{}">"#, id.0)
+    }
+
+    /// Write the end of the chunk of a suspended scope and swap it into its placeholder
+    fn end_chunk(buf: &mut impl Write, id: ScopeId) -> std::fmt::Result {
+        write!(
+            buf,
+            "</template><script>__dioxus_resolve_suspense({});document.currentScript.remove()</script>",
+            id.0
+        )
+    }
+
+    /// Collect the hydration ids in the order they appear in the document
+    fn document_order(&self, chunk: Option<ScopeId>, order: &mut Vec<usize>) {
+        let Some(streamed) = self.chunks.get(&chunk) else {
+            return;
+        };
+
+        let mut current = streamed.start;
+        for (position, scope) in &streamed.placeholders {
+            order.extend(current..*position);
+            self.document_order(Some(*scope), order);
+            current = *position;
+        }
+        order.extend(current..streamed.end);
+    }
+}
+
+impl Renderer {
+    /// Start streaming a VirtualDom.
+    ///
+    /// This renders everything that is ready and writes a placeholder for every component that is still suspended.
+    /// Keep polling the VirtualDom and call [`Renderer::render_resolved_suspense`] to stream the suspended components
+    /// in as they resolve, then call [`Renderer::finish_stream`] once nothing is pending.
+    ///
+    /// ```rust, ignore
+    /// let mut renderer = Renderer::new();
+    /// renderer.render_shell(&mut buf, &dom)?;
+    ///
+    /// while renderer.has_pending_suspense() {
+    ///     dom.wait_for_work().await;
+    ///     _ = dom.render_immediate();
+    ///     renderer.render_resolved_suspense(&mut buf, &dom)?;
+    /// }
+    ///
+    /// renderer.finish_stream(&mut buf)?;
+    /// ```
+    pub fn render_shell(&mut self, buf: &mut impl Write, dom: &VirtualDom) -> std::fmt::Result {
+        self.dynamic_node_id = 0;
+        self.suspense = Some(SuspenseStream::default());
+        self.render_chunk(buf, dom, None)
+    }
+
+    /// Check if there are any suspended components left that have not been streamed yet
+    pub fn has_pending_suspense(&self) -> bool {
+        self.suspense
+            .as_ref()
+            .map(|stream| !stream.pending.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Write a chunk for every suspended component that has resolved since the last call.
+    ///
+    /// Any components inside of the resolved components that are still suspended get a placeholder of their own.
+    pub fn render_resolved_suspense(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+    ) -> std::fmt::Result {
+        let Some(stream) = self.suspense.as_mut() else {
+            return Ok(());
+        };
+
+        let mut resolved = Vec::new();
+        stream.pending.retain(|&id| {
+            // If the scope was removed while it was suspended, the placeholder will never be filled
+            if dom.get_scope(id).is_none() {
+                return false;
+            }
+            if dom.is_suspended(id) {
+                return true;
+            }
+            resolved.push(id);
+            false
+        });
+
+        for id in resolved {
+            self.suspense.as_mut().unwrap().start_chunk(buf, id)?;
+            self.render_chunk(buf, dom, Some(id))?;
+            SuspenseStream::end_chunk(buf, id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Finish the stream. If the page is pre-rendered, this writes a script that restores the document order of the
+    /// hydration ids.
+    ///
+    /// Components that are still suspended are left for the client. Their placeholder is replaced with the placeholder
+    /// node the client renders while they are suspended, so the hydration ids of the page still line up.
+    pub fn finish_stream(&mut self, buf: &mut impl Write) -> std::fmt::Result {
+        let Some(mut stream) = self.suspense.take() else {
+            return Ok(());
+        };
+
+        for id in std::mem::take(&mut stream.pending) {
+            stream.start_chunk(buf, id)?;
+            let start = self.dynamic_node_id;
+            if self.pre_render {
+                write!(buf, "<pre data-node-hydration={start}></pre>")?;
+                self.dynamic_node_id += 1;
+            }
+            stream.chunks.insert(
+                Some(id),
+                StreamedChunk {
+                    start,
+                    end: self.dynamic_node_id,
+                    ..Default::default()
+                },
+            );
+            SuspenseStream::end_chunk(buf, id)?;
+        }
+
+        if !self.pre_render || !stream.script_written {
+            return Ok(());
+        }
+
+        let mut order = Vec::with_capacity(self.dynamic_node_id);
+        stream.document_order(None, &mut order);
+
+        let mut map = vec![0; self.dynamic_node_id];
+        for (document_id, streamed_id) in order.into_iter().enumerate() {
+            map[streamed_id] = document_id;
+        }
+
+        write!(buf, "<script>__dioxus_remap_hydration([")?;
+        for (i, id) in map.iter().enumerate() {
+            if i != 0 {
+                write!(buf, ",")?;
+            }
+            write!(buf, "{id}")?;
+        }
+        write!(buf, "]);document.currentScript.remove()</script>")
+    }
+
+    /// Render a scope into the current stream without resetting the hydration ids
+    fn render_chunk(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+        chunk: Option<ScopeId>,
+    ) -> std::fmt::Result {
+        let scope = chunk.unwrap_or(ScopeId::ROOT);
+
+        if let Some(stream) = self.suspense.as_mut() {
+            stream.current = chunk;
+            stream.chunks.insert(
+                chunk,
+                StreamedChunk {
+                    start: self.dynamic_node_id,
+                    ..Default::default()
+                },
+            );
+        }
+
+        match dom.get_scope(scope).unwrap().root_node() {
+            RenderReturn::Ready(node) => self.render_template(buf, dom, node)?,
+            RenderReturn::Aborted(_) if dom.is_suspended(scope) => {
+                self.render_suspense_placeholder(buf, scope)?
+            }
+            RenderReturn::Aborted(_) => {}
+        }
+
+        if let Some(stream) = self.suspense.as_mut() {
+            if let Some(streamed) = stream.chunks.get_mut(&chunk) {
+                streamed.end = self.dynamic_node_id;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write a placeholder for a suspended scope that will be replaced once the scope resolves
+    pub(crate) fn render_suspense_placeholder(
+        &mut self,
+        buf: &mut impl Write,
+        scope: ScopeId,
+    ) -> std::fmt::Result {
+        if let Some(stream) = self.suspense.as_mut() {
+            stream.pending.push(scope);
+            if let Some(streamed) = stream.chunks.get_mut(&stream.current) {
+                streamed.placeholders.push((self.dynamic_node_id, scope));
+            }
+        }
+
+        write!(
+            buf,
+            r#"<template data-dioxus-suspense="{}"></template>"#,
+            scope.0
+        )
+    }
+}
@@ -0,0 +1,73 @@
+#![allow(non_snake_case)]
+#![cfg(feature = "islands")]
+
+use dioxus::prelude::*;
+
+#[derive(Props, PartialEq, serde::Serialize)]
+struct CounterProps {
+    start: i32,
+}
+
+fn Counter(cx: Scope<CounterProps>) -> Element {
+    use_island(cx, "counter", cx.props);
+    let start = cx.props.start;
+
+    render! {
+        button { onclick: |_| {}, "{start}" }
+    }
+}
+
+#[test]
+fn only_islands_are_hydrated() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div { onclick: |_| {}, "static" }
+            Counter { start: 1 }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.pre_render = true;
+    renderer.islands = true;
+
+    assert_eq!(
+        renderer.render(&dom),
+        r#"<div>static</div><div data-dioxus-island="counter" data-dioxus-island-props="{&quot;start&quot;:1}" style="display:contents;"><button data-node-hydration="0,click:1"><!--node-id1-->1<!--#--></button></div>"#
+    );
+}
+
+#[test]
+fn pre_render_without_islands_hydrates_everything() {
+    fn app(cx: Scope) -> Element {
+        render! { Counter { start: 2 } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.pre_render = true;
+
+    assert_eq!(
+        renderer.render(&dom),
+        r#"<button data-node-hydration="0,click:1"><!--node-id1-->2<!--#--></button>"#
+    );
+}
+
+#[test]
+fn islands_without_pre_render_are_static() {
+    fn app(cx: Scope) -> Element {
+        render! { Counter { start: 3 } }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.islands = true;
+
+    assert_eq!(renderer.render(&dom), r#"<button>3</button>"#);
+}
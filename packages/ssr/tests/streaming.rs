@@ -0,0 +1,121 @@
+use dioxus::prelude::*;
+
+async fn stream(dom: &mut VirtualDom, renderer: &mut dioxus_ssr::Renderer) -> (String, String) {
+    let mut shell = String::new();
+    renderer.render_shell(&mut shell, dom).unwrap();
+
+    let mut chunks = String::new();
+    while renderer.has_pending_suspense() {
+        dom.wait_for_work().await;
+        _ = dom.render_immediate();
+        renderer.render_resolved_suspense(&mut chunks, dom).unwrap();
+    }
+    renderer.finish_stream(&mut chunks).unwrap();
+
+    (shell, chunks)
+}
+
+fn suspended_child(cx: Scope) -> Element {
+    let val = use_state(cx, || 0);
+
+    if **val < 3 {
+        let mut val = val.clone();
+        cx.spawn(async move {
+            val += 1;
+        });
+        cx.suspend()?;
+    }
+
+    render! { div { "child" } }
+}
+
+#[tokio::test]
+async fn streams_suspended_components() {
+    fn app(cx: Scope) -> Element {
+        render! {
+            div {
+                "Waiting for... "
+                suspended_child {}
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    let (shell, chunks) = stream(&mut dom, &mut renderer).await;
+
+    assert_eq!(
+        shell,
+        r#"<div>Waiting for... <template data-dioxus-suspense="1"></template></div>"#
+    );
+    assert!(chunks.contains(
+        r#"<template data-dioxus-suspense-chunk="1"><div>child</div></template><script>__dioxus_resolve_suspense(1);document.currentScript.remove()</script>"#
+    ));
+    assert!(!renderer.has_pending_suspense());
+}
+
+#[tokio::test]
+async fn streamed_hydration_ids_are_remapped() {
+    fn app(cx: Scope) -> Element {
+        let after = "after";
+        render! {
+            div {
+                suspended_child {}
+                "{after}"
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.pre_render = true;
+    let (_, chunks) = stream(&mut dom, &mut renderer).await;
+
+    // The suspended child is streamed after the text that follows it, so its id needs to be moved before the text
+    assert!(chunks.ends_with(
+        "<script>__dioxus_remap_hydration([0,2,1]);document.currentScript.remove()</script>"
+    ));
+}
+
+#[tokio::test]
+async fn unresolved_components_are_streamed_as_placeholders() {
+    fn never_resolves(cx: Scope) -> Element {
+        cx.suspend()?;
+        None
+    }
+
+    fn app(cx: Scope) -> Element {
+        let after = "after";
+        render! {
+            div {
+                never_resolves {}
+                "{after}"
+            }
+        }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.pre_render = true;
+    let mut shell = String::new();
+    renderer.render_shell(&mut shell, &dom).unwrap();
+    assert!(renderer.has_pending_suspense());
+
+    // Finish the stream before the component resolves, like a server that timed out
+    let mut chunks = String::new();
+    renderer.finish_stream(&mut chunks).unwrap();
+
+    assert!(chunks.contains(
+        r#"<template data-dioxus-suspense-chunk="1"><pre data-node-hydration=2></pre></template><script>__dioxus_resolve_suspense(1);document.currentScript.remove()</script>"#
+    ));
+    // The placeholder comes before the text in the document
+    assert!(chunks.ends_with(
+        "<script>__dioxus_remap_hydration([0,2,1]);document.currentScript.remove()</script>"
+    ));
+}
@@ -10,6 +10,8 @@
 pub struct Config {
     #[cfg(feature = "hydrate")]
     pub(crate) hydrate: bool,
+    #[cfg(feature = "islands")]
+    pub(crate) islands: bool,
     pub(crate) root: ConfigRoot,
     pub(crate) cached_strings: Vec<String>,
     pub(crate) default_panic_hook: bool,
@@ -20,6 +22,8 @@ impl Default for Config {
         Self {
             #[cfg(feature = "hydrate")]
             hydrate: false,
+            #[cfg(feature = "islands")]
+            islands: false,
             root: ConfigRoot::RootName("main".to_string()),
             cached_strings: Vec::new(),
             default_panic_hook: true,
@@ -8,12 +8,14 @@
 
 use dioxus_core::{
     BorrowedAttributeValue, ElementId, Mutation, Template, TemplateAttribute, TemplateNode,
+    VirtualDom,
 };
 use dioxus_html::event_bubbles;
 use dioxus_html::PlatformEventData;
 use dioxus_interpreter_js::{get_node, minimal_bindings, save_template, Channel};
 use futures_channel::mpsc;
 use rustc_hash::FxHashMap;
+use std::{cell::RefCell, rc::Rc};
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use web_sys::{Document, Element, Event};
 
@@ -40,7 +42,11 @@ pub struct UiEvent {
 //fn get_document(elem: &web_sys::Element) ->
 
 impl WebsysDom {
-    pub fn new(cfg: Config, event_channel: mpsc::UnboundedSender<UiEvent>) -> Self {
+    pub fn new(
+        cfg: Config,
+        virtual_dom: Rc<RefCell<VirtualDom>>,
+        event_channel: mpsc::UnboundedSender<UiEvent>,
+    ) -> Self {
         let (document, root) = match cfg.root {
             crate::cfg::ConfigRoot::RootName(rootname) => {
                 // eventually, we just want to let the interpreter do all the work of decoding events into our event type
@@ -100,12 +106,24 @@ impl WebsysDom {
                     }
 
                     let data = virtual_event_from_websys_event(event.clone(), target);
-                    let _ = event_channel.unbounded_send(UiEvent {
-                        name,
-                        bubbles,
-                        element,
-                        data,
-                    });
+
+                    // Handle the event while the browser dispatches it, so listeners can prevent the default action
+                    match virtual_dom.try_borrow_mut() {
+                        Ok(mut virtual_dom) => {
+                            if !virtual_dom.handle_event(&name, Rc::new(data), element, bubbles) {
+                                event.prevent_default();
+                            }
+                        }
+                        // The event was dispatched while the virtual dom is working, so we handle it once it is done
+                        Err(_) => {
+                            let _ = event_channel.unbounded_send(UiEvent {
+                                name,
+                                bubbles,
+                                element,
+                                data,
+                            });
+                        }
+                    }
                 }
             }
         }));
@@ -265,6 +283,8 @@ impl WebsysDom {
                 },
                 Remove { id } => i.remove(id.0 as u32),
                 PushRoot { id } => i.push_root(id.0 as u32),
+                Transition { id, kind } => i.transition(id.0 as u32, *kind as u8),
+                AssignPortalTarget { target, id } => i.assign_portal_target(target, id.0 as u32),
             }
         }
         edits.clear();
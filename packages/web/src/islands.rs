@@ -0,0 +1,185 @@
+//! Hydrate only the interactive islands of a page that was pre-rendered with islands enabled.
+//!
+//! Instead of rebuilding the whole page, the VirtualDom only contains the components marked with
+//! [`dioxus_html::islands::use_island`]. The static parts of the page never get templates or event listeners.
+
+use std::{cell::Cell, cell::RefCell, rc::Rc};
+
+use dioxus_core::{DynamicNode, Element, Properties, Scope, ScopeState, Template, TemplateNode};
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::JsCast;
+
+use crate::Config;
+
+/// A component that can be created from its serialized props
+trait IslandComponent {
+    fn render<'a>(&self, cx: &'a ScopeState, props: &str) -> Option<DynamicNode<'a>>;
+}
+
+struct IslandFn<P: 'static> {
+    name: &'static str,
+    component: fn(Scope<P>) -> Element,
+}
+
+impl<P> IslandComponent for IslandFn<P>
+where
+    P: DeserializeOwned + for<'a> Properties<'a> + 'static,
+{
+    fn render<'a>(&self, cx: &'a ScopeState, props: &str) -> Option<DynamicNode<'a>> {
+        let props: P = match serde_json::from_str(props) {
+            Ok(props) => props,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to deserialize the props of island {}: {err}",
+                    self.name
+                );
+                return None;
+            }
+        };
+        Some(cx.component(self.component, props, self.name))
+    }
+}
+
+type IslandFactory = Rc<dyn IslandComponent>;
+
+/// The components that can be hydrated as islands, registered by the same name they pass to
+/// [`dioxus_html::islands::use_island`].
+///
+/// ```rust, ignore
+/// fn main() {
+///     dioxus_web::launch_islands(
+///         Islands::new().island("counter", Counter).island("search", Search),
+///         Config::new(),
+///     );
+/// }
+/// ```
+#[derive(Default, Clone)]
+pub struct Islands {
+    components: FxHashMap<&'static str, IslandFactory>,
+}
+
+impl Islands {
+    /// Create an empty set of islands
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a component that can be hydrated as an island with the given name
+    pub fn island<P>(mut self, name: &'static str, component: fn(Scope<P>) -> Element) -> Self
+    where
+        P: DeserializeOwned + for<'a> Properties<'a> + 'static,
+    {
+        self.components
+            .insert(name, Rc::new(IslandFn { name, component }));
+        self
+    }
+}
+
+/// An island found in the document
+struct IslandInstance {
+    factory: IslandFactory,
+    props: String,
+}
+
+pub(crate) struct IslandsProps {
+    islands: Vec<IslandInstance>,
+}
+
+/// Find every island in the document in the order they appear in the page
+pub(crate) fn find_islands(islands: &Islands) -> Option<IslandsProps> {
+    let document = crate::event::load_document();
+    let elements = document.query_selector_all("[data-dioxus-island]").ok()?;
+
+    let mut found = Vec::new();
+    for i in 0..elements.length() {
+        let element: web_sys::Element = elements.get(i)?.dyn_into().ok()?;
+        let name = element.get_attribute("data-dioxus-island")?;
+        let props = element
+            .get_attribute("data-dioxus-island-props")
+            .unwrap_or_default();
+
+        // The hydration ids of every island are numbered together, so we can't skip over an island we don't know
+        let Some(factory) = islands.components.get(name.as_str()) else {
+            tracing::error!("Island {name} was not registered. Skipping hydration.");
+            return None;
+        };
+        found.push(IslandInstance {
+            factory: factory.clone(),
+            props,
+        });
+    }
+
+    Some(IslandsProps { islands: found })
+}
+
+/// The root of the VirtualDom. It renders every island in the page as a fragment
+pub(crate) fn islands_root(cx: Scope<IslandsProps>) -> Element {
+    const ROOT: Template<'static> = Template {
+        name: concat!(file!(), ":", line!(), ":", column!(), ":0"),
+        roots: &[TemplateNode::Dynamic { id: 0 }],
+        node_paths: &[&[0]],
+        attr_paths: &[],
+    };
+
+    let islands = cx.props.islands.iter().filter_map(|island| {
+        let node = island.factory.render(cx, &island.props)?;
+        Some(cx.vnode(
+            Cell::new(None),
+            None,
+            Cell::new(ROOT),
+            RefCell::new(bumpalo::collections::Vec::new_in(cx.bump())),
+            cx.bump().alloc([node]),
+            &[],
+        ))
+    });
+    let islands = cx.make_node(islands);
+
+    Some(cx.vnode(
+        Cell::new(None),
+        None,
+        Cell::new(ROOT),
+        RefCell::new(bumpalo::collections::Vec::new_in(cx.bump())),
+        cx.bump().alloc([islands]),
+        &[],
+    ))
+}
+
+/// Launch a VirtualDom that hydrates the islands of a page that was pre-rendered with islands enabled.
+///
+/// Events are handled at the body of the document, so the root set in the config is ignored.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// fn main() {
+///     dioxus_web::launch_islands(Islands::new().island("counter", Counter), Config::new());
+/// }
+///
+/// #[derive(Props, PartialEq, Serialize, Deserialize)]
+/// struct CounterProps {
+///     start: i32,
+/// }
+///
+/// fn Counter(cx: Scope<CounterProps>) -> Element {
+///     use_island(cx, "counter", cx.props);
+///     let count = use_state(cx, || cx.props.start);
+///
+///     render! {
+///         button { onclick: move |_| count += 1, "{count}" }
+///     }
+/// }
+/// ```
+pub fn launch_islands(islands: Islands, mut config: Config) {
+    let Some(props) = find_islands(&islands) else {
+        return;
+    };
+
+    if let Some(body) = crate::event::load_document().body() {
+        config = config.rootelement(body.into());
+    }
+    config.hydrate = true;
+    config.islands = true;
+
+    crate::launch_with_props(islands_root, props, config);
+}
@@ -55,14 +55,14 @@
 //     - Do the VDOM work during the idlecallback
 //     - Do DOM work in the next requestAnimationFrame callback
 
-use std::rc::Rc;
+use std::{cell::RefCell, future::Future, rc::Rc};
 
 pub use crate::cfg::Config;
 #[cfg(feature = "file_engine")]
 pub use crate::file_engine::WebFileEngineExt;
 use dioxus_core::{Element, Scope, VirtualDom};
 use futures_util::{
-    future::{select, Either},
+    future::{poll_fn, select, Either},
     pin_mut, FutureExt, StreamExt,
 };
 
@@ -77,6 +77,10 @@ pub use event::*;
 mod file_engine;
 #[cfg(all(feature = "hot_reload", debug_assertions))]
 mod hot_reload;
+#[cfg(feature = "islands")]
+mod islands;
+#[cfg(feature = "islands")]
+pub use islands::{launch_islands, Islands};
 #[cfg(feature = "hydrate")]
 mod rehydrate;
 
@@ -85,6 +89,9 @@ mod rehydrate;
 // mod ric_raf;
 // mod rehydrate;
 
+/// How long the VirtualDom may render before yielding to the browser, in milliseconds
+const FRAME_BUDGET_MS: f64 = 10.0;
+
 /// Launch the VirtualDOM given a root component and a configuration.
 ///
 /// This function expects the root component to not have root props. To launch the root component with root props, use
@@ -179,7 +186,7 @@ pub fn launch_with_props<T: 'static>(
 pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_props: T, cfg: Config) {
     tracing::info!("Starting up");
 
-    let mut dom = VirtualDom::new_with_props(root, root_props);
+    let dom = VirtualDom::new_with_props(root, root_props);
 
     #[cfg(feature = "eval")]
     {
@@ -210,10 +217,19 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
     #[cfg(not(feature = "hydrate"))]
     let should_hydrate = false;
 
-    let mut websys_dom = dom::WebsysDom::new(cfg, tx);
+    // The root of an islands app is the whole body, so we can't rebuild it from scratch if hydration fails
+    #[cfg(feature = "islands")]
+    let rebuild_on_failure = !cfg.islands;
+    #[cfg(not(feature = "islands"))]
+    let rebuild_on_failure = true;
+
+    // The event handler shares the virtual dom, so it can handle events while the browser dispatches them
+    let dom = Rc::new(RefCell::new(dom));
+    let mut websys_dom = dom::WebsysDom::new(cfg, dom.clone(), tx);
 
     tracing::info!("rebuilding app");
 
+    let mut virtual_dom = dom.borrow_mut();
     if should_hydrate {
         #[cfg(feature = "hydrate")]
         {
@@ -221,29 +237,32 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
             // it's a waste to produce edits just to get the vdom loaded
 
             {
-                let mutations = dom.rebuild();
+                let mutations = virtual_dom.rebuild();
                 web_sys::console::log_1(&format!("mutations: {:#?}", mutations).into());
                 let templates = mutations.templates;
                 websys_dom.load_templates(&templates);
                 websys_dom.interpreter.flush();
             }
-            if let Err(err) = websys_dom.rehydrate(&dom) {
+            if let Err(err) = websys_dom.rehydrate(&virtual_dom) {
                 tracing::error!("Rehydration failed. {:?}", err);
-                tracing::error!("Rebuild DOM into element from scratch");
-                websys_dom.root.set_text_content(None);
+                if rebuild_on_failure {
+                    tracing::error!("Rebuild DOM into element from scratch");
+                    websys_dom.root.set_text_content(None);
 
-                let edits = dom.rebuild();
+                    let edits = virtual_dom.rebuild();
 
-                websys_dom.load_templates(&edits.templates);
-                websys_dom.apply_edits(edits.edits);
+                    websys_dom.load_templates(&edits.templates);
+                    websys_dom.apply_edits(edits.edits);
+                }
             }
         }
     } else {
-        let edits = dom.rebuild();
+        let edits = virtual_dom.rebuild();
 
         websys_dom.load_templates(&edits.templates);
         websys_dom.apply_edits(edits.edits);
     }
+    drop(virtual_dom);
 
     // the mutations come back with nothing - we need to actually mount them
     websys_dom.mount();
@@ -254,7 +273,14 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         // if virtualdom has nothing, wait for it to have something before requesting idle time
         // if there is work then this future resolves immediately.
         let (mut res, template) = {
-            let work = dom.wait_for_work().fuse();
+            let work = poll_fn(|cx| {
+                // Only borrow the virtual dom while it is polled, so events can be handled between the polls
+                let mut virtual_dom = dom.borrow_mut();
+                let work = virtual_dom.wait_for_work();
+                pin_mut!(work);
+                work.poll(cx)
+            })
+            .fuse();
             pin_mut!(work);
 
             #[cfg(all(feature = "hot_reload", debug_assertions))]
@@ -271,13 +297,14 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
         };
 
         if let Some(template) = template {
-            dom.replace_template(template);
+            dom.borrow_mut().replace_template(template);
         }
 
         // Dequeue all of the events from the channel in send order
         // todo: we should re-order these if possible
+        let mut virtual_dom = dom.borrow_mut();
         while let Some(evt) = res {
-            dom.handle_event(
+            virtual_dom.handle_event(
                 evt.name.as_str(),
                 Rc::new(evt.data),
                 evt.element,
@@ -286,24 +313,19 @@ pub async fn run_with_props<T: 'static>(root: fn(Scope<T>) -> Element, root_prop
             res = rx.try_next().transpose().unwrap().ok();
         }
 
-        // Todo: This is currently disabled because it has a negative impact on response times for events but it could be re-enabled for tasks
-        // Jank free rendering
-        //
-        // 1. wait for the browser to give us "idle" time
-        // 2. During idle time, diff the dom
-        // 3. Stop diffing if the deadline is exceded
-        // 4. Wait for the animation frame to patch the dom
-
-        // wait for the mainthread to schedule us in
-        // let deadline = work_loop.wait_for_idle_time().await;
-
-        // run the virtualdom work phase until the frame deadline is reached
-        let edits = dom.render_immediate();
-
-        // wait for the animation frame to fire so we can apply our changes
-        // work_loop.wait_for_raf().await;
+        // Render for at most one frame. Updates from events are always rendered, but transitions and background
+        // updates that don't fit in the frame are left for the next one so the browser can handle input in between
+        let frame_start = js_sys::Date::now();
+        let edits =
+            virtual_dom.render_until(|| js_sys::Date::now() - frame_start > FRAME_BUDGET_MS);
 
         websys_dom.load_templates(&edits.templates);
         websys_dom.apply_edits(edits.edits);
+
+        if virtual_dom.has_dirty_scopes() {
+            drop(virtual_dom);
+            // Yield to the browser's event loop before rendering the rest
+            gloo_timers::future::TimeoutFuture::new(0).await;
+        }
     }
 }
@@ -38,7 +38,11 @@ impl WebsysDom {
     ) -> Result<(), RehydrationError> {
         let vnode = match scope.root_node() {
             dioxus_core::RenderReturn::Ready(ready) => ready,
-            _ => return Err(VNodeNotInitialized),
+            // Components that did not resolve before the server finished streaming are sent as a placeholder
+            dioxus_core::RenderReturn::Aborted(placeholder) => {
+                ids.push(placeholder.mounted_element().ok_or(VNodeNotInitialized)?.0 as u32);
+                return Ok(());
+            }
         };
         self.rehydrate_vnode(dom, vnode, ids, to_mount)
     }